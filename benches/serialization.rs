@@ -0,0 +1,162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Benchmarks for serializing and deserializing representative messages.
+//!
+//! Run with `cargo bench`. Criterion compares each run against the last, so
+//! regressions in the generated (de)serialization code show up as a change
+//! in the reported times.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use xrb::{
+	io::{InfallibleWritable, Readable, Writable},
+	set::WindowConfig,
+	unit::Px,
+	x11::{
+		event::Expose,
+		reply::QueryWindowTree,
+		request::{ConfigureWindow, PlaceImage, PlaceImageFormat},
+	},
+	Coords,
+	Dimensions,
+	Drawable,
+	GraphicsContext,
+	Region,
+	StackMode,
+	Window,
+};
+
+/// Writes the given `message` to a new buffer.
+fn encode(message: &impl Writable) -> Vec<u8> {
+	let mut bytes = Vec::with_capacity(message.x11_size());
+	message.write_to(&mut bytes).unwrap();
+
+	bytes
+}
+
+/// Benchmarks writing the given `message` and reading it back.
+///
+/// The first byte of every message identifies it and is not read by its
+/// [`Readable`] implementation, so it is skipped when reading.
+fn bench_message<M: Readable + Writable>(c: &mut Criterion, name: &str, message: &M) {
+	let bytes = encode(message);
+
+	let mut group = c.benchmark_group(name);
+	group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+	group.bench_function("write", |b| {
+		b.iter_batched_ref(
+			|| Vec::with_capacity(bytes.len()),
+			|buf| black_box(message).write_to(buf).unwrap(),
+			BatchSize::SmallInput,
+		);
+	});
+	group.bench_function("read", |b| {
+		b.iter(|| M::read_from(&mut black_box(&bytes[1..])).unwrap());
+	});
+
+	group.finish();
+}
+
+fn configure_window(c: &mut Criterion) {
+	let mut config = WindowConfig::builder();
+	config
+		.x(Px(-10))
+		.y(Px(20))
+		.width(Px(640))
+		.height(Px(480))
+		.border_width(Px(2))
+		.stack_mode(StackMode::Above);
+
+	bench_message(
+		c,
+		"ConfigureWindow",
+		&ConfigureWindow {
+			target: Window::new(0x0020_0001),
+			config: config.build(),
+		},
+	);
+}
+
+fn place_image(c: &mut Criterion) {
+	// 1 MiB of image data does not fit in a request without the BIG-REQUESTS
+	// extension, so this is the largest 32-bit image which does.
+	const WIDTH: u16 = 256;
+	const HEIGHT: u16 = 255;
+
+	bench_message(
+		c,
+		"PutImage (255 KiB)",
+		&PlaceImage {
+			format: PlaceImageFormat::Zpixmap,
+			target: Drawable::new(0x0020_0001),
+			graphics_context: GraphicsContext::new(0x0020_0002),
+			dimensions: Dimensions::new(Px(WIDTH), Px(HEIGHT)),
+			coordinates: Coords::new(Px(0), Px(0)),
+			left_padding: 0,
+			depth: 24,
+			data: vec![0x7f; 4 * usize::from(WIDTH) * usize::from(HEIGHT)],
+		},
+	);
+}
+
+fn expose_flood(c: &mut Criterion) {
+	const COUNT: u16 = 1000;
+
+	let events: Vec<_> = (0..COUNT)
+		.map(|i| Expose {
+			sequence: i,
+			window: Window::new(0x0020_0001),
+			region: Region::new(Px(i), Px(i), Px(64), Px(64)),
+			count: COUNT - i - 1,
+		})
+		.collect();
+	let bytes: Vec<u8> = events.iter().flat_map(encode).collect();
+
+	let mut group = c.benchmark_group("Expose (flood of 1000)");
+	group.throughput(Throughput::Elements(u64::from(COUNT)));
+
+	group.bench_function("write", |b| {
+		b.iter_batched_ref(
+			|| Vec::with_capacity(bytes.len()),
+			|buf| {
+				for event in black_box(&events) {
+					event.write_infallible(buf);
+				}
+			},
+			BatchSize::SmallInput,
+		);
+	});
+	group.bench_function("read", |b| {
+		b.iter(|| {
+			for event in black_box(&bytes).chunks_exact(32) {
+				Expose::read_from(&mut &event[1..]).unwrap();
+			}
+		});
+	});
+
+	group.finish();
+}
+
+fn query_window_tree(c: &mut Criterion) {
+	bench_message(
+		c,
+		"QueryTree (1000 children)",
+		&QueryWindowTree {
+			sequence: 1,
+			root: Window::new(0x0000_0001),
+			parent: None,
+			children: (0..1000).map(|i| Window::new(0x0020_0000 + i)).collect(),
+		},
+	);
+}
+
+criterion_group!(
+	benches,
+	configure_window,
+	place_image,
+	expose_flood,
+	query_window_tree
+);
+criterion_main!(benches);
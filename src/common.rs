@@ -4,7 +4,9 @@
 
 extern crate self as xrb;
 
-use array_init::array_init;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use array_init::try_array_init;
 use derive_more::{From, Into};
 use thiserror::Error;
 
@@ -17,19 +19,29 @@ use xrbk::{
 	pad,
 	Buf,
 	BufMut,
-	ConstantX11Size,
 	ReadError,
 	ReadError::FailedConversion,
 	ReadResult,
+	Readable,
 	ReadableWithContext,
 	Wrap,
 	Writable,
 	WriteResult,
 	X11Size,
 };
-use xrbk_macro::{derive_xrb, new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size};
+use xrbk_macro::{
+	derive_xrb,
+	new,
+	unwrap,
+	ConstantX11Size,
+	InfallibleWritable,
+	Readable,
+	Wrap,
+	Writable,
+	X11Size,
+};
 
-use crate::unit::Px;
+use crate::unit::{Angle, Px, ValueOutOfBounds};
 
 pub mod atom;
 pub mod set;
@@ -40,7 +52,19 @@ mod res_id;
 mod wrapper;
 
 /// Whether something is enabled or disabled.
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum Toggle {
 	/// The thing is disabled.
 	Disabled,
@@ -49,7 +73,19 @@ pub enum Toggle {
 }
 
 /// Whether something is enabled, disabled, or the default is chosen.
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum ToggleOrDefault {
 	/// The thing is disabled.
 	Disabled,
@@ -74,6 +110,7 @@ pub enum ToggleOrDefault {
 	PartialEq,
 	Hash,
 	Debug,
+	Default,
 	From,
 	Into,
 	// `new` and `unwrap` const fns
@@ -84,38 +121,164 @@ pub enum ToggleOrDefault {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Timestamp(pub(crate) u32);
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+/// Defines how the contents of a [window] are retained when it is resized.
+///
+/// Every variant other than [`Forget`] and [`Static`] anchors the contents to
+/// a point in the [window]: when the [window] is resized, the contents are
+/// moved so that they remain in the same position relative to that point.
+///
+/// The default bit gravity is [`Forget`].
+///
+/// [window]: Window
+///
+/// [`Forget`]: BitGravity::Forget
+/// [`Static`]: BitGravity::Static
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	Default,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum BitGravity {
-	Forget,
-	Static,
-	NorthWest,
-	North,
-	NorthEast,
-	West,
-	Center,
-	East,
-	SouthWest,
-	South,
-	SouthEast,
-}
-
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+	/// The contents of the [window] are discarded when it is resized.
+	///
+	/// [window]: Window
+	#[default]
+	Forget = 0,
+	/// The contents are anchored to the top-left corner of the [window].
+	///
+	/// [window]: Window
+	NorthWest = 1,
+	/// The contents are anchored to the center of the top edge of the [window].
+	///
+	/// [window]: Window
+	North = 2,
+	/// The contents are anchored to the top-right corner of the [window].
+	///
+	/// [window]: Window
+	NorthEast = 3,
+	/// The contents are anchored to the center of the left edge of the
+	/// [window].
+	///
+	/// [window]: Window
+	West = 4,
+	/// The contents are anchored to the center of the [window].
+	///
+	/// [window]: Window
+	Center = 5,
+	/// The contents are anchored to the center of the right edge of the
+	/// [window].
+	///
+	/// [window]: Window
+	East = 6,
+	/// The contents are anchored to the bottom-left corner of the [window].
+	///
+	/// [window]: Window
+	SouthWest = 7,
+	/// The contents are anchored to the center of the bottom edge of the
+	/// [window].
+	///
+	/// [window]: Window
+	South = 8,
+	/// The contents are anchored to the bottom-right corner of the [window].
+	///
+	/// [window]: Window
+	SouthEast = 9,
+	/// The contents remain in the same position relative to the root window.
+	Static = 10,
+}
+
+/// Defines how a [window] is repositioned when its parent is resized.
+///
+/// Every variant other than [`Unmap`] and [`Static`] anchors the [window] to
+/// a point in its parent: when the parent is resized, the [window] is moved
+/// so that it remains in the same position relative to that point.
+///
+/// The default window gravity is [`NorthWest`].
+///
+/// [window]: Window
+///
+/// [`Unmap`]: WindowGravity::Unmap
+/// [`Static`]: WindowGravity::Static
+/// [`NorthWest`]: WindowGravity::NorthWest
+#[doc(alias = "WinGravity")]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	Default,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum WindowGravity {
-	Unmap,
-	Static,
-	NorthWest,
-	North,
-	NorthEast,
-	West,
-	Center,
-	East,
-	SouthWest,
-	South,
-	SouthEast,
+	/// The [window] is unmapped when its parent is resized, instead of being
+	/// moved.
+	///
+	/// An [`UnmapNotify` event] is generated.
+	///
+	/// [window]: Window
+	/// [`UnmapNotify` event]: crate::x11::event::Unmap
+	Unmap = 0,
+	/// The [window] is anchored to the top-left corner of its parent.
+	///
+	/// [window]: Window
+	#[default]
+	NorthWest = 1,
+	/// The [window] is anchored to the center of the top edge of its parent.
+	///
+	/// [window]: Window
+	North = 2,
+	/// The [window] is anchored to the top-right corner of its parent.
+	///
+	/// [window]: Window
+	NorthEast = 3,
+	/// The [window] is anchored to the center of the left edge of its parent.
+	///
+	/// [window]: Window
+	West = 4,
+	/// The [window] is anchored to the center of its parent.
+	///
+	/// [window]: Window
+	Center = 5,
+	/// The [window] is anchored to the center of the right edge of its parent.
+	///
+	/// [window]: Window
+	East = 6,
+	/// The [window] is anchored to the bottom-left corner of its parent.
+	///
+	/// [window]: Window
+	SouthWest = 7,
+	/// The [window] is anchored to the center of the bottom edge of its parent.
+	///
+	/// [window]: Window
+	South = 8,
+	/// The [window] is anchored to the bottom-right corner of its parent.
+	///
+	/// [window]: Window
+	SouthEast = 9,
+	/// The [window] remains in the same position relative to the root window.
+	///
+	/// [window]: Window
+	Static = 10,
 }
 
 // The `derive_xrb!` attribute here is used to write the discriminants as `u16`.
@@ -123,7 +286,7 @@ derive_xrb! {
 	/// A [window]'s class; whether it has a visual output form.
 	///
 	/// [window]: Window
-	#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+	#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable)]
 	pub enum WindowClass: u16 {
 		/// A [window] that both receives input and has a visual output (i.e. what
 		/// one would normally consider a window to be).
@@ -136,10 +299,6 @@ derive_xrb! {
 		InputOnly = 2,
 	}
 
-	impl ConstantX11Size for WindowClass {
-		const X11_SIZE: usize = 2;
-	}
-
 	impl Wrap for WindowClass {
 		type Integer = u16;
 	}
@@ -167,14 +326,148 @@ derive_xrb! {
 	}
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+/// When the X server should maintain the contents of a [window] while it is
+/// obscured.
+///
+/// This is used in the [`maintain_contents` attribute] and the
+/// [`GetWindowAttributes` reply].
+///
+/// [window]: Window
+///
+/// [`maintain_contents` attribute]: crate::set::Attributes::maintain_contents
+/// [`GetWindowAttributes` reply]: crate::x11::reply::GetWindowAttributes
+#[doc(alias = "BackingStore")]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	Default,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum MaintainContents {
+	/// The contents of the [window] are not maintained.
+	///
+	/// [window]: Window
+	#[doc(alias = "NotUseful")]
+	#[default]
 	Never,
+	/// The contents of the [window] are maintained while it is mapped.
+	///
+	/// [window]: Window
 	WhenMapped,
+	/// The contents of the [window] are maintained even while it is unmapped.
+	///
+	/// [window]: Window
 	Always,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+/// The state of the [window] regarding how it is mapped.
+///
+/// This is used in the [`GetWindowAttributes` reply].
+///
+/// [window]: Window
+///
+/// [`GetWindowAttributes` reply]: crate::x11::reply::GetWindowAttributes
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
+pub enum MapState {
+	/// The [window] is not mapped.
+	///
+	/// [window]: Window
+	#[default]
+	Unmapped,
+
+	/// The [window] is mapped but one of its ancestors is unmapped.
+	///
+	/// [window]: Window
+	Unviewable,
+
+	/// The [window] is mapped and all of its ancestors are mapped.
+	///
+	/// [window]: Window
+	Viewable,
+}
+
+/// The state of a [window]'s visibility.
+///
+/// This is used in the [`Visibility` event].
+///
+/// [window]: Window
+/// [`Visibility` event]: crate::x11::event::Visibility
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Debug,
+	Hash,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
+pub enum VisibilityState {
+	/// There is nothing obscuring the `window`.
+	///
+	/// This is used in the [`Visibility` event] when a [window] changes state
+	/// to be `Unobscured`.
+	///
+	/// [window]: Window
+	/// [`Visibility` event]: crate::x11::event::Visibility
+	Unobscured,
+
+	/// The `window` is partially, but not fully, obscured.
+	///
+	/// This is used in the [`Visibility` event] when a [window] changes state
+	/// to be `PartiallyObscured`.
+	///
+	/// [window]: Window
+	/// [`Visibility` event]: crate::x11::event::Visibility
+	PartiallyObscured,
+
+	/// The `window` is fully obscured.
+	///
+	/// This is used in the [`Visibility` event] when a [window] changes state
+	/// to be `FullyObscured`.
+	///
+	/// [window]: Window
+	/// [`Visibility` event]: crate::x11::event::Visibility
+	FullyObscured,
+}
+
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum GrabMode {
 	Normal,
 	Grab,
@@ -184,7 +477,19 @@ pub enum GrabMode {
 /// Whether a grab causes a freeze in [event] processing.
 ///
 /// [event]: crate::message::Event
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum FreezeMode {
 	/// [Event] processing is not frozen.
 	///
@@ -200,32 +505,66 @@ pub enum FreezeMode {
 }
 
 /// The status of an attempted grab.
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	Default,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum GrabStatus {
 	/// The grab was successful.
-	Success,
+	#[default]
+	Success = 0,
 
 	/// Another client already had a grab.
-	AlreadyGrabbed,
-	/// Another client already had an active grab and had frozen [event]
-	/// processing.
-	///
-	/// [event]: crate::message::Event
-	Frozen,
+	AlreadyGrabbed = 1,
 	/// The given time was either earlier than the previous grab, or later than
 	/// the X server's [current time].
 	///
 	/// [current time]: CurrentableTime::CurrentTime
-	InvalidTime,
+	InvalidTime = 2,
 	/// The grabbed [window] or the [window] which the cursor was confined to is
 	/// not viewable, or the [window] which the cursor was confined to is
 	/// completely outside of the root [window].
 	///
 	/// [window]: Window
-	NotViewable,
+	NotViewable = 3,
+	/// Another client already had an active grab and had frozen [event]
+	/// processing.
+	///
+	/// [event]: crate::message::Event
+	Frozen = 4,
+}
+
+impl GrabStatus {
+	/// Returns whether the grab was successful.
+	#[must_use]
+	pub const fn is_success(&self) -> bool {
+		matches!(self, Self::Success)
+	}
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum StackMode {
 	Above,
 	Below,
@@ -250,6 +589,7 @@ pub enum StackMode {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Keysym(pub(crate) u32);
@@ -281,6 +621,7 @@ impl Keysym {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Keycode(pub(crate) u8);
@@ -310,6 +651,7 @@ impl Keycode {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Button(pub(crate) u8);
@@ -337,11 +679,14 @@ impl Button {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Char8(pub(crate) u8);
 
-#[derive(Clone, Eq, PartialEq, Hash, Debug, From, Into, X11Size, Writable)]
+#[derive(
+	Clone, Eq, PartialEq, Hash, Debug, Default, From, Into, X11Size, Writable, InfallibleWritable,
+)]
 pub struct String8(Vec<Char8>);
 
 impl String8 {
@@ -350,9 +695,53 @@ impl String8 {
 		self.0.len()
 	}
 
-	#[must_use]
-	pub fn is_empty(&self) -> bool {
-		self.0.is_empty()
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Encodes `text` as Latin-1 (ISO 8859-1).
+	///
+	/// # Errors
+	/// Returns an [`UnencodableCharacter`] error for the first character in
+	/// `text` which is not in Latin-1 (that is, which is above `U+00FF`).
+	pub fn from_latin1(text: &str) -> Result<Self, UnencodableCharacter> {
+		text.char_indices()
+			.map(|(index, character)| {
+				u8::try_from(character)
+					.map(Char8)
+					.map_err(|_| UnencodableCharacter { character, index })
+			})
+			.collect::<Result<_, _>>()
+			.map(Self)
+	}
+
+	/// Encodes `text` as Latin-1 (ISO 8859-1), replacing any characters which
+	/// are not in Latin-1 (that is, which are above `U+00FF`) with
+	/// `replacement`.
+	#[must_use]
+	pub fn from_latin1_lossy(text: &str, replacement: Char8) -> Self {
+		Self(
+			text.chars()
+				.map(|character| u8::try_from(character).map_or(replacement, Char8))
+				.collect(),
+		)
+	}
+
+	/// Decodes this `String8` as Latin-1 (ISO 8859-1).
+	///
+	/// Every byte is a valid Latin-1 character, so this cannot fail.
+	#[must_use]
+	pub fn to_latin1(&self) -> String {
+		self.0.iter().map(|&Char8(byte)| char::from(byte)).collect()
+	}
+}
+
+impl TryFrom<&str> for String8 {
+	type Error = UnencodableCharacter;
+
+	fn try_from(text: &str) -> Result<Self, Self::Error> {
+		Self::from_latin1(text)
 	}
 }
 
@@ -368,18 +757,24 @@ impl ReadableWithContext for String8 {
 }
 
 derive_xrb! {
+	/// A [`String8`] which is encoded preceded by its length.
+	///
+	/// The length is encoded as a single byte, so a `LengthString8` can contain
+	/// at most [`MAX_LEN`] characters.
+	///
+	/// [`MAX_LEN`]: LengthString8::MAX_LEN
 	#[derive(
 		Clone,
 		Eq,
 		PartialEq,
 		Hash,
 		Debug,
-		From,
+		Default,
 		Into,
 		// XRBK traits
 		X11Size,
 		Readable,
-		Writable,
+		Writable, InfallibleWritable,
 	)]
 	pub struct LengthString8 {
 		#[allow(clippy::cast_possible_truncation)]
@@ -390,6 +785,98 @@ derive_xrb! {
 	}
 }
 
+impl LengthString8 {
+	/// The maximum number of characters in a `LengthString8`.
+	pub const MAX_LEN: usize = u8::MAX as usize;
+
+	/// Creates a new `LengthString8` containing the given `string`.
+	///
+	/// # Errors
+	/// Returns a [`LengthString8TooLong`] error if `string` is longer than
+	/// [`MAX_LEN`].
+	///
+	/// [`MAX_LEN`]: LengthString8::MAX_LEN
+	pub fn new(string: String8) -> Result<Self, LengthString8TooLong> {
+		if string.len() > Self::MAX_LEN {
+			return Err(LengthString8TooLong(string.len()));
+		}
+
+		Ok(Self { string })
+	}
+
+	/// Encodes `text` as a Latin-1 (ISO 8859-1) `LengthString8`.
+	///
+	/// # Errors
+	/// Returns an [`InvalidLengthString8::Unencodable`] error for the first
+	/// character in `text` which is not in Latin-1, or an
+	/// [`InvalidLengthString8::TooLong`] error if `text` is longer than
+	/// [`MAX_LEN`] characters.
+	///
+	/// [`MAX_LEN`]: LengthString8::MAX_LEN
+	pub fn from_latin1(text: &str) -> Result<Self, InvalidLengthString8> {
+		Ok(Self::new(String8::from_latin1(text)?)?)
+	}
+
+	/// Returns the contained [`String8`].
+	#[must_use]
+	pub const fn as_string8(&self) -> &String8 {
+		&self.string
+	}
+
+	/// Returns the number of characters in this `LengthString8`.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.string.len()
+	}
+
+	/// Returns whether this `LengthString8` contains no characters.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.string.is_empty()
+	}
+
+	/// Decodes this `LengthString8` as Latin-1 (ISO 8859-1).
+	///
+	/// Every byte is a valid Latin-1 character, so this cannot fail.
+	#[must_use]
+	pub fn to_latin1(&self) -> String {
+		self.string.to_latin1()
+	}
+}
+
+impl TryFrom<String8> for LengthString8 {
+	type Error = LengthString8TooLong;
+
+	fn try_from(string: String8) -> Result<Self, Self::Error> {
+		Self::new(string)
+	}
+}
+
+impl TryFrom<&str> for LengthString8 {
+	type Error = InvalidLengthString8;
+
+	fn try_from(text: &str) -> Result<Self, Self::Error> {
+		Self::from_latin1(text)
+	}
+}
+
+/// An error generated when a [`LengthString8`] would be longer than
+/// [`LengthString8::MAX_LEN`] characters.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+#[error("a `LengthString8` can contain at most 255 characters, found {0}")]
+pub struct LengthString8TooLong(pub usize);
+
+/// An error generated when text cannot be encoded as a [`LengthString8`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidLengthString8 {
+	/// The text contains a character which is not in Latin-1.
+	#[error(transparent)]
+	Unencodable(#[from] UnencodableCharacter),
+	/// The text is too long.
+	#[error(transparent)]
+	TooLong(#[from] LengthString8TooLong),
+}
+
 #[derive(
 	Copy,
 	Clone,
@@ -407,6 +894,7 @@ derive_xrb! {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 )]
 pub struct Char16(pub(crate) u8, pub(crate) u8);
 
@@ -418,6 +906,12 @@ impl From<u16> for Char16 {
 	}
 }
 
+impl From<Char8> for Char16 {
+	fn from(Char8(char): Char8) -> Self {
+		Self::new(0, char)
+	}
+}
+
 impl From<Char16> for u16 {
 	fn from(char: Char16) -> Self {
 		let (byte1, byte2) = char.unwrap();
@@ -426,7 +920,9 @@ impl From<Char16> for u16 {
 	}
 }
 
-#[derive(Clone, Eq, PartialEq, Hash, Debug, From, Into, X11Size, Writable)]
+#[derive(
+	Clone, Eq, PartialEq, Hash, Debug, Default, From, Into, X11Size, Writable, InfallibleWritable,
+)]
 pub struct String16(Vec<Char16>);
 
 impl String16 {
@@ -439,6 +935,77 @@ impl String16 {
 	pub fn is_empty(&self) -> bool {
 		self.0.is_empty()
 	}
+
+	/// Encodes `text` as big-endian two-byte characters.
+	///
+	/// Each [`Char16`] can only represent a character in the Basic
+	/// Multilingual Plane (that is, up to `U+FFFF`), as used by fonts with
+	/// the `ISO10646-1` encoding.
+	///
+	/// # Errors
+	/// Returns an [`UnencodableCharacter`] error for the first character in
+	/// `text` which is outside of the Basic Multilingual Plane.
+	pub fn from_ucs2(text: &str) -> Result<Self, UnencodableCharacter> {
+		text.char_indices()
+			.map(|(index, character)| {
+				u16::try_from(u32::from(character))
+					.map(Char16::from)
+					.map_err(|_| UnencodableCharacter { character, index })
+			})
+			.collect::<Result<_, _>>()
+			.map(Self)
+	}
+
+	/// Encodes `text` as big-endian two-byte characters, replacing any
+	/// characters which are outside of the Basic Multilingual Plane (that is,
+	/// which are above `U+FFFF`) with `replacement`.
+	#[must_use]
+	pub fn from_ucs2_lossy(text: &str, replacement: Char16) -> Self {
+		Self(
+			text.chars()
+				.map(|character| {
+					u16::try_from(u32::from(character)).map_or(replacement, Char16::from)
+				})
+				.collect(),
+		)
+	}
+
+	/// Decodes this `String16` as big-endian two-byte characters, replacing
+	/// any surrogates with [`char::REPLACEMENT_CHARACTER`].
+	///
+	/// Like [`from_ucs2`], each [`Char16`] is a single character in the Basic
+	/// Multilingual Plane: surrogate pairs are not combined, since fonts with
+	/// the `ISO10646-1` encoding index their glyphs by code point.
+	///
+	/// [`from_ucs2`]: String16::from_ucs2
+	#[must_use]
+	pub fn to_string_lossy(&self) -> String {
+		self.0
+			.iter()
+			.map(|&character| {
+				char::from_u32(u16::from(character).into()).unwrap_or(char::REPLACEMENT_CHARACTER)
+			})
+			.collect()
+	}
+}
+
+impl TryFrom<&str> for String16 {
+	type Error = UnencodableCharacter;
+
+	fn try_from(text: &str) -> Result<Self, Self::Error> {
+		Self::from_ucs2(text)
+	}
+}
+
+/// An error generated when text contains a character which cannot be encoded
+/// in a [`String8`] or [`String16`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+#[error("the character {character:?} at byte index {index} cannot be encoded")]
+pub struct UnencodableCharacter {
+	/// The character which cannot be encoded.
+	pub character: char,
+	/// The byte index of the `character` within the text.
+	pub index: usize,
 }
 
 impl ReadableWithContext for String16 {
@@ -452,6 +1019,70 @@ impl ReadableWithContext for String16 {
 	}
 }
 
+/// An error generated when a coordinate or dimension does not fit within the
+/// type used to represent it in the X11 protocol.
+///
+/// Coordinates are represented as [`i16`] values and dimensions are
+/// represented as [`u16`] values in the X11 protocol.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+#[error("`{field}` is out of bounds: {bounds}")]
+pub struct GeometryOutOfBounds {
+	/// The name of the coordinate or dimension which is out of bounds.
+	pub field: &'static str,
+	/// The bounds which the value did not satisfy.
+	pub bounds: ValueOutOfBounds<i64>,
+}
+
+/// Converts the given coordinate to a [`Px<i16>`], returning an error if it
+/// does not fit.
+fn checked_coordinate(field: &'static str, value: i32) -> Result<Px<i16>, GeometryOutOfBounds> {
+	i16::try_from(value)
+		.map(Px)
+		.map_err(|_| GeometryOutOfBounds {
+			field,
+			bounds: ValueOutOfBounds {
+				min: i16::MIN.into(),
+				max: i16::MAX.into(),
+				found: value.into(),
+			},
+		})
+}
+
+/// Converts the given dimension to a [`Px<u16>`], returning an error if it
+/// does not fit.
+fn checked_dimension(field: &'static str, value: u32) -> Result<Px<u16>, GeometryOutOfBounds> {
+	u16::try_from(value)
+		.map(Px)
+		.map_err(|_| GeometryOutOfBounds {
+			field,
+			bounds: ValueOutOfBounds {
+				min: u16::MIN.into(),
+				max: u16::MAX.into(),
+				found: value.into(),
+			},
+		})
+}
+
+/// Converts the given coordinate to a [`Px<i16>`], clamping it to the bounds of
+/// [`i16`].
+#[allow(
+	clippy::cast_possible_truncation,
+	reason = "the value is clamped first"
+)]
+fn saturating_coordinate(value: i32) -> Px<i16> {
+	Px(value.clamp(i16::MIN.into(), i16::MAX.into()) as i16)
+}
+
+/// Converts the given dimension to a [`Px<u16>`], clamping it to the bounds of
+/// [`u16`].
+#[allow(
+	clippy::cast_possible_truncation,
+	reason = "the value is clamped first"
+)]
+fn saturating_dimension(value: u32) -> Px<u16> {
+	Px(value.min(u16::MAX.into()) as u16)
+}
+
 /// A 2D point with an `x`-coordinate and a `y`-coordinate.
 #[derive(
 	Copy,
@@ -460,6 +1091,7 @@ impl ReadableWithContext for String16 {
 	PartialEq,
 	Hash,
 	Debug,
+	Default,
 	From,
 	Into,
 	// `new` and `unwrap` const fns
@@ -470,6 +1102,7 @@ impl ReadableWithContext for String16 {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 )]
 pub struct Coords {
 	/// The x coordinate, measured in pixels.
@@ -478,6 +1111,28 @@ pub struct Coords {
 	pub y: Px<i16>,
 }
 
+impl Coords {
+	/// Creates new `Coords` from [`i32`] coordinates, returning an error if
+	/// either coordinate does not fit within an [`i16`].
+	///
+	/// # Errors
+	/// Returns [`GeometryOutOfBounds`] if `x` or `y` is less than [`i16::MIN`]
+	/// or greater than [`i16::MAX`].
+	pub fn try_new(x: i32, y: i32) -> Result<Self, GeometryOutOfBounds> {
+		Ok(Self::new(
+			checked_coordinate("x", x)?,
+			checked_coordinate("y", y)?,
+		))
+	}
+
+	/// Creates new `Coords` from [`i32`] coordinates, clamping each coordinate
+	/// to the bounds of an [`i16`].
+	#[must_use]
+	pub fn saturating_new(x: i32, y: i32) -> Self {
+		Self::new(saturating_coordinate(x), saturating_coordinate(y))
+	}
+}
+
 /// 2D dimensions (width and height), measured in pixels.
 #[derive(
 	Copy,
@@ -486,6 +1141,7 @@ pub struct Coords {
 	PartialEq,
 	Hash,
 	Debug,
+	Default,
 	From,
 	Into,
 	// `new` and `unwrap` const fns
@@ -496,6 +1152,7 @@ pub struct Coords {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 )]
 pub struct Dimensions {
 	/// The width, measured in pixels.
@@ -504,9 +1161,43 @@ pub struct Dimensions {
 	pub height: Px<u16>,
 }
 
+impl Dimensions {
+	/// Creates new `Dimensions` from [`u32`] dimensions, returning an error if
+	/// either dimension does not fit within a [`u16`].
+	///
+	/// # Errors
+	/// Returns [`GeometryOutOfBounds`] if `width` or `height` is greater than
+	/// [`u16::MAX`].
+	pub fn try_new(width: u32, height: u32) -> Result<Self, GeometryOutOfBounds> {
+		Ok(Self::new(
+			checked_dimension("width", width)?,
+			checked_dimension("height", height)?,
+		))
+	}
+
+	/// Creates new `Dimensions` from [`u32`] dimensions, clamping each
+	/// dimension to the bounds of a [`u16`].
+	#[must_use]
+	pub fn saturating_new(width: u32, height: u32) -> Self {
+		Self::new(saturating_dimension(width), saturating_dimension(height))
+	}
+}
+
 /// A rectangle with coordinates and dimensions.
 #[derive(
-	Copy, Clone, Eq, PartialEq, Hash, Debug, new, X11Size, ConstantX11Size, Readable, Writable,
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	Default,
+	new,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
 )]
 pub struct Rectangle {
 	/// The x-coordinate of the upper left corner of the `Rectangle`.
@@ -531,10 +1222,52 @@ impl Rectangle {
 	pub const fn as_dimensions(&self) -> Dimensions {
 		Dimensions::new(self.width, self.height)
 	}
+
+	/// Creates a new `Rectangle` from [`i32`] coordinates and [`u32`]
+	/// dimensions, returning an error if any of them do not fit within the
+	/// types used in the X11 protocol.
+	///
+	/// # Errors
+	/// Returns [`GeometryOutOfBounds`] if `x` or `y` does not fit within an
+	/// [`i16`], or if `width` or `height` does not fit within a [`u16`].
+	pub fn try_new(x: i32, y: i32, width: u32, height: u32) -> Result<Self, GeometryOutOfBounds> {
+		Ok(Self::new(
+			checked_coordinate("x", x)?,
+			checked_coordinate("y", y)?,
+			checked_dimension("width", width)?,
+			checked_dimension("height", height)?,
+		))
+	}
+
+	/// Creates a new `Rectangle` from [`i32`] coordinates and [`u32`]
+	/// dimensions, clamping each of them to the bounds of the types used in
+	/// the X11 protocol.
+	#[must_use]
+	pub fn saturating_new(x: i32, y: i32, width: u32, height: u32) -> Self {
+		Self::new(
+			saturating_coordinate(x),
+			saturating_coordinate(y),
+			saturating_dimension(width),
+			saturating_dimension(height),
+		)
+	}
 }
 
 /// Same as a [`Rectangle`], but with unsigned coordinates.
-#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, ConstantX11Size, Readable, Writable)]
+#[derive(
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	Default,
+	new,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub struct Region {
 	/// The x-coordinate of the upper left corner of the `Region`.
 	pub x: Px<u16>,
@@ -548,7 +1281,20 @@ pub struct Region {
 }
 
 /// A circular or elliptical arc.
-#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, ConstantX11Size, Readable, Writable)]
+#[derive(
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	Default,
+	new,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub struct Arc {
 	/// The [rectangle] which contains the arc.
 	///
@@ -562,22 +1308,40 @@ pub struct Arc {
 
 	/// Specifies the start of the `Arc`.
 	///
-	/// The angle is measured in degrees scaled by 64. Positive indicates
-	/// counterclockwise motion and negative indicates clockwise motion.
-	/// The angle is measured relative to the three-o'clock position from
-	/// the center of the rectangle.
-	pub start_angle: i16,
+	/// Positive indicates counterclockwise motion and negative indicates
+	/// clockwise motion. The angle is measured relative to the three-o'clock
+	/// position from the center of the rectangle.
+	pub start_angle: Angle,
 	/// Specifies the extent of the `Arc` relative to the `start_angle`.
 	///
-	/// The angle is measured in degrees scaled by 64. If greater than 360
-	/// degrees, this angle is truncated to 360 degrees.
-	pub end_angle: i16,
+	/// If greater than 360 degrees, this angle is truncated to 360 degrees.
+	pub end_angle: Angle,
 }
 
 /// The address family of a host.
 ///
 /// This is used in [`Host`].
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+///
+/// X servers report hosts of families which are not defined in the core X11
+/// protocol, such as the families used for local connections and Kerberos
+/// principals, so any family which is not recognized is read as
+/// [`HostFamily::Unknown`] rather than failing.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
+// `repr(u8)` is required for the explicit discriminants alongside `Unknown`.
+#[repr(u8)]
+#[non_exhaustive]
 pub enum HostFamily {
 	/// An IPv4 address.
 	///
@@ -599,6 +1363,17 @@ pub enum HostFamily {
 	///
 	/// See [`HostAddress::Ipv6`] for more information.
 	Ipv6,
+
+	/// A family which is not known to XRB, with the given discriminant.
+	///
+	/// This only holds discriminants which are not recognized: it is written
+	/// as just its discriminant, so `Unknown(0)` is read back as [`Ipv4`].
+	///
+	/// See [`HostAddress::Unknown`] for more information.
+	///
+	/// [`Ipv4`]: HostFamily::Ipv4
+	#[unknown]
+	Unknown(u8),
 }
 
 /// The string used to create an [`AsciiString`] was not encoded as ASCII.
@@ -609,7 +1384,7 @@ pub struct NonAsciiEncoding;
 /// A string comprised entirely of ASCII bytes.
 ///
 /// This is used for [`HostAddress::ServerInterpreted`].
-#[derive(Clone, Debug, Hash, PartialEq, Eq, X11Size, Writable)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, X11Size, Writable, InfallibleWritable)]
 pub struct AsciiString(Vec<u8>);
 
 impl AsciiString {
@@ -657,6 +1432,7 @@ impl ReadableWithContext for AsciiString {
 ///
 /// [host]: Host
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum HostAddress {
 	/// An IPv4 address.
 	Ipv4([u8; 4]),
@@ -687,9 +1463,25 @@ pub enum HostAddress {
 	},
 	/// An IPv6 address.
 	Ipv6([u8; 16]),
+
+	/// An address of a [family] which is not known to XRB.
+	///
+	/// [family]: HostFamily
+	Unknown {
+		/// The discriminant of the address' family.
+		family: u8,
+		/// The bytes of the address.
+		address: Vec<u8>,
+	},
 }
 
 impl HostAddress {
+	/// The `address_type` of a [`ServerInterpreted`] address which contains a
+	/// hostname.
+	///
+	/// [`ServerInterpreted`]: HostAddress::ServerInterpreted
+	const HOSTNAME: &'static [u8] = b"hostname";
+
 	/// The [`HostFamily`] associated with this address.
 	#[must_use]
 	pub const fn family(&self) -> HostFamily {
@@ -699,6 +1491,78 @@ impl HostAddress {
 			Self::Chaos(..) => HostFamily::Chaos,
 			Self::ServerInterpreted { .. } => HostFamily::ServerInterpreted,
 			Self::Ipv6(..) => HostFamily::Ipv6,
+
+			Self::Unknown { family, .. } => HostFamily::Unknown(*family),
+		}
+	}
+
+	/// Creates a [`ServerInterpreted`] address for the host with the given
+	/// hostname.
+	///
+	/// The hostname is resolved by the X server.
+	///
+	/// # Errors
+	/// Returns [`NonAsciiEncoding`] if the `hostname` is not ASCII.
+	///
+	/// [`ServerInterpreted`]: HostAddress::ServerInterpreted
+	pub fn hostname(hostname: &str) -> Result<Self, NonAsciiEncoding> {
+		Ok(Self::ServerInterpreted {
+			address_type: AsciiString::new(Self::HOSTNAME.to_vec())?,
+			address_value: AsciiString::new(hostname.as_bytes().to_vec())?,
+		})
+	}
+
+	/// Returns the hostname of this address, if it is a [`ServerInterpreted`]
+	/// hostname address.
+	///
+	/// [`ServerInterpreted`]: HostAddress::ServerInterpreted
+	#[must_use]
+	pub fn as_hostname(&self) -> Option<&str> {
+		match self {
+			Self::ServerInterpreted {
+				address_type,
+				address_value,
+			} if address_type.as_bytes() == Self::HOSTNAME => {
+				std::str::from_utf8(address_value.as_bytes()).ok()
+			},
+
+			_ => None,
+		}
+	}
+
+	/// Returns this address as an [`IpAddr`], if it is an [`Ipv4`] or [`Ipv6`]
+	/// address.
+	///
+	/// [`Ipv4`]: HostAddress::Ipv4
+	/// [`Ipv6`]: HostAddress::Ipv6
+	#[must_use]
+	pub fn ip_addr(&self) -> Option<IpAddr> {
+		match self {
+			Self::Ipv4(address) => Some(IpAddr::V4(Ipv4Addr::from(*address))),
+			Self::Ipv6(address) => Some(IpAddr::V6(Ipv6Addr::from(*address))),
+
+			_ => None,
+		}
+	}
+}
+
+impl From<Ipv4Addr> for HostAddress {
+	fn from(address: Ipv4Addr) -> Self {
+		Self::Ipv4(address.octets())
+	}
+}
+
+impl From<Ipv6Addr> for HostAddress {
+	fn from(address: Ipv6Addr) -> Self {
+		Self::Ipv6(address.octets())
+	}
+}
+
+impl From<IpAddr> for HostAddress {
+	fn from(address: IpAddr) -> Self {
+		match address {
+			IpAddr::V4(address) => address.into(),
+			IpAddr::V6(address) => address.into(),
 		}
 	}
 }
@@ -721,6 +1585,8 @@ impl X11Size for HostAddress {
 			},
 
 			Self::Ipv6(address) => address.x11_size(),
+
+			Self::Unknown { address, .. } => address.x11_size(),
 		}
 	}
 }
@@ -733,22 +1599,21 @@ impl ReadableWithContext for HostAddress {
 
 		match family {
 			HostFamily::Ipv4 => Ok(Self::Ipv4([
-				buf.get_u8(),
-				buf.get_u8(),
-				buf.get_u8(),
-				buf.get_u8(),
+				u8::read_from(buf)?,
+				u8::read_from(buf)?,
+				u8::read_from(buf)?,
+				u8::read_from(buf)?,
 			])),
-			HostFamily::DecNet => Ok(Self::DecNet([buf.get_u8(), buf.get_u8()])),
-			HostFamily::Chaos => Ok(Self::Chaos([buf.get_u8(), buf.get_u8()])),
+			HostFamily::DecNet => Ok(Self::DecNet([u8::read_from(buf)?, u8::read_from(buf)?])),
+			HostFamily::Chaos => Ok(Self::Chaos([u8::read_from(buf)?, u8::read_from(buf)?])),
 
 			HostFamily::ServerInterpreted => {
 				let mut address_type = vec![];
 				let mut address_value = vec![];
 
 				while buf.has_remaining() {
-					match buf.get_u8() {
+					match u8::read_from(buf)? {
 						0 => {
-							buf.advance(1);
 							address_value = <Vec<u8>>::read_with(buf, &buf.remaining())?;
 
 							break;
@@ -771,7 +1636,12 @@ impl ReadableWithContext for HostAddress {
 				}
 			},
 
-			HostFamily::Ipv6 => Ok(Self::Ipv6(array_init(|_| buf.get_u8()))),
+			HostFamily::Ipv6 => Ok(Self::Ipv6(try_array_init(|_| u8::read_from(buf))?)),
+
+			HostFamily::Unknown(family) => Ok(Self::Unknown {
+				family: *family,
+				address: <Vec<u8>>::read_with(buf, length)?,
+			}),
 		}
 	}
 }
@@ -795,6 +1665,8 @@ impl Writable for HostAddress {
 			},
 
 			Self::Ipv6(address) => address.write_to(buf)?,
+
+			Self::Unknown { address, .. } => address.write_to(buf)?,
 		}
 
 		Ok(())
@@ -822,3 +1694,330 @@ derive_xrb! {
 		[_; address => pad(address)],
 	}
 }
+
+impl Host {
+	/// Creates a `Host` with a [`ServerInterpreted`] address for the given
+	/// `hostname`.
+	///
+	/// # Errors
+	/// Returns [`NonAsciiEncoding`] if the `hostname` is not ASCII.
+	///
+	/// [`ServerInterpreted`]: HostAddress::ServerInterpreted
+	pub fn hostname(hostname: &str) -> Result<Self, NonAsciiEncoding> {
+		Ok(Self::new(HostAddress::hostname(hostname)?))
+	}
+}
+
+impl From<HostAddress> for Host {
+	fn from(address: HostAddress) -> Self {
+		Self::new(address)
+	}
+}
+
+impl From<IpAddr> for Host {
+	fn from(address: IpAddr) -> Self {
+		Self::new(address.into())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_gravity_encoding() {
+		use xrbk::{Readable, Wrap4, Writable};
+
+		assert_eq!(BitGravity::default(), BitGravity::Forget);
+		assert_eq!(WindowGravity::default(), WindowGravity::NorthWest);
+
+		for (discriminant, bit_gravity, window_gravity) in [
+			(0, BitGravity::Forget, WindowGravity::Unmap),
+			(1, BitGravity::NorthWest, WindowGravity::NorthWest),
+			(5, BitGravity::Center, WindowGravity::Center),
+			(9, BitGravity::SouthEast, WindowGravity::SouthEast),
+			(10, BitGravity::Static, WindowGravity::Static),
+		] {
+			// One byte in replies.
+			let mut bytes = Vec::new();
+			bit_gravity.write_to(&mut bytes).unwrap();
+			window_gravity.write_to(&mut bytes).unwrap();
+			assert_eq!(bytes, [discriminant, discriminant]);
+
+			// Four bytes in value lists.
+			let mut bytes = Vec::new();
+			Wrap4(window_gravity).write_to(&mut bytes).unwrap();
+			assert_eq!(bytes, [0, 0, 0, discriminant]);
+			assert_eq!(
+				Wrap4::<WindowGravity>::read_from(&mut &bytes[..]).unwrap(),
+				Wrap4(window_gravity),
+			);
+		}
+
+		assert!(BitGravity::read_from(&mut &[11][..]).is_err());
+	}
+
+	#[test]
+	fn test_window_state_discriminants() {
+		use crate::test_util::encode;
+
+		assert_eq!(encode(&MaintainContents::Never), [0]);
+		assert_eq!(encode(&MaintainContents::WhenMapped), [1]);
+		assert_eq!(encode(&MaintainContents::Always), [2]);
+
+		assert_eq!(encode(&MapState::Unmapped), [0]);
+		assert_eq!(encode(&MapState::Unviewable), [1]);
+		assert_eq!(encode(&MapState::Viewable), [2]);
+
+		assert_eq!(encode(&VisibilityState::Unobscured), [0]);
+		assert_eq!(encode(&VisibilityState::PartiallyObscured), [1]);
+		assert_eq!(encode(&VisibilityState::FullyObscured), [2]);
+	}
+
+	#[test]
+	fn test_status_discriminants() {
+		use xrbk::{Readable, Writable};
+
+		use crate::x11::{
+			reply::{SetButtonMappingStatus, SetModifierMappingStatus},
+			request::ClipRectanglesOrdering,
+		};
+
+		fn assert_encoding<T>(values: &[T])
+		where
+			T: Readable + Writable + PartialEq + std::fmt::Debug,
+		{
+			for (discriminant, value) in (0..).zip(values) {
+				let mut bytes = Vec::new();
+				value.write_to(&mut bytes).unwrap();
+				assert_eq!(bytes, [discriminant]);
+
+				assert_eq!(&T::read_from(&mut &bytes[..]).unwrap(), value);
+			}
+
+			let unrecognized = u8::try_from(values.len()).unwrap();
+			assert!(T::read_from(&mut &[unrecognized][..]).is_err());
+		}
+
+		assert_encoding(&[
+			GrabStatus::Success,
+			GrabStatus::AlreadyGrabbed,
+			GrabStatus::InvalidTime,
+			GrabStatus::NotViewable,
+			GrabStatus::Frozen,
+		]);
+		assert_encoding(&[
+			SetButtonMappingStatus::Success,
+			SetButtonMappingStatus::Busy,
+		]);
+		assert_encoding(&[
+			SetModifierMappingStatus::Success,
+			SetModifierMappingStatus::Busy,
+			SetModifierMappingStatus::Rejected,
+		]);
+		assert_encoding(&[
+			ClipRectanglesOrdering::Unsorted,
+			ClipRectanglesOrdering::SortedByY,
+			ClipRectanglesOrdering::SortedByYx,
+			ClipRectanglesOrdering::BandedByYx,
+		]);
+	}
+
+	#[test]
+	fn test_length_string8() {
+		use xrbk::{Readable, Writable};
+
+		let string = LengthString8::try_from("fixed").unwrap();
+		assert_eq!(string.len(), 5);
+		assert_eq!(string.to_latin1(), "fixed");
+
+		let mut bytes = Vec::new();
+		string.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, b"\x05fixed");
+		assert_eq!(LengthString8::read_from(&mut &bytes[..]).unwrap(), string);
+
+		let max = "a".repeat(LengthString8::MAX_LEN);
+		assert!(LengthString8::try_from(max.as_str()).is_ok());
+		assert_eq!(
+			LengthString8::try_from(format!("{max}a").as_str()),
+			Err(InvalidLengthString8::TooLong(LengthString8TooLong(256))),
+		);
+		assert!(matches!(
+			LengthString8::try_from("\u{263a}"),
+			Err(InvalidLengthString8::Unencodable(_)),
+		));
+	}
+
+	#[test]
+	fn test_unknown_host_family() {
+		use xrbk::{Readable, Writable};
+
+		// A `LocalHost` host, as reported by X servers for local connections.
+		let bytes = [252, 0, 0, 3, b'a', b'b', b'c', 0];
+
+		let host = Host::read_from(&mut &bytes[..]).unwrap();
+		assert_eq!(host.address.family(), HostFamily::Unknown(252));
+		assert_eq!(
+			host.address,
+			HostAddress::Unknown {
+				family: 252,
+				address: b"abc".to_vec(),
+			}
+		);
+
+		let mut written = Vec::new();
+		host.write_to(&mut written).unwrap();
+		assert_eq!(written, bytes);
+
+		assert_eq!(
+			HostFamily::read_from(&mut &[6][..]).unwrap(),
+			HostFamily::Ipv6
+		);
+		assert_eq!(HostFamily::Unknown(252).x11_size(), 1);
+
+		// A recognized discriminant is read as its own variant.
+		let mut written = Vec::new();
+		HostFamily::Unknown(0).write_to(&mut written).unwrap();
+		assert_eq!(written, [0]);
+		assert_eq!(
+			HostFamily::read_from(&mut &written[..]).unwrap(),
+			HostFamily::Ipv4
+		);
+	}
+
+	#[test]
+	fn test_constant_x11_sizes() {
+		use xrbk::ConstantX11Size;
+
+		use crate::{visual::VisualType, x11::request::ColormapEntryChange};
+
+		// Enums defined with `derive_xrb!` use the size of their discriminant
+		// type.
+		assert_eq!(WindowClass::X11_SIZE, 2);
+		assert_eq!(WindowClass::InputOnly.x11_size(), 2);
+		assert_eq!(HostFamily::X11_SIZE, 1);
+
+		// Structs defined with `derive_xrb!` include their unused bytes.
+		assert_eq!(ColormapEntryChange::X11_SIZE, 12);
+		assert_eq!(VisualType::X11_SIZE, 24);
+	}
+
+	#[test]
+	fn test_string8_latin1() {
+		let string = String8::from_latin1("café").unwrap();
+		assert_eq!(
+			string,
+			String8::from(vec![Char8(b'c'), Char8(b'a'), Char8(b'f'), Char8(0xe9)])
+		);
+		assert_eq!(string.to_latin1(), "café");
+
+		assert_eq!(
+			String8::from_latin1("a€b"),
+			Err(UnencodableCharacter {
+				character: '€',
+				index: 1,
+			}),
+		);
+		assert_eq!(
+			String8::from_latin1_lossy("a€b", Char8(b'?')).to_latin1(),
+			"a?b",
+		);
+	}
+
+	#[test]
+	fn test_string16_ucs2() {
+		let string = String16::from_ucs2("a€").unwrap();
+		assert_eq!(
+			string,
+			String16::from(vec![Char16(0x00, 0x61), Char16(0x20, 0xac)])
+		);
+		assert_eq!(string.to_string_lossy(), "a€");
+
+		assert_eq!(
+			String16::from_ucs2("€🦀"),
+			Err(UnencodableCharacter {
+				character: '🦀',
+				index: 3,
+			}),
+		);
+		assert_eq!(
+			String16::from_ucs2_lossy("€🦀", Char16(0xff, 0xfd)).to_string_lossy(),
+			"€\u{fffd}",
+		);
+
+		// Surrogate pairs are not combined.
+		let string = String16::from(vec![Char16(0xd8, 0x3e), Char16(0xdd, 0x80)]);
+		assert_eq!(string.to_string_lossy(), "\u{fffd}\u{fffd}");
+	}
+
+	#[test]
+	fn test_const_constructors() {
+		use crate::{
+			set::{PercentOrDefault, PitchOrDefault},
+			unit::{Hz, Percentage},
+		};
+
+		const ROOT: Window = Window::new(0x0000_0100);
+		const MASK: EventMask = EventMask::EXPOSURE.union(EventMask::STRUCTURE_NOTIFY);
+		const VOLUME: PercentOrDefault = match PercentOrDefault::new(50) {
+			Ok(volume) => volume,
+			Err(_) => panic!("50% is a valid volume"),
+		};
+		const PITCH: PitchOrDefault = match PitchOrDefault::new(-1) {
+			Ok(pitch) => pitch,
+			Err(_) => panic!("-1 resets the pitch"),
+		};
+
+		assert_eq!(ROOT.unwrap(), 0x0000_0100);
+		assert_eq!(MASK.bits(), 0x0002_8000);
+		assert_eq!(VOLUME.unwrap(), Percentage::new(50).ok());
+		assert_eq!(PITCH, PitchOrDefault::Reset);
+
+		assert!(PercentOrDefault::new(-2).is_err());
+		assert!(PercentOrDefault::new(101).is_err());
+		assert_eq!(PitchOrDefault::new(255), Ok(PitchOrDefault::Pitch(Hz(255))));
+		assert!(PitchOrDefault::new(256).is_err());
+	}
+
+	#[test]
+	fn test_geometry_bounds() {
+		let max = i32::from(i16::MAX);
+		let min = i32::from(i16::MIN);
+
+		assert_eq!(
+			Coords::try_new(max, min),
+			Ok(Coords::new(Px(i16::MAX), Px(i16::MIN)))
+		);
+		assert_eq!(Coords::try_new(max + 1, 0).unwrap_err().field, "x");
+		assert_eq!(Coords::try_new(0, min - 1).unwrap_err().field, "y");
+		assert_eq!(
+			Coords::saturating_new(max + 1, min - 1),
+			Coords::new(Px(i16::MAX), Px(i16::MIN))
+		);
+
+		let max = u32::from(u16::MAX);
+
+		assert_eq!(
+			Dimensions::try_new(max, 0),
+			Ok(Dimensions::new(Px(u16::MAX), Px(0)))
+		);
+		assert_eq!(Dimensions::try_new(0, max + 1).unwrap_err().field, "height");
+		assert_eq!(
+			Dimensions::saturating_new(max + 1, u32::MAX),
+			Dimensions::new(Px(u16::MAX), Px(u16::MAX))
+		);
+
+		// A negative size converted to a `u32` wraps around to a size which
+		// is far too large.
+		let negative = (-1_i32).cast_unsigned();
+
+		let error = Rectangle::try_new(0, 0, negative, 1).unwrap_err();
+		assert_eq!(error.field, "width");
+		assert_eq!(error.bounds.found, i64::from(u32::MAX));
+
+		assert_eq!(
+			Rectangle::saturating_new(-100_000, 100_000, negative, 10),
+			Rectangle::new(Px(i16::MIN), Px(i16::MAX), Px(u16::MAX), Px(10))
+		);
+	}
+}
@@ -4,8 +4,12 @@
 
 //! [`Atom`] and predefined atom `const`s defined in the core protocol.
 
-use derive_more::{From, Into};
-use xrbk_macro::{ConstantX11Size, Readable, Wrap, Writable, X11Size};
+use derive_more::Into;
+use xrbk_macro::{ConstantX11Size, InfallibleWritable, Readable, Wrap, Writable, X11Size};
+
+pub use cache::AtomCache;
+
+mod cache;
 
 /// A unique ID corresponding to a string name.
 ///
@@ -17,13 +21,13 @@ use xrbk_macro::{ConstantX11Size, Readable, Wrap, Writable, X11Size};
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Atom(u32);
@@ -53,6 +57,9 @@ macro_rules! atoms {
 			$(#[$attr])*
 			pub const $ATOM: Atom = Atom::new($id);
 		)*
+
+		/// The names of every predefined [`Atom`], paired with that [`Atom`].
+		const PREDEFINED: &[(&str, Atom)] = &[$((stringify!($ATOM), $ATOM)),*];
 	}
 }
 
@@ -0,0 +1,241 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::collections::HashMap;
+
+use super::{Atom, PREDEFINED};
+use crate::{
+	x11::{reply, request},
+	String8,
+	UnencodableCharacter,
+};
+
+/// A cache of the [`Atom`]s known by a client, and the names they correspond
+/// to.
+///
+/// Clients commonly need many [`Atom`]s, and interning each of them one at a
+/// time requires a round trip to the X server for each. An `AtomCache` instead
+/// produces a batch of [`GetAtom` requests] for every name it does not already
+/// know, matches their [replies] by sequence number, and thereafter answers
+/// lookups in both directions without contacting the X server.
+///
+/// Every [predefined atom] is known from the start.
+///
+/// # Examples
+/// ```no_run
+/// # use xrb::{atom::AtomCache, x11::{reply, request}};
+/// # fn send(request: &request::GetAtom) -> u16 { todo!() }
+/// # fn receive() -> reply::GetAtom { todo!() }
+/// let mut cache = AtomCache::new();
+///
+/// let requests = cache.requests(["WM_PROTOCOLS", "WM_DELETE_WINDOW"], false)?;
+///
+/// for request in &requests {
+///     let sequence = send(request);
+///     cache.sent(request, sequence);
+/// }
+///
+/// for _ in &requests {
+///     cache.receive(&receive());
+/// }
+///
+/// let wm_protocols = cache.get("WM_PROTOCOLS");
+/// # Ok::<(), xrb::UnencodableCharacter>(())
+/// ```
+///
+/// [`GetAtom` requests]: request::GetAtom
+/// [replies]: reply::GetAtom
+/// [predefined atom]: super
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AtomCache {
+	atoms: HashMap<String, Atom>,
+	names: HashMap<Atom, String>,
+
+	/// The names of [`GetAtom` requests] which have been sent but have not
+	/// yet received a [reply], indexed by their sequence numbers.
+	///
+	/// [`GetAtom` requests]: request::GetAtom
+	/// [reply]: reply::GetAtom
+	pending: HashMap<u16, String>,
+}
+
+impl Default for AtomCache {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl AtomCache {
+	/// Creates a new `AtomCache` containing every [predefined atom].
+	///
+	/// [predefined atom]: super
+	#[must_use]
+	pub fn new() -> Self {
+		let mut cache = Self {
+			atoms: HashMap::new(),
+			names: HashMap::new(),
+
+			pending: HashMap::new(),
+		};
+
+		for &(name, atom) in PREDEFINED {
+			cache.insert(name.to_owned(), atom);
+		}
+
+		cache
+	}
+
+	/// Returns the [`Atom`] with the given `name`, if it is known.
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<Atom> {
+		self.atoms.get(name).copied()
+	}
+
+	/// Returns the name of the given `atom`, if it is known.
+	#[must_use]
+	pub fn name(&self, atom: Atom) -> Option<&str> {
+		self.names.get(&atom).map(String::as_str)
+	}
+
+	/// Records that the given `atom` has the given `name`.
+	pub fn insert(&mut self, name: String, atom: Atom) {
+		self.names.insert(atom, name.clone());
+		self.atoms.insert(name, atom);
+	}
+
+	/// Returns whether there are [`GetAtom` requests] which have been
+	/// [sent] but not yet [received] a [reply].
+	///
+	/// [`GetAtom` requests]: request::GetAtom
+	/// [reply]: reply::GetAtom
+	///
+	/// [sent]: AtomCache::sent
+	/// [received]: AtomCache::receive
+	#[must_use]
+	pub fn is_pending(&self) -> bool {
+		!self.pending.is_empty()
+	}
+
+	/// Returns a [`GetAtom` request] for each of the given `names` which is
+	/// neither known nor awaiting a [reply].
+	///
+	/// Each [request] returned must be passed to [`sent`] along with its
+	/// sequence number once it has been sent.
+	///
+	/// See [`GetAtom::no_creation`] for the meaning of `no_creation`.
+	///
+	/// # Errors
+	/// Returns an [`UnencodableCharacter`] error if one of the `names` cannot
+	/// be encoded as Latin-1.
+	///
+	/// [`GetAtom` request]: request::GetAtom
+	/// [request]: request::GetAtom
+	/// [reply]: reply::GetAtom
+	///
+	/// [`sent`]: AtomCache::sent
+	/// [`GetAtom::no_creation`]: request::GetAtom::no_creation
+	pub fn requests<'names>(
+		&self, names: impl IntoIterator<Item = &'names str>, no_creation: bool,
+	) -> Result<Vec<request::GetAtom>, UnencodableCharacter> {
+		let mut requested: Vec<&str> = Vec::new();
+		let mut requests = Vec::new();
+
+		for name in names {
+			let known = self.atoms.contains_key(name)
+				|| self.pending.values().any(|pending| pending == name)
+				|| requested.contains(&name);
+
+			if !known {
+				requests.push(request::GetAtom {
+					no_creation,
+					name: String8::from_latin1(name)?,
+				});
+				requested.push(name);
+			}
+		}
+
+		Ok(requests)
+	}
+
+	/// Records that the given `request` was sent with the given `sequence`
+	/// number.
+	pub fn sent(&mut self, request: &request::GetAtom, sequence: u16) {
+		self.pending.insert(sequence, request.name.to_latin1());
+	}
+
+	/// Records the [`Atom`] returned in the given `reply`, returning it.
+	///
+	/// Returns [`None`] if the `reply` does not match a [sent] [request], or
+	/// if the [request]'s `no_creation` was `true` and no such [`Atom`]
+	/// exists.
+	///
+	/// [request]: request::GetAtom
+	/// [sent]: AtomCache::sent
+	pub fn receive(&mut self, reply: &reply::GetAtom) -> Option<Atom> {
+		let name = self.pending.remove(&reply.sequence)?;
+		let atom = reply.atom?;
+
+		self.insert(name, atom);
+
+		Some(atom)
+	}
+
+	/// Forgets the [request] sent with the given `sequence` number.
+	///
+	/// This should be called if an [error] is received in place of a [reply].
+	///
+	/// [request]: request::GetAtom
+	/// [reply]: reply::GetAtom
+	/// [error]: crate::message::Error
+	pub fn discard(&mut self, sequence: u16) {
+		self.pending.remove(&sequence);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::atom::WM_NAME;
+
+	#[test]
+	fn test_atom_cache() {
+		let mut cache = AtomCache::new();
+
+		assert_eq!(cache.get("WM_NAME"), Some(WM_NAME));
+		assert_eq!(cache.name(WM_NAME), Some("WM_NAME"));
+
+		let requests = cache
+			.requests(
+				["WM_NAME", "WM_PROTOCOLS", "_NET_WM_NAME", "WM_PROTOCOLS"],
+				true,
+			)
+			.unwrap();
+		assert_eq!(requests.len(), 2);
+
+		cache.sent(&requests[0], 10);
+		cache.sent(&requests[1], 11);
+		assert!(cache.requests(["WM_PROTOCOLS"], true).unwrap().is_empty());
+
+		let protocols = Atom::new(300);
+		assert_eq!(
+			cache.receive(&reply::GetAtom {
+				sequence: 10,
+				atom: Some(protocols),
+			}),
+			Some(protocols),
+		);
+		assert_eq!(
+			cache.receive(&reply::GetAtom {
+				sequence: 11,
+				atom: None,
+			}),
+			None,
+		);
+		assert!(!cache.is_pending());
+
+		assert_eq!(cache.get("WM_PROTOCOLS"), Some(protocols));
+		assert_eq!(cache.name(protocols), Some("WM_PROTOCOLS"));
+		assert_eq!(cache.get("_NET_WM_NAME"), None);
+	}
+}
@@ -5,10 +5,12 @@
 #![allow(missing_docs)]
 
 use bitflags::bitflags;
-use xrbk_macro::{ConstantX11Size, Readable, Writable, X11Size};
+use xrbk_macro::{ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
+
+use crate::set::{AttributesMask, GraphicsOptionsMask, KeyboardOptionsMask, Led, WindowConfigMask};
 
 bitflags! {
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct ColorChannelMask: u8 {
 		/// Whether the red color channel is enabled.
 		const RED = 0x01;
@@ -19,7 +21,7 @@ bitflags! {
 	}
 
 	/// A mask of events.
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct EventMask: u32 {
 		/// Key press events.
 		const KEY_PRESS = 0x0000_0001;
@@ -90,7 +92,7 @@ bitflags! {
 		const EXPOSURE = 0x0000_8000;
 		/// Events generated when the [visibility] of a window changes.
 		///
-		/// [visibility]: crate::x11::MapState
+		/// [visibility]: crate::VisibilityState
 		const VISIBILITY_CHANGE = 0x0001_0000;
 
 		/// Events generated when the structure of a window changes.
@@ -160,57 +162,60 @@ bitflags! {
 	/// - `PROPERTY_CHANGE`
 	/// - `COLORMAP_CHANGE`
 	/// - `OWNER_GRAB_BUTTON`
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
-	pub struct CursorEventMask: u32 {
+	///
+	/// Since every event it permits fits within 16 bits, a `CursorEventMask` is
+	/// encoded in 2 bytes.
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
+	pub struct CursorEventMask: u16 {
 		// removes KEY_PRESS and KEY_RELEASE
 		/// Mouse button press events.
-		const BUTTON_PRESS = 0x0000_0004;
+		const BUTTON_PRESS = 0x0004;
 		/// Mouse button release events.
-		const BUTTON_RELEASE = 0x0000_0008;
+		const BUTTON_RELEASE = 0x0008;
 
 		/// Cursor events generated when the cursor enters a window.
 		///
 		/// `ENTER_WINDOW` events are generated not only when the cursor moves
 		/// to enter another window, but when the window under the cursor's
 		/// current position changes.
-		const ENTER_WINDOW = 0x0000_0010;
+		const ENTER_WINDOW = 0x0010;
 		/// Cursor events generated when the cursor leaves a window.
 		///
 		/// `LEAVE_WINDOW` events are generated not only when the cursor moves
 		/// away from a window, but when the window under the cursor's current
 		/// position moves or changes to a different window.
-		const LEAVE_WINDOW = 0x0000_0020;
+		const LEAVE_WINDOW = 0x0020;
 
 		/// Cursor motion events generated when the cursor's position changes.
-		const ANY_MOTION = 0x0000_0040;
-		const MOTION_HINT = 0x0000_0080;
+		const ANY_MOTION = 0x0040;
+		const MOTION_HINT = 0x0080;
 		/// Cursor 'drag' events when the primary mouse button is held.
 		///
 		/// The primary mouse button is usually the one on the left, but many
 		/// tools offer options to switch the positions of the primary and
 		/// secondary mouse buttons.
-		const BUTTON_1_MOTION = 0x0000_0100;
+		const BUTTON_1_MOTION = 0x0100;
 		/// Cursor 'drag' events when the middle mouse button is held.
-		const BUTTON_2_MOTION = 0x0000_0200;
+		const BUTTON_2_MOTION = 0x0200;
 		/// Cursor 'drag' events when the secondary mouse button is held.
 		///
 		/// The secondary mouse button is usually the one on the right, but many
 		/// tools offer options to switch the positions of the primary and
 		/// secondary mouse buttons.
-		const BUTTON_3_MOTION = 0x0000_0400;
+		const BUTTON_3_MOTION = 0x0400;
 		/// Cursor 'drag' events when 'mouse button 4' is held.
-		const BUTTON_4_MOTION = 0x0000_0800;
+		const BUTTON_4_MOTION = 0x0800;
 		/// Cursor 'drag' events when 'mouse button 5' is held.
-		const BUTTON_5_MOTION = 0x0000_1000;
+		const BUTTON_5_MOTION = 0x1000;
 		/// Cursor 'drag' events when any mouse button is held.
-		const ANY_BUTTON_MOTION = 0x0000_2000;
+		const ANY_BUTTON_MOTION = 0x2000;
 
 		/// Events generated after every [`EnterWindow`] and [`Focus`] event
 		/// reporting the currently held keys.
 		///
 		/// [`EnterWindow`]: crate::x11::event::EnterWindow
 		/// [`Focus`]: crate::x11::event::Focus
-		const KEY_STATE = 0x0000_4000;
+		const KEY_STATE = 0x4000;
 
 		// removes other events irrelevant to the cursor and buttons
 	}
@@ -236,7 +241,7 @@ bitflags! {
 	/// - `PROPERTY_CHANGE`
 	/// - `COLORMAP_CHANGE`
 	/// - `OWNER_GRAB_BUTTON`
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct DeviceEventMask: u32 {
 		/// Key press events.
 		const KEY_PRESS = 0x0000_0001;
@@ -283,7 +288,7 @@ bitflags! {
 	///
 	/// This is the same as [`ModifierKeyMask`], but with masks for currently
 	/// held mouse buttons.
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct ModifierMask: u16 {
 		/// Whether `Shift` is held.
 		const SHIFT = 0x0001;
@@ -333,7 +338,7 @@ bitflags! {
 	/// mask for [`ANY_MODIFIER`].
 	///
 	/// [`ANY_MODIFIER`]: AnyModifierKeyMask::ANY_MODIFIER
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct ModifierKeyMask: u16 {
 		/// Whether `Shift` is held.
 		const SHIFT = 0x0001;
@@ -365,7 +370,7 @@ bitflags! {
 	/// [`ANY_MODIFIER`].
 	///
 	/// [`ANY_MODIFIER`]: AnyModifierKeyMask::ANY_MODIFIER
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct AnyModifierKeyMask: u16 {
 		/// Whether `Shift` is held.
 		const SHIFT = 0x0001;
@@ -375,7 +380,7 @@ bitflags! {
 		const CONTROL = 0x0004;
 
 		/// Whether 'modifier key 1' is held.
-		const MOD_1 = 0x0009;
+		const MOD_1 = 0x0008;
 		/// Whether 'modifier key 2' is held.
 		const MOD_2 = 0x0010;
 		/// Whether 'modifier key 3' is held.
@@ -391,6 +396,433 @@ bitflags! {
 		// removes BUTTON_#
 		// adds ANY_MODIFIER
 		/// Whether _any_ modifier key is held.
+		///
+		/// This is the protocol's `AnyModifier` value. It cannot be combined
+		/// meaningfully with any other modifier.
 		const ANY_MODIFIER = 0x8000;
 	}
+
+	/// A mask of keyboard [LEDs].
+	///
+	/// The least significant bit represents [LED] 1, and the most significant
+	/// bit represents [LED] 32.
+	///
+	/// [LED]: crate::set::Led
+	/// [LEDs]: crate::set::Led
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
+	pub struct LedMask: u32 {
+		/// Whether LED 1 is lit.
+		const LED_1 = 0x0000_0001;
+		/// Whether LED 2 is lit.
+		const LED_2 = 0x0000_0002;
+		/// Whether LED 3 is lit.
+		const LED_3 = 0x0000_0004;
+		/// Whether LED 4 is lit.
+		const LED_4 = 0x0000_0008;
+		/// Whether LED 5 is lit.
+		const LED_5 = 0x0000_0010;
+		/// Whether LED 6 is lit.
+		const LED_6 = 0x0000_0020;
+		/// Whether LED 7 is lit.
+		const LED_7 = 0x0000_0040;
+		/// Whether LED 8 is lit.
+		const LED_8 = 0x0000_0080;
+		/// Whether LED 9 is lit.
+		const LED_9 = 0x0000_0100;
+		/// Whether LED 10 is lit.
+		const LED_10 = 0x0000_0200;
+		/// Whether LED 11 is lit.
+		const LED_11 = 0x0000_0400;
+		/// Whether LED 12 is lit.
+		const LED_12 = 0x0000_0800;
+		/// Whether LED 13 is lit.
+		const LED_13 = 0x0000_1000;
+		/// Whether LED 14 is lit.
+		const LED_14 = 0x0000_2000;
+		/// Whether LED 15 is lit.
+		const LED_15 = 0x0000_4000;
+		/// Whether LED 16 is lit.
+		const LED_16 = 0x0000_8000;
+		/// Whether LED 17 is lit.
+		const LED_17 = 0x0001_0000;
+		/// Whether LED 18 is lit.
+		const LED_18 = 0x0002_0000;
+		/// Whether LED 19 is lit.
+		const LED_19 = 0x0004_0000;
+		/// Whether LED 20 is lit.
+		const LED_20 = 0x0008_0000;
+		/// Whether LED 21 is lit.
+		const LED_21 = 0x0010_0000;
+		/// Whether LED 22 is lit.
+		const LED_22 = 0x0020_0000;
+		/// Whether LED 23 is lit.
+		const LED_23 = 0x0040_0000;
+		/// Whether LED 24 is lit.
+		const LED_24 = 0x0080_0000;
+		/// Whether LED 25 is lit.
+		const LED_25 = 0x0100_0000;
+		/// Whether LED 26 is lit.
+		const LED_26 = 0x0200_0000;
+		/// Whether LED 27 is lit.
+		const LED_27 = 0x0400_0000;
+		/// Whether LED 28 is lit.
+		const LED_28 = 0x0800_0000;
+		/// Whether LED 29 is lit.
+		const LED_29 = 0x1000_0000;
+		/// Whether LED 30 is lit.
+		const LED_30 = 0x2000_0000;
+		/// Whether LED 31 is lit.
+		const LED_31 = 0x4000_0000;
+		/// Whether LED 32 is lit.
+		const LED_32 = 0x8000_0000;
+	}
+}
+
+impl ModifierMask {
+	/// Returns the modifier keys in this mask, discarding any mouse buttons.
+	#[must_use]
+	pub const fn keys(&self) -> ModifierKeyMask {
+		ModifierKeyMask::from_bits_truncate(self.bits())
+	}
+}
+
+impl From<ModifierKeyMask> for ModifierMask {
+	fn from(mask: ModifierKeyMask) -> Self {
+		// Every bit in a `ModifierKeyMask` has the same meaning in a
+		// `ModifierMask`.
+		Self::from_bits_truncate(mask.bits())
+	}
+}
+
+impl AnyModifierKeyMask {
+	/// Matches any combination of modifiers, including no modifiers.
+	///
+	/// This is an alias of [`ANY_MODIFIER`], the protocol's `AnyModifier`
+	/// value.
+	///
+	/// [`ANY_MODIFIER`]: AnyModifierKeyMask::ANY_MODIFIER
+	#[doc(alias = "AnyModifier")]
+	pub const ANY: Self = Self::ANY_MODIFIER;
+
+	/// Returns whether this mask is [`ANY_MODIFIER`].
+	///
+	/// [`ANY_MODIFIER`]: AnyModifierKeyMask::ANY_MODIFIER
+	#[must_use]
+	pub const fn is_any(&self) -> bool {
+		self.contains(Self::ANY_MODIFIER)
+	}
+}
+
+impl From<ModifierKeyMask> for AnyModifierKeyMask {
+	fn from(mask: ModifierKeyMask) -> Self {
+		// Every bit in a `ModifierKeyMask` has the same meaning in an
+		// `AnyModifierKeyMask`.
+		Self::from_bits_truncate(mask.bits())
+	}
+}
+
+impl LedMask {
+	/// The LED conventionally used for `Caps Lock`.
+	pub const CAPS_LOCK: Self = Self::LED_1;
+	/// The LED conventionally used for `Num Lock`.
+	pub const NUM_LOCK: Self = Self::LED_2;
+	/// The LED conventionally used for `Scroll Lock`.
+	pub const SCROLL_LOCK: Self = Self::LED_3;
+
+	/// Returns the mask containing only the given [LED].
+	///
+	/// [LED]: Led
+	#[must_use]
+	pub const fn led(led: Led) -> Self {
+		Self::from_bits_truncate(1 << (led.unwrap() - 1))
+	}
+
+	/// Returns whether the given [LED] is lit in this mask.
+	///
+	/// [LED]: Led
+	#[must_use]
+	pub const fn is_lit(&self, led: Led) -> bool {
+		self.contains(Self::led(led))
+	}
+}
+
+impl From<Led> for LedMask {
+	fn from(led: Led) -> Self {
+		Self::led(led)
+	}
+}
+
+/// An error generated when converting an [`AnyModifierKeyMask`] which contains
+/// [`ANY_MODIFIER`] into a [`ModifierKeyMask`].
+///
+/// [`ANY_MODIFIER`]: AnyModifierKeyMask::ANY_MODIFIER
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, thiserror::Error)]
+#[error("`AnyModifier` cannot be represented in a `ModifierKeyMask`")]
+pub struct UnexpectedAnyModifier;
+
+impl TryFrom<AnyModifierKeyMask> for ModifierKeyMask {
+	type Error = UnexpectedAnyModifier;
+
+	fn try_from(mask: AnyModifierKeyMask) -> Result<Self, Self::Error> {
+		if mask.is_any() {
+			return Err(UnexpectedAnyModifier);
+		}
+
+		Ok(Self::from_bits_truncate(mask.bits()))
+	}
+}
+
+/// An error generated when an [`EventMask`] contains events which are not
+/// permitted in a more restricted mask, such as a [`CursorEventMask`] or a
+/// [`DeviceEventMask`].
+///
+/// The X server would otherwise generate a [`Value` error] for a [request]
+/// containing such a mask.
+///
+/// [request]: crate::message::Request
+/// [`Value` error]: crate::x11::error::Value
+#[derive(Debug, Hash, PartialEq, Eq, thiserror::Error)]
+#[error("the event mask contains events which are not permitted here: {illegal:?}")]
+pub struct IllegalEventMask {
+	/// The events in the mask which are not permitted.
+	pub illegal: EventMask,
+}
+
+macro_rules! restricted_event_mask {
+	($($Mask:ident),+$(,)?) => {
+		$(
+			impl From<$Mask> for EventMask {
+				fn from(mask: $Mask) -> Self {
+					// Every bit in the restricted mask has the same meaning in an
+					// `EventMask`.
+					Self::from_bits_truncate(mask.bits().into())
+				}
+			}
+
+			impl TryFrom<EventMask> for $Mask {
+				type Error = IllegalEventMask;
+
+				fn try_from(mask: EventMask) -> Result<Self, Self::Error> {
+					let illegal = mask - EventMask::from(Self::all());
+
+					if illegal.is_empty() {
+						Ok(Self::from_event_mask_truncate(mask))
+					} else {
+						Err(IllegalEventMask { illegal })
+					}
+				}
+			}
+
+			impl $Mask {
+				#[doc = concat!(
+					"Converts `mask` to a `", stringify!($Mask), "`, discarding ",
+					"any events which are not permitted in it.",
+				)]
+				#[must_use]
+				// Every event permitted in the restricted mask fits within its
+				// bits, so only events which are discarded anyway are truncated.
+				#[allow(clippy::cast_possible_truncation)]
+				pub const fn from_event_mask_truncate(mask: EventMask) -> Self {
+					Self::from_bits_truncate(mask.bits() as _)
+				}
+			}
+		)+
+	};
+}
+
+restricted_event_mask!(CursorEventMask, DeviceEventMask);
+
+impl EventMask {
+	/// Returns whether every event in this mask is permitted in a
+	/// [`CursorEventMask`], as used by cursor grabs.
+	#[must_use]
+	pub const fn is_cursor_event_mask(&self) -> bool {
+		self.bits() & !(CursorEventMask::all().bits() as u32) == 0
+	}
+
+	/// Returns whether every event in this mask is permitted in a
+	/// [`DeviceEventMask`], as used by a `do_not_propagate_mask`.
+	#[must_use]
+	pub const fn is_device_event_mask(&self) -> bool {
+		DeviceEventMask::from_bits(self.bits()).is_some()
+	}
+}
+
+/// A mask whose flags have names, such as an [`EventMask`].
+///
+/// This is implemented for every mask in XRB.
+pub trait NamedFlags: Copy + 'static {
+	/// The name of the mask's type.
+	const NAME: &'static str;
+	/// The name and value of each of the mask's flags, in order of their bits.
+	///
+	/// Aliases of flags, such as [`LedMask::CAPS_LOCK`], are not included.
+	const FLAGS: &'static [(&'static str, Self)];
+
+	/// Returns the mask's bits, zero-extended to a [`u32`].
+	fn to_bits(self) -> u32;
+}
+
+macro_rules! named_flags {
+	($($Mask:ident { $($FLAG:ident),+$(,)? })+) => {
+		$(
+			impl NamedFlags for $Mask {
+				const NAME: &'static str = stringify!($Mask);
+				const FLAGS: &'static [(&'static str, Self)] = &[
+					$((stringify!($FLAG), Self::$FLAG)),+
+				];
+
+				fn to_bits(self) -> u32 {
+					self.bits().into()
+				}
+			}
+		)+
+	};
+}
+
+named_flags! {
+	ColorChannelMask { RED, GREEN, BLUE }
+	EventMask {
+		KEY_PRESS, KEY_RELEASE, BUTTON_PRESS, BUTTON_RELEASE, ENTER_WINDOW, LEAVE_WINDOW,
+		ANY_MOTION, MOTION_HINT, BUTTON_1_MOTION, BUTTON_2_MOTION, BUTTON_3_MOTION,
+		BUTTON_4_MOTION, BUTTON_5_MOTION, ANY_BUTTON_MOTION, KEYBOARD_STATE, EXPOSURE,
+		VISIBILITY_CHANGE, STRUCTURE_NOTIFY, RESIZE_REDIRECT, SUBSTRUCTURE_NOTIFY,
+		SUBSTRUCTURE_REDIRECT, FOCUS_CHANGE, PROPERTY_CHANGE, COLORMAP_CHANGE, OWNER_GRAB_BUTTON,
+	}
+	CursorEventMask {
+		BUTTON_PRESS, BUTTON_RELEASE, ENTER_WINDOW, LEAVE_WINDOW, ANY_MOTION, MOTION_HINT,
+		BUTTON_1_MOTION, BUTTON_2_MOTION, BUTTON_3_MOTION, BUTTON_4_MOTION, BUTTON_5_MOTION,
+		ANY_BUTTON_MOTION, KEY_STATE,
+	}
+	DeviceEventMask {
+		KEY_PRESS, KEY_RELEASE, BUTTON_PRESS, BUTTON_RELEASE, ANY_MOTION, BUTTON_1_MOTION,
+		BUTTON_2_MOTION, BUTTON_3_MOTION, BUTTON_4_MOTION, BUTTON_5_MOTION, ANY_BUTTON_MOTION,
+	}
+	ModifierMask {
+		SHIFT, LOCK, CONTROL, MOD_1, MOD_2, MOD_3, MOD_4, MOD_5, BUTTON_1, BUTTON_2, BUTTON_3,
+		BUTTON_4, BUTTON_5,
+	}
+	ModifierKeyMask { SHIFT, LOCK, CONTROL, MOD_1, MOD_2, MOD_3, MOD_4, MOD_5 }
+	AnyModifierKeyMask { SHIFT, LOCK, CONTROL, MOD_1, MOD_2, MOD_3, MOD_4, MOD_5, ANY_MODIFIER }
+	LedMask {
+		LED_1, LED_2, LED_3, LED_4, LED_5, LED_6, LED_7, LED_8, LED_9, LED_10, LED_11, LED_12,
+		LED_13, LED_14, LED_15, LED_16, LED_17, LED_18, LED_19, LED_20, LED_21, LED_22, LED_23,
+		LED_24, LED_25, LED_26, LED_27, LED_28, LED_29, LED_30, LED_31, LED_32,
+	}
+	AttributesMask {
+		BACKGROUND_PIXMAP, BACKGROUND_COLOR, BORDER_PIXMAP, BORDER_COLOR, BIT_GRAVITY,
+		WINDOW_GRAVITY, MAINTAIN_CONTENTS, MAINTAINED_PLANES, MAINTENANCE_FALLBACK_COLOR,
+		OVERRIDE_REDIRECT, MAINTAIN_WINDOWS_UNDER, EVENT_MASK, DO_NOT_PROPAGATE_MASK, COLORMAP,
+		CURSOR_APPEARANCE,
+	}
+	WindowConfigMask { X, Y, WIDTH, HEIGHT, BORDER_WIDTH, SIBLING, STACK_MODE }
+	GraphicsOptionsMask {
+		FUNCTION, PLANE_MASK, FOREGROUND_COLOR, BACKGROUND_COLOR, LINE_WIDTH, LINE_STYLE,
+		CAP_STYLE, JOIN_STYLE, FILL_STYLE, FILL_RULE, TILE, STIPPLE, TILE_STIPPLE_X,
+		TILE_STIPPLE_Y, FONT, CHILD_MODE, GRAPHICS_EXPOSURE, CLIP_X, CLIP_Y, CLIP_MASK,
+		DASH_OFFSET, DASHES, ARC_MODE,
+	}
+	KeyboardOptionsMask {
+		KEY_CLICK_VOLUME, BELL_VOLUME, BELL_PITCH, BELL_DURATION, LED, LED_MODE,
+		AUTO_REPEATED_KEY, AUTO_REPEAT_MODE,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_restricted_event_masks() {
+		let mask = EventMask::BUTTON_PRESS | EventMask::ENTER_WINDOW;
+		assert!(mask.is_cursor_event_mask());
+		assert!(!mask.is_device_event_mask());
+
+		assert_eq!(
+			CursorEventMask::try_from(mask),
+			Ok(CursorEventMask::BUTTON_PRESS | CursorEventMask::ENTER_WINDOW),
+		);
+		assert_eq!(
+			DeviceEventMask::try_from(mask),
+			Err(IllegalEventMask {
+				illegal: EventMask::ENTER_WINDOW,
+			}),
+		);
+		assert_eq!(
+			DeviceEventMask::from_event_mask_truncate(mask),
+			DeviceEventMask::BUTTON_PRESS,
+		);
+
+		assert_eq!(
+			EventMask::from(CursorEventMask::KEY_STATE),
+			EventMask::KEYBOARD_STATE
+		);
+	}
+
+	#[test]
+	fn test_modifier_mask_conversions() {
+		assert_eq!(
+			AnyModifierKeyMask::MOD_1.bits(),
+			ModifierKeyMask::MOD_1.bits()
+		);
+		assert_eq!(AnyModifierKeyMask::ANY.bits(), 0x8000);
+
+		let keys = ModifierKeyMask::SHIFT | ModifierKeyMask::MOD_1;
+		assert_eq!(
+			AnyModifierKeyMask::from(keys),
+			AnyModifierKeyMask::SHIFT | AnyModifierKeyMask::MOD_1,
+		);
+		assert_eq!(
+			ModifierKeyMask::try_from(AnyModifierKeyMask::from(keys)),
+			Ok(keys)
+		);
+		assert_eq!(
+			ModifierKeyMask::try_from(AnyModifierKeyMask::ANY),
+			Err(UnexpectedAnyModifier),
+		);
+
+		let state = ModifierMask::from(keys) | ModifierMask::BUTTON_1;
+		assert_eq!(state.keys(), keys);
+	}
+
+	#[test]
+	fn test_named_flags() {
+		fn check<Mask: NamedFlags>(all: Mask) {
+			let mut named = 0;
+
+			for &(name, flag) in Mask::FLAGS {
+				assert_eq!(flag.to_bits().count_ones(), 1, "{}::{name}", Mask::NAME);
+				assert_eq!(named & flag.to_bits(), 0, "{}::{name}", Mask::NAME);
+
+				named |= flag.to_bits();
+			}
+
+			// Every flag of the mask is named.
+			assert_eq!(named, all.to_bits(), "{}", Mask::NAME);
+		}
+
+		check(ColorChannelMask::all());
+		check(EventMask::all());
+		check(CursorEventMask::all());
+		check(DeviceEventMask::all());
+		check(ModifierMask::all());
+		check(ModifierKeyMask::all());
+		check(AnyModifierKeyMask::all());
+		check(LedMask::all());
+		check(AttributesMask::all());
+		check(WindowConfigMask::all());
+		check(GraphicsOptionsMask::all());
+		check(KeyboardOptionsMask::all());
+	}
+
+	#[test]
+	fn test_led_mask() {
+		let num_lock = Led::new(2).unwrap();
+		let mask = LedMask::CAPS_LOCK | LedMask::from(num_lock);
+
+		assert_eq!(mask.bits(), 0b11);
+		assert!(mask.is_lit(num_lock));
+		assert!(!mask.is_lit(Led::new(3).unwrap()));
+		assert_eq!(LedMask::led(Led::new(32).unwrap()), LedMask::LED_32);
+	}
 }
@@ -2,8 +2,115 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use derive_more::{From, Into};
-use xrbk_macro::{new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size};
+use derive_more::Into;
+use thiserror::Error;
+use xrbk::{Buf, ReadError, ReadResult, Readable};
+use xrbk_macro::{unwrap, ConstantX11Size, InfallibleWritable, Wrap, Writable, X11Size};
+
+use crate::{visual::VisualId, Atom};
+
+/// A resource ID as it is represented by other X11 libraries.
+///
+/// Resource IDs only use their bottom 29 bits: the top three bits are always
+/// zero.
+pub type RawXid = u32;
+
+/// The bits of a [`RawXid`] which may be used by a resource ID.
+const XID_MASK: RawXid = 0x1fff_ffff;
+
+/// An error generated when converting a [`RawXid`] with any of its top three
+/// bits set to a resource ID.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+#[error("resource IDs only use their bottom 29 bits, found {0:#010x}")]
+pub struct XidOutOfRange(pub RawXid);
+
+/// Implements constructors, [`TryFrom<RawXid>`], and [`Readable`] for
+/// resource IDs, checking that the [`RawXid`] only uses its bottom 29 bits.
+macro_rules! impl_raw_xid {
+	($($Id:ident),+$(,)?) => {
+		$(
+			impl $Id {
+				#[doc = concat!("Returns a new `", stringify!($Id), "`.")]
+				///
+				/// # Panics
+				/// In debug builds, this panics if any of the top three bits of
+				/// the `id` are set. Use [`try_new`](Self::try_new) to check
+				/// this in release builds too.
+				#[must_use]
+				pub const fn new(id: RawXid) -> Self {
+					debug_assert!(
+						id & !XID_MASK == 0,
+						"resource IDs only use their bottom 29 bits",
+					);
+
+					Self(id)
+				}
+
+				#[doc = concat!("Returns a new `", stringify!($Id), "`, checking that the `id`")]
+				/// only uses its bottom 29 bits.
+				///
+				/// # Errors
+				/// Returns [`XidOutOfRange`] if any of the top three bits of the
+				/// `id` are set.
+				pub const fn try_new(id: RawXid) -> Result<Self, XidOutOfRange> {
+					if id & !XID_MASK == 0 {
+						Ok(Self(id))
+					} else {
+						Err(XidOutOfRange(id))
+					}
+				}
+			}
+
+			impl TryFrom<RawXid> for $Id {
+				type Error = XidOutOfRange;
+
+				fn try_from(id: RawXid) -> Result<Self, Self::Error> {
+					Self::try_new(id)
+				}
+			}
+
+			impl Readable for $Id {
+				fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+					Self::try_new(u32::read_from(buf)?)
+						.map_err(|error| ReadError::FailedConversion(Box::new(error)))
+				}
+			}
+		)+
+	};
+}
+
+impl_raw_xid!(
+	Drawable,
+	Window,
+	Pixmap,
+	CursorAppearance,
+	Fontable,
+	Font,
+	GraphicsContext,
+	Colormap,
+);
+
+/// Implements [`TryFrom<RawXid>`] for IDs which are not resource IDs, but
+/// which, like them, only use their bottom 29 bits.
+macro_rules! impl_try_from_raw_xid {
+	($($Id:ident),+$(,)?) => {
+		$(
+			impl TryFrom<RawXid> for $Id {
+				type Error = XidOutOfRange;
+
+				fn try_from(id: RawXid) -> Result<Self, Self::Error> {
+					if id & !XID_MASK == 0 {
+						Ok(Self::new(id))
+					} else {
+						Err(XidOutOfRange(id))
+					}
+				}
+			}
+		)+
+	};
+}
+
+impl_try_from_raw_xid!(Atom, VisualId);
 
 /// A resource ID referring to either a [`Window`] or a [`Pixmap`].
 ///
@@ -23,16 +130,14 @@ use xrbk_macro::{new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
-	// `new` and `unwrap` const fns
-	new,
+	// `unwrap` const fn
 	unwrap,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
-	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Drawable(u32);
@@ -72,16 +177,14 @@ impl From<Pixmap> for Drawable {
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
-	// `new` and `unwrap` const fns
-	new,
+	// `unwrap` const fn
 	unwrap,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
-	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Window(u32);
@@ -110,16 +213,14 @@ impl From<Drawable> for Window {
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
-	// `new` and `unwrap` const fns
-	new,
+	// `unwrap` const fn
 	unwrap,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
-	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Pixmap(u32);
@@ -148,16 +249,14 @@ impl From<Drawable> for Pixmap {
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
-	// `new` and `unwrap` const fns
-	new,
+	// `unwrap` const fn
 	unwrap,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
-	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct CursorAppearance(u32);
@@ -170,16 +269,14 @@ pub struct CursorAppearance(u32);
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
-	// `new` and `unwrap` const fns
-	new,
+	// `unwrap` const fn
 	unwrap,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
-	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Fontable(u32);
@@ -215,16 +312,14 @@ impl From<GraphicsContext> for Fontable {
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
-	// `new` and `unwrap` const fns
-	new,
+	// `unwrap` const fn
 	unwrap,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
-	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Font(u32);
@@ -259,16 +354,14 @@ impl From<Fontable> for Font {
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
-	// `new` and `unwrap` const fns
-	new,
+	// `unwrap` const fn
 	unwrap,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
-	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct GraphicsContext(u32);
@@ -297,16 +390,66 @@ impl From<Fontable> for GraphicsContext {
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
-	// `new` and `unwrap` const fns
-	new,
+	// `unwrap` const fn
 	unwrap,
 	// XRBK traits
 	X11Size,
 	ConstantX11Size,
-	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct Colormap(u32);
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_raw_xid_conversions() {
+		let window = Window::try_from(0x1fff_ffff).unwrap();
+		assert_eq!(RawXid::from(window), 0x1fff_ffff);
+
+		assert_eq!(
+			Pixmap::try_from(0x2000_0000),
+			Err(XidOutOfRange(0x2000_0000))
+		);
+
+		assert_eq!(Atom::try_from(39), Ok(crate::atom::WM_NAME));
+		assert_eq!(Atom::try_from(0x4000_0000), Err(XidOutOfRange(0x4000_0000)));
+		assert_eq!(VisualId::try_from(0x21).map(VisualId::unwrap), Ok(0x21));
+		assert!(VisualId::try_from(u32::MAX).is_err());
+
+		// Resource IDs which are out of range are rejected when they are read
+		// too.
+		assert!(matches!(
+			Option::<Colormap>::read_from(&mut &[0x80, 0, 0, 1][..]),
+			Err(ReadError::FailedConversion(_)),
+		));
+		assert!(matches!(
+			Window::read_from(&mut &[0x20, 0, 0, 1][..]),
+			Err(ReadError::FailedConversion(_)),
+		));
+		assert_eq!(
+			Window::read_from(&mut &[0x1f, 0xff, 0xff, 0xff][..]).unwrap(),
+			window
+		);
+	}
+
+	#[test]
+	fn test_xid_try_new() {
+		assert_eq!(Window::try_new(0x0040_0001), Ok(Window::new(0x0040_0001)));
+		assert_eq!(
+			GraphicsContext::try_new(0xe000_0000),
+			Err(XidOutOfRange(0xe000_0000))
+		);
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "resource IDs only use their bottom 29 bits")]
+	fn test_xid_new_out_of_range() {
+		let _ = Font::new(0x4000_0000);
+	}
+}
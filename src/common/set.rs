@@ -18,6 +18,8 @@
 //! - [`WindowConfig`]
 //!   - [`WindowConfigBuilder`]
 //!   - [`WindowConfigMask`]
+//!
+//! Each of these sets implements [`ValueSet`].
 
 use crate::unit::Px;
 use xrbk::{
@@ -35,6 +37,7 @@ use xrbk::{
 mod attribute;
 mod graphics_options;
 mod keyboard_options;
+mod value_set;
 mod window_config;
 
 pub use attribute::*;
@@ -42,21 +45,8 @@ pub use graphics_options::*;
 pub use keyboard_options::*;
 pub use window_config::*;
 
-/// Reads an optional value for a set if the given `condition` is true.
-///
-/// This is not part of the public API.
-pub(self) fn read_set_value<T: Readable>(
-	buf: &mut impl Buf, x11_size: &mut usize, condition: bool,
-) -> ReadResult<Option<T>> {
-	Ok(if condition {
-		let ret = T::read_from(buf)?;
-		*x11_size += ret.x11_size();
-
-		Some(ret)
-	} else {
-		None
-	})
-}
+use value_set::impl_value_set;
+pub use value_set::ValueSet;
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub(self) struct __Px<Num>(Px<Num>);
@@ -140,7 +130,7 @@ impl Readable for __u8 {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match u8::try_from(buf.get_u32()) {
+		Ok(Self(match u8::try_from(u32::read_from(buf)?) {
 			Ok(u8) => u8,
 			Err(error) => return Err(ReadError::FailedConversion(Box::new(error))),
 		}))
@@ -182,7 +172,7 @@ impl Readable for __u16 {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match u16::try_from(buf.get_u32()) {
+		Ok(Self(match u16::try_from(u32::read_from(buf)?) {
 			Ok(u16) => u16,
 			Err(error) => return Err(ReadError::FailedConversion(Box::new(error))),
 		}))
@@ -224,7 +214,7 @@ impl Readable for __i16 {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match i16::try_from(buf.get_i32()) {
+		Ok(Self(match i16::try_from(i32::read_from(buf)?) {
 			Ok(i16) => i16,
 			Err(error) => return Err(ReadError::FailedConversion(Box::new(error))),
 		}))
@@ -267,7 +257,7 @@ impl Readable for __bool {
 	where
 		Self: Sized,
 	{
-		Ok(Self(buf.get_u32() != 0))
+		Ok(Self(u32::read_from(buf)? != 0))
 	}
 }
 
@@ -290,14 +280,95 @@ mod test {
 	use super::*;
 
 	#[test]
-	fn test_read_set_value_x11_size() {
-		let mut x11_size = 0;
-		let mut buf = &[0u8; 7][..];
-
-		let _ = read_set_value::<u32>(&mut buf, &mut x11_size, true).unwrap();
-		let _ = read_set_value::<u16>(&mut buf, &mut x11_size, true).unwrap();
-		let _ = read_set_value::<u8>(&mut buf, &mut x11_size, true).unwrap();
+	fn test_window_config_to_builder() {
+		use crate::{unit::Px, StackMode};
+
+		let mut config = WindowConfig::builder();
+		config.x(Px(0)).width(Px(640)).stack_mode(StackMode::Above);
+		let config = config.build();
+
+		let mut moved = config.to_builder();
+		moved.x(Px(1)).y(Px(2));
+		let moved = moved.build();
+
+		let mut expected = WindowConfig::builder();
+		expected
+			.x(Px(1))
+			.y(Px(2))
+			.width(Px(640))
+			.stack_mode(StackMode::Above);
+		let expected = expected.build();
+
+		assert_eq!(moved, expected);
+		assert_eq!(moved.x11_size(), config.x11_size() + 4);
+		assert_eq!(config.to_builder().build(), config);
+	}
 
-		assert_eq!(x11_size, 7);
+	#[test]
+	fn test_attributes_values_are_four_bytes() {
+		use crate::{
+			visual::ColorId,
+			BitGravity,
+			CopyableFromParent,
+			CursorAppearance,
+			DeviceEventMask,
+			EventMask,
+			MaintainContents,
+			ParentRelatable,
+			WindowGravity,
+		};
+
+		let mut attributes = Attributes::builder();
+		attributes
+			.background_pixmap(ParentRelatable::ParentRelative)
+			.background_color(ColorId::new(1))
+			.border_pixmap(CopyableFromParent::CopyFromParent)
+			.border_color(ColorId::new(2))
+			.bit_gravity(BitGravity::Static)
+			.window_gravity(WindowGravity::Unmap)
+			.maintain_contents(MaintainContents::Always)
+			.maintained_planes(0xffff_ffff)
+			.maintenance_fallback_color(ColorId::new(3))
+			.override_redirect(true)
+			.maintain_windows_under(false)
+			.event_mask(EventMask::EXPOSURE)
+			.do_not_propagate_mask(DeviceEventMask::empty())
+			.colormap(CopyableFromParent::CopyFromParent)
+			.cursor_appearance(Some(CursorAppearance::new(4)));
+		let attributes = attributes.build();
+
+		let mut bytes = Vec::new();
+		attributes.write_to(&mut bytes).unwrap();
+
+		assert_eq!(bytes.len(), 4 + 15 * 4);
+		assert_eq!(bytes.len(), attributes.x11_size());
+
+		let values: Vec<u32> = bytes
+			.chunks_exact(4)
+			.map(|chunk| u32::from_be_bytes(chunk.try_into().unwrap()))
+			.collect();
+		assert_eq!(
+			values,
+			[
+				0x7fff,
+				1,
+				1,
+				0,
+				2,
+				10,
+				0,
+				2,
+				0xffff_ffff,
+				3,
+				1,
+				0,
+				0x8000,
+				0,
+				0,
+				4
+			],
+		);
+
+		assert_eq!(Attributes::read_from(&mut &bytes[..]).unwrap(), attributes);
 	}
 }
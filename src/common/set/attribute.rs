@@ -16,20 +16,10 @@ use crate::{
 	Pixmap,
 	WindowGravity,
 };
-use xrbk::{
-	Buf,
-	BufMut,
-	ConstantX11Size,
-	ReadError,
-	ReadResult,
-	Readable,
-	Writable,
-	WriteResult,
-	X11Size,
-};
+use xrbk::{ConstantX11Size, Wrap4};
 
 use bitflags::bitflags;
-use xrbk_macro::{ConstantX11Size, Readable, Writable, X11Size};
+use xrbk_macro::{ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 /// This is a type alias for <code>[ParentRelatable]<[Option]<[Pixmap]>></code>.
 ///
@@ -81,7 +71,7 @@ pub type ColormapAttribute = CopyableFromParent<Colormap>;
 /// |[`border_color`]          |_N/A_              |[`InputOutput`] only       |
 /// |[`bit_gravity`]           |[`Forget`]         |[`InputOutput`] only       |
 /// |[`window_gravity`]        |[`NorthWest`]|[`InputOutput`] and [`InputOnly`]|
-/// |[`maintain_contents`]     |[`NotUseful`]      |[`InputOutput`] only       |
+/// |[`maintain_contents`]     |[`Never`]          |[`InputOutput`] only       |
 /// |[`maintained_planes`]     |`0x_ffff_ffff`     |[`InputOutput`] only       |
 /// |[`maintenance_fallback_color`]|[`ColorId::ZERO`]|[`InputOutput`] only     |
 /// |[`override_redirect`]     |`false`      |[`InputOutput`] and [`InputOnly`]|
@@ -110,7 +100,7 @@ pub type ColormapAttribute = CopyableFromParent<Colormap>;
 /// [`CopyFromParent`]: CopyableFromParent::CopyFromParent
 /// [`Forget`]: BitGravity::Forget
 /// [`NorthWest`]: WindowGravity::NorthWest
-/// [`NotUseful`]: MaintainContents::NotUseful
+/// [`Never`]: MaintainContents::Never
 /// [e]: EventMask::empty
 /// [d]: DeviceEventMask::empty
 ///
@@ -134,10 +124,10 @@ pub struct Attributes {
 	border_pixmap: Option<BorderPixmap>,
 	border_color: Option<ColorId>,
 
-	bit_gravity: Option<__BitGravity>,
-	window_gravity: Option<__WindowGravity>,
+	bit_gravity: Option<Wrap4<BitGravity>>,
+	window_gravity: Option<Wrap4<WindowGravity>>,
 
-	maintain_contents: Option<MaintainContents>,
+	maintain_contents: Option<Wrap4<MaintainContents>>,
 	maintained_planes: Option<u32>,
 	maintenance_fallback_color: Option<ColorId>,
 
@@ -160,6 +150,12 @@ impl Attributes {
 	pub const fn builder() -> AttributesBuilder {
 		AttributesBuilder::new()
 	}
+
+	/// The mask of the attributes which are configured in this set.
+	#[must_use]
+	pub const fn mask(&self) -> AttributesMask {
+		self.mask
+	}
 }
 
 /// A builder used to construct a new [`Attributes` set].
@@ -257,10 +253,10 @@ impl AttributesBuilder {
 			border_pixmap: self.border_pixmap,
 			border_color: self.border_color,
 
-			bit_gravity: self.bit_gravity.map(__BitGravity),
-			window_gravity: self.window_gravity.map(__WindowGravity),
+			bit_gravity: self.bit_gravity.map(Wrap4),
+			window_gravity: self.window_gravity.map(Wrap4),
 
-			maintain_contents: self.maintain_contents,
+			maintain_contents: self.maintain_contents.map(Wrap4),
 			maintained_planes: self.maintained_planes,
 			maintenance_fallback_color: self.maintenance_fallback_color,
 
@@ -673,9 +669,7 @@ impl Attributes {
 		reason = "const is omitted for API uniformity with the other methods and sets"
 	)]
 	pub fn bit_gravity(&self) -> Option<&BitGravity> {
-		self.bit_gravity
-			.as_ref()
-			.map(|__BitGravity(gravity)| gravity)
+		self.bit_gravity.as_ref().map(|Wrap4(gravity)| gravity)
 	}
 	/// Defines how the [window] should be repositioned if its parent is
 	/// resized.
@@ -689,9 +683,7 @@ impl Attributes {
 		reason = "const is omitted for API uniformity with the other methods and sets"
 	)]
 	pub fn window_gravity(&self) -> Option<&WindowGravity> {
-		self.window_gravity
-			.as_ref()
-			.map(|__WindowGravity(gravity)| gravity)
+		self.window_gravity.as_ref().map(|Wrap4(gravity)| gravity)
 	}
 
 	/// The conditions under which the X server should maintain the contents of
@@ -707,7 +699,9 @@ impl Attributes {
 		reason = "const is omitted for API uniformity with the other methods and sets"
 	)]
 	pub fn maintain_contents(&self) -> Option<&MaintainContents> {
-		self.maintain_contents.as_ref()
+		self.maintain_contents
+			.as_ref()
+			.map(|Wrap4(maintain_contents)| maintain_contents)
 	}
 	/// Which bit planes of the [window] hold dynamic data which must be
 	/// maintained for [`maintain_contents`] and [`maintain_windows_under`].
@@ -851,7 +845,7 @@ bitflags! {
 	///
 	/// [attributes]: Attributes
 	/// [window]: crate::Window
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct AttributesMask: u32 {
 		/// Whether the [`background_pixmap` attribute] is configured.
 		///
@@ -953,313 +947,29 @@ bitflags! {
 	}
 }
 
-impl X11Size for Attributes {
-	fn x11_size(&self) -> usize {
-		self.x11_size
-	}
-}
-
-impl Readable for Attributes {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		let mask = AttributesMask::read_from(buf)?;
-		let mut x11_size = mask.x11_size();
-
-		let background_pixmap = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::BACKGROUND_PIXMAP),
-		)?;
-		let background_color = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::BACKGROUND_COLOR),
-		)?;
-
-		let border_pixmap = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::BORDER_PIXMAP),
-		)?;
-		let border_color = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::BORDER_COLOR),
-		)?;
-
-		let bit_gravity = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::BIT_GRAVITY),
-		)?;
-		let window_gravity = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::WINDOW_GRAVITY),
-		)?;
-
-		let maintain_contents = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::MAINTAIN_CONTENTS),
-		)?;
-		let maintained_planes = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::MAINTAINED_PLANES),
-		)?;
-		let maintenance_fallback_color = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::MAINTENANCE_FALLBACK_COLOR),
-		)?;
-
-		let override_redirect = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::OVERRIDE_REDIRECT),
-		)?;
-		let maintain_windows_under = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::MAINTAIN_WINDOWS_UNDER),
-		)?;
-
-		let event_mask = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::EVENT_MASK),
-		)?;
-		let do_not_propagate_mask = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::DO_NOT_PROPAGATE_MASK),
-		)?;
-
-		let colormap =
-			super::read_set_value(buf, &mut x11_size, mask.contains(AttributesMask::COLORMAP))?;
-
-		let cursor_appearance = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(AttributesMask::CURSOR_APPEARANCE),
-		)?;
-
-		Ok(Self {
-			x11_size,
-			mask,
-
-			background_pixmap,
-			background_color,
-
-			border_pixmap,
-			border_color,
-
-			bit_gravity,
-			window_gravity,
-
-			maintain_contents,
-			maintained_planes,
-			maintenance_fallback_color,
-
-			override_redirect,
-			maintain_windows_under,
-
-			event_mask,
-			do_not_propagate_mask,
-
-			colormap,
-
-			cursor_appearance,
-		})
-	}
-}
-
-impl Writable for Attributes {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		self.mask.write_to(buf)?;
-
-		if let Some(background_pixmap) = &self.background_pixmap {
-			background_pixmap.write_to(buf)?;
-		}
-		if let Some(background_color) = &self.background_color {
-			background_color.write_to(buf)?;
-		}
-
-		if let Some(border_pixmap) = &self.border_pixmap {
-			border_pixmap.write_to(buf)?;
-		}
-		if let Some(border_color) = &self.border_color {
-			border_color.write_to(buf)?;
-		}
+super::impl_value_set! {
+	Attributes: AttributesMask => {
+		background_pixmap: BACKGROUND_PIXMAP,
+		background_color: BACKGROUND_COLOR,
 
-		if let Some(bit_gravity) = &self.bit_gravity {
-			bit_gravity.write_to(buf)?;
-		}
-		if let Some(window_gravity) = &self.window_gravity {
-			window_gravity.write_to(buf)?;
-		}
+		border_pixmap: BORDER_PIXMAP,
+		border_color: BORDER_COLOR,
 
-		if let Some(maintain_contents) = &self.maintain_contents {
-			maintain_contents.write_to(buf)?;
-		}
-		if let Some(maintained_planes) = &self.maintained_planes {
-			maintained_planes.write_to(buf)?;
-		}
-		if let Some(maintenance_fallback_color) = &self.maintenance_fallback_color {
-			maintenance_fallback_color.write_to(buf)?;
-		}
+		bit_gravity: BIT_GRAVITY,
+		window_gravity: WINDOW_GRAVITY,
 
-		if let Some(override_redirect) = &self.override_redirect {
-			override_redirect.write_to(buf)?;
-		}
-		if let Some(maintain_windows_under) = &self.maintain_windows_under {
-			maintain_windows_under.write_to(buf)?;
-		}
+		maintain_contents: MAINTAIN_CONTENTS,
+		maintained_planes: MAINTAINED_PLANES,
+		maintenance_fallback_color: MAINTENANCE_FALLBACK_COLOR,
 
-		if let Some(event_mask) = &self.event_mask {
-			event_mask.write_to(buf)?;
-		}
-		if let Some(do_not_propagate_mask) = &self.do_not_propagate_mask {
-			do_not_propagate_mask.write_to(buf)?;
-		}
+		override_redirect: OVERRIDE_REDIRECT,
+		maintain_windows_under: MAINTAIN_WINDOWS_UNDER,
 
-		if let Some(colormap) = &self.colormap {
-			colormap.write_to(buf)?;
-		}
+		event_mask: EVENT_MASK,
+		do_not_propagate_mask: DO_NOT_PROPAGATE_MASK,
 
-		if let Some(cursor_appearance) = &self.cursor_appearance {
-			cursor_appearance.write_to(buf)?;
-		}
+		colormap: COLORMAP,
 
-		Ok(())
+		cursor_appearance: CURSOR_APPEARANCE,
 	}
 }
-
-// Internal 4-byte representations of types {{{
-
-/// A type wrapping a [`BitGravity`] to represent [bit gravities] in
-/// [`Attributes`] as four bytes.
-///
-/// [bit gravities]: BitGravity
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __BitGravity(BitGravity);
-
-impl ConstantX11Size for __BitGravity {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __BitGravity {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __BitGravity {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => BitGravity::Forget,
-			discrim if discrim == 1 => BitGravity::Static,
-			discrim if discrim == 2 => BitGravity::NorthWest,
-			discrim if discrim == 3 => BitGravity::North,
-			discrim if discrim == 4 => BitGravity::NorthEast,
-			discrim if discrim == 5 => BitGravity::West,
-			discrim if discrim == 6 => BitGravity::Center,
-			discrim if discrim == 7 => BitGravity::East,
-			discrim if discrim == 8 => BitGravity::SouthWest,
-			discrim if discrim == 9 => BitGravity::South,
-			discrim if discrim == 10 => BitGravity::SouthEast,
-
-			other_discrim => {
-				return Err(ReadError::UnrecognizedDiscriminant(other_discrim as usize))
-			},
-		}))
-	}
-}
-
-impl Writable for __BitGravity {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(bit_gravity) = self;
-
-		match bit_gravity {
-			BitGravity::Forget => buf.put_u32(0),
-			BitGravity::Static => buf.put_u32(1),
-			BitGravity::NorthWest => buf.put_u32(2),
-			BitGravity::North => buf.put_u32(3),
-			BitGravity::NorthEast => buf.put_u32(4),
-			BitGravity::West => buf.put_u32(5),
-			BitGravity::Center => buf.put_u32(6),
-			BitGravity::East => buf.put_u32(7),
-			BitGravity::SouthWest => buf.put_u32(8),
-			BitGravity::South => buf.put_u32(9),
-			BitGravity::SouthEast => buf.put_u32(10),
-		}
-
-		Ok(())
-	}
-}
-
-/// A type wrapping a [`WindowGravity`] to represent [window gravities] in
-/// [`Attributes`] as four bytes.
-///
-/// [window gravities]: WindowGravity
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __WindowGravity(WindowGravity);
-
-impl ConstantX11Size for __WindowGravity {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __WindowGravity {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __WindowGravity {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => WindowGravity::Unmap,
-			discrim if discrim == 1 => WindowGravity::Static,
-			discrim if discrim == 2 => WindowGravity::NorthWest,
-			discrim if discrim == 3 => WindowGravity::North,
-			discrim if discrim == 4 => WindowGravity::NorthEast,
-			discrim if discrim == 5 => WindowGravity::West,
-			discrim if discrim == 6 => WindowGravity::Center,
-			discrim if discrim == 7 => WindowGravity::East,
-			discrim if discrim == 8 => WindowGravity::SouthWest,
-			discrim if discrim == 9 => WindowGravity::South,
-			discrim if discrim == 10 => WindowGravity::SouthEast,
-
-			other_discrim => {
-				return Err(ReadError::UnrecognizedDiscriminant(other_discrim as usize))
-			},
-		}))
-	}
-}
-
-impl Writable for __WindowGravity {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(window_gravity) = self;
-
-		match window_gravity {
-			WindowGravity::Unmap => buf.put_u32(0),
-			WindowGravity::Static => buf.put_u32(1),
-			WindowGravity::NorthWest => buf.put_u32(2),
-			WindowGravity::North => buf.put_u32(3),
-			WindowGravity::NorthEast => buf.put_u32(4),
-			WindowGravity::West => buf.put_u32(5),
-			WindowGravity::Center => buf.put_u32(6),
-			WindowGravity::East => buf.put_u32(7),
-			WindowGravity::SouthWest => buf.put_u32(8),
-			WindowGravity::South => buf.put_u32(9),
-			WindowGravity::SouthEast => buf.put_u32(10),
-		}
-
-		Ok(())
-	}
-}
-
-// }}}
@@ -2,7 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use xrbk_macro::{ConstantX11Size, Readable, Writable, X11Size};
+use xrbk_macro::{ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
 	set::{__Px, __bool, __u8},
@@ -17,9 +17,9 @@ use xrbk::{
 	Buf,
 	BufMut,
 	ConstantX11Size,
-	ReadError::UnrecognizedDiscriminant,
 	ReadResult,
 	Readable,
+	Wrap4,
 	Writable,
 	WriteResult,
 	X11Size,
@@ -27,7 +27,19 @@ use xrbk::{
 
 /// Given a source and destination pixel, represents a bitwise operation applied
 /// to the source and destination to determine the resultant pixel.
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum Function {
 	/// The resultant pixel is bitwise zero; that is, it has a `0` for each bit.
 	Clear,
@@ -123,7 +135,19 @@ impl LineWidth {
 }
 
 /// Defines which sections of a line are drawn.
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum LineStyle {
 	/// The full path of the line is drawn.
 	Solid,
@@ -145,7 +169,19 @@ pub enum LineStyle {
 }
 
 /// Defines how the endpoints of a path are drawn.
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum CapStyle {
 	/// Equivalent to [`Butt`], except in the case of [`LineWidth::Thin`], where
 	/// the final endpoint is not drawn.
@@ -177,7 +213,19 @@ pub enum CapStyle {
 /// Defines how the corners of [`Thick`] lines are drawn.
 ///
 /// [`Thick`]: LineWidth::Thick
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum JoinStyle {
 	/// The outer edges of the two lines extend to meet at an angle, if that
 	/// angle is at least 11 degrees.
@@ -198,7 +246,19 @@ pub enum JoinStyle {
 /// Defines the contents of the source for line, text, and fill [requests].
 ///
 /// [requests]: crate::x11::request
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum FillStyle {
 	/// This is the [foreground color], except for the odd dashes of line
 	/// requests with [`LineStyle::DoubleDash`], where it is the [background
@@ -228,7 +288,19 @@ pub enum FillStyle {
 /// [`FillPoly` requests]: crate::x11::request::FillPoly
 // Hell if I know what the X11 protocol is talking about for these variants.
 // Really technical language. I imagine it's simply not worth documenting.
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum FillRule {
 	EvenOdd,
 	Winding,
@@ -237,7 +309,19 @@ pub enum FillRule {
 /// Whether a source or destination [window] is clipped by its descendents.
 ///
 /// [window]: crate::Window
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum ChildMode {
 	/// Both source and destination [windows] are additionally clipped by all
 	/// viewable [`InputOutput`] children.
@@ -254,7 +338,19 @@ pub enum ChildMode {
 /// Controls filling in the [`PolyFillArc` request].
 ///
 /// [`PolyFillArc` request]: crate::x11::request::PolyFillArc
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum ArcMode {
 	/// Fills the shape created by tracing the arc and joining its endpoints in
 	/// a straight line.
@@ -340,7 +436,7 @@ pub struct GraphicsOptions {
 	#[derivative(Hash = "ignore", PartialEq = "ignore")]
 	mask: GraphicsOptionsMask,
 
-	function: Option<__Function>,
+	function: Option<Wrap4<Function>>,
 
 	plane_mask: Option<u32>,
 
@@ -349,11 +445,11 @@ pub struct GraphicsOptions {
 
 	line_width: Option<__LineWidth>,
 
-	line_style: Option<__LineStyle>,
-	cap_style: Option<__CapStyle>,
-	join_style: Option<__JoinStyle>,
-	fill_style: Option<__FillStyle>,
-	fill_rule: Option<__FillRule>,
+	line_style: Option<Wrap4<LineStyle>>,
+	cap_style: Option<Wrap4<CapStyle>>,
+	join_style: Option<Wrap4<JoinStyle>>,
+	fill_style: Option<Wrap4<FillStyle>>,
+	fill_rule: Option<Wrap4<FillRule>>,
 
 	tile: Option<Pixmap>,
 	stipple: Option<Pixmap>,
@@ -363,7 +459,7 @@ pub struct GraphicsOptions {
 
 	font: Option<Font>,
 
-	child_mode: Option<__ChildMode>,
+	child_mode: Option<Wrap4<ChildMode>>,
 
 	graphics_exposures: Option<__bool>,
 
@@ -374,7 +470,7 @@ pub struct GraphicsOptions {
 	dash_offset: Option<__Px<u16>>,
 	dashes: Option<__u8>,
 
-	arc_mode: Option<__ArcMode>,
+	arc_mode: Option<Wrap4<ArcMode>>,
 }
 
 impl GraphicsOptions {
@@ -501,7 +597,7 @@ impl GraphicsOptionsBuilder {
 
 			mask: self.mask,
 
-			function: self.function.map(__Function),
+			function: self.function.map(Wrap4),
 
 			plane_mask: self.plane_mask,
 
@@ -510,11 +606,11 @@ impl GraphicsOptionsBuilder {
 
 			line_width: self.line_width.map(__LineWidth),
 
-			line_style: self.line_style.map(__LineStyle),
-			cap_style: self.cap_style.map(__CapStyle),
-			join_style: self.join_style.map(__JoinStyle),
-			fill_style: self.fill_style.map(__FillStyle),
-			fill_rule: self.fill_rule.map(__FillRule),
+			line_style: self.line_style.map(Wrap4),
+			cap_style: self.cap_style.map(Wrap4),
+			join_style: self.join_style.map(Wrap4),
+			fill_style: self.fill_style.map(Wrap4),
+			fill_rule: self.fill_rule.map(Wrap4),
 
 			tile: self.tile,
 			stipple: self.stipple,
@@ -524,7 +620,7 @@ impl GraphicsOptionsBuilder {
 
 			font: self.font,
 
-			child_mode: self.child_mode.map(__ChildMode),
+			child_mode: self.child_mode.map(Wrap4),
 
 			graphics_exposures: self.graphics_exposures.map(__bool),
 
@@ -535,7 +631,7 @@ impl GraphicsOptionsBuilder {
 			dash_offset: self.dash_offset.map(__Px),
 			dashes: self.dashes.map(__u8),
 
-			arc_mode: self.arc_mode.map(__ArcMode),
+			arc_mode: self.arc_mode.map(Wrap4),
 		}
 	}
 }
@@ -918,7 +1014,7 @@ impl GraphicsOptions {
 	/// [color]: ColorId
 	#[must_use]
 	pub fn function(&self) -> Option<&Function> {
-		self.function.as_ref().map(|__Function(function)| function)
+		self.function.as_ref().map(|Wrap4(function)| function)
 	}
 
 	/// The mask applied to the graphics operation's effects on bit planes.
@@ -958,36 +1054,28 @@ impl GraphicsOptions {
 	/// See [`LineStyle`] for more information.
 	#[must_use]
 	pub fn line_style(&self) -> Option<&LineStyle> {
-		self.line_style
-			.as_ref()
-			.map(|__LineStyle(line_style)| line_style)
+		self.line_style.as_ref().map(|Wrap4(line_style)| line_style)
 	}
 	/// Defines how the endpoints of a line are drawn.
 	///
 	/// See [`CapStyle`] for more information.
 	#[must_use]
 	pub fn cap_style(&self) -> Option<&CapStyle> {
-		self.cap_style
-			.as_ref()
-			.map(|__CapStyle(cap_style)| cap_style)
+		self.cap_style.as_ref().map(|Wrap4(cap_style)| cap_style)
 	}
 	/// Defines how the corners of [`LineWidth::Thick`] lines are drawn.
 	///
 	/// See [`JoinStyle`] for more information.
 	#[must_use]
 	pub fn join_style(&self) -> Option<&JoinStyle> {
-		self.join_style
-			.as_ref()
-			.map(|__JoinStyle(join_style)| join_style)
+		self.join_style.as_ref().map(|Wrap4(join_style)| join_style)
 	}
 	/// Defines the contents of the source for line, text, and fill requests.
 	///
 	/// See [`FillStyle`] for more information.
 	#[must_use]
 	pub fn fill_style(&self) -> Option<&FillStyle> {
-		self.fill_style
-			.as_ref()
-			.map(|__FillStyle(fill_style)| fill_style)
+		self.fill_style.as_ref().map(|Wrap4(fill_style)| fill_style)
 	}
 	/// Defines which pixels are drawn for paths in [`FillPoly` requests].
 	///
@@ -996,9 +1084,7 @@ impl GraphicsOptions {
 	/// [`FillPoly` requests]: crate::x11::request::FillPoly
 	#[must_use]
 	pub fn fill_rule(&self) -> Option<&FillRule> {
-		self.fill_rule
-			.as_ref()
-			.map(|__FillRule(fill_rule)| fill_rule)
+		self.fill_rule.as_ref().map(|Wrap4(fill_rule)| fill_rule)
 	}
 
 	/// The [pixmap] which is tiled in graphics operations.
@@ -1055,9 +1141,7 @@ impl GraphicsOptions {
 	/// [windows]: crate::Window
 	#[must_use]
 	pub fn child_mode(&self) -> Option<&ChildMode> {
-		self.child_mode
-			.as_ref()
-			.map(|__ChildMode(child_mode)| child_mode)
+		self.child_mode.as_ref().map(|Wrap4(child_mode)| child_mode)
 	}
 
 	/// Whether [`GraphicsExposure` events] are generated.
@@ -1120,7 +1204,7 @@ impl GraphicsOptions {
 	/// [`PolyFillArc` requests]: crate::x11::request::PolyFillArc
 	#[must_use]
 	pub fn arc_mode(&self) -> Option<&ArcMode> {
-		self.arc_mode.as_ref().map(|__ArcMode(arc_mode)| arc_mode)
+		self.arc_mode.as_ref().map(|Wrap4(arc_mode)| arc_mode)
 	}
 }
 
@@ -1133,7 +1217,7 @@ bitflags! {
 	/// [`GraphicsContext`]: crate::GraphicsContext
 	/// [`CopyGraphicsContext` request]: crate::x11::request::CopyGraphicsContext
 	/// [`GraphicsOptions` set]: GraphicsOptions
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct GraphicsOptionsMask: u32 {
 		/// Whether the [function] applied to determine the resultant pixel in a
 		/// graphics [request] is configured in the [`GraphicsContext`].
@@ -1330,274 +1414,43 @@ bitflags! {
 
 // impl XRBK traits for GraphicsOptions {{{
 
-impl X11Size for GraphicsOptions {
-	fn x11_size(&self) -> usize {
-		self.x11_size
-	}
-}
+super::impl_value_set! {
+	GraphicsOptions: GraphicsOptionsMask => {
+		function: FUNCTION,
 
-impl Readable for GraphicsOptions {
-	#[allow(clippy::too_many_lines)]
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		let mask = GraphicsOptionsMask::read_from(buf)?;
-		let mut x11_size = mask.x11_size();
-
-		let function = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::FUNCTION),
-		)?;
-
-		let plane_mask = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::PLANE_MASK),
-		)?;
-
-		let foreground_color = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::FOREGROUND_COLOR),
-		)?;
-		let background_color = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::BACKGROUND_COLOR),
-		)?;
-
-		let line_width = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::LINE_WIDTH),
-		)?;
-
-		let line_style = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::LINE_STYLE),
-		)?;
-		let cap_style = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::CAP_STYLE),
-		)?;
-		let join_style = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::JOIN_STYLE),
-		)?;
-		let fill_style = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::FILL_STYLE),
-		)?;
-		let fill_rule = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::FILL_RULE),
-		)?;
-
-		let tile =
-			super::read_set_value(buf, &mut x11_size, mask.contains(GraphicsOptionsMask::TILE))?;
-		let stipple = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::STIPPLE),
-		)?;
-
-		let tile_stipple_x = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::TILE_STIPPLE_X),
-		)?;
-		let tile_stipple_y = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::TILE_STIPPLE_Y),
-		)?;
-
-		let font =
-			super::read_set_value(buf, &mut x11_size, mask.contains(GraphicsOptionsMask::FONT))?;
-
-		let child_mode = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::CHILD_MODE),
-		)?;
-
-		let graphics_exposures = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::GRAPHICS_EXPOSURE),
-		)?;
-
-		let clip_x = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::CLIP_X),
-		)?;
-		let clip_y = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::CLIP_Y),
-		)?;
-		let clip_mask = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::CLIP_MASK),
-		)?;
-
-		let dash_offset = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::DASH_OFFSET),
-		)?;
-		let dashes = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::DASHES),
-		)?;
-
-		let arc_mode = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(GraphicsOptionsMask::ARC_MODE),
-		)?;
-
-		Ok(Self {
-			x11_size,
-
-			mask,
-
-			function,
-
-			plane_mask,
-
-			foreground_color,
-			background_color,
-
-			line_width,
-
-			line_style,
-			cap_style,
-			join_style,
-			fill_style,
-			fill_rule,
-
-			tile,
-			stipple,
-
-			tile_stipple_x,
-			tile_stipple_y,
-
-			font,
-
-			child_mode,
-
-			graphics_exposures,
-
-			clip_x,
-			clip_y,
-			clip_mask,
-
-			dash_offset,
-			dashes,
-
-			arc_mode,
-		})
-	}
-}
+		plane_mask: PLANE_MASK,
 
-impl Writable for GraphicsOptions {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		self.mask.write_to(buf)?;
+		foreground_color: FOREGROUND_COLOR,
+		background_color: BACKGROUND_COLOR,
 
-		if let Some(function) = &self.function {
-			function.write_to(buf)?;
-		}
+		line_width: LINE_WIDTH,
 
-		if let Some(plane_mask) = &self.plane_mask {
-			plane_mask.write_to(buf)?;
-		}
+		line_style: LINE_STYLE,
+		cap_style: CAP_STYLE,
+		join_style: JOIN_STYLE,
+		fill_style: FILL_STYLE,
+		fill_rule: FILL_RULE,
 
-		if let Some(foreground_color) = &self.foreground_color {
-			foreground_color.write_to(buf)?;
-		}
-		if let Some(background_color) = &self.background_color {
-			background_color.write_to(buf)?;
-		}
-
-		if let Some(line_width) = &self.line_width {
-			line_width.write_to(buf)?;
-		}
+		tile: TILE,
+		stipple: STIPPLE,
 
-		if let Some(line_style) = &self.line_style {
-			line_style.write_to(buf)?;
-		}
-		if let Some(cap_style) = &self.cap_style {
-			cap_style.write_to(buf)?;
-		}
-		if let Some(join_style) = &self.join_style {
-			join_style.write_to(buf)?;
-		}
-		if let Some(fill_style) = &self.fill_style {
-			fill_style.write_to(buf)?;
-		}
-		if let Some(fill_rule) = &self.fill_rule {
-			fill_rule.write_to(buf)?;
-		}
+		tile_stipple_x: TILE_STIPPLE_X,
+		tile_stipple_y: TILE_STIPPLE_Y,
 
-		if let Some(tile) = &self.tile {
-			tile.write_to(buf)?;
-		}
-		if let Some(stipple) = &self.stipple {
-			stipple.write_to(buf)?;
-		}
+		font: FONT,
 
-		if let Some(tile_stipple_x) = &self.tile_stipple_x {
-			tile_stipple_x.write_to(buf)?;
-		}
-		if let Some(tile_stipple_y) = &self.tile_stipple_y {
-			tile_stipple_y.write_to(buf)?;
-		}
+		child_mode: CHILD_MODE,
 
-		if let Some(font) = &self.font {
-			font.write_to(buf)?;
-		}
+		graphics_exposures: GRAPHICS_EXPOSURE,
 
-		if let Some(child_mode) = &self.child_mode {
-			child_mode.write_to(buf)?;
-		}
+		clip_x: CLIP_X,
+		clip_y: CLIP_Y,
+		clip_mask: CLIP_MASK,
 
-		if let Some(graphics_exposures) = &self.graphics_exposures {
-			graphics_exposures.write_to(buf)?;
-		}
+		dash_offset: DASH_OFFSET,
+		dashes: DASHES,
 
-		if let Some(clip_x) = &self.clip_x {
-			clip_x.write_to(buf)?;
-		}
-		if let Some(clip_y) = &self.clip_y {
-			clip_y.write_to(buf)?;
-		}
-		if let Some(clip_mask) = &self.clip_mask {
-			clip_mask.write_to(buf)?;
-		}
-
-		if let Some(dash_offset) = &self.dash_offset {
-			dash_offset.write_to(buf)?;
-		}
-		if let Some(dashes) = &self.dashes {
-			dashes.write_to(buf)?;
-		}
-
-		if let Some(arc_mode) = &self.arc_mode {
-			arc_mode.write_to(buf)?;
-		}
-
-		Ok(())
+		arc_mode: ARC_MODE,
 	}
 }
 
@@ -1605,80 +1458,6 @@ impl Writable for GraphicsOptions {
 
 // Internal 4-byte representations of types {{{
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __Function(Function);
-
-impl ConstantX11Size for __Function {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __Function {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __Function {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => Function::Clear,
-			discrim if discrim == 1 => Function::And,
-			discrim if discrim == 2 => Function::AndReverse,
-			discrim if discrim == 3 => Function::Copy,
-
-			discrim if discrim == 4 => Function::AndInverted,
-			discrim if discrim == 5 => Function::NoOp,
-			discrim if discrim == 6 => Function::Xor,
-			discrim if discrim == 7 => Function::Or,
-
-			discrim if discrim == 8 => Function::Nor,
-			discrim if discrim == 9 => Function::Equiv,
-			discrim if discrim == 10 => Function::Invert,
-			discrim if discrim == 11 => Function::OrReverse,
-
-			discrim if discrim == 12 => Function::CopyInverted,
-			discrim if discrim == 13 => Function::OrInverted,
-			discrim if discrim == 14 => Function::Nand,
-			discrim if discrim == 15 => Function::Set,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __Function {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(function) = self;
-
-		match function {
-			Function::Clear => buf.put_u32(0),
-			Function::And => buf.put_u32(1),
-			Function::AndReverse => buf.put_u32(2),
-			Function::Copy => buf.put_u32(3),
-
-			Function::AndInverted => buf.put_u32(4),
-			Function::NoOp => buf.put_u32(5),
-			Function::Xor => buf.put_u32(6),
-			Function::Or => buf.put_u32(7),
-
-			Function::Nor => buf.put_u32(8),
-			Function::Equiv => buf.put_u32(9),
-			Function::Invert => buf.put_u32(10),
-			Function::OrReverse => buf.put_u32(11),
-
-			Function::CopyInverted => buf.put_u32(12),
-			Function::OrInverted => buf.put_u32(13),
-			Function::Nand => buf.put_u32(14),
-			Function::Set => buf.put_u32(15),
-		}
-
-		Ok(())
-	}
-}
-
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 struct __LineWidth(LineWidth);
 
@@ -1701,7 +1480,7 @@ impl Readable for __LineWidth {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_u32() {
+		Ok(Self(match u32::read_from(buf)? {
 			discrim if discrim == 0 => LineWidth::Thin,
 			other_width => LineWidth::Thick(other_width as u16),
 		}))
@@ -1721,296 +1500,4 @@ impl Writable for __LineWidth {
 	}
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __LineStyle(LineStyle);
-
-impl ConstantX11Size for __LineStyle {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __LineStyle {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __LineStyle {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => LineStyle::Solid,
-			discrim if discrim == 1 => LineStyle::OnOffDash,
-			discrim if discrim == 2 => LineStyle::DoubleDash,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __LineStyle {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(line_style) = self;
-
-		match line_style {
-			LineStyle::Solid => buf.put_u32(0),
-			LineStyle::OnOffDash => buf.put_u32(1),
-			LineStyle::DoubleDash => buf.put_u32(2),
-		}
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __CapStyle(CapStyle);
-
-impl ConstantX11Size for __CapStyle {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __CapStyle {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __CapStyle {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => CapStyle::NotLast,
-			discrim if discrim == 1 => CapStyle::Butt,
-			discrim if discrim == 2 => CapStyle::Round,
-			discrim if discrim == 3 => CapStyle::Projecting,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __CapStyle {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(cap_style) = self;
-
-		match cap_style {
-			CapStyle::NotLast => buf.put_u32(0),
-			CapStyle::Butt => buf.put_u32(1),
-			CapStyle::Round => buf.put_u32(2),
-			CapStyle::Projecting => buf.put_u32(3),
-		}
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __JoinStyle(JoinStyle);
-
-impl ConstantX11Size for __JoinStyle {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __JoinStyle {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __JoinStyle {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => JoinStyle::Miter,
-			discrim if discrim == 1 => JoinStyle::Round,
-			discrim if discrim == 2 => JoinStyle::Bevel,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __JoinStyle {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(join_style) = self;
-
-		match join_style {
-			JoinStyle::Miter => buf.put_u32(0),
-			JoinStyle::Round => buf.put_u32(1),
-			JoinStyle::Bevel => buf.put_u32(2),
-		}
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __FillStyle(FillStyle);
-
-impl ConstantX11Size for __FillStyle {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __FillStyle {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __FillStyle {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => FillStyle::Solid,
-			discrim if discrim == 1 => FillStyle::Tiled,
-			discrim if discrim == 2 => FillStyle::Stippled,
-			discrim if discrim == 3 => FillStyle::OpaqueStippled,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __FillStyle {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(fill_style) = self;
-
-		match fill_style {
-			FillStyle::Solid => buf.put_u32(0),
-			FillStyle::Tiled => buf.put_u32(1),
-			FillStyle::Stippled => buf.put_u32(2),
-			FillStyle::OpaqueStippled => buf.put_u32(3),
-		}
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __FillRule(FillRule);
-
-impl ConstantX11Size for __FillRule {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __FillRule {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __FillRule {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => FillRule::EvenOdd,
-			discrim if discrim == 1 => FillRule::Winding,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __FillRule {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(fill_rule) = self;
-
-		match fill_rule {
-			FillRule::EvenOdd => buf.put_u32(0),
-			FillRule::Winding => buf.put_u32(1),
-		}
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __ChildMode(ChildMode);
-
-impl ConstantX11Size for __ChildMode {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __ChildMode {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __ChildMode {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => ChildMode::ClipByChildren,
-			discrim if discrim == 1 => ChildMode::IncludeDescendents,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __ChildMode {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(child_mode) = self;
-
-		match child_mode {
-			ChildMode::ClipByChildren => buf.put_u32(0),
-			ChildMode::IncludeDescendents => buf.put_u32(1),
-		}
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __ArcMode(ArcMode);
-
-impl ConstantX11Size for __ArcMode {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __ArcMode {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __ArcMode {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => ArcMode::Chord,
-			discrim if discrim == 1 => ArcMode::PieSlice,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __ArcMode {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(arc_mode) = self;
-
-		match arc_mode {
-			ArcMode::Chord => buf.put_u32(0),
-			ArcMode::PieSlice => buf.put_u32(1),
-		}
-
-		Ok(())
-	}
-}
-
 // }}}
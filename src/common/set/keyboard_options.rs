@@ -2,21 +2,21 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::{Keycode, ToggleOrDefault};
+use crate::{Keycode, Toggle, ToggleOrDefault};
 use std::fmt::{Display, Formatter};
 use xrbk::{
 	Buf,
 	BufMut,
 	ConstantX11Size,
 	ReadError,
-	ReadError::UnrecognizedDiscriminant,
 	ReadResult,
 	Readable,
+	Wrap4,
 	Writable,
 	WriteResult,
 	X11Size,
 };
-use xrbk_macro::{ConstantX11Size, Readable, Writable, X11Size};
+use xrbk_macro::{ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::unit::{Hz, Ms, Percentage, ValueOutOfBounds};
 use bitflags::bitflags;
@@ -48,23 +48,22 @@ impl PercentOrDefault {
 	/// [`ValueOutOfBounds` error].
 	///
 	/// [`ValueOutOfBounds` error]: ValueOutOfBounds
-	pub fn new(value: i8) -> Result<Self, ValueOutOfBounds<i8>> {
+	#[allow(clippy::cast_sign_loss, reason = "`value` is known to be non-negative")]
+	pub const fn new(value: i8) -> Result<Self, ValueOutOfBounds<i8>> {
 		match value {
-			reset if reset == -1 => Ok(Self::Default),
-
-			value => match u8::try_from(value) {
-				Ok(value) if (0..=100).contains(&value) => Ok(Self::Percent(unsafe {
-					// It's fine to call this function, because we have checked
-					// the bounds ourselves.
-					Percentage::new_unchecked(value)
-				})),
-
-				_ => Err(ValueOutOfBounds {
-					min: 0,
-					max: 100,
-					found: value,
-				}),
-			},
+			-1 => Ok(Self::Default),
+
+			0..=100 => Ok(Self::Percent(unsafe {
+				// It's fine to call this function, because we have checked the
+				// bounds ourselves.
+				Percentage::new_unchecked(value as u8)
+			})),
+
+			other => Err(ValueOutOfBounds {
+				min: 0,
+				max: 100,
+				found: other,
+			}),
 		}
 	}
 
@@ -80,8 +79,11 @@ impl PercentOrDefault {
 	/// Generates a [`ValueOutOfBounds` error] if `percentage > 100`.
 	///
 	/// [`ValueOutOfBounds` error]: ValueOutOfBounds
-	pub fn new_percent(percentage: u8) -> Result<Self, ValueOutOfBounds<u8>> {
-		Ok(Self::Percent(Percentage::new(percentage)?))
+	pub const fn new_percent(percentage: u8) -> Result<Self, ValueOutOfBounds<u8>> {
+		match Percentage::new(percentage) {
+			Ok(percentage) => Ok(Self::Percent(percentage)),
+			Err(error) => Err(error),
+		}
 	}
 
 	/// Returns the [percent] value wrapped by [`PercentOrDefault::Percent`],
@@ -131,18 +133,22 @@ impl PitchOrDefault {
 	/// If `value < -1`, this generates a [`ValueOutOfBounds` error].
 	///
 	/// [`ValueOutOfBounds` error]: ValueOutOfBounds
-	pub fn new(value: i16) -> Result<Self, ValueOutOfBounds<i16>> {
+	#[allow(
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss,
+		reason = "`value` is known to fit in a `u8`"
+	)]
+	pub const fn new(value: i16) -> Result<Self, ValueOutOfBounds<i16>> {
 		match value {
-			reset if reset == -1 => Ok(Self::Reset),
+			-1 => Ok(Self::Reset),
 
-			other => u8::try_from(other).map_or(
-				Err(ValueOutOfBounds {
-					min: -1,
-					max: i16::from(u8::MAX),
-					found: other,
-				}),
-				|pitch| Ok(Self::Pitch(Hz(pitch))),
-			),
+			0..=0xff => Ok(Self::Pitch(Hz(value as u8))),
+
+			other => Err(ValueOutOfBounds {
+				min: -1,
+				max: u8::MAX as i16,
+				found: other,
+			}),
 		}
 	}
 
@@ -204,18 +210,22 @@ impl DurationOrDefault {
 	/// If `value < -1`, this generates a [`ValueOutOfBounds` error].
 	///
 	/// [`ValueOutOfBounds` error]: NegativeValue
-	pub fn new(value: i16) -> Result<Self, ValueOutOfBounds<i16>> {
+	#[allow(
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss,
+		reason = "`value` is known to fit in a `u8`"
+	)]
+	pub const fn new(value: i16) -> Result<Self, ValueOutOfBounds<i16>> {
 		match value {
-			reset if reset == -1 => Ok(Self::Reset),
+			-1 => Ok(Self::Reset),
 
-			other => u8::try_from(other).map_or(
-				Err(ValueOutOfBounds {
-					min: -1,
-					max: i16::from(u8::MAX),
-					found: other,
-				}),
-				|duration| Ok(Self::Duration(Ms(duration))),
-			),
+			0..=0xff => Ok(Self::Duration(Ms(value as u8))),
+
+			other => Err(ValueOutOfBounds {
+				min: -1,
+				max: u8::MAX as i16,
+				found: other,
+			}),
 		}
 	}
 
@@ -297,7 +307,19 @@ impl Led {
 }
 
 /// Whether LEDs are turned on or off.
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[derive(
+	Clone,
+	Copy,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum LedMode {
 	/// The LED(s) is/are turned on.
 	Off,
@@ -305,6 +327,85 @@ pub enum LedMode {
 	On,
 }
 
+/// Whether keys are automatically repeated while they are held down.
+///
+/// This is used both to configure the [auto repeat mode] with a
+/// [`ChangeKeyboardOptions` request] and to report the global auto repeat mode
+/// in a [`GetKeyboardOptions` reply].
+///
+/// [auto repeat mode]: KeyboardOptions::auto_repeat_mode
+/// [`ChangeKeyboardOptions` request]: crate::x11::request::ChangeKeyboardOptions
+/// [`GetKeyboardOptions` reply]: crate::x11::reply::GetKeyboardOptions
+#[doc(alias("AutoRepeat"))]
+#[derive(
+	Clone,
+	Copy,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
+pub enum AutoRepeatMode {
+	/// Auto repeat is disabled.
+	Off,
+	/// Auto repeat is enabled.
+	On,
+
+	/// The default auto repeat mode is restored.
+	///
+	/// This is only used when configuring the auto repeat mode; the X server
+	/// always reports either [`Off`] or [`On`].
+	///
+	/// [`Off`]: AutoRepeatMode::Off
+	/// [`On`]: AutoRepeatMode::On
+	Default,
+}
+
+impl AutoRepeatMode {
+	/// Returns whether this is [`On`].
+	///
+	/// [`On`]: AutoRepeatMode::On
+	#[must_use]
+	pub const fn is_on(&self) -> bool {
+		matches!(self, Self::On)
+	}
+}
+
+impl From<Toggle> for AutoRepeatMode {
+	fn from(toggle: Toggle) -> Self {
+		match toggle {
+			Toggle::Disabled => Self::Off,
+			Toggle::Enabled => Self::On,
+		}
+	}
+}
+
+impl From<ToggleOrDefault> for AutoRepeatMode {
+	fn from(toggle: ToggleOrDefault) -> Self {
+		match toggle {
+			ToggleOrDefault::Disabled => Self::Off,
+			ToggleOrDefault::Enabled => Self::On,
+
+			ToggleOrDefault::Default => Self::Default,
+		}
+	}
+}
+
+impl From<bool> for AutoRepeatMode {
+	fn from(on: bool) -> Self {
+		if on {
+			Self::On
+		} else {
+			Self::Off
+		}
+	}
+}
+
 /// A set of options which control various aspects of the keyboard.
 ///
 /// This set is used in the [`ChangeKeyboardControl` request].
@@ -342,11 +443,11 @@ pub struct KeyboardOptions {
 	bell_duration: Option<__DurationOrDefault>,
 
 	led: Option<__Led>,
-	led_mode: Option<__LedMode>,
+	led_mode: Option<Wrap4<LedMode>>,
 
-	auto_repeated_key: Option<__Keycode>,
+	auto_repeated_key: Option<Wrap4<Keycode>>,
 
-	auto_repeat_mode: Option<__ToggleOrDefault>,
+	auto_repeat_mode: Option<Wrap4<AutoRepeatMode>>,
 }
 
 impl KeyboardOptions {
@@ -383,7 +484,7 @@ pub struct KeyboardOptionsBuilder {
 
 	auto_repeated_key: Option<Keycode>,
 
-	auto_repeat_mode: Option<ToggleOrDefault>,
+	auto_repeat_mode: Option<AutoRepeatMode>,
 }
 
 impl KeyboardOptionsBuilder {
@@ -434,11 +535,11 @@ impl KeyboardOptionsBuilder {
 			bell_duration: self.bell_duration.map(__DurationOrDefault),
 
 			led: self.led.map(__Led),
-			led_mode: self.led_mode.map(__LedMode),
+			led_mode: self.led_mode.map(Wrap4),
 
-			auto_repeated_key: self.auto_repeated_key.map(__Keycode),
+			auto_repeated_key: self.auto_repeated_key.map(Wrap4),
 
-			auto_repeat_mode: self.auto_repeat_mode.map(__ToggleOrDefault),
+			auto_repeat_mode: self.auto_repeat_mode.map(Wrap4),
 		}
 	}
 }
@@ -575,12 +676,12 @@ impl KeyboardOptionsBuilder {
 	/// Configures whether auto repeat mode is enabled.
 	///
 	/// See [`KeyboardOptions::auto_repeat_mode`] for more information.
-	pub fn auto_repeat_mode(&mut self, auto_repeat_mode: ToggleOrDefault) -> &mut Self {
+	pub fn auto_repeat_mode(&mut self, auto_repeat_mode: impl Into<AutoRepeatMode>) -> &mut Self {
 		if self.auto_repeat_mode.is_none() {
 			self.x11_size += 4;
 		}
 
-		self.auto_repeat_mode = Some(auto_repeat_mode);
+		self.auto_repeat_mode = Some(auto_repeat_mode.into());
 		self.mask |= KeyboardOptionsMask::AUTO_REPEAT_MODE;
 
 		self
@@ -654,7 +755,7 @@ impl KeyboardOptions {
 	/// [LED]: Led
 	#[must_use]
 	pub fn led_mode(&self) -> Option<&LedMode> {
-		self.led_mode.as_ref().map(|__LedMode(mode)| mode)
+		self.led_mode.as_ref().map(|Wrap4(mode)| mode)
 	}
 
 	/// The specific key which the [`auto_repeat_mode`] applies to.
@@ -662,7 +763,7 @@ impl KeyboardOptions {
 	/// [`auto_repeat_mode`]: KeyboardOptions::auto_repeat_mode
 	#[must_use]
 	pub fn auto_repeated_key(&self) -> Option<&Keycode> {
-		self.auto_repeated_key.as_ref().map(|__Keycode(key)| key)
+		self.auto_repeated_key.as_ref().map(|Wrap4(key)| key)
 	}
 
 	/// Whether the relevant keys have their auto repeats applied.
@@ -677,10 +778,8 @@ impl KeyboardOptions {
 	///
 	/// [`auto_repeated_key`]: KeyboardOptions::auto_repeated_key
 	#[must_use]
-	pub fn auto_repeat_mode(&self) -> Option<&ToggleOrDefault> {
-		self.auto_repeat_mode
-			.as_ref()
-			.map(|__ToggleOrDefault(toggle_or_default)| toggle_or_default)
+	pub fn auto_repeat_mode(&self) -> Option<&AutoRepeatMode> {
+		self.auto_repeat_mode.as_ref().map(|Wrap4(mode)| mode)
 	}
 }
 
@@ -690,7 +789,7 @@ bitflags! {
 	/// This mask is used in the [`KeyboardOptions` set].
 	///
 	/// [`KeyboardOptions` set]: KeyboardOptions
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct KeyboardOptionsMask: u32 {
 		/// Whether the [volume of key clicks] is configured.
 		///
@@ -749,116 +848,20 @@ bitflags! {
 	}
 }
 
-impl X11Size for KeyboardOptions {
-	fn x11_size(&self) -> usize {
-		self.x11_size
-	}
-}
+super::impl_value_set! {
+	KeyboardOptions: KeyboardOptionsMask => {
+		key_click_volume: KEY_CLICK_VOLUME,
 
-impl Readable for KeyboardOptions {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		let mask = KeyboardOptionsMask::read_from(buf)?;
-		let mut x11_size = mask.x11_size();
-
-		let key_click_volume = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(KeyboardOptionsMask::KEY_CLICK_VOLUME),
-		)?;
-
-		let bell_volume = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(KeyboardOptionsMask::BELL_VOLUME),
-		)?;
-		let bell_pitch = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(KeyboardOptionsMask::BELL_PITCH),
-		)?;
-		let bell_duration = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(KeyboardOptionsMask::BELL_DURATION),
-		)?;
-
-		let led =
-			super::read_set_value(buf, &mut x11_size, mask.contains(KeyboardOptionsMask::LED))?;
-		let led_mode = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(KeyboardOptionsMask::LED_MODE),
-		)?;
-
-		let auto_repeated_key = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(KeyboardOptionsMask::AUTO_REPEATED_KEY),
-		)?;
-
-		let auto_repeat_mode = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(KeyboardOptionsMask::AUTO_REPEAT_MODE),
-		)?;
-
-		Ok(Self {
-			x11_size,
-			mask,
-
-			key_click_volume,
-
-			bell_volume,
-			bell_pitch,
-			bell_duration,
-
-			led,
-			led_mode,
-
-			auto_repeated_key,
-
-			auto_repeat_mode,
-		})
-	}
-}
-
-impl Writable for KeyboardOptions {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		self.mask.write_to(buf)?;
+		bell_volume: BELL_VOLUME,
+		bell_pitch: BELL_PITCH,
+		bell_duration: BELL_DURATION,
 
-		if let Some(key_click_volume) = &self.key_click_volume {
-			key_click_volume.write_to(buf)?;
-		}
+		led: LED,
+		led_mode: LED_MODE,
 
-		if let Some(bell_volume) = &self.bell_volume {
-			bell_volume.write_to(buf)?;
-		}
-		if let Some(bell_pitch) = &self.bell_pitch {
-			bell_pitch.write_to(buf)?;
-		}
-		if let Some(bell_duration) = &self.bell_duration {
-			bell_duration.write_to(buf)?;
-		}
+		auto_repeated_key: AUTO_REPEATED_KEY,
 
-		if let Some(led) = &self.led {
-			led.write_to(buf)?;
-		}
-		if let Some(led_mode) = &self.led_mode {
-			led_mode.write_to(buf)?;
-		}
-
-		if let Some(auto_repeated_key) = &self.auto_repeated_key {
-			auto_repeated_key.write_to(buf)?;
-		}
-
-		if let Some(auto_repeat_mode) = &self.auto_repeat_mode {
-			auto_repeat_mode.write_to(buf)?;
-		}
-
-		Ok(())
+		auto_repeat_mode: AUTO_REPEAT_MODE,
 	}
 }
 
@@ -884,7 +887,7 @@ impl Readable for __PercentOrDefault {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_i32() {
+		Ok(Self(match i32::read_from(buf)? {
 			reset if reset == -1 => PercentOrDefault::Default,
 
 			value => match u8::try_from(value) {
@@ -940,7 +943,7 @@ impl Readable for __PitchOrDefault {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_i32() {
+		Ok(Self(match i32::read_from(buf)? {
 			reset if reset == -1 => PitchOrDefault::Reset,
 
 			other => match u8::try_from(other) {
@@ -993,7 +996,7 @@ impl Readable for __DurationOrDefault {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match buf.get_i32() {
+		Ok(Self(match i32::read_from(buf)? {
 			reset if reset == -1 => DurationOrDefault::Reset,
 
 			other => match u8::try_from(other) {
@@ -1042,7 +1045,7 @@ impl Readable for __Led {
 	where
 		Self: Sized,
 	{
-		Ok(Self(match u8::try_from(buf.get_u32()) {
+		Ok(Self(match u8::try_from(u32::read_from(buf)?) {
 			Ok(zero) if zero == 0 => return Err(ReadError::Other(Box::new(LedError::Zero))),
 			Ok(high) if high > 32 => {
 				return Err(ReadError::Other(Box::new(LedError::TooHigh(high))))
@@ -1064,123 +1067,3 @@ impl Writable for __Led {
 		Ok(())
 	}
 }
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __LedMode(LedMode);
-
-impl ConstantX11Size for __LedMode {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __LedMode {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __LedMode {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			off if off == 0 => LedMode::Off,
-			on if on == 1 => LedMode::On,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __LedMode {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(led_mode) = self;
-
-		match led_mode {
-			LedMode::Off => buf.put_u32(0),
-			LedMode::On => buf.put_u32(1),
-		}
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __Keycode(Keycode);
-
-impl ConstantX11Size for __Keycode {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __Keycode {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __Keycode {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(Keycode::new(
-			buf.get_u32()
-				.try_into()
-				.expect("must fit into u8; represents u8 value"),
-		)))
-	}
-}
-
-impl Writable for __Keycode {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(Keycode(keycode)) = self;
-
-		buf.put_u32((*keycode).into());
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __ToggleOrDefault(ToggleOrDefault);
-
-impl ConstantX11Size for __ToggleOrDefault {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __ToggleOrDefault {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __ToggleOrDefault {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => ToggleOrDefault::Disabled,
-			discrim if discrim == 1 => ToggleOrDefault::Enabled,
-
-			discrim if discrim == 2 => ToggleOrDefault::Default,
-
-			other_discrim => return Err(UnrecognizedDiscriminant(other_discrim as usize)),
-		}))
-	}
-}
-
-impl Writable for __ToggleOrDefault {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(toggle_or_default) = self;
-
-		match toggle_or_default {
-			ToggleOrDefault::Disabled => buf.put_u32(0),
-			ToggleOrDefault::Enabled => buf.put_u32(1),
-
-			ToggleOrDefault::Default => buf.put_u32(2),
-		}
-
-		Ok(())
-	}
-}
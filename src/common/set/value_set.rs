@@ -0,0 +1,177 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use xrbk::{Buf, ReadResult, Readable, Writable, X11Size};
+
+/// A set of optional values, each of which is only present if its flag is set
+/// in the set's [mask].
+///
+/// Each value in a `ValueSet` is encoded in the order of its flag in the
+/// [mask], after the [mask] itself.
+///
+/// [mask]: ValueSet::Mask
+pub trait ValueSet: X11Size + Readable + Writable {
+	/// The mask specifying which values are present in this set.
+	type Mask;
+
+	/// Returns the [mask] specifying which values are present in this set.
+	///
+	/// [mask]: ValueSet::Mask
+	fn value_mask(&self) -> Self::Mask;
+}
+
+/// Reads an optional value for a set if the given `condition` is true.
+///
+/// This is not part of the public API.
+pub(super) fn read_set_value<T: Readable>(
+	buf: &mut impl Buf, x11_size: &mut usize, condition: bool,
+) -> ReadResult<Option<T>> {
+	Ok(if condition {
+		let ret = T::read_from(buf)?;
+		*x11_size += ret.x11_size();
+
+		Some(ret)
+	} else {
+		None
+	})
+}
+
+/// Implements [`ValueSet`], [`X11Size`], [`Readable`], [`Writable`], and
+/// [`InfallibleWritable`] for a set.
+///
+/// Every value must be [`InfallibleWritable`].
+///
+/// The set must have an `x11_size: usize` field caching its size, a `mask`
+/// field, and a field of type `Option<T>` for each of its values. The values
+/// must be listed in the order their flags appear in the mask, each followed
+/// by the name of its flag.
+///
+/// The number of `unused` bytes following the mask may optionally be given.
+///
+/// ```ignore
+/// impl_value_set! {
+///     WindowConfig: WindowConfigMask, unused 2 => {
+///         x: X,
+///         y: Y,
+///     }
+/// }
+/// ```
+///
+/// This is not part of the public API.
+macro_rules! impl_value_set {
+	(
+		$Set:ty: $Mask:ident $(, unused $unused:literal)? => {
+			$($field:ident: $Flag:ident),+$(,)?
+		}
+	) => {
+		impl $crate::set::ValueSet for $Set {
+			type Mask = $Mask;
+
+			fn value_mask(&self) -> Self::Mask {
+				self.mask
+			}
+		}
+
+		impl ::xrbk::X11Size for $Set {
+			fn x11_size(&self) -> usize {
+				self.x11_size
+			}
+		}
+
+		impl ::xrbk::Readable for $Set {
+			fn read_from(buf: &mut impl ::xrbk::Buf) -> ::xrbk::ReadResult<Self>
+			where
+				Self: Sized,
+			{
+				let mask = <$Mask as ::xrbk::Readable>::read_from(buf)?;
+				let mut x11_size = <$Mask as ::xrbk::ConstantX11Size>::X11_SIZE;
+
+				$(
+					buf.advance($unused);
+					x11_size += $unused;
+				)?
+
+				$(
+					let $field = $crate::set::value_set::read_set_value(
+						buf,
+						&mut x11_size,
+						mask.contains($Mask::$Flag),
+					)?;
+				)+
+
+				Ok(Self {
+					x11_size,
+					mask,
+
+					$($field,)+
+				})
+			}
+		}
+
+		impl ::xrbk::Writable for $Set {
+			fn write_to(&self, buf: &mut impl ::xrbk::BufMut) -> ::xrbk::WriteResult {
+				::xrbk::Writable::write_to(&self.mask, buf)?;
+
+				$(
+					buf.put_bytes(0, $unused);
+				)?
+
+				$(
+					if let Some($field) = &self.$field {
+						::xrbk::Writable::write_to($field, buf)?;
+					}
+				)+
+
+				Ok(())
+			}
+		}
+
+		impl ::xrbk::InfallibleWritable for $Set {}
+	};
+}
+
+pub(super) use impl_value_set;
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_read_set_value_x11_size() {
+		let mut x11_size = 0;
+		let mut buf = &[0u8; 7][..];
+
+		let _ = read_set_value::<u32>(&mut buf, &mut x11_size, true).unwrap();
+		let _ = read_set_value::<u16>(&mut buf, &mut x11_size, true).unwrap();
+		let _ = read_set_value::<u8>(&mut buf, &mut x11_size, true).unwrap();
+
+		assert_eq!(x11_size, 7);
+	}
+
+	#[test]
+	fn test_value_set_round_trip() {
+		use crate::{
+			set::{WindowConfig, WindowConfigMask},
+			unit::Px,
+			StackMode,
+		};
+
+		let mut config = WindowConfig::builder();
+		config.y(Px(-5)).stack_mode(StackMode::Opposite);
+		let config = config.build();
+
+		assert_eq!(
+			config.value_mask(),
+			WindowConfigMask::Y | WindowConfigMask::STACK_MODE
+		);
+
+		let mut bytes = Vec::new();
+		config.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, [0, 0x42, 0, 0, 0xff, 0xff, 0xff, 0xfb, 0, 0, 0, 4]);
+
+		let read = WindowConfig::read_from(&mut &bytes[..]).unwrap();
+		assert_eq!(read.x11_size(), bytes.len());
+		assert_eq!(read, config);
+	}
+}
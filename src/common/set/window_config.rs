@@ -2,22 +2,12 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::{StackMode, Window};
+use crate::{Coords, Dimensions, Rectangle, StackMode, Window};
 
 use crate::{set::__Px, unit::Px};
 use bitflags::bitflags;
-use xrbk::{
-	Buf,
-	BufMut,
-	ConstantX11Size,
-	ReadError,
-	ReadResult,
-	Readable,
-	Writable,
-	WriteResult,
-	X11Size,
-};
-use xrbk_macro::{ConstantX11Size, Readable, Writable, X11Size};
+use xrbk::{ConstantX11Size, Wrap4};
+use xrbk_macro::{ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 /// A set of options with which a [window] is configured.
 ///
@@ -66,7 +56,7 @@ pub struct WindowConfig {
 
 	sibling: Option<Window>,
 
-	stack_mode: Option<__StackMode>,
+	stack_mode: Option<Wrap4<StackMode>>,
 }
 
 impl WindowConfig {
@@ -76,6 +66,37 @@ impl WindowConfig {
 	pub const fn builder() -> WindowConfigBuilder {
 		WindowConfigBuilder::new()
 	}
+
+	/// Returns a [`WindowConfigBuilder`] with the options configured in this
+	/// `WindowConfig` set.
+	///
+	/// This allows a single option to be changed without configuring every
+	/// other option again.
+	#[must_use]
+	pub fn to_builder(&self) -> WindowConfigBuilder {
+		WindowConfigBuilder {
+			x11_size: self.x11_size,
+
+			mask: self.mask,
+
+			x: self.x().copied(),
+			y: self.y().copied(),
+			width: self.width().copied(),
+			height: self.height().copied(),
+
+			border_width: self.border_width().copied(),
+
+			sibling: self.sibling,
+
+			stack_mode: self.stack_mode().copied(),
+		}
+	}
+}
+
+impl From<WindowConfig> for WindowConfigBuilder {
+	fn from(config: WindowConfig) -> Self {
+		config.to_builder()
+	}
 }
 
 /// A builder used to construct a new [`WindowConfig` set].
@@ -115,7 +136,8 @@ impl WindowConfigBuilder {
 	#[must_use]
 	pub const fn new() -> Self {
 		Self {
-			x11_size: WindowConfigMask::X11_SIZE,
+			// The mask is followed by 2 unused bytes.
+			x11_size: WindowConfigMask::X11_SIZE + 2,
 
 			mask: WindowConfigMask::empty(),
 
@@ -152,7 +174,7 @@ impl WindowConfigBuilder {
 
 			sibling: self.sibling,
 
-			stack_mode: self.stack_mode.map(__StackMode),
+			stack_mode: self.stack_mode.map(Wrap4),
 		}
 	}
 }
@@ -223,6 +245,35 @@ impl WindowConfigBuilder {
 		self
 	}
 
+	/// Configures the [x coordinate] and [y coordinate] of the [window].
+	///
+	/// [x coordinate]: WindowConfig::x
+	/// [y coordinate]: WindowConfig::y
+	/// [window]: Window
+	pub fn coords(&mut self, coords: Coords) -> &mut Self {
+		self.x(coords.x).y(coords.y)
+	}
+	/// Configures the [width] and [height] of the [window].
+	///
+	/// [width]: WindowConfig::width
+	/// [height]: WindowConfig::height
+	/// [window]: Window
+	pub fn dimensions(&mut self, dimensions: Dimensions) -> &mut Self {
+		self.width(dimensions.width).height(dimensions.height)
+	}
+	/// Configures the [x coordinate], [y coordinate], [width], and [height] of
+	/// the [window].
+	///
+	/// [x coordinate]: WindowConfig::x
+	/// [y coordinate]: WindowConfig::y
+	/// [width]: WindowConfig::width
+	/// [height]: WindowConfig::height
+	/// [window]: Window
+	pub fn rectangle(&mut self, rectangle: Rectangle) -> &mut Self {
+		self.coords(rectangle.as_coords())
+			.dimensions(rectangle.as_dimensions())
+	}
+
 	/// Configures the width of the [window]'s border.
 	///
 	/// See [`WindowConfig::border_width`] for more information.
@@ -340,150 +391,22 @@ impl WindowConfig {
 	/// [`sibling`]: WindowConfig::sibling
 	#[must_use]
 	pub fn stack_mode(&self) -> Option<&StackMode> {
-		self.stack_mode
-			.as_ref()
-			.map(|__StackMode(stack_mode)| stack_mode)
-	}
-}
-
-impl X11Size for WindowConfig {
-	fn x11_size(&self) -> usize {
-		self.x11_size
+		self.stack_mode.as_ref().map(|Wrap4(stack_mode)| stack_mode)
 	}
 }
 
-impl Readable for WindowConfig {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		let mask = WindowConfigMask::read_from(buf)?;
-		// 2 unused bytes after the mask.
-		buf.advance(2);
+super::impl_value_set! {
+	WindowConfig: WindowConfigMask, unused 2 => {
+		x: X,
+		y: Y,
+		width: WIDTH,
+		height: HEIGHT,
 
-		let mut x11_size = mask.x11_size() + 2;
+		border_width: BORDER_WIDTH,
 
-		let x = super::read_set_value(buf, &mut x11_size, mask.contains(WindowConfigMask::X))?;
-		let y = super::read_set_value(buf, &mut x11_size, mask.contains(WindowConfigMask::Y))?;
-		let width =
-			super::read_set_value(buf, &mut x11_size, mask.contains(WindowConfigMask::WIDTH))?;
-		let height =
-			super::read_set_value(buf, &mut x11_size, mask.contains(WindowConfigMask::HEIGHT))?;
-
-		let border_width = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(WindowConfigMask::BORDER_WIDTH),
-		)?;
-
-		let sibling =
-			super::read_set_value(buf, &mut x11_size, mask.contains(WindowConfigMask::SIBLING))?;
-
-		let stack_mode = super::read_set_value(
-			buf,
-			&mut x11_size,
-			mask.contains(WindowConfigMask::STACK_MODE),
-		)?;
-
-		Ok(Self {
-			x11_size,
-
-			mask,
-
-			x,
-			y,
-			width,
-			height,
-
-			border_width,
-
-			sibling,
-
-			stack_mode,
-		})
-	}
-}
-
-impl Writable for WindowConfig {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		self.mask.write_to(buf)?;
-		// 2 unused bytes after the mask.
-		buf.put_bytes(0, 2);
-
-		if let Some(x) = self.x {
-			x.write_to(buf)?;
-		}
-		if let Some(y) = self.y {
-			y.write_to(buf)?;
-		}
-		if let Some(width) = self.width {
-			width.write_to(buf)?;
-		}
-		if let Some(height) = self.height {
-			height.write_to(buf)?;
-		}
-
-		if let Some(border_width) = self.border_width {
-			border_width.write_to(buf)?;
-		}
-
-		if let Some(sibling) = &self.sibling {
-			sibling.write_to(buf)?;
-		}
-
-		if let Some(stack_mode) = &self.stack_mode {
-			stack_mode.write_to(buf)?;
-		}
-
-		Ok(())
-	}
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct __StackMode(StackMode);
-
-impl ConstantX11Size for __StackMode {
-	const X11_SIZE: usize = 4;
-}
-
-impl X11Size for __StackMode {
-	fn x11_size(&self) -> usize {
-		Self::X11_SIZE
-	}
-}
-
-impl Readable for __StackMode {
-	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
-	where
-		Self: Sized,
-	{
-		Ok(Self(match buf.get_u32() {
-			discrim if discrim == 0 => StackMode::Above,
-			discrim if discrim == 1 => StackMode::Below,
-			discrim if discrim == 2 => StackMode::TopIf,
-			discrim if discrim == 3 => StackMode::BottomIf,
-			discrim if discrim == 4 => StackMode::Opposite,
-
-			other_discrim => {
-				return Err(ReadError::UnrecognizedDiscriminant(other_discrim as usize))
-			},
-		}))
-	}
-}
-
-impl Writable for __StackMode {
-	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let Self(stack_mode) = self;
-
-		match stack_mode {
-			StackMode::Above => buf.put_u32(0),
-			StackMode::Below => buf.put_u32(1),
-			StackMode::TopIf => buf.put_u32(2),
-			StackMode::BottomIf => buf.put_u32(3),
-			StackMode::Opposite => buf.put_u32(4),
-		}
+		sibling: SIBLING,
 
-		Ok(())
+		stack_mode: STACK_MODE,
 	}
 }
 
@@ -496,7 +419,7 @@ bitflags! {
 	/// [window]: Window
 	/// [`WindowConfigs` set]: WindowConfig
 	/// [`ConfigureWindowRequest` event]: crate::x11::event::ConfigureWindowRequest
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct WindowConfigMask: u16 {
 		/// Whether the [x coordinate] of the [window] is configured.
 		///
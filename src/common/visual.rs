@@ -15,7 +15,18 @@ use crate::{
 	Window,
 };
 use derive_more::{From, Into};
-use xrbk_macro::{derive_xrb, new, unwrap, ConstantX11Size, Readable, Wrap, Writable, X11Size};
+use thiserror::Error;
+use xrbk_macro::{
+	derive_xrb,
+	new,
+	unwrap,
+	ConstantX11Size,
+	InfallibleWritable,
+	Readable,
+	Wrap,
+	Writable,
+	X11Size,
+};
 
 /// A color in the X Window System.
 ///
@@ -57,6 +68,7 @@ use xrbk_macro::{derive_xrb, new, unwrap, ConstantX11Size, Readable, Wrap, Writa
 	X11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 )]
 pub struct ColorId(u32);
 
@@ -81,6 +93,7 @@ impl ColorId {
 	PartialEq,
 	Hash,
 	Debug,
+	Default,
 	From,
 	Into,
 	// `new` and `unwrap` const fns
@@ -91,6 +104,7 @@ impl ColorId {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 )]
 pub struct RgbColor(
 	/// Red.
@@ -292,7 +306,6 @@ impl From<RgbColor> for (u32, u32, u32) {
 	PartialEq,
 	Hash,
 	Debug,
-	From,
 	Into,
 	// `new` and `unwrap` const fns
 	new,
@@ -302,6 +315,7 @@ impl From<RgbColor> for (u32, u32, u32) {
 	ConstantX11Size,
 	Readable,
 	Writable,
+	InfallibleWritable,
 	Wrap,
 )]
 pub struct VisualId(u32);
@@ -316,9 +330,9 @@ derive_xrb! {
 		Debug,
 		new,
 		// XRBK traits
-		X11Size,
+		X11Size, ConstantX11Size,
 		Readable,
-		Writable,
+		Writable, InfallibleWritable,
 	)]
 	pub struct Format {
 		pub depth: u8,
@@ -329,7 +343,7 @@ derive_xrb! {
 }
 
 derive_xrb! {
-	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable)]
+	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct Screen {
 		pub root: Window,
 		pub default_colormap: Colormap,
@@ -359,7 +373,7 @@ derive_xrb! {
 		pub allowed_depths: Vec<Depth>,
 	}
 
-	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable)]
+	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct Depth {
 		pub depth: u8,
 		_,
@@ -373,7 +387,136 @@ derive_xrb! {
 	}
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+impl Screen {
+	/// Returns whether [windows] and [pixmaps] of the given `depth` are
+	/// supported on this `Screen`.
+	///
+	/// [windows]: Window
+	/// [pixmaps]: crate::Pixmap
+	#[must_use]
+	pub fn supports_depth(&self, depth: u8) -> bool {
+		self.allowed_depths
+			.iter()
+			.any(|allowed| allowed.depth == depth)
+	}
+
+	/// Returns the first [`VisualType`] of the given `class` supported at the
+	/// given `depth`, if there is one.
+	#[must_use]
+	pub fn find_visual(&self, depth: u8, class: VisualClass) -> Option<&VisualType> {
+		self.allowed_depths
+			.iter()
+			.filter(|allowed| allowed.depth == depth)
+			.flat_map(|allowed| &allowed.visuals)
+			.find(|visual| visual.class == class)
+	}
+
+	/// Returns the [`VisualType`] with the given `visual_id` along with the
+	/// depth it is supported at, if it is supported on this `Screen`.
+	#[must_use]
+	pub fn visual(&self, visual_id: VisualId) -> Option<(u8, &VisualType)> {
+		self.allowed_depths.iter().find_map(|allowed| {
+			allowed
+				.visuals
+				.iter()
+				.find(|visual| visual.visual_id == visual_id)
+				.map(|visual| (allowed.depth, visual))
+		})
+	}
+
+	/// Returns the [`VisualType`] of the `root_visual`.
+	#[must_use]
+	pub fn root_visual_type(&self) -> Option<&VisualType> {
+		self.visual(self.root_visual).map(|(_, visual)| visual)
+	}
+
+	/// Checks that the given `visual` is supported at the given `depth` on this
+	/// `Screen`.
+	///
+	/// # Errors
+	/// Returns an [`UnsupportedDepth`] error if the `depth` is not supported,
+	/// or an [`UnsupportedVisual`] error if the `visual` is not supported at
+	/// that `depth`.
+	///
+	/// [`UnsupportedDepth`]: UnsupportedVisual::UnsupportedDepth
+	/// [`UnsupportedVisual`]: UnsupportedVisual::UnsupportedVisual
+	pub fn check_visual(&self, depth: u8, visual: VisualId) -> Result<(), UnsupportedVisual> {
+		let mut allowed = self
+			.allowed_depths
+			.iter()
+			.filter(|allowed| allowed.depth == depth)
+			.peekable();
+
+		if allowed.peek().is_none() {
+			return Err(UnsupportedVisual::UnsupportedDepth { depth });
+		}
+
+		if allowed
+			.flat_map(|allowed| &allowed.visuals)
+			.any(|supported| supported.visual_id == visual)
+		{
+			Ok(())
+		} else {
+			Err(UnsupportedVisual::UnsupportedVisual { depth, visual })
+		}
+	}
+}
+
+/// An error returned when a combination of depth and [visual] is not supported
+/// by a [`Screen`].
+///
+/// Sending a [request] using such a combination would generate a
+/// [`Match` error].
+///
+/// [visual]: VisualType
+/// [request]: crate::message::Request
+/// [`Match` error]: crate::x11::error::Match
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum UnsupportedVisual {
+	/// The depth is not supported by the [`Screen`].
+	#[error("depth {depth} is not supported by the screen")]
+	UnsupportedDepth {
+		/// The unsupported depth.
+		depth: u8,
+	},
+
+	/// The [visual] is not supported at the depth.
+	///
+	/// [visual]: VisualType
+	#[error("visual {visual:?} is not supported at depth {depth}")]
+	UnsupportedVisual {
+		/// The depth.
+		depth: u8,
+		/// The [visual] which is not supported at the `depth`.
+		///
+		/// [visual]: VisualType
+		visual: VisualId,
+	},
+
+	/// An [`InputOnly`] [window] was given a depth other than zero.
+	///
+	/// [window]: Window
+	/// [`InputOnly`]: crate::WindowClass::InputOnly
+	#[error("`InputOnly` windows must have a depth of zero, not {depth}")]
+	InputOnlyDepth {
+		/// The nonzero depth.
+		depth: u8,
+	},
+}
+
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum VisualClass {
 	StaticGray,
 	GrayScale,
@@ -384,13 +527,119 @@ pub enum VisualClass {
 }
 
 derive_xrb! {
-	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, Readable, Writable)]
+	#[derive(Clone, Eq, PartialEq, Hash, Debug, new, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable)]
 	pub struct VisualType {
 		pub visual_id: VisualId,
 		pub class: VisualClass,
 		pub bits_per_rgb_value: u8,
 		pub colormap_entries: u16,
-		pub color_mask: RgbColor,
+		/// The bits of a pixel value which make up its red subfield.
+		///
+		/// This is only meaningful for [`DirectColor`] and [`TrueColor`]
+		/// visuals.
+		///
+		/// [`DirectColor`]: VisualClass::DirectColor
+		/// [`TrueColor`]: VisualClass::TrueColor
+		pub red_mask: u32,
+		/// The bits of a pixel value which make up its green subfield.
+		///
+		/// This is only meaningful for [`DirectColor`] and [`TrueColor`]
+		/// visuals.
+		///
+		/// [`DirectColor`]: VisualClass::DirectColor
+		/// [`TrueColor`]: VisualClass::TrueColor
+		pub green_mask: u32,
+		/// The bits of a pixel value which make up its blue subfield.
+		///
+		/// This is only meaningful for [`DirectColor`] and [`TrueColor`]
+		/// visuals.
+		///
+		/// [`DirectColor`]: VisualClass::DirectColor
+		/// [`TrueColor`]: VisualClass::TrueColor
+		pub blue_mask: u32,
 		[_; 4],
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use xrbk::{Readable, Writable};
+
+	use super::*;
+
+	fn visual_type(id: u32, class: VisualClass) -> VisualType {
+		VisualType::new(
+			VisualId::new(id),
+			class,
+			8,
+			256,
+			0x00ff_0000,
+			0x0000_ff00,
+			0x0000_00ff,
+		)
+	}
+
+	#[test]
+	fn test_find_visual() {
+		let depth_24 = Depth::new(
+			24,
+			vec![
+				visual_type(0x21, VisualClass::TrueColor),
+				visual_type(0x22, VisualClass::DirectColor),
+			],
+		);
+		let depth_32 = Depth::new(32, vec![visual_type(0x23, VisualClass::TrueColor)]);
+
+		let mut bytes = Vec::new();
+		depth_24.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), 8 + 2 * 24);
+		assert_eq!(Depth::read_from(&mut &bytes[..]).unwrap(), depth_24);
+
+		let screen = Screen {
+			root: Window::new(0x0000_0100),
+			default_colormap: Colormap::new(0x20),
+			white: ColorId::new(0x00ff_ffff),
+			black: ColorId::ZERO,
+			current_input_masks: EventMask::empty(),
+			width_px: Px(1920),
+			height_px: Px(1080),
+			width_mm: Mm(508),
+			height_mm: Mm(286),
+			min_installed_colormaps: 1,
+			max_installed_colormaps: 1,
+			root_visual: VisualId::new(0x21),
+			maintain_contents_mode: MaintainContents::Never,
+			maintain_windows_under: false,
+			root_depth: 24,
+			allowed_depths: vec![Depth::new(1, vec![]), depth_24, depth_32],
+		};
+
+		assert!(screen.supports_depth(1));
+		assert!(!screen.supports_depth(16));
+
+		assert_eq!(
+			screen
+				.find_visual(32, VisualClass::TrueColor)
+				.map(|visual| visual.visual_id),
+			Some(VisualId::new(0x23)),
+		);
+		assert_eq!(screen.find_visual(32, VisualClass::DirectColor), None);
+		assert_eq!(
+			screen.root_visual_type().map(|visual| visual.class),
+			Some(VisualClass::TrueColor),
+		);
+
+		assert_eq!(screen.check_visual(24, VisualId::new(0x22)), Ok(()));
+		assert_eq!(
+			screen.check_visual(32, VisualId::new(0x22)),
+			Err(UnsupportedVisual::UnsupportedVisual {
+				depth: 32,
+				visual: VisualId::new(0x22),
+			}),
+		);
+		assert_eq!(
+			screen.check_visual(16, VisualId::new(0x22)),
+			Err(UnsupportedVisual::UnsupportedDepth { depth: 16 }),
+		);
+	}
+}
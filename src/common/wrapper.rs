@@ -6,6 +6,7 @@ use xrbk::{
 	Buf,
 	BufMut,
 	ConstantX11Size,
+	InfallibleWritable,
 	ReadError,
 	ReadResult,
 	Readable,
@@ -63,10 +64,186 @@ macro_rules! impl_writable {
 				$($token)*
 			}
 		}
+
+		// None of the wrappers perform a conversion which can fail.
+		impl InfallibleWritable for $type {}
+	}
+} // }}}
+
+/// A wrapper which is either a special value or a specific [`Value`].
+///
+/// Each wrapper's special value is encoded as a sentinel in place of the
+/// specific [`Value`]. For most wrappers, that sentinel is zero, so the wrapper
+/// is encoded in exactly the same way as <code>[Option]<[Value]></code>:
+///
+/// |Wrapper                |Special value      |Sentinel|
+/// |-----------------------|-------------------|--------|
+/// |[`Option`]             |[`None`]           |`0`     |
+/// |[`CopyableFromParent`] |[`CopyFromParent`] |`0`     |
+/// |[`ParentRelatable`]    |[`ParentRelative`] |`1`     |
+/// |[`Any`]                |[`Any::Any`]       |`0`     |
+/// |[`CurrentableTime`]    |[`CurrentTime`]    |`0`     |
+///
+/// [`Value`]: SpecialValue::Value
+/// [Value]: SpecialValue::Value
+///
+/// [`CopyFromParent`]: CopyableFromParent::CopyFromParent
+/// [`ParentRelative`]: ParentRelatable::ParentRelative
+/// [`CurrentTime`]: CurrentableTime::CurrentTime
+pub trait SpecialValue: Sized {
+	/// The type of the specific value which the special value takes the place
+	/// of.
+	type Value;
+
+	/// The special value.
+	const SPECIAL: Self;
+
+	/// Wraps the given specific `value`.
+	fn other(value: Self::Value) -> Self;
+
+	/// Converts this wrapper to an [`Option`], where the special value is
+	/// [`None`].
+	fn into_option(self) -> Option<Self::Value>;
+
+	/// Converts the given `option` to this wrapper, where [`None`] is the
+	/// special value.
+	fn from_option(option: Option<Self::Value>) -> Self {
+		option.map_or(Self::SPECIAL, Self::other)
+	}
+}
+
+impl<T> SpecialValue for Option<T> {
+	type Value = T;
+
+	const SPECIAL: Self = None;
+
+	fn other(value: T) -> Self {
+		Some(value)
+	}
+
+	fn into_option(self) -> Self {
+		self
 	}
+}
+
+/// Implements [`SpecialValue`], conversion from `T`, and combinators for a
+/// wrapper with a special variant and an `Other(T)` variant.
+macro_rules! special_value {
+	// {{{
+	($Wrapper:ident::$Special:ident, $is_special:ident) => {
+		impl<T> SpecialValue for $Wrapper<T> {
+			type Value = T;
+
+			const SPECIAL: Self = Self::$Special;
+
+			fn other(value: T) -> Self {
+				Self::Other(value)
+			}
+
+			fn into_option(self) -> Option<T> {
+				match self {
+					Self::$Special => None,
+					Self::Other(value) => Some(value),
+				}
+			}
+		}
+
+		impl<T> From<T> for $Wrapper<T> {
+			fn from(value: T) -> Self {
+				Self::Other(value)
+			}
+		}
+
+		impl<T> $Wrapper<T> {
+			#[doc = concat!(
+						"Returns whether this is [`",
+						stringify!($Special),
+						"`](",
+						stringify!($Wrapper),
+						"::",
+						stringify!($Special),
+						")."
+					)]
+			#[must_use]
+			pub const fn $is_special(&self) -> bool {
+				matches!(self, Self::$Special)
+			}
+
+			/// Returns whether this is a specific value.
+			#[must_use]
+			pub const fn is_other(&self) -> bool {
+				matches!(self, Self::Other(_))
+			}
+
+			#[doc = concat!(
+						"Converts from `&",
+						stringify!($Wrapper),
+						"<T>` to `",
+						stringify!($Wrapper),
+						"<&T>`."
+					)]
+			#[must_use]
+			pub const fn as_ref(&self) -> $Wrapper<&T> {
+				match self {
+					Self::$Special => $Wrapper::$Special,
+					Self::Other(value) => $Wrapper::Other(value),
+				}
+			}
+
+			/// Maps a specific value with the given function, leaving the
+			/// special value unchanged.
+			pub fn map<U>(self, f: impl FnOnce(T) -> U) -> $Wrapper<U> {
+				match self {
+					Self::$Special => $Wrapper::$Special,
+					Self::Other(value) => $Wrapper::Other(f(value)),
+				}
+			}
+
+			/// Returns the specific value, or `default` if this is the special
+			/// value.
+			#[must_use]
+			pub fn unwrap_or(self, default: T) -> T {
+				self.into_option().unwrap_or(default)
+			}
+
+			/// Returns the specific value, or the result of calling `f` if this
+			/// is the special value.
+			pub fn unwrap_or_else(self, f: impl FnOnce() -> T) -> T {
+				self.into_option().unwrap_or_else(f)
+			}
+		}
+	};
+}
+
+/// Implements the XRBK traits for wrappers whose special value is encoded as
+/// zero, in the same way as <code>[Option]<[Value]></code>.
+///
+/// [Value]: SpecialValue::Value
+macro_rules! impl_zero_special_value {
+	($($type:ty),+$(,)?) => {
+		$(
+			impl_constant_x11_size!($type {
+				<<$type as SpecialValue>::Value as ConstantX11Size>::X11_SIZE
+			});
+
+			impl_readable!($type: buf {
+				Ok(Self::from_option(
+					Option::<<$type as SpecialValue>::Value>::read_from(buf)?,
+				))
+			});
+
+			impl_writable!($type: &self, buf {
+				self.into_option().write_to(buf)
+			});
+		)+
+	};
 } // }}}
 
 /// Values which may be copied from the 'parent'.
+///
+/// [`CopyFromParent`] is encoded as zero.
+///
+/// [`CopyFromParent`]: CopyableFromParent::CopyFromParent
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum CopyableFromParent<T> {
 	/// A value is initialized by copying the matching value of the parent.
@@ -86,104 +263,29 @@ pub enum CopyableFromParent<T> {
 	Other(T),
 }
 
-impl_constant_x11_size!(CopyableFromParent<WindowClass> { // {{{
-	WindowClass::X11_SIZE
-});
-
-impl_readable!(CopyableFromParent<WindowClass>: buf {
-	match buf.get_u32() {
-		discrim if discrim == 0 => Ok(Self::CopyFromParent),
-
-		discrim if discrim == 1 => Ok(Self::Other(WindowClass::InputOutput)),
-		discrim if discrim == 2 => Ok(Self::Other(WindowClass::InputOnly)),
-
-		other_discrim => Err(ReadError::UnrecognizedDiscriminant(other_discrim as usize)),
-	}
-});
-
-impl_writable!(CopyableFromParent<WindowClass>: &self, buf {
-	match self {
-		Self::CopyFromParent => buf.put_u32(0),
-		Self::Other(class) => class.write_to(buf)?,
-	}
-
-	Ok(())
-});
-
-impl_constant_x11_size!(CopyableFromParent<Pixmap> {
-	Pixmap::X11_SIZE
-});
-
-impl_readable!(CopyableFromParent<Pixmap>: buf {
-	Ok(match buf.get_u32() {
-		discrim if discrim == 0 => Self::CopyFromParent,
-		val => Self::Other(Pixmap::new(val)),
-	})
-});
-
-impl_writable!(CopyableFromParent<Pixmap>: &self, buf {
-	match self {
-		Self::CopyFromParent => buf.put_u32(0),
-		Self::Other(val) => val.write_to(buf)?,
-	}
-
-	Ok(())
-});
-
-impl_constant_x11_size!(CopyableFromParent<VisualId> {
-	VisualId::X11_SIZE
-});
-
-impl_readable!(CopyableFromParent<VisualId>: buf {
-	Ok(match buf.get_u32() {
-		discrim if discrim == 0 => Self::CopyFromParent,
-		val => Self::Other(VisualId::new(val)),
-	})
-});
-
-impl_writable!(CopyableFromParent<VisualId>: &self, buf {
-	match self {
-		Self::CopyFromParent => buf.put_u32(0),
-		Self::Other(id) => id.write_to(buf)?,
-	}
-
-	Ok(())
-});
+special_value!(CopyableFromParent::CopyFromParent, is_copy_from_parent);
 
-impl_constant_x11_size!(CopyableFromParent<Colormap> {
-	Colormap::X11_SIZE
-});
+impl_zero_special_value!(
+	CopyableFromParent<WindowClass>,
+	CopyableFromParent<Pixmap>,
+	CopyableFromParent<VisualId>,
+	CopyableFromParent<Colormap>,
+);
 
-impl_readable!(CopyableFromParent<Colormap>: buf {
-	Ok(match buf.get_u32() {
-		discrim if discrim == 0 => Self::CopyFromParent,
-		val => Self::Other(Colormap::new(val)),
-	})
-});
-
-impl_writable!(CopyableFromParent<Colormap>: &self, buf {
-	match self {
-		Self::CopyFromParent => buf.put_u32(0),
-		Self::Other(val) => val.write_to(buf)?,
-	}
-
-	Ok(())
-});
-
-impl_constant_x11_size!(CopyableFromParent<u8> {
+impl_constant_x11_size!(CopyableFromParent<u8> { // {{{
 	u8::X11_SIZE
 });
 
 impl_readable!(CopyableFromParent<u8>: buf {
-	Ok(match buf.get_u8() {
-		discrim if discrim == 0 => Self::CopyFromParent,
+	Ok(match u8::read_from(buf)? {
+		0 => Self::CopyFromParent,
 		val => Self::Other(val),
 	})
 });
 
 impl_writable!(CopyableFromParent<u8>: &self, buf {
 	match self {
-		Self::CopyFromParent => buf.put_u32(0),
+		Self::CopyFromParent => buf.put_u8(0),
 		Self::Other(val) => val.write_to(buf)?,
 	}
 
@@ -196,8 +298,13 @@ impl_writable!(CopyableFromParent<u8>: &self, buf {
 /// This is only used for [pixmaps]. The purpose of specifying `T` is to clearly
 /// show that it 'wraps' a [pixmap].
 ///
+/// [`ParentRelative`] is encoded as one, as zero is already taken by
+/// <code>Other([None])</code>.
+///
 /// [pixmaps]: Pixmap
 /// [pixmap]: Pixmap
+///
+/// [`ParentRelative`]: ParentRelatable::ParentRelative
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum ParentRelatable<T> {
 	/// The value of the 'parent' is used, as long as the parent has the same
@@ -208,17 +315,21 @@ pub enum ParentRelatable<T> {
 	Other(T),
 }
 
+special_value!(ParentRelatable::ParentRelative, is_parent_relative);
+
 impl_constant_x11_size!(ParentRelatable<Option<Pixmap>> { // {{{
 	Pixmap::X11_SIZE
 });
 
 impl_readable!(ParentRelatable<Option<Pixmap>>: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::Other(None),
 
 		discrim if discrim == 1 => Self::ParentRelative,
 
-		val => Self::Other(Some(Pixmap::new(val))),
+		val => Self::Other(Some(
+			Pixmap::try_new(val).map_err(|error| ReadError::FailedConversion(Box::new(error)))?,
+		)),
 	})
 });
 
@@ -235,6 +346,8 @@ impl_writable!(ParentRelatable<Option<Pixmap>>: &self, buf {
 
 /// Either [`Any`] value or a specific value.
 ///
+/// [`Any`] is encoded as zero.
+///
 /// [`Any`]: Any::Any
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum Any<T> {
@@ -245,108 +358,113 @@ pub enum Any<T> {
 	Other(T),
 }
 
-impl_constant_x11_size!(Any<Atom> { // {{{
-	Atom::X11_SIZE
-});
+special_value!(Any::Any, is_any);
 
-impl_readable!(Any<Atom>: buf {
-	Ok(match buf.get_u32() {
-		discrim if discrim == 0 => Self::Any,
-		val => Self::Other(Atom::new(val)),
-	})
-});
+impl_zero_special_value!(Any<Atom>, Any<Button>, Any<Keycode>);
 
-impl_writable!(Any<Atom>: &self, buf {
-	match self {
-		Self::Any => buf.put_u32(0),
-		Self::Other(atom) => atom.write_to(buf)?,
-	}
+/// A time which may simply fill in for the current server time.
+///
+/// [`CurrentTime`] is encoded as zero.
+///
+/// [`CurrentTime`]: CurrentableTime::CurrentTime
+#[doc(alias = "Time")]
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub enum CurrentableTime {
+	/// The X server should treat this time as its current time.
+	#[default]
+	CurrentTime,
 
-	Ok(())
-});
+	/// The X server should treat this time as this `Timestamp`.
+	Other(Timestamp),
+}
 
-impl_constant_x11_size!(Any<Button> {
-	Button::X11_SIZE
-});
+impl SpecialValue for CurrentableTime {
+	type Value = Timestamp;
 
-impl_readable!(Any<Button>: buf {
-	Ok(match buf.get_u8() {
-		discrim if discrim == 0 => Self::Any,
-		val => Self::Other(Button::new(val)),
-	})
-});
+	const SPECIAL: Self = Self::CurrentTime;
 
-impl_writable!(Any<Button>: &self, buf {
-	match self {
-		Self::Any => buf.put_u8(0),
-		Self::Other(button) => button.write_to(buf)?,
+	fn other(timestamp: Timestamp) -> Self {
+		Self::Other(timestamp)
 	}
 
-	Ok(())
-});
-
-impl_constant_x11_size!(Any<Keycode> {
-	Keycode::X11_SIZE
-});
-
-impl_readable!(Any<Keycode>: buf {
-	Ok(match buf.get_u8() {
-		discrim if discrim == 0 => Self::Any,
-		val => Self::Other(Keycode::new(val)),
-	})
-});
-
-impl_writable!(Any<Keycode>: &self, buf {
-	match self {
-		Self::Any => buf.put_u8(0),
-		Self::Other(keycode) => keycode.write_to(buf)?,
+	fn into_option(self) -> Option<Timestamp> {
+		match self {
+			Self::CurrentTime => None,
+			Self::Other(timestamp) => Some(timestamp),
+		}
 	}
+}
 
-	Ok(())
-}); // }}}
-
-/// A time which may simply fill in for the current server time.
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
-pub enum CurrentableTime {
-	/// The X server should treat this time as its current time.
-	CurrentTime,
-
-	/// The X server should treat this time as this `Timestamp`.
-	Other(Timestamp),
+impl From<Timestamp> for CurrentableTime {
+	fn from(timestamp: Timestamp) -> Self {
+		Self::Other(timestamp)
+	}
 }
 
-impl_constant_x11_size!(CurrentableTime { // {{{
-	Timestamp::X11_SIZE
-});
+impl CurrentableTime {
+	/// Creates a `CurrentableTime` from its encoded `time`, where `0` is
+	/// [`CurrentTime`].
+	///
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	#[must_use]
+	pub const fn new(time: u32) -> Self {
+		match time {
+			0 => Self::CurrentTime,
+			time => Self::Other(Timestamp::new(time)),
+		}
+	}
 
-impl_readable!(CurrentableTime: buf {
-	Ok(match buf.get_u32() {
-		discrim if discrim == 0 => Self::CurrentTime,
-		val => Self::Other(Timestamp::new(val)),
-	})
-});
+	/// Returns the encoded time, where [`CurrentTime`] is `0`.
+	///
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	#[must_use]
+	pub const fn unwrap(self) -> u32 {
+		match self {
+			Self::CurrentTime => 0,
+			Self::Other(timestamp) => timestamp.unwrap(),
+		}
+	}
 
-impl_writable!(CurrentableTime: &self, buf {
-	match self {
-		Self::CurrentTime => buf.put_u32(0),
-		Self::Other(timestamp) => timestamp.write_to(buf)?,
+	/// Returns the specific [`Timestamp`], if this is not [`CurrentTime`].
+	///
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	#[must_use]
+	pub const fn timestamp(self) -> Option<Timestamp> {
+		match self {
+			Self::CurrentTime => None,
+			Self::Other(timestamp) => Some(timestamp),
+		}
 	}
+}
 
-	Ok(())
-}); // }}}
+impl_zero_special_value!(CurrentableTime);
 
 /// The `destination` of a [`SendEvent` request].
 ///
+/// [`Cursor`] is encoded as zero and [`Focus`] as one; neither is a valid
+/// [window] ID.
+///
+/// [`Cursor`]: DestinationWindow::Cursor
+/// [`Focus`]: DestinationWindow::Focus
+/// [window]: Window
+///
 /// [`SendEvent` request]: crate::x11::request::SendEvent
+#[doc(alias = "Destination")]
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum DestinationWindow {
 	/// The [window] that the cursor is currently located within.
 	///
 	/// [window]: Window
+	#[doc(alias = "PointerWindow")]
 	Cursor,
 	/// The [window] which is currently focused.
 	///
+	/// If the focused [window] contains the cursor, the [window] that the
+	/// cursor is located within is used instead if it is a descendant of the
+	/// focused [window].
+	///
 	/// [window]: Window
+	#[doc(alias = "InputFocus")]
 	Focus,
 
 	/// This [window] in particular.
@@ -355,16 +473,39 @@ pub enum DestinationWindow {
 	Other(Window),
 }
 
+impl DestinationWindow {
+	/// Returns the specific [window] of this destination, if it is
+	/// [`Other`].
+	///
+	/// [window]: Window
+	/// [`Other`]: DestinationWindow::Other
+	#[must_use]
+	pub const fn window(&self) -> Option<Window> {
+		match self {
+			Self::Other(window) => Some(*window),
+			Self::Cursor | Self::Focus => None,
+		}
+	}
+}
+
+impl From<Window> for DestinationWindow {
+	fn from(window: Window) -> Self {
+		Self::Other(window)
+	}
+}
+
 impl_constant_x11_size!(DestinationWindow { // {{{
 	Window::X11_SIZE
 });
 
 impl_readable!(DestinationWindow: buf {
-	Ok(match buf.get_u32() {
-		discrim if discrim == 0 => Self::Cursor,
-		discrim if discrim == 1 => Self::Focus,
+	Ok(match u32::read_from(buf)? {
+		0 => Self::Cursor,
+		1 => Self::Focus,
 
-		val => Self::Other(Window::new(val)),
+		val => Self::Other(
+			Window::try_new(val).map_err(|error| ReadError::FailedConversion(Box::new(error)))?,
+		),
 	})
 });
 
@@ -406,11 +547,13 @@ impl_constant_x11_size!(FocusWindow { // {{{
 });
 
 impl_readable!(FocusWindow: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		discrim if discrim == 0 => Self::None,
 		discrim if discrim == 1 => Self::CursorRoot,
 
-		val => Self::Other(Window::new(val)),
+		val => Self::Other(
+			Window::try_new(val).map_err(|error| ReadError::FailedConversion(Box::new(error)))?,
+		),
 	})
 });
 
@@ -454,7 +597,7 @@ impl_constant_x11_size!(KillClientTarget { // {{{
 });
 
 impl_readable!(KillClientTarget: buf {
-	Ok(match buf.get_u32() {
+	Ok(match u32::read_from(buf)? {
 		0 => Self::DestroyTemporarilyRetainedResources,
 		resource => Self::KillClient { resource },
 	})
@@ -468,3 +611,63 @@ impl_writable!(KillClientTarget: &self, buf {
 
 	Ok(())
 }); // }}}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_util::encode;
+
+	#[test]
+	fn test_special_values_match_option_encoding() {
+		let class = CopyableFromParent::<WindowClass>::CopyFromParent;
+		assert_eq!(encode(&class), [0, 0]);
+		assert_eq!(encode(&class.map(|_| WindowClass::InputOnly)), [0, 0]);
+		assert_eq!(
+			encode(&CopyableFromParent::from(WindowClass::InputOnly)),
+			[0, 2]
+		);
+		assert_eq!(
+			CopyableFromParent::<WindowClass>::read_from(&mut &[0, 1][..]).unwrap(),
+			CopyableFromParent::Other(WindowClass::InputOutput),
+		);
+
+		assert_eq!(encode(&CopyableFromParent::<u8>::CopyFromParent), [0]);
+
+		let time = CurrentableTime::from(Timestamp::new(5));
+		assert_eq!(encode(&time), encode(&Some(Timestamp::new(5))));
+		assert_eq!(
+			CurrentableTime::from_option(None),
+			CurrentableTime::CurrentTime
+		);
+		assert_eq!(CurrentableTime::new(0), CurrentableTime::CurrentTime);
+		assert_eq!(CurrentableTime::new(5), time);
+		assert_eq!(
+			(time.unwrap(), time.timestamp()),
+			(5, Some(Timestamp::new(5)))
+		);
+
+		let button: Any<Button> = Any::Any;
+		assert_eq!(encode(&button), encode(&None::<Button>));
+		assert_eq!(button.unwrap_or(Button::new(3)), Button::new(3));
+	}
+
+	#[test]
+	fn test_destination_window_encoding() {
+		let window = Window::new(0x0020_0001);
+
+		for (destination, bytes) in [
+			(DestinationWindow::Cursor, [0, 0, 0, 0]),
+			(DestinationWindow::Focus, [0, 0, 0, 1]),
+			(DestinationWindow::from(window), [0x00, 0x20, 0x00, 0x01]),
+		] {
+			assert_eq!(encode(&destination), bytes);
+			assert_eq!(
+				DestinationWindow::read_from(&mut &bytes[..]).unwrap(),
+				destination
+			);
+		}
+
+		assert_eq!(DestinationWindow::from(window).window(), Some(window));
+		assert_eq!(DestinationWindow::Focus.window(), None);
+	}
+}
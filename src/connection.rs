@@ -4,8 +4,10 @@
 
 //! Messages to initialize a connection with an X server.
 
+use std::fmt::{self, Display, Formatter};
+
 use xrbk::X11Size;
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
 	visual::{Format, Screen},
@@ -18,17 +20,50 @@ const fn pad(n: usize) -> usize {
 	(4 - (n % 4)) % 4
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+/// The byte order which a client asks the X server to use when connecting.
+///
+/// Every message sent on a connection after the [`InitConnection`] message -
+/// in both directions - uses this byte order, regardless of the byte order
+/// native to either the client or the X server. The byte order of images is
+/// separate; see [`ImageEndianness`].
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum Endianness {
+	/// The most significant byte is sent first.
+	///
+	/// This is encoded as `'B'`.
+	#[doc(alias = "MSBFirst")]
 	BigEndian = 0x42,
+	/// The least significant byte is sent first.
+	///
+	/// This is encoded as `'l'`.
+	#[doc(alias = "LSBFirst")]
 	LittleEndian = 0x6c,
 }
 
+/// The byte order used by XRB to serialize and deserialize messages.
+///
+/// XRB always asks for this byte order in the [`InitConnection`] message, so
+/// messages are encoded the same way whatever the byte order of the X server.
+pub const BYTE_ORDER: Endianness = Endianness::BigEndian;
+
 derive_xrb! {
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[derive(Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct InitConnection {
-		// XRBK assumes the endianness is big endian, so we hardcode that in.
-		let byte_order: Endianness = Endianness::BigEndian,
+		// Every reader and writer in XRB uses big-endian byte order, so that is
+		// what must be requested here.
+		let byte_order: Endianness = BYTE_ORDER,
 		_,
 
 		// XRB is implemented for one specific version of the X11 protocol, so
@@ -52,14 +87,26 @@ derive_xrb! {
 	}
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Hash,
+	Debug,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum ImageEndianness {
 	LittleEndian,
 	BigEndian,
 }
 
 derive_xrb! {
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[derive(Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	pub enum ConnectionResponse {
 		/// There was a failure in attempting the connection.
 		Failed(ConnectionFailure),
@@ -75,6 +122,163 @@ pub enum ConnError {
 	AuthenticationError(ConnectionAuthenticationError),
 }
 
+/// A summary of the limits and formats of an X server, taken from its
+/// [`ConnectionSuccess`] response.
+///
+/// This is [`Copy`], unlike [`ConnectionSuccess`], so that it can be handed to
+/// any code which needs to consult the server's limits without keeping the
+/// whole [`ConnectionSuccess`] response around.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ServerCapabilities {
+	/// The major version of the X11 protocol used by the X server.
+	pub protocol_major_version: u16,
+	/// The minor version of the X11 protocol used by the X server.
+	pub protocol_minor_version: u16,
+
+	/// The maximum length of a [request] accepted by the X server, in units of
+	/// 4 bytes.
+	///
+	/// [request]: crate::message::Request
+	pub maximum_request_length: u16,
+
+	/// The byte order of images sent to and from the X server.
+	pub image_byte_order: ImageEndianness,
+	/// The bit order of each scanline unit in bitmaps.
+	pub bitmap_format_bit_order: ImageEndianness,
+	/// The number of bits in each scanline unit of bitmaps.
+	pub bitmap_format_scanline_unit: u8,
+	/// The number of bits that each scanline of a bitmap is padded to.
+	pub bitmap_format_scanline_padding: u8,
+
+	/// The lowest [keycode] used by the X server.
+	///
+	/// [keycode]: Keycode
+	pub min_keycode: Keycode,
+	/// The highest [keycode] used by the X server.
+	///
+	/// [keycode]: Keycode
+	pub max_keycode: Keycode,
+}
+
+impl ServerCapabilities {
+	/// Returns whether the X server uses the version of the X11 protocol
+	/// implemented by XRB.
+	///
+	/// Only the major version must match: minor versions are backwards
+	/// compatible.
+	#[must_use]
+	pub const fn is_supported_version(&self) -> bool {
+		self.protocol_major_version == crate::PROTOCOL_MAJOR_VERSION
+	}
+
+	/// The maximum length of a [request] accepted by the X server, in bytes.
+	///
+	/// [request]: crate::message::Request
+	#[must_use]
+	pub const fn maximum_request_bytes(&self) -> usize {
+		self.maximum_request_length as usize * 4
+	}
+}
+
+/// The version of an X server's release, parsed from its `release_number`.
+///
+/// The format of the `release_number` is specific to each vendor. See
+/// [`ConnectionSuccess::vendor_release`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct VendorRelease {
+	/// The major version of the release.
+	pub major: u32,
+	/// The minor version of the release.
+	pub minor: u32,
+	/// The patch version of the release.
+	pub patch: u32,
+	/// The snapshot number of the release, used for development snapshots
+	/// and release candidates.
+	pub snapshot: u32,
+}
+
+impl VendorRelease {
+	/// The `vendor` string of the X.Org Foundation's X server.
+	pub const XORG_VENDOR: &'static str = "The X.Org Foundation";
+
+	/// Parses an X.Org `release_number`, which is encoded as
+	/// `major * 10_000_000 + minor * 100_000 + patch * 1000 + snapshot`.
+	///
+	/// # Examples
+	/// ```
+	/// # use xrb::connection::VendorRelease;
+	/// assert_eq!(
+	///     VendorRelease::from_xorg(12_101_004),
+	///     VendorRelease { major: 1, minor: 21, patch: 1, snapshot: 4 },
+	/// );
+	/// ```
+	#[must_use]
+	pub const fn from_xorg(release_number: u32) -> Self {
+		Self {
+			major: release_number / 10_000_000,
+			minor: release_number / 100_000 % 100,
+			patch: release_number / 1000 % 100,
+			snapshot: release_number % 1000,
+		}
+	}
+}
+
+impl Display for VendorRelease {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+
+		if self.snapshot != 0 {
+			write!(f, ".{}", self.snapshot)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl ConnectionSuccess {
+	/// Returns whether the given resource `id` is within the range of resource
+	/// IDs assigned to this client.
+	///
+	/// Resources created by this client have IDs within this range, while
+	/// resources created by other clients do not.
+	#[must_use]
+	pub const fn owns_resource(&self, id: u32) -> bool {
+		id & !self.resource_id_mask == self.resource_id_base
+	}
+
+	/// Summarizes the limits and formats of the X server.
+	#[must_use]
+	pub const fn capabilities(&self) -> ServerCapabilities {
+		ServerCapabilities {
+			protocol_major_version: self.protocol_major_version,
+			protocol_minor_version: self.protocol_minor_version,
+
+			maximum_request_length: self.maximum_request_length,
+
+			image_byte_order: self.image_byte_order,
+			bitmap_format_bit_order: self.bitmap_format_bit_order,
+			bitmap_format_scanline_unit: self.bitmap_format_scanline_unit,
+			bitmap_format_scanline_padding: self.bitmap_format_scanline_padding,
+
+			min_keycode: self.min_keycode,
+			max_keycode: self.max_keycode,
+		}
+	}
+
+	/// Parses the `release_number` according to the format used by the
+	/// `vendor`.
+	///
+	/// Returns [`None`] if the format used by the `vendor` is not known. Only
+	/// the [X.Org Foundation][xorg]'s format is currently known.
+	///
+	/// [xorg]: VendorRelease::XORG_VENDOR
+	#[must_use]
+	pub fn vendor_release(&self) -> Option<VendorRelease> {
+		(self.vendor.to_latin1() == VendorRelease::XORG_VENDOR)
+			.then(|| VendorRelease::from_xorg(self.release_number))
+	}
+}
+
 impl ConnectionResponse {
 	// false negative for this lint here, so we allow it
 	#[allow(clippy::missing_const_for_fn)]
@@ -89,7 +293,7 @@ impl ConnectionResponse {
 
 derive_xrb! {
 	/// There was a failure in attempting the connection.
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[derive(Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ConnectionFailure {
 		#[allow(clippy::cast_possible_truncation)]
 		let reason_len: u8 = reason => reason.len() as u8,
@@ -109,11 +313,11 @@ derive_xrb! {
 		/// The reason for the failure.
 		#[context(reason_len => *reason_len as usize)]
 		pub reason: String8,
-		[_; ..],
+		[_; reason => pad(reason.len())],
 	}
 
 	/// The connection was successfully established.
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[derive(Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ConnectionSuccess {
 		_,
 		/// The major version of the X11 protocol used by the X server.
@@ -157,7 +361,9 @@ derive_xrb! {
 
 		#[context(vendor_len => *vendor_len as usize)]
 		pub vendor: String8,
-		[_; ..],
+		// The response's leading byte isn't part of this struct, so `[_; ..]`
+		// would pad to the wrong boundary.
+		[_; vendor => pad(vendor.len())],
 
 		#[context(pixmap_formats_len => *pixmap_formats_len as usize)]
 		pub pixmap_formats: Vec<Format>,
@@ -166,7 +372,7 @@ derive_xrb! {
 	}
 
 	/// The connection was refused because authentication was unsuccessful.
-	#[derive(Debug, X11Size, Readable, Writable)]
+	#[derive(Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ConnectionAuthenticationError {
 		[_; 5],
 
@@ -214,3 +420,104 @@ mod r#try {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn success() -> ConnectionSuccess {
+		ConnectionSuccess {
+			protocol_major_version: 11,
+			protocol_minor_version: 0,
+			release_number: 12_101_004,
+			resource_id_base: 0x0040_0000,
+			resource_id_mask: 0x001f_ffff,
+			motion_buffer_size: 256,
+			maximum_request_length: u16::MAX,
+			image_byte_order: ImageEndianness::LittleEndian,
+			bitmap_format_bit_order: ImageEndianness::LittleEndian,
+			bitmap_format_scanline_unit: 32,
+			bitmap_format_scanline_padding: 32,
+			min_keycode: Keycode(8),
+			max_keycode: Keycode(255),
+			vendor: String8::from_latin1(VendorRelease::XORG_VENDOR).unwrap(),
+			pixmap_formats: vec![],
+			roots: vec![],
+		}
+	}
+
+	#[test]
+	fn test_capabilities() {
+		let success = success();
+
+		let capabilities = success.capabilities();
+		assert!(capabilities.is_supported_version());
+		assert_eq!(capabilities.maximum_request_bytes(), 262_140);
+		assert_eq!(capabilities.min_keycode, Keycode(8));
+
+		let release = success.vendor_release().unwrap();
+		assert_eq!(release.to_string(), "1.21.1.4");
+		assert_eq!(VendorRelease::from_xorg(12_000_000).to_string(), "1.20.0");
+	}
+
+	#[test]
+	fn test_owns_resource() {
+		// A base of 0x0040_0000 and a mask of 0x001f_ffff.
+		let success = success();
+
+		assert!(success.owns_resource(0x0040_0000));
+		assert!(success.owns_resource(0x0040_0001));
+		assert!(success.owns_resource(0x005f_ffff));
+
+		assert!(!success.owns_resource(0x003f_ffff));
+		assert!(!success.owns_resource(0x0060_0000));
+		assert!(!success.owns_resource(0x0000_0001));
+	}
+
+	#[test]
+	fn test_byte_order() {
+		use xrbk::{Readable, Writable};
+
+		let init = InitConnection {
+			auth_protocol_name: String8::from_latin1("").unwrap(),
+			auth_protocol_data: String8::from_latin1("").unwrap(),
+		};
+
+		let mut bytes = Vec::new();
+		init.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, [b'B', 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+		// The X server replies in the byte order requested by the client, so
+		// this is what any X server sends in response to `bytes`.
+		#[rustfmt::skip]
+		let response = [
+			1, 0, 0, 11, 0, 0, 0, 9,
+			0x00, 0xb8, 0xa5, 0x8c, // release number
+			0x00, 0x40, 0x00, 0x00, // resource ID base
+			0x00, 0x1f, 0xff, 0xff, // resource ID mask
+			0x00, 0x00, 0x01, 0x00, // motion buffer size
+			0, 3, 0xff, 0xff, 0, 0, 0, 0,
+			32, 32, 8, 255, 0, 0, 0, 0,
+			b'X', b'R', b'B', 0,
+		];
+
+		let ConnectionResponse::Success(success) =
+			ConnectionResponse::read_from(&mut &response[..]).unwrap()
+		else {
+			panic!("expected a `ConnectionSuccess` response");
+		};
+
+		assert_eq!(success.protocol_major_version, 11);
+		assert_eq!(success.release_number, 12_101_004);
+		assert_eq!(success.resource_id_mask, 0x001f_ffff);
+		assert_eq!(success.motion_buffer_size, 256);
+		assert_eq!(success.maximum_request_length, u16::MAX);
+		assert_eq!(success.vendor.to_latin1(), "XRB");
+
+		let mut bytes = Vec::new();
+		ConnectionResponse::Success(success)
+			.write_to(&mut bytes)
+			.unwrap();
+		assert_eq!(bytes, response);
+	}
+}
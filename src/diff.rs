@@ -0,0 +1,290 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Field-level comparison of two streams of [dissected] messages.
+//!
+//! When debugging a reimplementation of a client against Xlib, the X11
+//! traffic of both can be captured, [dissected], and compared: a [`Differ`]
+//! pairs the messages of the two streams in order and reports each field
+//! which differs between them, such as `body.target` or `header.sequence`.
+//!
+//! Each message is flattened into a list of fields: its `kind`, `name`, and
+//! `length`, each of its `header` fields, and each of the [fields] of its
+//! decoded `body`, named by its path (such as `body.window`). The value of a
+//! field of the `body` is its [`Debug`] representation. Fields which only one
+//! of the two messages has are reported too, so a message which is missing
+//! from one of the streams is reported in full.
+//!
+//! The [`Display`] implementation of a [`MessageDiff`] shows the differing
+//! fields side by side.
+//!
+//! [dissected]: crate::dissect
+//! [fields]: crate::message::Message::fields
+//! [`Debug`]: std::fmt::Debug
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::dissect::Dissection;
+
+/// A field which differs between two messages.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FieldDiff {
+	/// The path of the field, such as `body.window`.
+	pub path: String,
+
+	/// The value of the field in the left message, if it has the field.
+	pub left: Option<String>,
+	/// The value of the field in the right message, if it has the field.
+	pub right: Option<String>,
+}
+
+/// The fields which differ between the messages at the same position in two
+/// streams.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct MessageDiff {
+	/// The position of the messages within their streams.
+	pub index: usize,
+	/// The fields which differ between the messages.
+	pub fields: Vec<FieldDiff>,
+}
+
+impl Display for MessageDiff {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		const MISSING: &str = "<missing>";
+
+		let path_width = self.fields.iter().map(|field| field.path.len()).max();
+		let left_width = self
+			.fields
+			.iter()
+			.map(|field| field.left.as_deref().unwrap_or(MISSING).len())
+			.max();
+
+		write!(f, "message {}:", self.index)?;
+
+		for FieldDiff { path, left, right } in &self.fields {
+			write!(
+				f,
+				"\n  {path:<path_width$}  {:<left_width$} | {}",
+				left.as_deref().unwrap_or(MISSING),
+				right.as_deref().unwrap_or(MISSING),
+				path_width = path_width.unwrap_or_default(),
+				left_width = left_width.unwrap_or_default(),
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Compares [dissected] messages field by field.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [dissected]: crate::dissect
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Differ {
+	ignored: Vec<String>,
+}
+
+impl Differ {
+	/// Creates a new `Differ` which compares every field.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Ignores differences in the field with the given `name`.
+	///
+	/// The `name` matches either a whole path, such as `header.sequence`, or
+	/// the last part of a path, such as `sequence`.
+	#[must_use]
+	pub fn ignore(mut self, name: impl Into<String>) -> Self {
+		self.ignored.push(name.into());
+
+		self
+	}
+
+	/// Returns whether differences in the field at the given `path` are
+	/// ignored.
+	fn is_ignored(&self, path: &str) -> bool {
+		self.ignored.iter().any(|name| {
+			path == name
+				|| path
+					.strip_suffix(name.as_str())
+					.is_some_and(|parent| parent.ends_with('.'))
+		})
+	}
+
+	/// Returns the fields which differ between the `left` and `right`
+	/// messages.
+	#[must_use]
+	pub fn diff(&self, left: &Dissection, right: &Dissection) -> Vec<FieldDiff> {
+		self.diff_fields(&flatten(left), &flatten(right))
+	}
+
+	fn diff_fields(&self, left: &[(String, String)], right: &[(String, String)]) -> Vec<FieldDiff> {
+		let find = |fields: &[(String, String)], path: &str| {
+			fields
+				.iter()
+				.find(|(other, _)| other == path)
+				.map(|(_, value)| value.clone())
+		};
+
+		// Fields only in `right` are listed after those in `left`.
+		let paths = left.iter().map(|(path, _)| path).chain(
+			right
+				.iter()
+				.map(|(path, _)| path)
+				.filter(|path| find(left, path).is_none()),
+		);
+
+		paths
+			.filter(|path| !self.is_ignored(path))
+			.filter_map(|path| {
+				let (left, right) = (find(left, path), find(right, path));
+
+				(left != right).then(|| FieldDiff {
+					path: path.clone(),
+					left,
+					right,
+				})
+			})
+			.collect()
+	}
+
+	/// Compares the messages of the `left` and `right` streams in order,
+	/// returning a [`MessageDiff`] for each position at which they differ.
+	///
+	/// If one stream is longer than the other, its extra messages are
+	/// reported as differing from missing messages.
+	pub fn diff_streams<'a>(
+		&self, left: impl IntoIterator<Item = &'a Dissection>,
+		right: impl IntoIterator<Item = &'a Dissection>,
+	) -> Vec<MessageDiff> {
+		let (mut left, mut right) = (left.into_iter(), right.into_iter());
+		let mut diffs = Vec::new();
+
+		for index in 0.. {
+			let (left, right) = match (left.next(), right.next()) {
+				(None, None) => break,
+				(left, right) => (left.map(flatten), right.map(flatten)),
+			};
+
+			let fields = self.diff_fields(
+				left.as_deref().unwrap_or_default(),
+				right.as_deref().unwrap_or_default(),
+			);
+
+			if !fields.is_empty() {
+				diffs.push(MessageDiff { index, fields });
+			}
+		}
+
+		diffs
+	}
+}
+
+/// Flattens a [dissected] message into a list of field paths and values.
+///
+/// [dissected]: crate::dissect
+fn flatten(dissection: &Dissection) -> Vec<(String, String)> {
+	let mut fields = vec![
+		("kind".to_owned(), dissection.kind.to_string()),
+		(
+			"name".to_owned(),
+			dissection.name.unwrap_or("<unknown>").to_owned(),
+		),
+		("length".to_owned(), dissection.length.to_string()),
+	];
+
+	fields.extend(
+		dissection
+			.header
+			.iter()
+			.map(|field| (format!("header.{}", field.name), field.value.to_string())),
+	);
+
+	match &dissection.body {
+		Some(body) => fields.extend(
+			body.fields()
+				.into_iter()
+				.map(|(name, value)| (format!("body.{name}"), format!("{value:?}"))),
+		),
+		None => fields.push(("body".to_owned(), "<not decoded>".to_owned())),
+	}
+
+	fields
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::dissect::dissect_request;
+
+	/// Dissects a `ChangeSaveSet` request for the given `window`.
+	fn change_save_set(mode: u8, window: u32) -> Dissection {
+		let mut bytes = vec![6, mode, 0, 2];
+		bytes.extend(window.to_be_bytes());
+
+		dissect_request(&bytes).unwrap()
+	}
+
+	#[test]
+	fn test_diff_messages() {
+		let differ = Differ::new();
+
+		let left = change_save_set(0, 0x0040_0001);
+		assert_eq!(differ.diff(&left, &change_save_set(0, 0x0040_0001)), []);
+
+		let diff = differ.diff(&left, &change_save_set(1, 0x0040_0002));
+		let paths: Vec<_> = diff.iter().map(|field| field.path.as_str()).collect();
+
+		assert!(paths.contains(&"header.metabyte"), "{paths:?}");
+		assert!(
+			paths.iter().any(|path| path.starts_with("body.")),
+			"{paths:?}"
+		);
+
+		let differ = differ.ignore("metabyte");
+		assert!(differ
+			.diff(&left, &change_save_set(1, 0x0040_0001))
+			.iter()
+			.all(|field| field.path != "header.metabyte"));
+	}
+
+	#[test]
+	fn test_diff_streams() {
+		let left = [change_save_set(0, 1), change_save_set(0, 2)];
+		let right = [change_save_set(0, 1)];
+
+		let diffs = Differ::new().diff_streams(&left, &right);
+
+		assert_eq!(diffs.len(), 1);
+		assert_eq!(diffs[0].index, 1);
+		assert!(diffs[0].fields.iter().all(|field| field.right.is_none()));
+
+		let display = diffs[0].to_string();
+		assert!(display.starts_with("message 1:"), "{display}");
+		assert!(display.contains("<missing>"), "{display}");
+	}
+
+	#[test]
+	fn test_flatten() {
+		let fields = flatten(&change_save_set(0, 0x0040_0001));
+		let body: Vec<_> = fields
+			.iter()
+			.filter(|(path, _)| path.starts_with("body."))
+			.map(|(path, value)| (path.as_str(), value.as_str()))
+			.collect();
+
+		assert_eq!(
+			body,
+			[
+				("body.change_mode", "Add"),
+				("body.window", "Window(4194305)"),
+			],
+		);
+	}
+}
@@ -0,0 +1,958 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A dissector for X11 messages in their wire format.
+//!
+//! The dissector takes the raw bytes of a [request] or a message sent by the
+//! X server (a [reply], [event], or [error]) and produces a [`Dissection`]: a
+//! structured, human-readable description of the message's header fields, its
+//! decoded body, and the regions of bytes which are unused.
+//!
+//! Messages are identified with the [registry], so any message defined in the
+//! core X11 protocol can be decoded. Messages which are not known to the
+//! [registry], such as those from extensions, are still dissected: their
+//! header is described, but their body is not decoded.
+//!
+//! The [events] of extensions can be decoded too by registering them with a
+//! [`Dissector`], once the extension's first event code and major opcode are
+//! known from its [`QueryExtension` reply]. Extensions are described by
+//! implementing [`ExtensionEvents`], so crates which implement extensions
+//! that XRB does not can have their [events] decoded as well.
+//!
+//! This is intended for debugging tools, such as proxies which log the X11
+//! traffic passing through them.
+//!
+//! [request]: crate::message::Request
+//! [reply]: crate::message::Reply
+//! [event]: crate::message::Event
+//! [error]: crate::message::Error
+//! [events]: crate::message::Event
+//!
+//! [registry]: crate::x11::registry
+//! [`QueryExtension` reply]: crate::x11::reply::QueryExtension
+
+use std::{
+	fmt,
+	fmt::{Debug, Display, Formatter},
+	ops::Range,
+};
+
+use thiserror::Error;
+use xrbk::{ReadError, TrackingBuf};
+
+use crate::{
+	message::{Event, Message, SYNTHETIC_EVENT_FLAG},
+	x11::{
+		event,
+		registry,
+		registry::{DecodedMessage, Decoder, Entry},
+	},
+};
+
+/// The size of every [event] and [error], and the minimum size of every
+/// [reply], in bytes.
+///
+/// [event]: crate::message::Event
+/// [error]: crate::message::Error
+/// [reply]: crate::message::Reply
+const SERVER_MESSAGE_SIZE: usize = 32;
+
+/// The code of the [`KeyboardState` event], which, unlike every other [event],
+/// has no sequence number.
+///
+/// [event]: Event
+/// [`KeyboardState` event]: event::KeyboardState
+const KEYBOARD_STATE_CODE: u8 = <event::KeyboardState as Event>::CODE;
+
+/// The code of `GenericEvent` [events], which are used by extensions to
+/// send [events] longer than 32 bytes without using up event codes.
+///
+/// A `GenericEvent` identifies its extension by its major opcode and its
+/// [event] type within that extension.
+///
+/// [event]: crate::message::Event
+/// [events]: crate::message::Event
+const GENERIC_EVENT_CODE: u8 = 35;
+
+/// The kind of message that was dissected.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Kind {
+	/// A [request](crate::message::Request).
+	Request,
+	/// A [reply](crate::message::Reply).
+	Reply,
+	/// An [event](crate::message::Event).
+	Event,
+	/// An [error](crate::message::Error).
+	Error,
+}
+
+impl Display for Kind {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Request => "request",
+			Self::Reply => "reply",
+			Self::Event => "event",
+			Self::Error => "error",
+		})
+	}
+}
+
+/// A field in the header of a dissected message.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Field {
+	/// The name of the field.
+	pub name: &'static str,
+	/// The range of bytes occupied by the field within the message.
+	pub range: Range<usize>,
+	/// The value of the field.
+	pub value: u32,
+}
+
+/// An error generated when a message cannot be dissected.
+#[derive(Debug, Error)]
+pub enum DissectError {
+	/// There were not enough bytes for the message.
+	#[error("expected at least {expected} bytes for the message, found {found}")]
+	Incomplete {
+		/// The number of bytes required.
+		expected: usize,
+		/// The number of bytes given.
+		found: usize,
+	},
+
+	/// The length of a [request] was zero.
+	///
+	/// This is only valid if the BIG-REQUESTS extension is in use, which is not
+	/// supported by the dissector.
+	///
+	/// [request]: crate::message::Request
+	#[error("the request's length was zero")]
+	ZeroLength,
+
+	/// The message's body could not be read.
+	///
+	/// This includes a body which ends before all of its fields could be read,
+	/// which is reported as a [`ReadError::NotEnoughBytes`].
+	#[error("failed to read the message's body: {0}")]
+	Read(#[from] ReadError),
+}
+
+/// A dissected message.
+///
+/// The [`Display`] implementation of a `Dissection` provides a human-readable
+/// dump of the message.
+pub struct Dissection {
+	/// The kind of message that was dissected.
+	pub kind: Kind,
+	/// The name of the message, if it is known to the [registry].
+	///
+	/// [registry]: crate::x11::registry
+	pub name: Option<&'static str>,
+	/// The total length of the message, in bytes.
+	///
+	/// Any bytes following this length were not part of the message.
+	pub length: usize,
+
+	/// The fields in the header of the message.
+	pub header: Vec<Field>,
+	/// The decoded body of the message.
+	///
+	/// This is [`None`] if the message is not known to the [registry].
+	///
+	/// Its fields can be listed with [`Message::fields`].
+	///
+	/// [registry]: crate::x11::registry
+	/// [`Message::fields`]: crate::message::Message::fields
+	pub body: Option<Box<dyn DecodedMessage>>,
+	/// The regions of bytes within the message which are known to be unused.
+	///
+	/// These are given by the layout of the decoded body, as returned by
+	/// [`Message::unused`]. If the body was not decoded, they are only known
+	/// for [errors]: every core [error] uses none of the bytes following its
+	/// major opcode.
+	///
+	/// [error]: crate::message::Error
+	/// [errors]: crate::message::Error
+	/// [`Message::unused`]: crate::message::Message::unused
+	pub unused: Vec<Range<usize>>,
+	/// The offsets within the message at which each value read while decoding
+	/// the body began.
+	///
+	/// This marks the boundaries between fields of the message, including
+	/// those in its header. It is empty if the body was not decoded.
+	pub boundaries: Vec<usize>,
+}
+
+impl Dissection {
+	/// Returns the [field] in the header with the given `name`, if there is
+	/// one.
+	///
+	/// [field]: Field
+	#[must_use]
+	pub fn field(&self, name: &str) -> Option<&Field> {
+		self.header.iter().find(|field| field.name == name)
+	}
+
+	/// Whether the message is an [event] which was sent with a [`SendEvent`
+	/// request].
+	///
+	/// [event]: crate::message::Event
+	/// [`SendEvent` request]: crate::x11::request::SendEvent
+	#[must_use]
+	pub fn is_synthetic(&self) -> bool {
+		self.kind == Kind::Event
+			&& self
+				.field("code")
+				.is_some_and(|code| code.value & u32::from(SYNTHETIC_EVENT_FLAG) != 0)
+	}
+}
+
+impl Debug for Dissection {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Dissection")
+			.field("kind", &self.kind)
+			.field("name", &self.name)
+			.field("length", &self.length)
+			.field("header", &self.header)
+			.field("body", &self.body)
+			.field("unused", &self.unused)
+			.field("boundaries", &self.boundaries)
+			.finish()
+	}
+}
+
+impl Display for Dissection {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		writeln!(
+			f,
+			"{}{} {} ({} bytes)",
+			if self.is_synthetic() {
+				"synthetic "
+			} else {
+				""
+			},
+			self.kind,
+			self.name.unwrap_or("<unknown>"),
+			self.length,
+		)?;
+
+		for Field { name, range, value } in &self.header {
+			writeln!(
+				f,
+				"  [{:>3}..{:<3}] {name} = {value}",
+				range.start, range.end
+			)?;
+		}
+
+		for range in &self.unused {
+			writeln!(f, "  [{:>3}..{:<3}] <unused>", range.start, range.end)?;
+		}
+
+		if let Some((first, rest)) = self.boundaries.split_first() {
+			write!(f, "  boundaries: {first}")?;
+
+			for offset in rest {
+				write!(f, ", {offset}")?;
+			}
+
+			writeln!(f)?;
+		}
+
+		match &self.body {
+			Some(body) => write!(f, "{body:#?}"),
+			None => write!(f, "<body not decoded>"),
+		}
+	}
+}
+
+/// Ensures that `bytes` contains at least `expected` bytes.
+const fn expect(bytes: &[u8], expected: usize) -> Result<(), DissectError> {
+	if bytes.len() < expected {
+		Err(DissectError::Incomplete {
+			expected,
+			found: bytes.len(),
+		})
+	} else {
+		Ok(())
+	}
+}
+
+fn u16_at(bytes: &[u8], index: usize) -> u16 {
+	u16::from_be_bytes([bytes[index], bytes[index + 1]])
+}
+
+fn u32_at(bytes: &[u8], index: usize) -> u32 {
+	u32::from_be_bytes([
+		bytes[index],
+		bytes[index + 1],
+		bytes[index + 2],
+		bytes[index + 3],
+	])
+}
+
+fn field(name: &'static str, range: Range<usize>, bytes: &[u8]) -> Field {
+	let value = match range.len() {
+		1 => u32::from(bytes[range.start]),
+		2 => u32::from(u16_at(bytes, range.start)),
+		_ => u32_at(bytes, range.start),
+	};
+
+	Field { name, range, value }
+}
+
+/// Returns the [unused] bytes of a message with the given decoded `body`.
+///
+/// [unused]: Dissection::unused
+fn unused(body: Option<&dyn DecodedMessage>) -> Vec<Range<usize>> {
+	body.map(Message::unused).unwrap_or_default()
+}
+
+/// The decoded body of a message and the [boundaries] of the values read to
+/// decode it.
+///
+/// [boundaries]: Dissection::boundaries
+type Decoded = (Option<Box<dyn DecodedMessage>>, Vec<usize>);
+
+/// Decodes the body of a message with the `decoder` from its registry entry.
+///
+/// Errors generated while decoding, including a body which ends before all of
+/// its fields could be read, are wrapped with the offset at which they happened
+/// and the bytes preceding it.
+fn decode(decoder: Option<Decoder>, bytes: &[u8]) -> Result<Decoded, DissectError> {
+	let Some(decoder) = decoder else {
+		return Ok((None, Vec::new()));
+	};
+
+	let mut buf = TrackingBuf::new(bytes).record_boundaries();
+
+	match decoder(&mut buf) {
+		Ok(body) => Ok((Some(body), buf.boundaries().to_vec())),
+		Err(error) => Err(buf.wrap_error(error).into()),
+	}
+}
+
+/// Dissects the raw bytes of a [request].
+///
+/// `bytes` must begin with the [request]'s major opcode. Any bytes following
+/// the end of the [request] are ignored.
+///
+/// # Errors
+/// Returns a [`DissectError`] if `bytes` does not contain the entire
+/// [request], if the [request]'s length is zero, or if the body of a known
+/// [request] could not be decoded.
+///
+/// [request]: crate::message::Request
+pub fn dissect_request(bytes: &[u8]) -> Result<Dissection, DissectError> {
+	const HEADER_SIZE: usize = 4;
+
+	expect(bytes, HEADER_SIZE)?;
+
+	let length = usize::from(u16_at(bytes, 2)) * 4;
+	if length == 0 {
+		return Err(DissectError::ZeroLength);
+	}
+
+	expect(bytes, length)?;
+	let bytes = &bytes[..length];
+
+	let entry = registry::request(bytes[0]);
+
+	let (body, boundaries) = decode(entry.map(|entry| entry.decode), bytes)?;
+	let unused = unused(body.as_deref());
+
+	Ok(Dissection {
+		kind: Kind::Request,
+		name: entry.map(|entry| entry.name),
+		length,
+
+		header: vec![
+			field("major_opcode", 0..1, bytes),
+			field("metabyte", 1..2, bytes),
+			field("length", 2..4, bytes),
+		],
+		body,
+		unused,
+		boundaries,
+	})
+}
+
+/// Dissects the raw bytes of a message sent by the X server: a [reply], an
+/// [event], or an [error].
+///
+/// `bytes` must begin with the first byte of the message. Any bytes following
+/// the end of the message are ignored.
+///
+/// The kind of [request] a [reply] was generated by cannot be determined from
+/// the [reply] itself, so `request_major_opcode` is used to identify
+/// [replies][reply]. If it is [`None`], the body of a [reply] is not decoded.
+///
+/// # Errors
+/// Returns a [`DissectError`] if `bytes` does not contain the entire message,
+/// or if the body of a known message could not be decoded.
+///
+/// [request]: crate::message::Request
+/// [reply]: crate::message::Reply
+/// [event]: crate::message::Event
+/// [error]: crate::message::Error
+pub fn dissect_server_message(
+	bytes: &[u8], request_major_opcode: Option<u8>,
+) -> Result<Dissection, DissectError> {
+	Dissector::new().dissect_server_message(bytes, request_major_opcode)
+}
+
+fn dissect_reply(
+	bytes: &[u8], request_major_opcode: Option<u8>,
+) -> Result<Dissection, DissectError> {
+	let length = SERVER_MESSAGE_SIZE + (u32_at(bytes, 4) as usize) * 4;
+
+	expect(bytes, length)?;
+	let bytes = &bytes[..length];
+
+	let entry = request_major_opcode.and_then(registry::reply);
+
+	let (body, boundaries) = decode(entry.map(|entry| entry.decode), bytes)?;
+	let unused = unused(body.as_deref());
+
+	Ok(Dissection {
+		kind: Kind::Reply,
+		name: entry.map(|entry| entry.name),
+		length,
+
+		header: vec![
+			field("reply", 0..1, bytes),
+			field("metabyte", 1..2, bytes),
+			field("sequence", 2..4, bytes),
+			field("length", 4..8, bytes),
+		],
+		body,
+		unused,
+		boundaries,
+	})
+}
+
+fn dissect_error(bytes: &[u8]) -> Result<Dissection, DissectError> {
+	let entry = registry::error(bytes[1]);
+
+	let (body, boundaries) = decode(entry.map(|entry| entry.decode), bytes)?;
+	// Core errors do not use any bytes after the major opcode.
+	let unused = body
+		.as_ref()
+		.map_or_else(|| vec![11..SERVER_MESSAGE_SIZE], |body| body.unused());
+
+	Ok(Dissection {
+		kind: Kind::Error,
+		name: entry.map(|entry| entry.name),
+		length: SERVER_MESSAGE_SIZE,
+
+		header: vec![
+			field("error", 0..1, bytes),
+			field("code", 1..2, bytes),
+			field("sequence", 2..4, bytes),
+			field("data", 4..8, bytes),
+			field("minor_opcode", 8..10, bytes),
+			field("major_opcode", 10..11, bytes),
+		],
+		body,
+		unused,
+		boundaries,
+	})
+}
+
+/// The [events] of an extension, which can be registered with a
+/// [`Dissector`] to be decoded.
+///
+/// This only covers dissection: registered [events] are decoded into a
+/// [`Dissection`], not into a typed [event] which can be matched on. Typed
+/// dispatch of extension [events] is left until XRB has an `AnyEvent` enum
+/// for the core protocol's [events] to be dispatched through.
+///
+/// [event]: crate::message::Event
+/// [events]: crate::message::Event
+pub trait ExtensionEvents {
+	/// The name of the extension.
+	const NAME: &'static str;
+
+	/// The extension's [events], with [`Entry::code`] set to each [event]'s
+	/// code relative to the extension's first event code.
+	///
+	/// [event]: crate::message::Event
+	/// [events]: crate::message::Event
+	const EVENTS: &'static [Entry];
+
+	/// The extension's `GenericEvent` [events].
+	///
+	/// [events]: crate::message::Event
+	const GENERIC_EVENTS: &'static [GenericEntry] = &[];
+}
+
+/// An entry describing a single `GenericEvent` [event] of an extension.
+///
+/// `GenericEvent`s are identified by a 16-bit event type rather than by their
+/// code, so they cannot be described by an [`Entry`].
+///
+/// [event]: crate::message::Event
+#[derive(Clone, Copy)]
+pub struct GenericEntry {
+	/// The name of the [event].
+	///
+	/// [event]: crate::message::Event
+	pub name: &'static str,
+	/// The event type of the [event] within its extension.
+	///
+	/// [event]: crate::message::Event
+	pub event_type: u16,
+	/// Decodes the [event].
+	///
+	/// [event]: crate::message::Event
+	pub decode: Decoder,
+}
+
+impl Debug for GenericEntry {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.debug_struct("GenericEntry")
+			.field("name", &self.name)
+			.field("event_type", &self.event_type)
+			.finish_non_exhaustive()
+	}
+}
+
+/// An extension registered with a [`Dissector`].
+#[derive(Clone, Copy, Debug)]
+struct RegisteredExtension {
+	name: &'static str,
+
+	first_event: u8,
+	major_opcode: u8,
+
+	events: &'static [Entry],
+	generic_events: &'static [GenericEntry],
+}
+
+/// A dissector which decodes the [events] of registered extensions as well
+/// as messages defined in the core X11 protocol.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [events]: crate::message::Event
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Default)]
+pub struct Dissector {
+	extensions: Vec<RegisteredExtension>,
+}
+
+impl Dissector {
+	/// Creates a new `Dissector` with no extensions registered.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			extensions: Vec::new(),
+		}
+	}
+
+	/// Registers the [events] of the extension `Ext`.
+	///
+	/// `first_event` and `major_opcode` are those given in the
+	/// [`QueryExtension` reply] for the extension.
+	///
+	/// [events]: crate::message::Event
+	/// [`QueryExtension` reply]: crate::x11::reply::QueryExtension
+	pub fn register_extension<Ext: ExtensionEvents>(
+		&mut self, first_event: u8, major_opcode: u8,
+	) -> &mut Self {
+		self.extensions.push(RegisteredExtension {
+			name: Ext::NAME,
+
+			first_event,
+			major_opcode,
+
+			events: Ext::EVENTS,
+			generic_events: Ext::GENERIC_EVENTS,
+		});
+
+		self
+	}
+
+	/// Returns the names of the registered extensions.
+	pub fn extensions(&self) -> impl Iterator<Item = &'static str> + '_ {
+		self.extensions.iter().map(|extension| extension.name)
+	}
+
+	/// Dissects the raw bytes of a message sent by the X server: a [reply], an
+	/// [event], or an [error].
+	///
+	/// See [`dissect_server_message`] for more information. [Events] of the
+	/// registered extensions are decoded too.
+	///
+	/// # Errors
+	/// See [`dissect_server_message`].
+	///
+	/// [reply]: crate::message::Reply
+	/// [event]: crate::message::Event
+	/// [Events]: crate::message::Event
+	/// [error]: crate::message::Error
+	pub fn dissect_server_message(
+		&self, bytes: &[u8], request_major_opcode: Option<u8>,
+	) -> Result<Dissection, DissectError> {
+		expect(bytes, SERVER_MESSAGE_SIZE)?;
+
+		match bytes[0] {
+			0 => dissect_error(&bytes[..SERVER_MESSAGE_SIZE]),
+			1 => dissect_reply(bytes, request_major_opcode),
+
+			code if code & !SYNTHETIC_EVENT_FLAG == GENERIC_EVENT_CODE => {
+				self.dissect_generic_event(bytes)
+			},
+			_ => self.dissect_event(&bytes[..SERVER_MESSAGE_SIZE]),
+		}
+	}
+
+	/// Returns the registry entry for the [event] with the given `code`, from
+	/// the core X11 protocol or a registered extension.
+	///
+	/// [event]: crate::message::Event
+	fn event(&self, code: u8) -> Option<&'static Entry> {
+		registry::event(code).or_else(|| {
+			self.extensions.iter().find_map(|extension| {
+				let offset = code.checked_sub(extension.first_event)?;
+
+				extension.events.iter().find(|entry| entry.code == offset)
+			})
+		})
+	}
+
+	fn dissect_event(&self, bytes: &[u8]) -> Result<Dissection, DissectError> {
+		// The most significant bit of an event's code is set if the event was
+		// sent with a `SendEvent` request.
+		let code = bytes[0] & !SYNTHETIC_EVENT_FLAG;
+		let entry = self.event(code);
+
+		let mut header = vec![field("code", 0..1, bytes)];
+
+		if code != KEYBOARD_STATE_CODE {
+			header.push(field("metabyte", 1..2, bytes));
+			header.push(field("sequence", 2..4, bytes));
+		}
+
+		let (body, boundaries) = decode(entry.map(|entry| entry.decode), bytes)?;
+		let unused = unused(body.as_deref());
+
+		Ok(Dissection {
+			kind: Kind::Event,
+			name: entry.map(|entry| entry.name),
+			length: SERVER_MESSAGE_SIZE,
+
+			header,
+			body,
+			unused,
+			boundaries,
+		})
+	}
+
+	fn dissect_generic_event(&self, bytes: &[u8]) -> Result<Dissection, DissectError> {
+		let length = SERVER_MESSAGE_SIZE + (u32_at(bytes, 4) as usize) * 4;
+
+		expect(bytes, length)?;
+		let bytes = &bytes[..length];
+
+		let (major_opcode, event_type) = (bytes[1], u16_at(bytes, 8));
+		let entry = self
+			.extensions
+			.iter()
+			.filter(|extension| extension.major_opcode == major_opcode)
+			.flat_map(|extension| extension.generic_events)
+			.find(|entry| entry.event_type == event_type);
+
+		let (body, boundaries) = decode(entry.map(|entry| entry.decode), bytes)?;
+		let unused = unused(body.as_deref());
+
+		Ok(Dissection {
+			kind: Kind::Event,
+			name: entry.map(|entry| entry.name),
+			length,
+
+			header: vec![
+				field("code", 0..1, bytes),
+				field("extension", 1..2, bytes),
+				field("sequence", 2..4, bytes),
+				field("length", 4..8, bytes),
+				field("event_type", 8..10, bytes),
+			],
+			body,
+			unused,
+			boundaries,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::fmt::Debug;
+
+	use xrbk::{Buf, ReadResult};
+
+	use super::*;
+	use crate::message::MessageKind;
+
+	/// The bytes of an extension's event, as decoded in the tests.
+	#[derive(Debug)]
+	struct Bytes(Vec<u8>);
+
+	impl Message for Bytes {
+		fn name(&self) -> &'static str {
+			"Bytes"
+		}
+
+		fn kind(&self) -> MessageKind {
+			MessageKind::Event { code: 1 }
+		}
+
+		fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+			vec![("0", &self.0)]
+		}
+
+		fn unused(&self) -> Vec<Range<usize>> {
+			Vec::new()
+		}
+	}
+
+	#[allow(clippy::unnecessary_wraps, reason = "this must be a `Decoder`")]
+	fn decode_bytes(buf: &mut TrackingBuf<&[u8]>) -> ReadResult<Box<dyn DecodedMessage>> {
+		let bytes = buf.chunk().to_vec();
+		buf.advance(bytes.len());
+
+		Ok(Box::new(Bytes(bytes)))
+	}
+
+	struct Example;
+
+	impl ExtensionEvents for Example {
+		const NAME: &'static str = "EXAMPLE";
+
+		const EVENTS: &'static [Entry] = &[Entry {
+			name: "ExampleNotify",
+			code: 1,
+			decode: decode_bytes,
+
+			reencode: |bytes| Ok(bytes.to_vec()),
+		}];
+		const GENERIC_EVENTS: &'static [GenericEntry] = &[GenericEntry {
+			name: "ExampleGeneric",
+			event_type: 0x0103,
+			decode: decode_bytes,
+		}];
+	}
+
+	#[test]
+	fn test_extension_events() {
+		let mut dissector = Dissector::new();
+		dissector.register_extension::<Example>(90, 130);
+
+		let mut event = [0; 32];
+		event[0] = 91;
+
+		assert_eq!(dissect_server_message(&event, None).unwrap().name, None);
+		assert_eq!(
+			dissector.dissect_server_message(&event, None).unwrap().name,
+			Some("ExampleNotify")
+		);
+
+		// A `GenericEvent` with one extra 4-byte unit.
+		let mut generic = [0; 36];
+		generic[..10].copy_from_slice(&[35, 130, 0, 1, 0, 0, 0, 1, 1, 3]);
+
+		let dissection = dissector.dissect_server_message(&generic, None).unwrap();
+		assert_eq!(dissection.name, Some("ExampleGeneric"));
+		assert_eq!(dissection.length, 36);
+		assert_eq!(
+			dissection.field("event_type").map(|field| field.value),
+			Some(0x0103)
+		);
+
+		// Only the low byte of the event type matches.
+		generic[8] = 0;
+		assert_eq!(
+			dissector
+				.dissect_server_message(&generic, None)
+				.unwrap()
+				.name,
+			None
+		);
+		generic[8] = 1;
+
+		assert!(dissect_server_message(&generic[..32], None).is_err());
+	}
+
+	#[test]
+	fn test_boundaries_and_error_context() {
+		// A `ChangeSavedWindows` request.
+		let bytes = [6, 0, 0, 2, 0, 0x40, 0, 1];
+		let dissection = dissect_request(&bytes).unwrap();
+
+		assert_eq!(dissection.boundaries, [0, 1, 2, 4]);
+		assert!(dissection.to_string().contains("boundaries: 0, 1, 2, 4"));
+
+		// A `change_mode` of 2 is neither `Add` nor `Remove`.
+		let error = dissect_request(&[6, 2, 0, 2, 0, 0x40, 0, 1]).unwrap_err();
+
+		assert!(
+			matches!(
+				&error,
+				DissectError::Read(ReadError::AtOffset { offset: 2, preceding, .. })
+					if preceding == &[6, 2]
+			),
+			"{error}"
+		);
+		// The field begins at byte 1, but it has been read by the time that it
+		// fails.
+		let message = error.to_string();
+		assert!(message.contains("`change_mode`"), "{error}");
+		assert!(message.contains("at byte 1:"), "{error}");
+		assert!(message.ends_with("(after 2 bytes: 06 02)"), "{error}");
+	}
+
+	#[test]
+	fn test_dissect_request() {
+		// A `MapWindow` request.
+		let dissection = dissect_request(&[8, 0, 0, 2, 0, 0x20, 0, 1, 0xff]).unwrap();
+
+		assert_eq!(dissection.kind, Kind::Request);
+		assert_eq!(dissection.name, Some("MapWindow"));
+		assert_eq!(dissection.length, 8);
+		assert_eq!(dissection.field("length").map(|field| field.value), Some(2));
+		assert!(dissection.body.is_some());
+		// `MapWindow` has no metabyte.
+		assert_eq!(dissection.unused, [1..2]);
+
+		// A request which is not known to the registry.
+		let dissection = dissect_request(&[200, 0, 0, 1]).unwrap();
+		assert_eq!(dissection.name, None);
+		assert!(dissection.body.is_none());
+
+		assert!(matches!(
+			dissect_request(&[8, 0, 0, 0]),
+			Err(DissectError::ZeroLength)
+		));
+		assert!(matches!(
+			dissect_request(&[8, 0, 0, 2, 0]),
+			Err(DissectError::Incomplete {
+				expected: 8,
+				found: 5
+			})
+		));
+		assert!(matches!(
+			dissect_request(&[8, 0]),
+			Err(DissectError::Incomplete {
+				expected: 4,
+				found: 2
+			})
+		));
+
+		// A `GetAtom` request with a name 100 bytes long, but no name.
+		let Err(DissectError::Read(error)) = dissect_request(&[16, 0, 0, 2, 0, 100, 0, 0]) else {
+			panic!("expected a read error");
+		};
+		assert!(matches!(error.root(), ReadError::NotEnoughBytes { .. }));
+	}
+
+	#[test]
+	fn test_dissect_reply() {
+		// A `GetFocus` reply.
+		let mut reply = [0; 32];
+		reply[..12].copy_from_slice(&[1, 1, 0, 5, 0, 0, 0, 0, 0, 0x20, 0, 1]);
+
+		let dissection = dissect_server_message(&reply, Some(43)).unwrap();
+		assert_eq!(dissection.kind, Kind::Reply);
+		assert_eq!(dissection.name, Some("GetFocus"));
+		assert_eq!(dissection.length, 32);
+		assert_eq!(
+			dissection.field("sequence").map(|field| field.value),
+			Some(5)
+		);
+		assert!(dissection.body.is_some());
+		// The focus window is followed by 20 unused bytes.
+		assert_eq!(dissection.unused, [12..32]);
+
+		// Replies cannot be identified without the request's major opcode.
+		let dissection = dissect_server_message(&reply, None).unwrap();
+		assert_eq!(dissection.name, None);
+		assert!(dissection.body.is_none());
+
+		// A reply with one more 4-byte unit than there are bytes.
+		reply[7] = 1;
+		assert!(matches!(
+			dissect_server_message(&reply, Some(43)),
+			Err(DissectError::Incomplete {
+				expected: 36,
+				found: 32
+			})
+		));
+	}
+
+	#[test]
+	fn test_dissect_event() {
+		// An `Expose` event.
+		let mut event = [0; 32];
+		event[..4].copy_from_slice(&[12, 0, 0, 7]);
+
+		let dissection = dissect_server_message(&event, None).unwrap();
+		assert_eq!(dissection.kind, Kind::Event);
+		assert_eq!(dissection.name, Some("Expose"));
+		assert_eq!(
+			dissection.field("sequence").map(|field| field.value),
+			Some(7)
+		);
+		assert!(!dissection.is_synthetic());
+		assert_eq!(dissection.unused, [1..2, 18..32]);
+
+		// Events sent with `SendEvent` have the most significant bit of their
+		// code set.
+		event[0] |= SYNTHETIC_EVENT_FLAG;
+		let dissection = dissect_server_message(&event, None).unwrap();
+		assert_eq!(dissection.name, Some("Expose"));
+		assert!(dissection.is_synthetic());
+
+		// A `KeyboardState` event has no sequence number.
+		event[0] = KEYBOARD_STATE_CODE;
+		let dissection = dissect_server_message(&event, None).unwrap();
+		assert_eq!(dissection.name, Some("KeyboardState"));
+		assert!(dissection.field("sequence").is_none());
+
+		assert!(matches!(
+			dissect_server_message(&event[..31], None),
+			Err(DissectError::Incomplete {
+				expected: 32,
+				found: 31
+			})
+		));
+	}
+
+	#[test]
+	fn test_dissect_error() {
+		// A `Value` error for a `GetAtom` request.
+		let mut error = [0; 32];
+		error[..11].copy_from_slice(&[0, 2, 0, 3, 0, 0, 1, 0, 0, 0, 16]);
+
+		let dissection = dissect_server_message(&error, None).unwrap();
+		assert_eq!(dissection.kind, Kind::Error);
+		assert_eq!(dissection.name, Some("Value"));
+		assert_eq!(
+			dissection.field("major_opcode").map(|field| field.value),
+			Some(16)
+		);
+		assert_eq!(dissection.unused, [11..32]);
+
+		// A `Request` error has no error data.
+		error[1] = 1;
+		let dissection = dissect_server_message(&error, None).unwrap();
+		assert_eq!(dissection.name, Some("Request"));
+		assert_eq!(dissection.unused, [4..8, 11..32]);
+
+		// An error which is not known to the registry.
+		error[1] = 200;
+		let dissection = dissect_server_message(&error, None).unwrap();
+		assert!(dissection.body.is_none());
+		assert_eq!(dissection.unused, [11..32]);
+	}
+}
@@ -0,0 +1,253 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers for the conventions defined in the [Extended Window Manager Hints
+//! (EWMH)][EWMH] specification.
+//!
+//! [EWMH]: https://specifications.freedesktop.org/wm-spec/latest/
+
+use crate::{
+	atom::{self, AtomCache},
+	set::Attributes,
+	unit::Px,
+	x11::request::{CreateWindow, DataList, ModifyProperty, ModifyPropertyMode},
+	Atom,
+	CopyableFromParent,
+	Rectangle,
+	Window,
+	WindowClass,
+};
+
+/// The [atoms] which an EWMH-compliant window manager must publish on the
+/// root window.
+///
+/// None of these are [predefined atoms], so they must be interned, for
+/// example with an [`AtomCache`].
+///
+/// [atoms]: Atom
+/// [predefined atoms]: crate::atom
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct EwmhAtoms {
+	/// The `_NET_SUPPORTED` [atom].
+	///
+	/// [atom]: Atom
+	pub net_supported: Atom,
+	/// The `_NET_SUPPORTING_WM_CHECK` [atom].
+	///
+	/// [atom]: Atom
+	pub net_supporting_wm_check: Atom,
+	/// The `_NET_WM_NAME` [atom].
+	///
+	/// [atom]: Atom
+	pub net_wm_name: Atom,
+	/// The `UTF8_STRING` [atom], the type of `_NET_WM_NAME`.
+	///
+	/// [atom]: Atom
+	pub utf8_string: Atom,
+}
+
+impl EwmhAtoms {
+	/// The names of the [atoms] which must be interned.
+	///
+	/// [atoms]: Atom
+	pub const NAMES: [&'static str; 4] = [
+		"_NET_SUPPORTED",
+		"_NET_SUPPORTING_WM_CHECK",
+		"_NET_WM_NAME",
+		"UTF8_STRING",
+	];
+
+	/// Looks up the EWMH [atoms] in the given `cache`.
+	///
+	/// Returns [`None`] if any of them are not known to the `cache`.
+	///
+	/// [atoms]: Atom
+	#[must_use]
+	pub fn from_cache(cache: &AtomCache) -> Option<Self> {
+		let [net_supported, net_supporting_wm_check, net_wm_name, utf8_string] = Self::NAMES;
+
+		Some(Self {
+			net_supported: cache.get(net_supported)?,
+			net_supporting_wm_check: cache.get(net_supporting_wm_check)?,
+			net_wm_name: cache.get(net_wm_name)?,
+			utf8_string: cache.get(utf8_string)?,
+		})
+	}
+}
+
+/// The properties an EWMH-compliant window manager publishes on the root
+/// window to announce itself.
+///
+/// Once configured, [`requests()`] produces the [requests] which publish these
+/// properties.
+///
+/// [`requests()`]: RootProperties::requests
+/// [requests]: crate::message::Request
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RootProperties {
+	wm_name: String,
+	supported: Vec<Atom>,
+}
+
+/// The [requests] which publish a window manager's [`RootProperties`].
+///
+/// The [requests] must be sent in order: `create_check_window` first, then
+/// each of the `properties`.
+///
+/// [requests]: crate::message::Request
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct RootPropertyRequests {
+	/// Creates the `_NET_SUPPORTING_WM_CHECK` window.
+	///
+	/// This is an unmapped, [`InputOnly`] child of the root window which
+	/// exists for as long as the window manager is running.
+	///
+	/// [`InputOnly`]: WindowClass::InputOnly
+	pub create_check_window: CreateWindow,
+	/// Sets the `_NET_SUPPORTING_WM_CHECK` and `_NET_WM_NAME` properties on
+	/// the check window, then the `_NET_SUPPORTING_WM_CHECK` and
+	/// `_NET_SUPPORTED` properties on the root window.
+	pub properties: [ModifyProperty; 4],
+}
+
+impl RootProperties {
+	/// Creates new `RootProperties` for a window manager with the given
+	/// `wm_name`.
+	#[must_use]
+	pub fn new(wm_name: impl Into<String>) -> Self {
+		Self {
+			wm_name: wm_name.into(),
+			supported: Vec::new(),
+		}
+	}
+
+	/// Adds the given hints to the list of hints the window manager supports,
+	/// published as `_NET_SUPPORTED`.
+	pub fn supported(&mut self, hints: impl IntoIterator<Item = Atom>) -> &mut Self {
+		for hint in hints {
+			if !self.supported.contains(&hint) {
+				self.supported.push(hint);
+			}
+		}
+
+		self
+	}
+
+	/// The name of the window manager, published as the check window's
+	/// `_NET_WM_NAME`.
+	#[must_use]
+	pub fn wm_name(&self) -> &str {
+		&self.wm_name
+	}
+
+	/// Produces the [requests] which publish these properties.
+	///
+	/// `check_window` is the ID to use for the `_NET_SUPPORTING_WM_CHECK`
+	/// window, which must be allocated by the window manager. `root` is the
+	/// root window of the screen being managed.
+	///
+	/// `_NET_SUPPORTED` and `_NET_SUPPORTING_WM_CHECK` are always included in
+	/// the published list of supported hints.
+	///
+	/// [requests]: crate::message::Request
+	#[must_use]
+	#[allow(clippy::cast_possible_wrap, reason = "property data is reinterpreted")]
+	pub fn requests(
+		&self, root: Window, check_window: Window, atoms: &EwmhAtoms,
+	) -> RootPropertyRequests {
+		let mut attributes = Attributes::builder();
+		attributes.override_redirect(true);
+
+		let create_check_window = CreateWindow {
+			depth: CopyableFromParent::CopyFromParent,
+			window_id: check_window,
+			parent: root,
+			geometry: Rectangle::new(Px(-1), Px(-1), Px(1), Px(1)),
+			border_width: Px(0),
+			class: CopyableFromParent::Other(WindowClass::InputOnly),
+			visual: CopyableFromParent::CopyFromParent,
+			attributes: attributes.build(),
+		};
+
+		let mut supported = vec![atoms.net_supported, atoms.net_supporting_wm_check];
+		for hint in &self.supported {
+			if !supported.contains(hint) {
+				supported.push(*hint);
+			}
+		}
+
+		let check = |target| ModifyProperty {
+			modify_mode: ModifyPropertyMode::Replace,
+			target,
+			property: atoms.net_supporting_wm_check,
+			r#type: atom::WINDOW,
+			data: DataList::I32(vec![check_window.unwrap() as i32]),
+		};
+
+		RootPropertyRequests {
+			create_check_window,
+			properties: [
+				check(check_window),
+				ModifyProperty {
+					modify_mode: ModifyPropertyMode::Replace,
+					target: check_window,
+					property: atoms.net_wm_name,
+					r#type: atoms.utf8_string,
+					data: DataList::I8(self.wm_name.bytes().map(|byte| byte as i8).collect()),
+				},
+				check(root),
+				ModifyProperty {
+					modify_mode: ModifyPropertyMode::Replace,
+					target: root,
+					property: atoms.net_supported,
+					r#type: atom::ATOM,
+					data: DataList::I32(
+						supported.iter().map(|atom| atom.unwrap() as i32).collect(),
+					),
+				},
+			],
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use xrbk::{Writable, X11Size};
+
+	use super::*;
+
+	const ATOMS: EwmhAtoms = EwmhAtoms {
+		net_supported: Atom::new(300),
+		net_supporting_wm_check: Atom::new(301),
+		net_wm_name: Atom::new(302),
+		utf8_string: Atom::new(303),
+	};
+
+	#[test]
+	fn test_root_property_requests() {
+		let root = Window::new(0x0000_0100);
+		let check_window = Window::new(0x0020_0001);
+
+		let mut properties = RootProperties::new("xrb-wm");
+		properties.supported([ATOMS.net_wm_name, ATOMS.net_supported]);
+
+		let requests = properties.requests(root, check_window, &ATOMS);
+		assert_eq!(requests.create_check_window.parent, root);
+
+		let [check, name, root_check, supported] = &requests.properties;
+		assert_eq!((check.target, root_check.target), (check_window, root));
+		assert_eq!(
+			supported.data,
+			DataList::I32(vec![300, 301, 302]),
+			"`_NET_SUPPORTED` should not contain duplicates",
+		);
+
+		// The name is 6 bytes long, so 2 bytes of padding must follow it.
+		let mut bytes = Vec::new();
+		name.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), 24 + 6 + 2);
+		assert_eq!(bytes.len(), name.x11_size());
+		assert_eq!(&bytes[24..30], b"xrb-wm");
+	}
+}
@@ -0,0 +1,205 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Version negotiation for X11 extensions.
+//!
+//! Before an extension's [requests] may be used, the client must check that
+//! the extension is present with a [`QueryExtension` request], then send the
+//! extension's own `QueryVersion` [request] with the highest version of the
+//! extension that it supports. The X server replies with the version that it
+//! will use, which is never higher than the version sent by the client.
+//!
+//! Each extension module implements [`Extension`], which gives the name of
+//! the extension, the [`REQUIRED_VERSION`] which the module's messages are
+//! written against, and its `QueryVersion` [request] and [reply] pair, so
+//! that every extension is negotiated in the same way.
+//!
+//! An extension's [requests] are sent with the major opcode which the X server
+//! assigned to the extension, which is only known once the X server has
+//! replied to the [`QueryExtension` request]. The `QueryVersion` [request] is
+//! therefore a [`RawRequest`] created with that major opcode.
+//!
+//! [request]: crate::message::Request
+//! [requests]: crate::message::Request
+//! [reply]: crate::message::Reply
+//! [`QueryExtension` request]: QueryExtension
+//! [`REQUIRED_VERSION`]: Extension::REQUIRED_VERSION
+
+#[cfg(feature = "xinput")]
+pub mod xinput;
+
+use std::fmt::{self, Display, Formatter};
+
+use xrbk::Readable;
+
+use crate::{message::RawRequest, x11::request::QueryExtension, Char8, String8};
+
+/// The version of an X11 extension.
+///
+/// Versions are ordered by their `major` version, then by their `minor`
+/// version.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExtensionVersion {
+	/// The major version.
+	///
+	/// The major version increments when incompatible changes are made to
+	/// the extension.
+	pub major: u32,
+	/// The minor version.
+	///
+	/// The minor version increments when compatible features are added to
+	/// the extension.
+	pub minor: u32,
+}
+
+impl ExtensionVersion {
+	/// Creates a new `ExtensionVersion` with the given `major` and `minor`
+	/// versions.
+	#[must_use]
+	pub const fn new(major: u32, minor: u32) -> Self {
+		Self { major, minor }
+	}
+
+	/// Returns whether this version includes the features of the given
+	/// `major` and `minor` version.
+	///
+	/// That is the case if this version has the same `major` version and a
+	/// `minor` version at least as high.
+	///
+	/// # Examples
+	/// ```
+	/// # use xrb::extension::ExtensionVersion;
+	/// let version = ExtensionVersion::new(2, 3);
+	///
+	/// assert!(version.supports(2, 0));
+	/// assert!(version.supports(2, 3));
+	/// assert!(!version.supports(2, 4));
+	/// assert!(!version.supports(1, 0));
+	/// ```
+	#[must_use]
+	pub const fn supports(&self, major: u32, minor: u32) -> bool {
+		self.major == major && self.minor >= minor
+	}
+
+	/// Returns the version which will be used by a client which supports up to
+	/// this version and an X server which supports up to the given `server`
+	/// version.
+	///
+	/// This is the lower of the two versions, as the X server does.
+	#[must_use]
+	pub fn negotiate(self, server: Self) -> Self {
+		self.min(server)
+	}
+}
+
+impl Display for ExtensionVersion {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "{}.{}", self.major, self.minor)
+	}
+}
+
+impl From<(u32, u32)> for ExtensionVersion {
+	fn from((major, minor): (u32, u32)) -> Self {
+		Self::new(major, minor)
+	}
+}
+
+/// An X11 extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+pub trait Extension {
+	/// The name of the extension, as given in a [`QueryExtension` request].
+	///
+	/// [`QueryExtension` request]: QueryExtension
+	const NAME: &'static str;
+
+	/// The version of the extension which this module's messages implement.
+	///
+	/// This is the version which should be sent in the extension's
+	/// `QueryVersion` [request].
+	///
+	/// [request]: crate::message::Request
+	const REQUIRED_VERSION: ExtensionVersion;
+
+	/// The extension's `QueryVersion` [request].
+	///
+	/// [request]: crate::message::Request
+	type QueryVersion: RawRequest;
+	/// The [reply] to the extension's `QueryVersion` [request].
+	///
+	/// [request]: crate::message::Request
+	/// [reply]: crate::message::Reply
+	type QueryVersionReply: Readable;
+
+	/// Returns the `QueryVersion` [request] which asks for the
+	/// [`REQUIRED_VERSION`].
+	///
+	/// `major_opcode` is the major opcode of the extension, as given in the
+	/// [`QueryExtension` reply].
+	///
+	/// [request]: crate::message::Request
+	/// [`REQUIRED_VERSION`]: Extension::REQUIRED_VERSION
+	/// [`QueryExtension` reply]: crate::x11::reply::QueryExtension
+	#[must_use]
+	fn query_version(major_opcode: u8) -> Self::QueryVersion;
+
+	/// Returns the version of the extension which the X server will use, as
+	/// given in the [reply] to the `QueryVersion` [request].
+	///
+	/// [request]: crate::message::Request
+	/// [reply]: crate::message::Reply
+	#[must_use]
+	fn reply_version(reply: &Self::QueryVersionReply) -> ExtensionVersion;
+
+	/// Returns the [`QueryExtension` request] which checks that this extension
+	/// is present.
+	///
+	/// [`QueryExtension` request]: QueryExtension
+	#[must_use]
+	fn query_extension() -> QueryExtension {
+		QueryExtension {
+			// Extension names are ASCII.
+			name: String8::from_latin1_lossy(Self::NAME, Char8(b'?')),
+		}
+	}
+
+	/// Returns whether the `version` of the extension used by the X server, as
+	/// given in the [reply] to the `QueryVersion` [request], supports the
+	/// [`REQUIRED_VERSION`].
+	///
+	/// [request]: crate::message::Request
+	/// [reply]: crate::message::Reply
+	/// [`REQUIRED_VERSION`]: Extension::REQUIRED_VERSION
+	#[must_use]
+	fn is_supported(version: ExtensionVersion) -> bool {
+		let required = Self::REQUIRED_VERSION;
+
+		version.supports(required.major, required.minor)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_extension_version() {
+		let version = ExtensionVersion::new(1, 2);
+
+		assert!(version.supports(1, 0));
+		assert!(version.supports(1, 2));
+		assert!(!version.supports(1, 3));
+		assert!(!version.supports(0, 9));
+
+		assert!(ExtensionVersion::new(1, 10) > ExtensionVersion::new(1, 9));
+		assert!(ExtensionVersion::new(2, 0) > ExtensionVersion::new(1, 9));
+		assert_eq!(
+			ExtensionVersion::new(2, 4).negotiate(ExtensionVersion::new(2, 2)),
+			ExtensionVersion::new(2, 2)
+		);
+		assert_eq!(version.to_string(), "1.2");
+	}
+}
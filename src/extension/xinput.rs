@@ -0,0 +1,1162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Messages and types used by version 2 of the X Input extension (XI2).
+//!
+//! Only some of the XI2 requests and events are implemented so far, along
+//! with the building blocks which are fiddly to get right by hand.
+//!
+//! An [`XiSelectEvents`] request selects events with a list of event masks,
+//! each of which applies to a single device, or to all devices or all master
+//! devices. Each mask is encoded as a device ID, the length of the mask in
+//! 4-byte units, and the mask itself. [`XiEventMasks`] builds that list from
+//! [`DeviceSelector`]s and [`XiEventMask`]s.
+//!
+//! XI2 events and replies give coordinates as [`Fp1616`] and [`Fp3232`]
+//! fixed-point numbers. Valuator values are given as a mask of which
+//! valuators are present followed by the value of each of them, which
+//! [`Valuators`] decodes into valuator indexes and values.
+//!
+//! Touch events are grouped into the touches they belong to by a
+//! [`TouchSequence`].
+//!
+//! The extension itself is described by [`XInput`], with which the
+//! [dissector] can be told to decode XI2 touch events. Its version is
+//! negotiated with an [`XiQueryVersion`] request.
+//!
+//! [dissector]: crate::dissect::Dissector
+//! [`TouchSequence`]: touch::TouchSequence
+
+pub mod touch;
+
+use std::{fmt::Debug, ops::Range};
+
+use bitflags::bitflags;
+use xrbk::{
+	check_remaining,
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadError,
+	ReadResult,
+	Readable,
+	ReadableWithContext,
+	TrackingBuf,
+	Writable,
+	WriteResult,
+	X11Size,
+};
+use xrbk_macro::{ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
+
+use self::touch::{TouchEvent, TouchPhase};
+use crate::{
+	dissect::{ExtensionEvents, GenericEntry},
+	extension::{Extension, ExtensionVersion},
+	message::{Message, MessageKind, RawRequest},
+	x11::registry::{DecodedMessage, Entry},
+	Window,
+};
+
+/// The X Input extension.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct XInput;
+
+impl Extension for XInput {
+	const NAME: &'static str = "XInputExtension";
+
+	/// Version 2.2, which added touch events.
+	const REQUIRED_VERSION: ExtensionVersion = ExtensionVersion::new(2, 2);
+
+	type QueryVersion = XiQueryVersion;
+	type QueryVersionReply = XiQueryVersionReply;
+
+	#[allow(
+		clippy::cast_possible_truncation,
+		reason = "the required version fits in the `u16`s of the request"
+	)]
+	fn query_version(major_opcode: u8) -> XiQueryVersion {
+		let required = Self::REQUIRED_VERSION;
+
+		XiQueryVersion {
+			major_opcode,
+
+			major_version: required.major as u16,
+			minor_version: required.minor as u16,
+		}
+	}
+
+	fn reply_version(reply: &XiQueryVersionReply) -> ExtensionVersion {
+		ExtensionVersion::new(reply.major_version.into(), reply.minor_version.into())
+	}
+}
+
+/// An `XIQueryVersion` request, which tells the X server the highest version
+/// of XI2 supported by the client.
+///
+/// The X server replies with an [`XiQueryVersionReply`] containing the version
+/// of XI2 which it will use. A client must send this request before any other
+/// XI2 requests.
+///
+/// This request is created with the major opcode of the X Input extension,
+/// which is only known at runtime, so it is sent as a [`RawRequest`].
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct XiQueryVersion {
+	/// The major opcode of the X Input extension, as given in the
+	/// [`QueryExtension` reply].
+	///
+	/// [`QueryExtension` reply]: crate::x11::reply::QueryExtension
+	pub major_opcode: u8,
+
+	/// The major version of XI2 supported by the client.
+	pub major_version: u16,
+	/// The minor version of XI2 supported by the client.
+	pub minor_version: u16,
+}
+
+impl XiQueryVersion {
+	/// The minor opcode of the `XIQueryVersion` request.
+	pub const MINOR_OPCODE: u8 = 47;
+}
+
+impl Message for XiQueryVersion {
+	fn name(&self) -> &'static str {
+		"XiQueryVersion"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::Request {
+			major_opcode: self.major_opcode,
+			minor_opcode: Some(Self::MINOR_OPCODE.into()),
+		}
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("major_opcode", &self.major_opcode),
+			("major_version", &self.major_version),
+			("minor_version", &self.minor_version),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		Vec::new()
+	}
+}
+
+impl RawRequest for XiQueryVersion {
+	fn major_opcode(&self) -> u8 {
+		self.major_opcode
+	}
+
+	fn minor_opcode(&self) -> Option<u16> {
+		Some(Self::MINOR_OPCODE.into())
+	}
+
+	fn request_length(&self) -> u16 {
+		2
+	}
+
+	fn write_request_to(&self, mut buf: &mut dyn BufMut) -> WriteResult {
+		self.write_to(&mut buf)
+	}
+}
+
+impl ConstantX11Size for XiQueryVersion {
+	const X11_SIZE: usize = 8;
+}
+
+impl X11Size for XiQueryVersion {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for XiQueryVersion {
+	/// Reads an `XiQueryVersion` request.
+	///
+	/// Unlike core requests, the major opcode is read too, as it is not known
+	/// in advance.
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let major_opcode = u8::read_from(buf)?;
+
+		let minor_opcode = u8::read_from(buf)?;
+		if minor_opcode != Self::MINOR_OPCODE {
+			return Err(ReadError::UnrecognizedDiscriminant(minor_opcode.into()));
+		}
+
+		// The length of the request.
+		check_remaining(buf, 2)?;
+		buf.advance(2);
+
+		Ok(Self {
+			major_opcode,
+
+			major_version: u16::read_from(buf)?,
+			minor_version: u16::read_from(buf)?,
+		})
+	}
+}
+
+impl Writable for XiQueryVersion {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		buf.put_u8(self.major_opcode);
+		buf.put_u8(Self::MINOR_OPCODE);
+		buf.put_u16(self.request_length());
+
+		buf.put_u16(self.major_version);
+		buf.put_u16(self.minor_version);
+
+		Ok(())
+	}
+}
+
+/// The reply to an [`XiQueryVersion`] request.
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct XiQueryVersionReply {
+	/// The sequence number identifying the [`XiQueryVersion`] request which
+	/// generated this reply.
+	pub sequence: u16,
+
+	/// The major version of XI2 which the X server will use.
+	pub major_version: u16,
+	/// The minor version of XI2 which the X server will use.
+	pub minor_version: u16,
+}
+
+impl ConstantX11Size for XiQueryVersionReply {
+	const X11_SIZE: usize = 32;
+}
+
+impl X11Size for XiQueryVersionReply {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for XiQueryVersionReply {
+	/// Reads an `XiQueryVersionReply`, following the reply code.
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		// Unused metabyte.
+		check_remaining(buf, 1)?;
+		buf.advance(1);
+		let sequence = u16::read_from(buf)?;
+		// The length of the reply, which is always 0.
+		check_remaining(buf, 4)?;
+		buf.advance(4);
+
+		let major_version = u16::read_from(buf)?;
+		let minor_version = u16::read_from(buf)?;
+		// 20 unused bytes.
+		check_remaining(buf, 20)?;
+		buf.advance(20);
+
+		Ok(Self {
+			sequence,
+
+			major_version,
+			minor_version,
+		})
+	}
+}
+
+impl Writable for XiQueryVersionReply {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		// The reply code.
+		buf.put_u8(1);
+		// Unused metabyte.
+		buf.put_u8(0);
+		buf.put_u16(self.sequence);
+		// The length of the reply.
+		buf.put_u32(0);
+
+		buf.put_u16(self.major_version);
+		buf.put_u16(self.minor_version);
+		buf.put_bytes(0, 20);
+
+		Ok(())
+	}
+}
+
+impl ExtensionEvents for XInput {
+	const NAME: &'static str = <Self as Extension>::NAME;
+
+	/// XI2 events are all `GenericEvent`s.
+	const EVENTS: &'static [Entry] = &[];
+
+	/// Only touch events are decoded so far, as [`TouchEvent`]s.
+	const GENERIC_EVENTS: &'static [GenericEntry] = &[
+		touch_entry("TouchBegin", TouchPhase::Begin),
+		touch_entry("TouchUpdate", TouchPhase::Update),
+		touch_entry("TouchEnd", TouchPhase::End),
+	];
+}
+
+/// Returns the [`GenericEntry`] for the touch events of the given `phase`.
+const fn touch_entry(name: &'static str, phase: TouchPhase) -> GenericEntry {
+	GenericEntry {
+		name,
+		event_type: phase.event_type(),
+		decode: decode_touch_event,
+	}
+}
+
+fn decode_touch_event(buf: &mut TrackingBuf<&[u8]>) -> ReadResult<Box<dyn DecodedMessage>> {
+	// The `GenericEvent` code.
+	check_remaining(buf, 1)?;
+	buf.advance(1);
+
+	Ok(Box::new(TouchEvent::read_from_event(buf)?))
+}
+
+/// An `XISelectEvents` request, which selects the XI2 events which the client
+/// is interested in on a `window`.
+///
+/// This request is created with the major opcode of the X Input extension,
+/// which is only known at runtime, so it is sent as a [`RawRequest`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct XiSelectEvents {
+	/// The major opcode of the X Input extension, as given in the
+	/// [`QueryExtension` reply].
+	///
+	/// [`QueryExtension` reply]: crate::x11::reply::QueryExtension
+	pub major_opcode: u8,
+
+	/// The window on which the events are selected.
+	pub window: Window,
+	/// The events selected for each device.
+	pub masks: XiEventMasks,
+}
+
+impl XiSelectEvents {
+	/// The minor opcode of the `XISelectEvents` request.
+	pub const MINOR_OPCODE: u8 = 46;
+}
+
+impl Message for XiSelectEvents {
+	fn name(&self) -> &'static str {
+		"XiSelectEvents"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::Request {
+			major_opcode: self.major_opcode,
+			minor_opcode: Some(Self::MINOR_OPCODE.into()),
+		}
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("major_opcode", &self.major_opcode),
+			("window", &self.window),
+			("masks", &self.masks),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		vec![10..12]
+	}
+}
+
+impl RawRequest for XiSelectEvents {
+	fn major_opcode(&self) -> u8 {
+		self.major_opcode
+	}
+
+	fn minor_opcode(&self) -> Option<u16> {
+		Some(Self::MINOR_OPCODE.into())
+	}
+
+	#[allow(
+		clippy::cast_possible_truncation,
+		reason = "requests longer than `u16::MAX` units are rejected by the X server"
+	)]
+	fn request_length(&self) -> u16 {
+		(self.x11_size() / 4) as u16
+	}
+
+	fn write_request_to(&self, mut buf: &mut dyn BufMut) -> WriteResult {
+		self.write_to(&mut buf)
+	}
+}
+
+impl X11Size for XiSelectEvents {
+	fn x11_size(&self) -> usize {
+		const HEADER: usize = 4;
+
+		HEADER + Window::X11_SIZE + 4 + self.masks.x11_size()
+	}
+}
+
+impl Readable for XiSelectEvents {
+	/// Reads an `XiSelectEvents` request.
+	///
+	/// Unlike core requests, the major opcode is read too, as it is not known
+	/// in advance.
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let major_opcode = u8::read_from(buf)?;
+
+		let minor_opcode = u8::read_from(buf)?;
+		if minor_opcode != Self::MINOR_OPCODE {
+			return Err(ReadError::UnrecognizedDiscriminant(minor_opcode.into()));
+		}
+
+		// The length of the request.
+		check_remaining(buf, 2)?;
+		buf.advance(2);
+
+		let window = Window::read_from(buf)?;
+		let num_mask = usize::from(u16::read_from(buf)?);
+		// 2 unused bytes.
+		check_remaining(buf, 2)?;
+		buf.advance(2);
+
+		Ok(Self {
+			major_opcode,
+
+			window,
+			masks: XiEventMasks::read_with(buf, &num_mask)?,
+		})
+	}
+}
+
+impl Writable for XiSelectEvents {
+	#[allow(
+		clippy::cast_possible_truncation,
+		reason = "the number of masks is encoded as a `u16`"
+	)]
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		buf.put_u8(self.major_opcode);
+		buf.put_u8(Self::MINOR_OPCODE);
+		buf.put_u16(self.request_length());
+
+		self.window.write_to(buf)?;
+		buf.put_u16(self.masks.len() as u16);
+		// 2 unused bytes.
+		buf.put_bytes(0, 2);
+
+		self.masks.write_to(buf)
+	}
+}
+
+/// The device(s) to which an [`XiEventMask`] applies.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DeviceSelector {
+	/// All devices, both master and slave.
+	All,
+	/// All master devices.
+	AllMaster,
+	/// The device with the given ID.
+	Device(u16),
+}
+
+impl DeviceSelector {
+	/// The device ID which represents all devices.
+	pub const ALL_ID: u16 = 0;
+	/// The device ID which represents all master devices.
+	pub const ALL_MASTER_ID: u16 = 1;
+
+	/// Returns the device ID with which this `DeviceSelector` is encoded.
+	#[must_use]
+	pub const fn id(self) -> u16 {
+		match self {
+			Self::All => Self::ALL_ID,
+			Self::AllMaster => Self::ALL_MASTER_ID,
+			Self::Device(id) => id,
+		}
+	}
+}
+
+impl From<u16> for DeviceSelector {
+	fn from(id: u16) -> Self {
+		match id {
+			Self::ALL_ID => Self::All,
+			Self::ALL_MASTER_ID => Self::AllMaster,
+
+			id => Self::Device(id),
+		}
+	}
+}
+
+bitflags! {
+	/// A mask of XI2 events.
+	///
+	/// Each event type's flag is the bit at the position of its event type
+	/// code.
+	#[derive(Default)]
+	pub struct XiEventMask: u64 {
+		/// `DeviceChanged` events.
+		const DEVICE_CHANGED = 1 << 1;
+		/// `KeyPress` events.
+		const KEY_PRESS = 1 << 2;
+		/// `KeyRelease` events.
+		const KEY_RELEASE = 1 << 3;
+		/// `ButtonPress` events.
+		const BUTTON_PRESS = 1 << 4;
+		/// `ButtonRelease` events.
+		const BUTTON_RELEASE = 1 << 5;
+		/// `Motion` events.
+		const MOTION = 1 << 6;
+		/// `Enter` events.
+		const ENTER = 1 << 7;
+		/// `Leave` events.
+		const LEAVE = 1 << 8;
+		/// `FocusIn` events.
+		const FOCUS_IN = 1 << 9;
+		/// `FocusOut` events.
+		const FOCUS_OUT = 1 << 10;
+		/// `HierarchyChanged` events.
+		const HIERARCHY_CHANGED = 1 << 11;
+		/// `Property` events.
+		const PROPERTY = 1 << 12;
+		/// `RawKeyPress` events.
+		const RAW_KEY_PRESS = 1 << 13;
+		/// `RawKeyRelease` events.
+		const RAW_KEY_RELEASE = 1 << 14;
+		/// `RawButtonPress` events.
+		const RAW_BUTTON_PRESS = 1 << 15;
+		/// `RawButtonRelease` events.
+		const RAW_BUTTON_RELEASE = 1 << 16;
+		/// `RawMotion` events.
+		const RAW_MOTION = 1 << 17;
+		/// `TouchBegin` events (XI 2.2).
+		const TOUCH_BEGIN = 1 << 18;
+		/// `TouchUpdate` events (XI 2.2).
+		const TOUCH_UPDATE = 1 << 19;
+		/// `TouchEnd` events (XI 2.2).
+		const TOUCH_END = 1 << 20;
+		/// `TouchOwnership` events (XI 2.2).
+		const TOUCH_OWNERSHIP = 1 << 21;
+		/// `RawTouchBegin` events (XI 2.2).
+		const RAW_TOUCH_BEGIN = 1 << 22;
+		/// `RawTouchUpdate` events (XI 2.2).
+		const RAW_TOUCH_UPDATE = 1 << 23;
+		/// `RawTouchEnd` events (XI 2.2).
+		const RAW_TOUCH_END = 1 << 24;
+		/// `BarrierHit` events (XI 2.3).
+		const BARRIER_HIT = 1 << 25;
+		/// `BarrierLeave` events (XI 2.3).
+		const BARRIER_LEAVE = 1 << 26;
+		/// `GesturePinchBegin` events (XI 2.4).
+		const GESTURE_PINCH_BEGIN = 1 << 27;
+		/// `GesturePinchUpdate` events (XI 2.4).
+		const GESTURE_PINCH_UPDATE = 1 << 28;
+		/// `GesturePinchEnd` events (XI 2.4).
+		const GESTURE_PINCH_END = 1 << 29;
+		/// `GestureSwipeBegin` events (XI 2.4).
+		const GESTURE_SWIPE_BEGIN = 1 << 30;
+		/// `GestureSwipeUpdate` events (XI 2.4).
+		const GESTURE_SWIPE_UPDATE = 1 << 31;
+		/// `GestureSwipeEnd` events (XI 2.4).
+		const GESTURE_SWIPE_END = 1 << 32;
+	}
+}
+
+impl XiEventMask {
+	/// Returns the number of 4-byte units with which this mask is encoded.
+	///
+	/// Masks are encoded without any trailing zero units, but always with at
+	/// least one unit.
+	#[must_use]
+	pub const fn units(self) -> u16 {
+		if self.bits() >> 32 == 0 {
+			1
+		} else {
+			2
+		}
+	}
+}
+
+/// A list of [`XiEventMask`]s for different devices, as used in an
+/// [`XiSelectEvents`] request.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct XiEventMasks {
+	masks: Vec<(DeviceSelector, XiEventMask)>,
+}
+
+impl XiEventMasks {
+	/// Creates a new, empty list of `XiEventMasks`.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { masks: Vec::new() }
+	}
+
+	/// Selects the events in the given `mask` for the given `device`.
+	///
+	/// If events have already been selected for the `device`, the `mask` is
+	/// added to them.
+	#[must_use]
+	pub fn select(mut self, device: impl Into<DeviceSelector>, mask: XiEventMask) -> Self {
+		let device = device.into();
+
+		match self.masks.iter_mut().find(|(other, _)| *other == device) {
+			Some((_, selected)) => *selected |= mask,
+			None => self.masks.push((device, mask)),
+		}
+
+		self
+	}
+
+	/// Returns the [`XiEventMask`] selected for each device.
+	#[must_use]
+	pub fn masks(&self) -> &[(DeviceSelector, XiEventMask)] {
+		&self.masks
+	}
+
+	/// Returns the number of masks in the list.
+	///
+	/// This is the `num_mask` field of an [`XiSelectEvents`] request.
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.masks.len()
+	}
+
+	/// Returns whether the list contains no masks.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.masks.is_empty()
+	}
+}
+
+impl X11Size for XiEventMasks {
+	fn x11_size(&self) -> usize {
+		self.masks
+			.iter()
+			.map(|(_, mask)| 4 + 4 * usize::from(mask.units()))
+			.sum()
+	}
+}
+
+impl Writable for XiEventMasks {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		for &(device, mask) in &self.masks {
+			buf.put_u16(device.id());
+			buf.put_u16(mask.units());
+
+			// The mask is a list of `CARD32`s, the first of which holds the
+			// lowest bits.
+			#[allow(
+				clippy::cast_possible_truncation,
+				reason = "the mask is deliberately split into 32-bit units"
+			)]
+			for unit in 0..mask.units() {
+				buf.put_u32((mask.bits() >> (32 * unit)) as u32);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl ReadableWithContext for XiEventMasks {
+	/// The number of masks in the list.
+	type Context = usize;
+
+	/// Reads a list of `XiEventMasks`.
+	///
+	/// Bits for events which are not known to XRB are ignored.
+	fn read_with(buf: &mut impl Buf, num_mask: &usize) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let mut masks = Vec::with_capacity(*num_mask);
+
+		for _ in 0..*num_mask {
+			let device = DeviceSelector::from(u16::read_from(buf)?);
+			let units = u16::read_from(buf)?;
+
+			let mut bits = 0;
+			for unit in 0..units {
+				let value = u64::from(u32::read_from(buf)?);
+
+				if unit < 2 {
+					bits |= value << (32 * unit);
+				}
+			}
+
+			masks.push((device, XiEventMask::from_bits_truncate(bits)));
+		}
+
+		Ok(Self { masks })
+	}
+}
+
+/// A signed fixed-point number with 16 integral bits and 16 fractional bits
+/// (`FP1616`).
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Hash,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
+pub struct Fp1616(pub i32);
+
+impl Fp1616 {
+	/// The value of the lowest fractional bit.
+	const SCALE: f64 = 65536.0;
+
+	/// Creates an `Fp1616` from the closest representable value to the given
+	/// `value`.
+	///
+	/// Values outside of the range of an `Fp1616` are saturated, and `NaN` is
+	/// treated as zero.
+	#[allow(
+		clippy::cast_possible_truncation,
+		reason = "`as` saturates values outside of the range of an `i32`"
+	)]
+	#[must_use]
+	pub fn from_f64(value: f64) -> Self {
+		Self((value * Self::SCALE).round() as i32)
+	}
+
+	/// Returns the value of this `Fp1616` as an [`f64`].
+	///
+	/// Every `Fp1616` can be represented exactly as an [`f64`].
+	#[must_use]
+	pub fn to_f64(self) -> f64 {
+		f64::from(self.0) / Self::SCALE
+	}
+}
+
+impl From<i16> for Fp1616 {
+	fn from(integral: i16) -> Self {
+		Self(i32::from(integral) << 16)
+	}
+}
+
+impl From<Fp1616> for f64 {
+	fn from(value: Fp1616) -> Self {
+		value.to_f64()
+	}
+}
+
+/// A signed fixed-point number with 32 integral bits and 32 fractional bits
+/// (`FP3232`).
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Hash,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
+pub struct Fp3232 {
+	/// The integral part of the number, rounded towards negative infinity.
+	pub integral: i32,
+	/// The fractional part of the number, in units of 2<sup>-32</sup>.
+	///
+	/// The fractional part is always added to the integral part, so -1.25 is
+	/// represented with an `integral` of -2 and a `fraction` of 0.75.
+	pub fraction: u32,
+}
+
+impl Fp3232 {
+	/// The value of the lowest fractional bit.
+	const SCALE: f64 = 4_294_967_296.0;
+
+	/// Creates a new `Fp3232` with the given `integral` and `fraction` parts.
+	#[must_use]
+	pub const fn new(integral: i32, fraction: u32) -> Self {
+		Self { integral, fraction }
+	}
+
+	/// Creates an `Fp3232` from the closest representable value to the given
+	/// `value`.
+	///
+	/// Values outside of the range of an `Fp3232` are saturated, and `NaN` is
+	/// treated as zero.
+	#[allow(
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss,
+		reason = "`as` saturates values outside of the range of the parts"
+	)]
+	#[must_use]
+	pub fn from_f64(value: f64) -> Self {
+		let integral = value.floor();
+
+		Self {
+			integral: integral as i32,
+			fraction: ((value - integral) * Self::SCALE) as u32,
+		}
+	}
+
+	/// Returns the value of this `Fp3232` as an [`f64`].
+	///
+	/// Values with more than 53 significant bits are rounded.
+	#[must_use]
+	pub fn to_f64(self) -> f64 {
+		f64::from(self.integral) + f64::from(self.fraction) / Self::SCALE
+	}
+}
+
+impl From<i32> for Fp3232 {
+	fn from(integral: i32) -> Self {
+		Self::new(integral, 0)
+	}
+}
+
+impl From<Fp3232> for f64 {
+	fn from(value: Fp3232) -> Self {
+		value.to_f64()
+	}
+}
+
+/// The values of a set of valuators, as given in XI2 events.
+///
+/// Valuators are encoded as a mask with a bit set for each valuator which is
+/// present, followed by an [`Fp3232`] value for each of them in order of
+/// their indexes.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Valuators {
+	mask: Vec<u32>,
+	values: Vec<Fp3232>,
+}
+
+impl Valuators {
+	/// Creates a new, empty set of `Valuators`.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			mask: Vec::new(),
+			values: Vec::new(),
+		}
+	}
+
+	/// Returns whether the mask has the bit for the given `index` set.
+	fn is_set(&self, index: u16) -> bool {
+		let (unit, bit) = (usize::from(index / 32), index % 32);
+
+		self.mask
+			.get(unit)
+			.is_some_and(|unit| unit & (1 << bit) != 0)
+	}
+
+	/// Returns the position of the value of the valuator with the given
+	/// `index` in the list of values, if it were present.
+	fn position(&self, index: u16) -> usize {
+		let (unit, bit) = (usize::from(index / 32), index % 32);
+
+		let before: u32 = self
+			.mask
+			.iter()
+			.take(unit)
+			.map(|unit| unit.count_ones())
+			.sum();
+		let within = self
+			.mask
+			.get(unit)
+			.map_or(0, |unit| (unit & ((1 << bit) - 1)).count_ones());
+
+		(before + within) as usize
+	}
+
+	/// Sets the value of the valuator with the given `index`.
+	#[must_use]
+	pub fn with(mut self, index: u16, value: Fp3232) -> Self {
+		let position = self.position(index);
+
+		if self.is_set(index) {
+			self.values[position] = value;
+		} else {
+			let unit = usize::from(index / 32);
+
+			if self.mask.len() <= unit {
+				self.mask.resize(unit + 1, 0);
+			}
+
+			self.mask[unit] |= 1 << (index % 32);
+			self.values.insert(position, value);
+		}
+
+		self
+	}
+
+	/// Returns the value of the valuator with the given `index`, if it is
+	/// present.
+	#[must_use]
+	pub fn get(&self, index: u16) -> Option<Fp3232> {
+		self.is_set(index)
+			.then(|| self.values[self.position(index)])
+	}
+
+	/// Returns an iterator over the index and value of each valuator which is
+	/// present, in order of their indexes.
+	pub fn iter(&self) -> impl Iterator<Item = (u16, f64)> + '_ {
+		#[allow(
+			clippy::cast_possible_truncation,
+			reason = "the mask's length is encoded as a `u16`"
+		)]
+		let indexes = self.mask.iter().enumerate().flat_map(|(unit, &bits)| {
+			(0..32)
+				.filter(move |bit| bits & (1 << bit) != 0)
+				.map(move |bit| (unit * 32 + bit) as u16)
+		});
+
+		indexes.zip(self.values.iter().map(|value| value.to_f64()))
+	}
+
+	/// Returns the number of valuators which are present.
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.values.len()
+	}
+
+	/// Returns whether no valuators are present.
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.values.is_empty()
+	}
+
+	/// Returns the length of the mask in 4-byte units.
+	///
+	/// This is the `valuators_len` field of the event.
+	#[allow(
+		clippy::cast_possible_truncation,
+		reason = "valuator indexes are `u16`s, so the mask is at most 2048 units long"
+	)]
+	#[must_use]
+	pub const fn mask_len(&self) -> u16 {
+		self.mask.len() as u16
+	}
+}
+
+impl X11Size for Valuators {
+	fn x11_size(&self) -> usize {
+		self.mask.len() * 4 + self.values.len() * Fp3232::X11_SIZE
+	}
+}
+
+impl Writable for Valuators {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		for &unit in &self.mask {
+			buf.put_u32(unit);
+		}
+
+		for value in &self.values {
+			value.write_to(buf)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl ReadableWithContext for Valuators {
+	/// The length of the mask in 4-byte units.
+	type Context = u16;
+
+	fn read_with(buf: &mut impl Buf, mask_len: &u16) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let mask: Vec<u32> = (0..*mask_len)
+			.map(|_| u32::read_from(buf))
+			.collect::<ReadResult<_>>()?;
+		let count: u32 = mask.iter().map(|unit| unit.count_ones()).sum();
+
+		let values = (0..count)
+			.map(|_| Fp3232::read_from(buf))
+			.collect::<ReadResult<_>>()?;
+
+		Ok(Self { mask, values })
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::dissect::Dissector;
+
+	#[test]
+	fn test_extension() {
+		assert_eq!(
+			XInput::query_extension().name.to_latin1(),
+			"XInputExtension"
+		);
+
+		assert!(XInput::is_supported(ExtensionVersion::new(2, 4)));
+		assert!(XInput::is_supported(ExtensionVersion::new(2, 2)));
+		assert!(!XInput::is_supported(ExtensionVersion::new(2, 1)));
+		assert!(!XInput::is_supported(ExtensionVersion::new(1, 5)));
+	}
+
+	#[test]
+	fn test_query_version_round_trip() {
+		let request = XInput::query_version(131);
+		assert_eq!(request.major_version, 2);
+		assert_eq!(request.minor_version, 2);
+
+		let mut bytes = vec![];
+		request.write_request_to(&mut bytes).unwrap();
+		assert_eq!(bytes, [131, 47, 0, 2, 0, 2, 0, 2]);
+		assert_eq!(bytes.len(), request.x11_size());
+		assert_eq!(XiQueryVersion::read_from(&mut &bytes[..]).unwrap(), request);
+
+		let reply = XiQueryVersionReply {
+			sequence: 5,
+			major_version: 2,
+			minor_version: 4,
+		};
+
+		let mut bytes = vec![];
+		reply.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), XiQueryVersionReply::X11_SIZE);
+		assert_eq!(&bytes[..12], [1, 0, 0, 5, 0, 0, 0, 0, 0, 2, 0, 4]);
+
+		let decoded = XiQueryVersionReply::read_from(&mut &bytes[1..]).unwrap();
+		assert_eq!(decoded, reply);
+		assert_eq!(XInput::reply_version(&decoded), ExtensionVersion::new(2, 4));
+		assert!(XInput::is_supported(XInput::reply_version(&decoded)));
+	}
+
+	#[test]
+	fn test_dissect_touch_event() {
+		let mut dissector = Dissector::new();
+		dissector.register_extension::<XInput>(66, 131);
+
+		// A `TouchEnd` event with no buttons or valuators.
+		let mut bytes = [0; 80];
+		bytes[..12].copy_from_slice(&[35, 131, 0, 1, 0, 0, 0, 12, 0, 20, 0, 2]);
+
+		let dissection = dissector.dissect_server_message(&bytes, None).unwrap();
+		assert_eq!(dissection.name, Some("TouchEnd"));
+		assert_eq!(dissection.length, 80);
+		assert!(format!("{dissection}").contains("phase: End"));
+	}
+
+	#[test]
+	fn test_xi_event_masks_encoding() {
+		let masks = XiEventMasks::new()
+			.select(DeviceSelector::AllMaster, XiEventMask::KEY_PRESS)
+			.select(2, XiEventMask::GESTURE_SWIPE_END)
+			.select(DeviceSelector::AllMaster, XiEventMask::MOTION);
+
+		assert_eq!(masks.len(), 2);
+		assert_eq!(masks.x11_size(), 8 + 12);
+
+		let mut bytes = vec![];
+		masks.write_to(&mut bytes).unwrap();
+
+		assert_eq!(
+			bytes,
+			[
+				0,
+				1,
+				0,
+				1,
+				0,
+				0,
+				0,
+				0b0100_0100, //
+				0,
+				2,
+				0,
+				2,
+				0,
+				0,
+				0,
+				0,
+				0,
+				0,
+				0,
+				1,
+			]
+		);
+
+		assert_eq!(XiEventMasks::read_with(&mut &bytes[..], &2).unwrap(), masks);
+		assert_eq!(DeviceSelector::from(0), DeviceSelector::All);
+	}
+
+	#[test]
+	fn test_select_events_round_trip() {
+		let request = XiSelectEvents {
+			major_opcode: 131,
+
+			window: Window::new(0x0020_0001),
+			masks: XiEventMasks::new().select(
+				DeviceSelector::All,
+				XiEventMask::TOUCH_BEGIN | XiEventMask::TOUCH_END,
+			),
+		};
+
+		let mut bytes = vec![];
+		request.write_request_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), request.x11_size());
+		assert_eq!(request.request_length(), 5);
+		assert_eq!(&bytes[..12], [131, 46, 0, 5, 0, 0x20, 0, 1, 0, 1, 0, 0]);
+		assert_eq!(&bytes[12..], [0, 0, 0, 1, 0, 0x14, 0, 0]);
+
+		assert_eq!(XiSelectEvents::read_from(&mut &bytes[..]).unwrap(), request);
+	}
+
+	#[test]
+	fn test_fixed_point() {
+		assert_eq!(Fp1616::from(3).to_f64(), 3.0);
+		assert_eq!(Fp1616::from_f64(-1.5), Fp1616(-0x0001_8000));
+		assert_eq!(f64::from(Fp1616(0x0000_4000)), 0.25);
+
+		assert_eq!(Fp3232::from_f64(-1.25), Fp3232::new(-2, 0xC000_0000));
+		assert_eq!(Fp3232::new(-2, 0xC000_0000).to_f64(), -1.25);
+		assert!(Fp3232::from_f64(-1.25) < Fp3232::from(-1));
+	}
+
+	#[test]
+	fn test_valuators() {
+		let valuators = Valuators::new()
+			.with(33, Fp3232::from(7))
+			.with(0, Fp3232::from_f64(0.5))
+			.with(2, Fp3232::from(-1));
+
+		assert_eq!(valuators.mask_len(), 2);
+		assert_eq!(valuators.get(2), Some(Fp3232::from(-1)));
+		assert_eq!(valuators.get(1), None);
+		assert_eq!(
+			valuators.iter().collect::<Vec<_>>(),
+			[(0, 0.5), (2, -1.0), (33, 7.0)]
+		);
+
+		let mut bytes = vec![];
+		valuators.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), valuators.x11_size());
+		assert_eq!(&bytes[..8], [0, 0, 0, 0b101, 0, 0, 0, 0b10]);
+
+		assert_eq!(
+			Valuators::read_with(&mut &bytes[..], &valuators.mask_len()).unwrap(),
+			valuators
+		);
+	}
+}
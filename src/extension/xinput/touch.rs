@@ -0,0 +1,417 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tracking XI2 touch sequences.
+//!
+//! Each touch on a touch device is reported as a sequence of events: a
+//! `TouchBegin` event, any number of `TouchUpdate` events, and a `TouchEnd`
+//! event, all with the same touch ID. Touches from multiple devices and
+//! multiple fingers are interleaved, so a client must group the events by
+//! their device and touch ID to know where each touch started and where it
+//! is now. A [`TouchSequence`] does that bookkeeping.
+//!
+//! Touch events also carry [flags] which say whether the touch is emulating
+//! pointer events and whether its `TouchEnd` event has been delayed because
+//! the client does not own the touch yet; these are kept with each
+//! [`Touch`].
+//!
+//! [flags]: TouchFlags
+
+use std::{collections::HashMap, fmt::Debug, ops::Range};
+
+use bitflags::bitflags;
+use xrbk::{check_remaining, Buf, ReadError, ReadResult, Readable, ReadableWithContext};
+
+use super::{Fp1616, Valuators};
+use crate::message::{Message, MessageKind};
+
+/// The stage of a touch sequence which a [`TouchEvent`] reports.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum TouchPhase {
+	/// A `TouchBegin` event: a new touch has started.
+	Begin,
+	/// A `TouchUpdate` event: the touch has moved or its flags have changed.
+	Update,
+	/// A `TouchEnd` event: the touch has ended.
+	End,
+}
+
+impl TouchPhase {
+	/// Returns the XI2 event type of the events which report this phase.
+	#[must_use]
+	pub const fn event_type(self) -> u16 {
+		match self {
+			Self::Begin => 18,
+			Self::Update => 19,
+			Self::End => 20,
+		}
+	}
+}
+
+bitflags! {
+	/// The flags of an XI2 touch event which concern its touch sequence.
+	#[derive(Default)]
+	pub struct TouchFlags: u32 {
+		/// The touch has physically ended, but its `TouchEnd` event is delayed
+		/// until the client owns the touch.
+		const PENDING_END = 1 << 16;
+		/// The touch is emulating pointer events.
+		const EMULATING_POINTER = 1 << 17;
+	}
+}
+
+/// The fields of an XI2 touch event which are needed to track its touch
+/// sequence.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TouchEvent {
+	/// Which of `TouchBegin`, `TouchUpdate`, or `TouchEnd` this event is.
+	pub phase: TouchPhase,
+
+	/// The ID of the device which generated the event.
+	pub device: u16,
+	/// The ID of the touch, called `detail` in the event.
+	pub touch_id: u32,
+
+	/// The x-coordinate of the touch relative to the root window.
+	pub root_x: Fp1616,
+	/// The y-coordinate of the touch relative to the root window.
+	pub root_y: Fp1616,
+
+	/// The event's flags.
+	pub flags: TouchFlags,
+}
+
+impl TouchEvent {
+	/// Reads a `TouchEvent` from an XI2 touch event, following its
+	/// `GenericEvent` code.
+	///
+	/// The fields of the event which are not needed to track its touch
+	/// sequence are skipped.
+	///
+	/// # Errors
+	/// Returns [`ReadError::UnrecognizedDiscriminant`] if the event is not a
+	/// `TouchBegin`, `TouchUpdate`, or `TouchEnd` event.
+	pub fn read_from_event(buf: &mut impl Buf) -> ReadResult<Self> {
+		// The extension's major opcode, the sequence number, and the length.
+		check_remaining(buf, 7)?;
+		buf.advance(7);
+
+		let phase = match u16::read_from(buf)? {
+			18 => TouchPhase::Begin,
+			19 => TouchPhase::Update,
+			20 => TouchPhase::End,
+
+			other => return Err(ReadError::UnrecognizedDiscriminant(usize::from(other))),
+		};
+
+		let device = u16::read_from(buf)?;
+		// The time.
+		check_remaining(buf, 4)?;
+		buf.advance(4);
+		let touch_id = u32::read_from(buf)?;
+		// The root, event, and child windows.
+		check_remaining(buf, 12)?;
+		buf.advance(12);
+
+		let root_x = Fp1616::read_from(buf)?;
+		let root_y = Fp1616::read_from(buf)?;
+		// The event coordinates.
+		check_remaining(buf, 8)?;
+		buf.advance(8);
+
+		let buttons_len = u16::read_from(buf)?;
+		let valuators_len = u16::read_from(buf)?;
+		// The source device and two unused bytes.
+		check_remaining(buf, 4)?;
+		buf.advance(4);
+
+		let flags = TouchFlags::from_bits_truncate(u32::read_from(buf)?);
+
+		// The modifier and group state, then the button mask.
+		check_remaining(buf, 20 + 4 * usize::from(buttons_len))?;
+		buf.advance(20 + 4 * usize::from(buttons_len));
+		Valuators::read_with(buf, &valuators_len)?;
+
+		Ok(Self {
+			phase,
+
+			device,
+			touch_id,
+
+			root_x,
+			root_y,
+
+			flags,
+		})
+	}
+}
+
+impl Message for TouchEvent {
+	fn name(&self) -> &'static str {
+		match self.phase {
+			TouchPhase::Begin => "TouchBegin",
+			TouchPhase::Update => "TouchUpdate",
+			TouchPhase::End => "TouchEnd",
+		}
+	}
+
+	fn kind(&self) -> MessageKind {
+		// XI2 events are sent as `GenericEvent`s.
+		MessageKind::Event { code: 35 }
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("phase", &self.phase),
+			("device", &self.device),
+			("touch_id", &self.touch_id),
+			("root_x", &self.root_x),
+			("root_y", &self.root_y),
+			("flags", &self.flags),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		// The two bytes following the source device.
+		vec![54..56]
+	}
+}
+
+/// The state of a single touch, as tracked by a [`TouchSequence`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Touch {
+	/// The ID of the device on which the touch happened.
+	pub device: u16,
+	/// The ID of the touch.
+	pub touch_id: u32,
+
+	/// The coordinates, relative to the root window, at which the touch
+	/// began.
+	pub start: (f64, f64),
+	/// The latest coordinates of the touch, relative to the root window.
+	pub position: (f64, f64),
+
+	/// The flags of the latest event for the touch.
+	pub flags: TouchFlags,
+}
+
+impl Touch {
+	/// Returns how far the touch has moved since it began.
+	#[must_use]
+	pub fn delta(&self) -> (f64, f64) {
+		(
+			self.position.0 - self.start.0,
+			self.position.1 - self.start.1,
+		)
+	}
+
+	/// Returns whether the touch is emulating pointer events.
+	#[must_use]
+	pub const fn is_emulating_pointer(&self) -> bool {
+		self.flags.contains(TouchFlags::EMULATING_POINTER)
+	}
+
+	/// Returns whether the touch has physically ended but its `TouchEnd`
+	/// event has not been received yet.
+	#[must_use]
+	pub const fn is_pending_end(&self) -> bool {
+		self.flags.contains(TouchFlags::PENDING_END)
+	}
+}
+
+/// Tracks the touches which are in progress.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Default)]
+pub struct TouchSequence {
+	touches: HashMap<(u16, u32), Touch>,
+}
+
+impl TouchSequence {
+	/// Creates a new `TouchSequence` with no touches in progress.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Updates the touch reported by the given `event` and returns its state.
+	///
+	/// The touch is forgotten once its `TouchEnd` event is handled, and its
+	/// final state is returned.
+	///
+	/// Returns [`None`] for `TouchUpdate` and `TouchEnd` events for touches
+	/// which did not begin with a `TouchBegin` event handled by this
+	/// `TouchSequence`, such as touches which began before events were
+	/// selected.
+	pub fn handle(&mut self, event: &TouchEvent) -> Option<Touch> {
+		let key = (event.device, event.touch_id);
+		let position = (event.root_x.to_f64(), event.root_y.to_f64());
+
+		match event.phase {
+			TouchPhase::Begin => {
+				let touch = Touch {
+					device: event.device,
+					touch_id: event.touch_id,
+
+					start: position,
+					position,
+
+					flags: event.flags,
+				};
+				self.touches.insert(key, touch);
+
+				Some(touch)
+			},
+
+			TouchPhase::Update => {
+				let touch = self.touches.get_mut(&key)?;
+
+				touch.position = position;
+				touch.flags = event.flags;
+
+				Some(*touch)
+			},
+
+			TouchPhase::End => {
+				let mut touch = self.touches.remove(&key)?;
+
+				touch.position = position;
+				touch.flags = event.flags;
+
+				Some(touch)
+			},
+		}
+	}
+
+	/// Returns the touch with the given `touch_id` on the given `device`, if
+	/// it is in progress.
+	#[must_use]
+	pub fn get(&self, device: u16, touch_id: u32) -> Option<&Touch> {
+		self.touches.get(&(device, touch_id))
+	}
+
+	/// Returns an iterator over the touches which are in progress, in no
+	/// particular order.
+	pub fn touches(&self) -> impl Iterator<Item = &Touch> {
+		self.touches.values()
+	}
+
+	/// Returns the touch on the given `device` which is emulating pointer
+	/// events, if there is one.
+	#[must_use]
+	pub fn emulating_pointer(&self, device: u16) -> Option<&Touch> {
+		self.touches()
+			.find(|touch| touch.device == device && touch.is_emulating_pointer())
+	}
+
+	/// Returns the number of touches which are in progress.
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.touches.len()
+	}
+
+	/// Returns whether no touches are in progress.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.touches.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn event(phase: TouchPhase, touch_id: u32, x: i16, y: i16, flags: TouchFlags) -> TouchEvent {
+		TouchEvent {
+			phase,
+			device: 2,
+			touch_id,
+			root_x: x.into(),
+			root_y: y.into(),
+			flags,
+		}
+	}
+
+	#[test]
+	fn test_read_touch_event() {
+		let mut bytes = vec![0; 80];
+		// `GenericEvent`, the extension's major opcode, the sequence number,
+		// and the length.
+		bytes[..8].copy_from_slice(&[35, 131, 0, 1, 0, 0, 0, 16]);
+		// `TouchUpdate`, the device, the time, and the touch ID.
+		bytes[8..20].copy_from_slice(&[0, 19, 0, 2, 0, 0, 0, 0, 0, 0, 0, 7]);
+		// The root coordinates: (1.5, -2.0).
+		bytes[32..40].copy_from_slice(&[0, 1, 0x80, 0, 0xff, 0xfe, 0, 0]);
+		// One unit of button mask and one unit of valuator mask.
+		bytes[48..52].copy_from_slice(&[0, 1, 0, 1]);
+		bytes[56..60].copy_from_slice(&[0, 2, 0, 0]);
+
+		// The button mask, then the valuator mask with one valuator set and
+		// its value.
+		bytes.extend([0; 4]);
+		bytes.extend([0, 0, 0, 1]);
+		bytes.extend([0; 8]);
+
+		let buf = &mut &bytes[1..];
+		assert_eq!(
+			TouchEvent::read_from_event(buf).unwrap(),
+			TouchEvent {
+				phase: TouchPhase::Update,
+				device: 2,
+				touch_id: 7,
+				root_x: Fp1616::from_f64(1.5),
+				root_y: Fp1616::from(-2),
+				flags: TouchFlags::EMULATING_POINTER,
+			}
+		);
+		assert!(buf.is_empty());
+
+		bytes[9] = 21;
+		assert!(TouchEvent::read_from_event(&mut &bytes[1..]).is_err());
+	}
+
+	#[test]
+	fn test_touch_sequence() {
+		let mut touches = TouchSequence::new();
+
+		touches.handle(&event(
+			TouchPhase::Begin,
+			1,
+			10,
+			10,
+			TouchFlags::EMULATING_POINTER,
+		));
+		touches.handle(&event(TouchPhase::Begin, 2, 50, 50, TouchFlags::empty()));
+		assert_eq!(touches.len(), 2);
+
+		let touch = touches
+			.handle(&event(
+				TouchPhase::Update,
+				1,
+				15,
+				30,
+				TouchFlags::EMULATING_POINTER,
+			))
+			.unwrap();
+		assert_eq!(touch.delta(), (5.0, 20.0));
+		assert_eq!(
+			touches.emulating_pointer(2).map(|touch| touch.touch_id),
+			Some(1)
+		);
+
+		let touch = touches
+			.handle(&event(TouchPhase::End, 2, 40, 50, TouchFlags::empty()))
+			.unwrap();
+		assert_eq!(touch.start, (50.0, 50.0));
+		assert_eq!(touch.position, (40.0, 50.0));
+		assert!(touches.get(2, 2).is_none());
+
+		assert_eq!(
+			touches.handle(&event(TouchPhase::Update, 3, 0, 0, TouchFlags::empty())),
+			None
+		);
+	}
+}
@@ -0,0 +1,367 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Geometry calculations for window managers which reparent clients into
+//! frames.
+//!
+//! When a client asks for its [window] to be placed somewhere, it refers to the
+//! geometry of its [window] as if it were not framed. The [ICCCM] specifies
+//! that the window manager must place the frame such that the _reference
+//! point_ given by the client's `win_gravity` stays where the client asked for
+//! it to be:
+//! - For [`Static`] gravity, the reference point is the top-left corner of the
+//!   inside of the client's [window], so the client's contents do not move.
+//! - For every other gravity, the reference point is the corresponding point on
+//!   the outer edge of the client's [window] (including its border), and the
+//!   corresponding point on the outer edge of the frame is placed there.
+//!
+//! [`FrameGeometry`] performs these calculations in both directions and
+//! produces the [`ConfigureWindow` requests] for the frame and client.
+//!
+//! [window]: Window
+//! [ICCCM]: https://x.org/releases/X11R7.7/doc/xorg-docs/icccm/icccm.html
+//! [`Static`]: WindowGravity::Static
+//! [`ConfigureWindow` requests]: ConfigureWindow
+
+use crate::{
+	set::WindowConfig,
+	unit::Px,
+	x11::request::ConfigureWindow,
+	Dimensions,
+	Rectangle,
+	Window,
+	WindowGravity,
+};
+
+/// The widths of the decorations which a frame adds around each edge of a
+/// client [window].
+///
+/// [window]: Window
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct FrameExtents {
+	/// The width of the frame's left edge.
+	pub left: Px<u16>,
+	/// The width of the frame's right edge.
+	pub right: Px<u16>,
+	/// The height of the frame's top edge, including any title bar.
+	pub top: Px<u16>,
+	/// The height of the frame's bottom edge.
+	pub bottom: Px<u16>,
+}
+
+impl FrameExtents {
+	/// Creates new `FrameExtents` with the given widths for each edge.
+	#[must_use]
+	pub const fn new(left: Px<u16>, right: Px<u16>, top: Px<u16>, bottom: Px<u16>) -> Self {
+		Self {
+			left,
+			right,
+			top,
+			bottom,
+		}
+	}
+
+	/// Creates new `FrameExtents` with the same `width` for every edge.
+	#[must_use]
+	pub const fn uniform(width: Px<u16>) -> Self {
+		Self::new(width, width, width, width)
+	}
+}
+
+/// The geometry of a frame and the client [window] reparented into it.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [window]: Window
+/// [module-level documentation]: self
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct FrameGeometry {
+	/// The geometry of the frame, relative to its parent.
+	pub frame: Rectangle,
+	/// The geometry of the client [window], relative to the frame.
+	///
+	/// The client [window]'s border width is always zero within the frame.
+	///
+	/// [window]: Window
+	pub client: Rectangle,
+}
+
+/// Returns how far along the width and height the reference point of the
+/// given `gravity` is, in halves.
+///
+/// [`Static`] gravity has no reference point on the edges, so it is
+/// [`None`].
+///
+/// [`Static`]: WindowGravity::Static
+const fn gravity_factors(gravity: WindowGravity) -> Option<(i32, i32)> {
+	match gravity {
+		WindowGravity::Unmap | WindowGravity::NorthWest => Some((0, 0)),
+		WindowGravity::North => Some((1, 0)),
+		WindowGravity::NorthEast => Some((2, 0)),
+		WindowGravity::West => Some((0, 1)),
+		WindowGravity::Center => Some((1, 1)),
+		WindowGravity::East => Some((2, 1)),
+		WindowGravity::SouthWest => Some((0, 2)),
+		WindowGravity::South => Some((1, 2)),
+		WindowGravity::SouthEast => Some((2, 2)),
+
+		WindowGravity::Static => None,
+	}
+}
+
+impl FrameGeometry {
+	/// Calculates the geometry of a frame with the given `extents` around a
+	/// client [window] which asked for the given `client` geometry and
+	/// `border_width`, with the given `gravity`.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub fn from_client(
+		client: Rectangle, border_width: Px<u16>, gravity: WindowGravity, extents: FrameExtents,
+	) -> Self {
+		let border_width = i32::from(border_width.0);
+		let (width, height) = (i32::from(client.width.0), i32::from(client.height.0));
+		let (left, top) = (i32::from(extents.left.0), i32::from(extents.top.0));
+
+		let frame_width = width + left + i32::from(extents.right.0);
+		let frame_height = height + top + i32::from(extents.bottom.0);
+
+		let (x, y) = match gravity_factors(gravity) {
+			Some((x_factor, y_factor)) => {
+				// The difference in size between the client's outer edges and
+				// the frame's outer edges.
+				let width_diff = width + 2 * border_width - frame_width;
+				let height_diff = height + 2 * border_width - frame_height;
+
+				(
+					i32::from(client.x.0) + width_diff * x_factor / 2,
+					i32::from(client.y.0) + height_diff * y_factor / 2,
+				)
+			},
+
+			None => (
+				i32::from(client.x.0) + border_width - left,
+				i32::from(client.y.0) + border_width - top,
+			),
+		};
+
+		Self {
+			frame: Rectangle::saturating_new(
+				x,
+				y,
+				frame_width.unsigned_abs(),
+				frame_height.unsigned_abs(),
+			),
+			client: Rectangle::saturating_new(
+				left,
+				top,
+				width.unsigned_abs(),
+				height.unsigned_abs(),
+			),
+		}
+	}
+
+	/// Calculates the geometry which the client [window] should be given,
+	/// with the given `border_width`, when it is reparented out of the frame
+	/// and into the frame's parent.
+	///
+	/// This is the inverse of [`from_client`]: the client is placed such that
+	/// its `gravity`'s reference point is where it was in the frame.
+	///
+	/// [window]: Window
+	/// [`from_client`]: FrameGeometry::from_client
+	#[must_use]
+	pub fn to_client(&self, border_width: Px<u16>, gravity: WindowGravity) -> Rectangle {
+		let border_width = i32::from(border_width.0);
+		let (left, top) = (i32::from(self.client.x.0), i32::from(self.client.y.0));
+
+		let (x, y) = match gravity_factors(gravity) {
+			Some((x_factor, y_factor)) => {
+				let width_diff = i32::from(self.client.width.0) + 2 * border_width
+					- i32::from(self.frame.width.0);
+				let height_diff = i32::from(self.client.height.0) + 2 * border_width
+					- i32::from(self.frame.height.0);
+
+				(
+					i32::from(self.frame.x.0) - width_diff * x_factor / 2,
+					i32::from(self.frame.y.0) - height_diff * y_factor / 2,
+				)
+			},
+
+			None => (
+				i32::from(self.frame.x.0) - border_width + left,
+				i32::from(self.frame.y.0) - border_width + top,
+			),
+		};
+
+		Rectangle::saturating_new(
+			x,
+			y,
+			u32::from(self.client.width.0),
+			u32::from(self.client.height.0),
+		)
+	}
+
+	/// Returns the geometry of the client [window] relative to the frame's
+	/// parent.
+	///
+	/// This is the geometry which should be reported to the client in a
+	/// synthetic [`Configure` event] when the frame is moved.
+	///
+	/// [window]: Window
+	/// [`Configure` event]: crate::x11::event::Configure
+	#[must_use]
+	pub fn client_absolute(&self) -> Rectangle {
+		Rectangle::saturating_new(
+			i32::from(self.frame.x.0) + i32::from(self.client.x.0),
+			i32::from(self.frame.y.0) + i32::from(self.client.y.0),
+			u32::from(self.client.width.0),
+			u32::from(self.client.height.0),
+		)
+	}
+
+	/// Resizes the client [window] to the given `dimensions`, moving the
+	/// frame such that the reference point of the given `gravity` stays in
+	/// place.
+	///
+	/// With [`Static`] gravity, the top-left corner stays in place, as the
+	/// client's contents do not move.
+	///
+	/// [window]: Window
+	/// [`Static`]: WindowGravity::Static
+	#[must_use]
+	pub fn resize(&self, dimensions: Dimensions, gravity: WindowGravity) -> Self {
+		let (x_factor, y_factor) = gravity_factors(gravity).unwrap_or((0, 0));
+
+		let width_diff = i32::from(self.client.width.0) - i32::from(dimensions.width.0);
+		let height_diff = i32::from(self.client.height.0) - i32::from(dimensions.height.0);
+
+		let frame_width = i32::from(self.frame.width.0) - width_diff;
+		let frame_height = i32::from(self.frame.height.0) - height_diff;
+
+		Self {
+			frame: Rectangle::saturating_new(
+				i32::from(self.frame.x.0) + width_diff * x_factor / 2,
+				i32::from(self.frame.y.0) + height_diff * y_factor / 2,
+				frame_width.max(0).unsigned_abs(),
+				frame_height.max(0).unsigned_abs(),
+			),
+			client: Rectangle {
+				width: dimensions.width,
+				height: dimensions.height,
+				..self.client
+			},
+		}
+	}
+
+	/// Returns the [`WindowConfig`] which places the frame at its geometry.
+	#[must_use]
+	pub fn frame_config(&self) -> WindowConfig {
+		let mut builder = WindowConfig::builder();
+		builder.rectangle(self.frame);
+
+		builder.build()
+	}
+
+	/// Returns the [`WindowConfig`] which places the client [window] at its
+	/// geometry within the frame, with no border.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub fn client_config(&self) -> WindowConfig {
+		let mut builder = WindowConfig::builder();
+		builder.rectangle(self.client).border_width(Px(0));
+
+		builder.build()
+	}
+
+	/// Returns the [`ConfigureWindow` request] which places the given `frame`
+	/// at its geometry.
+	///
+	/// [`ConfigureWindow` request]: ConfigureWindow
+	#[must_use]
+	pub fn configure_frame(&self, frame: Window) -> ConfigureWindow {
+		ConfigureWindow {
+			target: frame,
+			config: self.frame_config(),
+		}
+	}
+
+	/// Returns the [`ConfigureWindow` request] which places the given
+	/// `client` [window] at its geometry within the frame.
+	///
+	/// [window]: Window
+	/// [`ConfigureWindow` request]: ConfigureWindow
+	#[must_use]
+	pub fn configure_client(&self, client: Window) -> ConfigureWindow {
+		ConfigureWindow {
+			target: client,
+			config: self.client_config(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	const EXTENTS: FrameExtents = FrameExtents::new(Px(2), Px(2), Px(20), Px(2));
+
+	fn client() -> Rectangle {
+		Rectangle::new(Px(100), Px(100), Px(200), Px(100))
+	}
+
+	#[test]
+	fn test_frame_geometry_gravity() {
+		let cases = [
+			(WindowGravity::NorthWest, (100, 100)),
+			// The frame is 4 pixels wider and 22 pixels taller than the
+			// client, and the client's 1 pixel border is removed.
+			(WindowGravity::Center, (99, 90)),
+			(WindowGravity::SouthEast, (98, 80)),
+			(WindowGravity::Static, (99, 81)),
+		];
+
+		for (gravity, (x, y)) in cases {
+			let geometry = FrameGeometry::from_client(client(), Px(1), gravity, EXTENTS);
+
+			assert_eq!(
+				geometry.frame,
+				Rectangle::new(Px(x), Px(y), Px(204), Px(122)),
+				"{gravity:?}",
+			);
+			assert_eq!(
+				geometry.client,
+				Rectangle::new(Px(2), Px(20), Px(200), Px(100))
+			);
+			assert_eq!(geometry.to_client(Px(1), gravity), client(), "{gravity:?}");
+		}
+
+		let geometry = FrameGeometry::from_client(client(), Px(1), WindowGravity::Static, EXTENTS);
+		assert_eq!(
+			geometry.client_absolute(),
+			Rectangle::new(Px(101), Px(101), Px(200), Px(100))
+		);
+	}
+
+	#[test]
+	fn test_frame_geometry_resize() {
+		let geometry =
+			FrameGeometry::from_client(client(), Px(0), WindowGravity::NorthWest, EXTENTS);
+
+		let resized = geometry.resize(Dimensions::new(Px(100), Px(50)), WindowGravity::SouthEast);
+		assert_eq!(
+			resized.frame,
+			Rectangle::new(Px(200), Px(150), Px(104), Px(72))
+		);
+		assert_eq!(
+			resized.client.as_dimensions(),
+			Dimensions::new(Px(100), Px(50))
+		);
+
+		let config = resized.client_config();
+		assert_eq!(config.border_width(), Some(&Px(0)));
+		assert_eq!(config.x(), Some(&Px(2)));
+	}
+}
@@ -0,0 +1,578 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers for the conventions defined in the [Inter-Client Communication
+//! Conventions Manual (ICCCM)][ICCCM].
+//!
+//! [ICCCM]: https://x.org/releases/X11R7.7/doc/xorg-docs/icccm/icccm.html
+
+use thiserror::Error;
+
+use crate::{
+	atom::AtomCache,
+	x11::{
+		event::{
+			ClientMessage,
+			ClientMessageData,
+			ConvertSelectionRequest,
+			Selection,
+			SelectionClear,
+		},
+		request::{ConvertSelection, SendEvent, SetSelectionOwner},
+	},
+	Atom,
+	CurrentableTime,
+	DestinationWindow,
+	EventMask,
+	Timestamp,
+	Window,
+};
+
+/// The [atoms] used by the `WM_PROTOCOLS` protocol.
+///
+/// None of these are [predefined atoms], so they must be interned, for
+/// example with an [`AtomCache`].
+///
+/// [atoms]: Atom
+/// [predefined atoms]: crate::atom
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct WmProtocolsAtoms {
+	/// The `WM_PROTOCOLS` [atom].
+	///
+	/// This is both the name of the property listing the protocols a client
+	/// participates in and the `type` of each [`WmProtocol`] message.
+	///
+	/// [atom]: Atom
+	pub wm_protocols: Atom,
+	/// The `WM_DELETE_WINDOW` [atom].
+	///
+	/// [atom]: Atom
+	pub wm_delete_window: Atom,
+	/// The `WM_TAKE_FOCUS` [atom].
+	///
+	/// [atom]: Atom
+	pub wm_take_focus: Atom,
+}
+
+impl WmProtocolsAtoms {
+	/// The names of the [atoms] which must be interned.
+	///
+	/// [atoms]: Atom
+	pub const NAMES: [&'static str; 3] = ["WM_PROTOCOLS", "WM_DELETE_WINDOW", "WM_TAKE_FOCUS"];
+
+	/// Looks up the `WM_PROTOCOLS` [atoms] in the given `cache`.
+	///
+	/// Returns [`None`] if any of them are not known to the `cache`.
+	///
+	/// [atoms]: Atom
+	#[must_use]
+	pub fn from_cache(cache: &AtomCache) -> Option<Self> {
+		let [wm_protocols, wm_delete_window, wm_take_focus] = Self::NAMES;
+
+		Some(Self {
+			wm_protocols: cache.get(wm_protocols)?,
+			wm_delete_window: cache.get(wm_delete_window)?,
+			wm_take_focus: cache.get(wm_take_focus)?,
+		})
+	}
+}
+
+/// A message of the `WM_PROTOCOLS` protocol, sent to a client in a
+/// [`ClientMessage` event].
+///
+/// [`ClientMessage` event]: ClientMessage
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum WmProtocol {
+	/// A request for the client to close the window.
+	///
+	/// A client which participates in the `WM_DELETE_WINDOW` protocol may
+	/// choose to ask the user whether to save their work, or to ignore the
+	/// request entirely.
+	DeleteWindow {
+		/// The time at which the request was made.
+		time: CurrentableTime,
+	},
+
+	/// An invitation for the client to take the input focus.
+	TakeFocus {
+		/// The time of the event which caused the invitation.
+		///
+		/// The client should use this time when it sets the focus.
+		time: CurrentableTime,
+	},
+
+	/// Another protocol, identified by its `protocol` [atom].
+	///
+	/// [atom]: Atom
+	Other {
+		/// The [atom] identifying the protocol.
+		///
+		/// [atom]: Atom
+		protocol: Atom,
+		/// The time at which the message was sent.
+		time: CurrentableTime,
+	},
+}
+
+#[allow(
+	clippy::cast_possible_wrap,
+	clippy::cast_sign_loss,
+	reason = "`ClientMessage` data is reinterpreted as unsigned values"
+)]
+impl WmProtocol {
+	/// The [atom] identifying this protocol.
+	///
+	/// [atom]: Atom
+	#[must_use]
+	pub const fn protocol(&self, atoms: &WmProtocolsAtoms) -> Atom {
+		match self {
+			Self::DeleteWindow { .. } => atoms.wm_delete_window,
+			Self::TakeFocus { .. } => atoms.wm_take_focus,
+
+			Self::Other { protocol, .. } => *protocol,
+		}
+	}
+
+	/// The time at which this message was sent.
+	#[must_use]
+	pub const fn time(&self) -> CurrentableTime {
+		match self {
+			Self::DeleteWindow { time } | Self::TakeFocus { time } | Self::Other { time, .. } => {
+				*time
+			},
+		}
+	}
+
+	/// Creates the [`ClientMessage` event] carrying this message to the given
+	/// `window`.
+	///
+	/// [`ClientMessage` event]: ClientMessage
+	#[must_use]
+	pub const fn to_client_message(
+		&self, window: Window, atoms: &WmProtocolsAtoms,
+	) -> ClientMessage {
+		ClientMessage {
+			sequence: 0,
+			window,
+			r#type: atoms.wm_protocols,
+			data: ClientMessageData::I32([
+				self.protocol(atoms).unwrap() as i32,
+				self.time().unwrap() as i32,
+				0,
+				0,
+				0,
+			]),
+		}
+	}
+
+	/// Creates a [`SendEvent` request] which sends this message to the
+	/// client which created the given `window`.
+	///
+	/// [`SendEvent` request]: SendEvent
+	#[must_use]
+	pub const fn to_send_event(
+		&self, window: Window, atoms: &WmProtocolsAtoms,
+	) -> SendEvent<ClientMessage> {
+		SendEvent {
+			propagate: false,
+			destination: DestinationWindow::Other(window),
+			// An empty event mask sends the event to the client which created
+			// the window.
+			event_mask: EventMask::empty(),
+			event: self.to_client_message(window, atoms),
+		}
+	}
+
+	/// Parses a `WmProtocol` message from the given [`ClientMessage` event].
+	///
+	/// Returns [`None`] if the `message` is not a `WM_PROTOCOLS` message.
+	///
+	/// [`ClientMessage` event]: ClientMessage
+	#[must_use]
+	pub const fn from_client_message(
+		message: &ClientMessage, atoms: &WmProtocolsAtoms,
+	) -> Option<Self> {
+		let ClientMessageData::I32([protocol, time, ..]) = message.data else {
+			return None;
+		};
+
+		if message.r#type.unwrap() != atoms.wm_protocols.unwrap() {
+			return None;
+		}
+
+		let protocol = Atom::new(protocol as u32);
+		let time = CurrentableTime::new(time as u32);
+
+		Some(
+			if protocol.unwrap() == atoms.wm_delete_window.unwrap() {
+				Self::DeleteWindow { time }
+			} else if protocol.unwrap() == atoms.wm_take_focus.unwrap() {
+				Self::TakeFocus { time }
+			} else {
+				Self::Other { protocol, time }
+			},
+		)
+	}
+}
+
+/// Returns whether the `earlier` time is before the `later` time, taking into
+/// account that [timestamps] wrap around.
+///
+/// [timestamps]: Timestamp
+#[allow(
+	clippy::cast_possible_wrap,
+	reason = "times more than half the range apart are treated as having wrapped around"
+)]
+const fn is_before(earlier: Timestamp, later: Timestamp) -> bool {
+	(later.unwrap().wrapping_sub(earlier.unwrap()) as i32) > 0
+}
+
+/// An error generated when a selection transfer violates the conventions of
+/// the [ICCCM].
+///
+/// [ICCCM]: https://x.org/releases/X11R7.7/doc/xorg-docs/icccm/icccm.html
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidSelectionTransfer {
+	/// The transfer used [`CurrentTime`] rather than the [timestamp] of the
+	/// event which caused it.
+	///
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	/// [timestamp]: Timestamp
+	#[error("selection transfers must not use `CurrentTime`")]
+	CurrentTime,
+
+	/// The transfer was requested before the selection owner acquired the
+	/// selection.
+	#[error("the selection transfer was requested before the selection was acquired")]
+	BeforeOwnership,
+}
+
+/// A conversion of a selection into a `target_type`, shared by the client
+/// which requests it and the selection owner which performs it.
+///
+/// The flow of a selection transfer is:
+/// 1. The requester sends a [`ConvertSelection` request], created with
+///    [`to_convert_selection`].
+/// 2. The selection owner receives a [`ConvertSelectionRequest` event], read
+///    with [`from_request`].
+/// 3. The selection owner stores the converted selection in the `property` on
+///    the `requester` and sends a [`Selection` event], created with
+///    [`to_send_event`].
+/// 4. The requester receives that [`Selection` event] (or one generated by the
+///    X server if the selection has no owner) and checks it with [`result`].
+///
+/// [`ConvertSelection` request]: ConvertSelection
+/// [`ConvertSelectionRequest` event]: ConvertSelectionRequest
+/// [`Selection` event]: Selection
+///
+/// [`to_convert_selection`]: SelectionTransfer::to_convert_selection
+/// [`from_request`]: SelectionTransfer::from_request
+/// [`to_send_event`]: SelectionTransfer::to_send_event
+/// [`result`]: SelectionTransfer::result
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SelectionTransfer {
+	/// The [window] which requested the conversion, on which the converted
+	/// selection is stored.
+	///
+	/// [window]: Window
+	pub requester: Window,
+
+	/// The selection which is converted, such as `PRIMARY` or `CLIPBOARD`.
+	pub selection: Atom,
+	/// The type which the selection is converted into, such as
+	/// `UTF8_STRING`.
+	pub target_type: Atom,
+	/// The property on the `requester` in which the converted selection is
+	/// stored.
+	pub property: Atom,
+
+	/// The [timestamp] of the event which caused the conversion to be
+	/// requested.
+	///
+	/// The [ICCCM] forbids using [`CurrentTime`] for selection transfers.
+	///
+	/// [timestamp]: Timestamp
+	/// [ICCCM]: https://x.org/releases/X11R7.7/doc/xorg-docs/icccm/icccm.html
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	pub time: Timestamp,
+}
+
+impl SelectionTransfer {
+	/// Creates the [`ConvertSelection` request] which requests this transfer.
+	///
+	/// [`ConvertSelection` request]: ConvertSelection
+	#[must_use]
+	pub const fn to_convert_selection(&self) -> ConvertSelection {
+		ConvertSelection {
+			requester: self.requester,
+			selection: self.selection,
+			target_type: self.target_type,
+			property: Some(self.property),
+			time: CurrentableTime::Other(self.time),
+		}
+	}
+
+	/// Reads the transfer requested by the given
+	/// [`ConvertSelectionRequest` event].
+	///
+	/// If the event's `property` is [`None`], the requester is an obsolete
+	/// client: as the [ICCCM] recommends, the `target_type` is used as the
+	/// `property` instead.
+	///
+	/// # Errors
+	/// Returns [`InvalidSelectionTransfer::CurrentTime`] if the event's `time`
+	/// is [`CurrentTime`].
+	///
+	/// [`ConvertSelectionRequest` event]: ConvertSelectionRequest
+	/// [ICCCM]: https://x.org/releases/X11R7.7/doc/xorg-docs/icccm/icccm.html
+	/// [`CurrentTime`]: CurrentableTime::CurrentTime
+	pub const fn from_request(
+		event: &ConvertSelectionRequest,
+	) -> Result<Self, InvalidSelectionTransfer> {
+		let CurrentableTime::Other(time) = event.time else {
+			return Err(InvalidSelectionTransfer::CurrentTime);
+		};
+
+		let property = match event.property {
+			Some(property) => property,
+			None => event.target_type,
+		};
+
+		Ok(Self {
+			requester: event.requester,
+			selection: event.selection,
+			target_type: event.target_type,
+			property,
+			time,
+		})
+	}
+
+	/// Creates the [`Selection` event] which notifies the `requester` that this
+	/// transfer was completed.
+	///
+	/// If `converted` is false, the event reports that the selection could not
+	/// be converted to the `target_type`.
+	///
+	/// [`Selection` event]: Selection
+	#[must_use]
+	pub const fn to_selection(&self, converted: bool) -> Selection {
+		Selection {
+			sequence: 0,
+			time: CurrentableTime::Other(self.time),
+			requester: self.requester,
+			selection: self.selection,
+			target_type: self.target_type,
+			property: if converted { Some(self.property) } else { None },
+		}
+	}
+
+	/// Creates the [`SendEvent` request] which sends the
+	/// [`to_selection`] event to the `requester`.
+	///
+	/// [`SendEvent` request]: SendEvent
+	/// [`to_selection`]: SelectionTransfer::to_selection
+	#[must_use]
+	pub const fn to_send_event(&self, converted: bool) -> SendEvent<Selection> {
+		SendEvent {
+			propagate: false,
+			destination: DestinationWindow::Other(self.requester),
+			// An empty event mask sends the event to the client which created
+			// the window.
+			event_mask: EventMask::empty(),
+			event: self.to_selection(converted),
+		}
+	}
+
+	/// Returns the result of this transfer reported by the given
+	/// [`Selection` event].
+	///
+	/// Returns [`None`] if the `event` is not about this transfer,
+	/// `Some(true)` if the selection was converted and stored in the
+	/// `property`, and `Some(false)` if it was not.
+	///
+	/// [`Selection` event]: Selection
+	#[must_use]
+	pub const fn result(&self, event: &Selection) -> Option<bool> {
+		let matches = event.requester.unwrap() == self.requester.unwrap()
+			&& event.selection.unwrap() == self.selection.unwrap()
+			&& event.target_type.unwrap() == self.target_type.unwrap()
+			&& event.time.unwrap() == self.time.unwrap();
+
+		if matches {
+			Some(event.property.is_some())
+		} else {
+			None
+		}
+	}
+}
+
+/// Ownership of a selection, acquired with a [`SetSelectionOwner` request].
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SelectionOwnership {
+	/// The [window] which owns the selection.
+	///
+	/// [window]: Window
+	pub owner: Window,
+	/// The selection which is owned.
+	pub selection: Atom,
+	/// The [timestamp] at which the selection was acquired.
+	///
+	/// [timestamp]: Timestamp
+	pub time: Timestamp,
+}
+
+impl SelectionOwnership {
+	/// Creates the [`SetSelectionOwner` request] which acquires this
+	/// ownership.
+	///
+	/// The client should then check with a [`GetSelectionOwner` request] that
+	/// it did acquire the selection.
+	///
+	/// [`SetSelectionOwner` request]: SetSelectionOwner
+	/// [`GetSelectionOwner` request]: crate::x11::request::GetSelectionOwner
+	#[must_use]
+	pub const fn to_set_selection_owner(&self) -> SetSelectionOwner {
+		SetSelectionOwner {
+			new_owner: Some(self.owner),
+			selection: self.selection,
+			time: CurrentableTime::Other(self.time),
+		}
+	}
+
+	/// Checks that the given `transfer` of this selection may be performed.
+	///
+	/// # Errors
+	/// Returns [`InvalidSelectionTransfer::BeforeOwnership`] if the `transfer`
+	/// was requested before this ownership was acquired.
+	pub const fn check(
+		&self, transfer: &SelectionTransfer,
+	) -> Result<(), InvalidSelectionTransfer> {
+		if is_before(transfer.time, self.time) {
+			Err(InvalidSelectionTransfer::BeforeOwnership)
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Returns whether the given [`SelectionClear` event] means that this
+	/// ownership has been lost.
+	///
+	/// [`SelectionClear` event]: SelectionClear
+	#[must_use]
+	pub const fn is_cleared_by(&self, event: &SelectionClear) -> bool {
+		event.selection.unwrap() == self.selection.unwrap() && !is_before(event.time, self.time)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use xrbk::X11Size;
+
+	use super::*;
+
+	const ATOMS: WmProtocolsAtoms = WmProtocolsAtoms {
+		wm_protocols: Atom::new(300),
+		wm_delete_window: Atom::new(301),
+		wm_take_focus: Atom::new(302),
+	};
+
+	#[test]
+	fn test_wm_protocol_round_trip() {
+		let window = Window::new(0x0020_0001);
+
+		for message in [
+			WmProtocol::DeleteWindow {
+				time: CurrentableTime::CurrentTime,
+			},
+			WmProtocol::TakeFocus {
+				time: CurrentableTime::Other(Timestamp(u32::MAX)),
+			},
+			WmProtocol::Other {
+				protocol: Atom::new(303),
+				time: CurrentableTime::Other(Timestamp(42)),
+			},
+		] {
+			let event = message.to_client_message(window, &ATOMS);
+
+			assert_eq!(event.window, window);
+			assert_eq!(message.to_send_event(window, &ATOMS).x11_size(), 44);
+			assert_eq!(
+				WmProtocol::from_client_message(&event, &ATOMS),
+				Some(message)
+			);
+		}
+
+		let other = ClientMessage {
+			sequence: 0,
+			window,
+			r#type: Atom::new(303),
+			data: ClientMessageData::I32([301, 0, 0, 0, 0]),
+		};
+		assert_eq!(WmProtocol::from_client_message(&other, &ATOMS), None);
+	}
+
+	#[test]
+	fn test_selection_transfer() {
+		let ownership = SelectionOwnership {
+			owner: Window::new(0x0020_0001),
+			selection: Atom::new(1),
+			time: Timestamp::new(100),
+		};
+		let transfer = SelectionTransfer {
+			requester: Window::new(0x0040_0001),
+			selection: Atom::new(1),
+			target_type: Atom::new(31),
+			property: Atom::new(304),
+			time: Timestamp::new(150),
+		};
+
+		let request = transfer.to_convert_selection();
+		let event = ConvertSelectionRequest {
+			sequence: 0,
+			time: request.time,
+			owner: ownership.owner,
+			requester: request.requester,
+			selection: request.selection,
+			target_type: request.target_type,
+			property: request.property,
+		};
+		assert_eq!(SelectionTransfer::from_request(&event), Ok(transfer));
+		assert_eq!(ownership.check(&transfer), Ok(()));
+
+		let obsolete = ConvertSelectionRequest {
+			time: CurrentableTime::Other(Timestamp::new(50)),
+			property: None,
+			..event
+		};
+		let obsolete = SelectionTransfer::from_request(&obsolete).unwrap();
+		assert_eq!(obsolete.property, obsolete.target_type);
+		assert_eq!(
+			ownership.check(&obsolete),
+			Err(InvalidSelectionTransfer::BeforeOwnership)
+		);
+
+		let current_time = ConvertSelectionRequest {
+			time: CurrentableTime::CurrentTime,
+			..event
+		};
+		assert_eq!(
+			SelectionTransfer::from_request(&current_time),
+			Err(InvalidSelectionTransfer::CurrentTime)
+		);
+
+		assert_eq!(transfer.result(&transfer.to_selection(true)), Some(true));
+		assert_eq!(transfer.result(&obsolete.to_selection(false)), None);
+		assert_eq!(
+			transfer.to_send_event(false).destination,
+			DestinationWindow::Other(transfer.requester)
+		);
+
+		assert!(ownership.is_cleared_by(&SelectionClear {
+			sequence: 0,
+			time: Timestamp::new(200),
+			owner: Window::new(0x0060_0001),
+			selection: Atom::new(1),
+		}));
+	}
+}
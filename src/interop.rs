@@ -0,0 +1,300 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Conversions between XRB's messages and those generated by [`x11rb`].
+//!
+//! These allow a codebase using [`x11rb`] to move to XRB gradually, or to use
+//! XRB alongside [`x11rb`]. Only common [requests] and [events] are covered so
+//! far.
+//!
+//! [`x11rb`] uses plain integers for resource IDs, so converting from
+//! [`x11rb`]'s messages can fail: [`XidOutOfRange`] is returned if any of their
+//! resource IDs use the top three bits, which resource IDs never use.
+//!
+//! [`x11rb`]: x11rb_protocol
+//! [requests]: crate::message::Request
+//! [events]: crate::message::Event
+
+use thiserror::Error;
+use x11rb_protocol::protocol::xproto;
+
+use crate::{
+	message::Event,
+	unit::Px,
+	x11::{
+		event::{self, ClientMessageData},
+		request,
+	},
+	Atom,
+	Rectangle,
+	Region,
+	Window,
+	XidOutOfRange,
+};
+
+/// Implements conversions for [requests] with a single `target` [window].
+///
+/// [requests]: crate::message::Request
+/// [window]: Window
+macro_rules! impl_window_request {
+	($($Request:ident <=> $X11rbRequest:ident),+$(,)?) => {
+		$(
+			impl From<request::$Request> for xproto::$X11rbRequest {
+				fn from(request: request::$Request) -> Self {
+					Self {
+						window: request.target.unwrap(),
+					}
+				}
+			}
+
+			impl TryFrom<xproto::$X11rbRequest> for request::$Request {
+				type Error = XidOutOfRange;
+
+				fn try_from(request: xproto::$X11rbRequest) -> Result<Self, Self::Error> {
+					Ok(Self {
+						target: Window::try_new(request.window)?,
+					})
+				}
+			}
+		)+
+	};
+}
+
+impl_window_request! {
+	DestroyWindow <=> DestroyWindowRequest,
+	MapWindow <=> MapWindowRequest,
+	UnmapWindow <=> UnmapWindowRequest,
+}
+
+impl From<event::Expose> for xproto::ExposeEvent {
+	fn from(expose: event::Expose) -> Self {
+		Self {
+			response_type: event::Expose::CODE,
+			sequence: expose.sequence,
+			window: expose.window.unwrap(),
+			x: expose.region.x.0,
+			y: expose.region.y.0,
+			width: expose.region.width.0,
+			height: expose.region.height.0,
+			count: expose.count,
+		}
+	}
+}
+
+impl TryFrom<xproto::ExposeEvent> for event::Expose {
+	type Error = XidOutOfRange;
+
+	fn try_from(expose: xproto::ExposeEvent) -> Result<Self, Self::Error> {
+		Ok(Self {
+			sequence: expose.sequence,
+			window: Window::try_new(expose.window)?,
+			region: Region::new(
+				Px(expose.x),
+				Px(expose.y),
+				Px(expose.width),
+				Px(expose.height),
+			),
+			count: expose.count,
+		})
+	}
+}
+
+impl From<event::Map> for xproto::MapNotifyEvent {
+	fn from(map: event::Map) -> Self {
+		Self {
+			response_type: event::Map::CODE,
+			sequence: map.sequence,
+			event: map.event_window.unwrap(),
+			window: map.window.unwrap(),
+			override_redirect: map.override_redirect,
+		}
+	}
+}
+
+impl TryFrom<xproto::MapNotifyEvent> for event::Map {
+	type Error = XidOutOfRange;
+
+	fn try_from(map: xproto::MapNotifyEvent) -> Result<Self, Self::Error> {
+		Ok(Self {
+			sequence: map.sequence,
+			event_window: Window::try_new(map.event)?,
+			window: Window::try_new(map.window)?,
+			override_redirect: map.override_redirect,
+		})
+	}
+}
+
+impl From<event::Configure> for xproto::ConfigureNotifyEvent {
+	fn from(configure: event::Configure) -> Self {
+		let Rectangle {
+			x,
+			y,
+			width,
+			height,
+		} = configure.geometry;
+
+		Self {
+			response_type: event::Configure::CODE,
+			sequence: configure.sequence,
+			event: configure.event_window.unwrap(),
+			window: configure.window.unwrap(),
+			above_sibling: configure.sibling_below.map_or(0, Window::unwrap),
+			x: x.0,
+			y: y.0,
+			width: width.0,
+			height: height.0,
+			border_width: configure.border_width.0,
+			override_redirect: configure.override_redirect,
+		}
+	}
+}
+
+impl TryFrom<xproto::ConfigureNotifyEvent> for event::Configure {
+	type Error = XidOutOfRange;
+
+	fn try_from(configure: xproto::ConfigureNotifyEvent) -> Result<Self, Self::Error> {
+		Ok(Self {
+			sequence: configure.sequence,
+			event_window: Window::try_new(configure.event)?,
+			window: Window::try_new(configure.window)?,
+			sibling_below: match configure.above_sibling {
+				0 => None,
+				sibling => Some(Window::try_new(sibling)?),
+			},
+			geometry: Rectangle::new(
+				Px(configure.x),
+				Px(configure.y),
+				Px(configure.width),
+				Px(configure.height),
+			),
+			border_width: Px(configure.border_width),
+			override_redirect: configure.override_redirect,
+		})
+	}
+}
+
+impl From<event::ClientMessage> for xproto::ClientMessageEvent {
+	fn from(message: event::ClientMessage) -> Self {
+		let (format, data) = match message.data {
+			ClientMessageData::I8(data) => (8, data.map(i8::cast_unsigned).into()),
+			ClientMessageData::I16(data) => (16, data.map(i16::cast_unsigned).into()),
+			ClientMessageData::I32(data) => (32, data.map(i32::cast_unsigned).into()),
+		};
+
+		Self {
+			response_type: event::ClientMessage::CODE,
+			format,
+			sequence: message.sequence,
+			window: message.window.unwrap(),
+			type_: message.r#type.unwrap(),
+			data,
+		}
+	}
+}
+
+/// An error generated when converting an [`x11rb`] `ClientMessageEvent` with a
+/// format other than 8, 16, or 32 into a [`ClientMessage` event].
+///
+/// [`x11rb`]: x11rb_protocol
+/// [`ClientMessage` event]: event::ClientMessage
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+#[error("expected a `ClientMessage` format of 8, 16, or 32, found {0}")]
+pub struct UnrecognizedFormat(pub u8);
+
+/// An error generated when converting an [`x11rb`] `ClientMessageEvent` into a
+/// [`ClientMessage` event].
+///
+/// [`x11rb`]: x11rb_protocol
+/// [`ClientMessage` event]: event::ClientMessage
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum ClientMessageConversionError {
+	/// The `ClientMessageEvent`'s format was not 8, 16, or 32.
+	#[error(transparent)]
+	UnrecognizedFormat(#[from] UnrecognizedFormat),
+
+	/// The `ClientMessageEvent`'s window or type used the top three bits.
+	#[error(transparent)]
+	XidOutOfRange(#[from] XidOutOfRange),
+}
+
+impl TryFrom<xproto::ClientMessageEvent> for event::ClientMessage {
+	type Error = ClientMessageConversionError;
+
+	fn try_from(message: xproto::ClientMessageEvent) -> Result<Self, Self::Error> {
+		let data = match message.format {
+			8 => ClientMessageData::I8(message.data.as_data8().map(u8::cast_signed)),
+			16 => ClientMessageData::I16(message.data.as_data16().map(u16::cast_signed)),
+			32 => ClientMessageData::I32(message.data.as_data32().map(u32::cast_signed)),
+
+			other => return Err(UnrecognizedFormat(other).into()),
+		};
+
+		Ok(Self {
+			sequence: message.sequence,
+			window: Window::try_new(message.window)?,
+			r#type: Atom::try_from(message.type_)?,
+			data,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_x11rb_round_trip() {
+		// XRB's messages are not `Clone`, so each is constructed twice.
+		let request = || request::MapWindow {
+			target: Window::new(0x0020_0001),
+		};
+		let x11rb_request = xproto::MapWindowRequest::from(request());
+		assert_eq!(x11rb_request.window, 0x0020_0001);
+		assert_eq!(request::MapWindow::try_from(x11rb_request), Ok(request()));
+
+		let configure = || event::Configure {
+			sequence: 3,
+			event_window: Window::new(0x0000_0100),
+			window: Window::new(0x0020_0001),
+			sibling_below: None,
+			geometry: Rectangle::new(Px(-5), Px(10), Px(640), Px(480)),
+			border_width: Px(1),
+			override_redirect: false,
+		};
+		let x11rb_configure = xproto::ConfigureNotifyEvent::from(configure());
+		assert_eq!(x11rb_configure.above_sibling, 0);
+		assert_eq!(event::Configure::try_from(x11rb_configure), Ok(configure()));
+
+		let message = || event::ClientMessage {
+			sequence: 3,
+			window: Window::new(0x0020_0001),
+			r#type: crate::atom::WM_NAME,
+			data: ClientMessageData::I16([-1, 2, -3, 4, -5, 6, -7, 8, -9, 10]),
+		};
+		let x11rb_message = xproto::ClientMessageEvent::from(message());
+		assert_eq!(x11rb_message.format, 16);
+		assert_eq!(x11rb_message.data.as_data16()[..2], [0xffff, 2]);
+
+		assert_eq!(event::ClientMessage::try_from(x11rb_message), Ok(message()));
+	}
+
+	#[test]
+	fn test_x11rb_invalid_window() {
+		let request = xproto::DestroyWindowRequest {
+			window: 0xe020_0001,
+		};
+		assert_eq!(
+			request::DestroyWindow::try_from(request),
+			Err(XidOutOfRange(0xe020_0001))
+		);
+
+		let message = xproto::ClientMessageEvent::new(32, 0x2000_0000, 0u32, [0u32; 5]);
+		assert_eq!(
+			event::ClientMessage::try_from(message),
+			Err(ClientMessageConversionError::XidOutOfRange(XidOutOfRange(
+				0x2000_0000
+			)))
+		);
+	}
+}
@@ -0,0 +1,46 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Traits and errors for serializing and deserializing X11 messages and types.
+//!
+//! Every message and type in XRB implements these traits, so they are all that
+//! is needed to send and receive messages without depending on XRBK directly.
+//! They are part of XRB's stable API: the traits and errors re-exported here
+//! will not change without a breaking release of XRB.
+//!
+//! # Examples
+//! ```
+//! use xrb::{
+//!     io::{Readable, Writable, X11Size},
+//!     x11::request::MapWindow,
+//!     Window,
+//! };
+//!
+//! let request = MapWindow {
+//!     target: Window::new(0x0020_0001),
+//! };
+//!
+//! let mut bytes = Vec::with_capacity(request.x11_size());
+//! request.write_to(&mut bytes)?;
+//! assert_eq!(bytes, [8, 0, 0, 2, 0, 0x20, 0, 1]);
+//!
+//! // The major opcode is read before the request itself.
+//! assert_eq!(MapWindow::read_from(&mut &bytes[1..])?, request);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub use xrbk::{
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	InfallibleWritable,
+	ReadError,
+	ReadResult,
+	Readable,
+	ReadableWithContext,
+	Writable,
+	WriteError,
+	WriteResult,
+	X11Size,
+};
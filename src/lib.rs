@@ -40,6 +40,8 @@
 #![allow(clippy::doc_markdown)]
 #![allow(clippy::wildcard_imports)]
 #![allow(clippy::module_name_repetitions)]
+// Lists of byte ranges, such as `Message::unused`, often have a single range.
+#![allow(clippy::single_range_in_vec_init)]
 
 //! <h1 align="center" style="margin-bottom: 0;">
 //!     X Rust Bindings
@@ -84,6 +86,32 @@ pub const PROTOCOL_MINOR_VERSION: u16 = 0;
 
 pub(crate) mod common;
 pub mod connection;
+pub mod diff;
+pub mod dissect;
+#[cfg(feature = "ewmh")]
+pub mod ewmh;
+pub mod extension;
+pub mod frame;
+#[cfg(feature = "icccm")]
+pub mod icccm;
+#[cfg(feature = "interop")]
+pub mod interop;
+pub mod io;
+pub mod mapping;
 pub mod message;
+pub mod monitor;
+#[cfg(feature = "names")]
+pub mod names;
+pub mod passive_grabs;
+pub mod property;
+#[cfg(feature = "raw-window-handle")]
+pub mod raw_handle;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod tree;
 pub mod unit;
+pub mod window;
 pub mod x11;
+pub mod xlfd;
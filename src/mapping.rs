@@ -0,0 +1,285 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A cache of the keyboard, modifier, and button mappings which is kept up to
+//! date with [`MappingChange` events].
+//!
+//! When a [`MappingChange` event] is received, a client must fetch whichever
+//! mapping was changed again. [`MappingState::handle`] returns the [request]
+//! which must be sent to do so, and the `merge_*` methods update the cache with
+//! the [replies] to those [requests].
+//!
+//! [`MappingChange` event]: MappingChange
+//! [`MappingChange` events]: MappingChange
+//! [request]: crate::message::Request
+//! [requests]: crate::message::Request
+//! [replies]: crate::message::Reply
+
+use std::collections::HashMap;
+
+use crate::{
+	x11::{
+		event::{MappingChange, MappingRequest},
+		reply::{self, KeyMapping},
+		request::{ButtonMapping, GetButtonMapping, GetKeyboardMapping, GetModifierMapping},
+	},
+	Keycode,
+	Keysym,
+	ModifierKeyMask,
+};
+
+/// The [request] which must be sent to refresh a mapping after a
+/// [`MappingChange` event].
+///
+/// [request]: crate::message::Request
+/// [`MappingChange` event]: MappingChange
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub enum Refresh {
+	/// The modifier mapping must be fetched again.
+	Modifiers(GetModifierMapping),
+	/// The mapping of the given range of [keycodes] must be fetched again.
+	///
+	/// [keycodes]: Keycode
+	Keyboard(GetKeyboardMapping),
+	/// The button mapping must be fetched again.
+	Buttons(GetButtonMapping),
+}
+
+/// A cache of the keyboard, modifier, and button mappings.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MappingState {
+	keyboard: HashMap<Keycode, KeyMapping>,
+	modifiers: Option<[Vec<Keycode>; 8]>,
+	buttons: Option<ButtonMapping>,
+}
+
+impl MappingState {
+	/// Creates a new, empty `MappingState`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Forgets the mapping changed by the given [`MappingChange` event] and
+	/// returns the [request] which must be sent to fetch it again.
+	///
+	/// Returns [`None`] if the event changed no [keycodes], or if it was
+	/// generated by a [request] which is not known to XRB.
+	///
+	/// [`MappingChange` event]: MappingChange
+	/// [request]: crate::message::Request
+	/// [keycodes]: Keycode
+	pub fn handle(&mut self, event: &MappingChange) -> Option<Refresh> {
+		match event.request {
+			MappingRequest::Modifier => {
+				self.modifiers = None;
+
+				Some(Refresh::Modifiers(GetModifierMapping))
+			},
+
+			MappingRequest::Keyboard => {
+				let first = event.first_keycode.unwrap();
+				let last = first.saturating_add(event.count.checked_sub(1)?);
+
+				self.keyboard
+					.retain(|keycode, _| !(first..=last).contains(&keycode.unwrap()));
+
+				Some(Refresh::Keyboard(GetKeyboardMapping {
+					range: Keycode::new(first)..=Keycode::new(last),
+				}))
+			},
+
+			MappingRequest::Cursor => {
+				self.buttons = None;
+
+				Some(Refresh::Buttons(GetButtonMapping))
+			},
+
+			MappingRequest::Unknown(_) => None,
+		}
+	}
+
+	/// Merges the [reply] to the given [`GetKeyboardMapping` request] into
+	/// the cache.
+	///
+	/// [reply]: reply::GetKeyboardMapping
+	/// [`GetKeyboardMapping` request]: GetKeyboardMapping
+	pub fn merge_keyboard(
+		&mut self, request: &GetKeyboardMapping, reply: reply::GetKeyboardMapping,
+	) {
+		let keycodes =
+			(request.range.start().unwrap()..=request.range.end().unwrap()).map(Keycode::new);
+
+		self.keyboard.extend(keycodes.zip(reply.mappings));
+	}
+
+	/// Replaces the cached modifier mapping with the given [reply].
+	///
+	/// [reply]: reply::GetModifierMapping
+	pub fn merge_modifiers(&mut self, reply: reply::GetModifierMapping) {
+		self.modifiers = Some([
+			reply.shift_keycodes,
+			reply.capslock_keycodes,
+			reply.ctrl_keycodes,
+			reply.mod1_keycodes,
+			reply.mod2_keycodes,
+			reply.mod3_keycodes,
+			reply.mod4_keycodes,
+			reply.mod5_keycodes,
+		]);
+	}
+
+	/// Replaces the cached button mapping with the given [reply].
+	///
+	/// [reply]: reply::GetButtonMapping
+	pub fn merge_buttons(&mut self, reply: &reply::GetButtonMapping) {
+		self.buttons = Some(ButtonMapping::from_reply(reply));
+	}
+
+	/// Returns the cached [keysyms] mapped to the given [keycode], if any.
+	///
+	/// [keysyms]: Keysym
+	/// [keycode]: Keycode
+	#[must_use]
+	pub fn keysyms(&self, keycode: Keycode) -> Option<&[Keysym]> {
+		self.keyboard.get(&keycode).map(Vec::as_slice)
+	}
+
+	/// Returns the cached [keycodes] which the given [keysym] is mapped to, in
+	/// ascending order.
+	///
+	/// [keycodes]: Keycode
+	/// [keysym]: Keysym
+	#[must_use]
+	pub fn keycodes(&self, keysym: Keysym) -> Vec<Keycode> {
+		let mut keycodes: Vec<_> = self
+			.keyboard
+			.iter()
+			.filter(|(_, keysyms)| keysyms.contains(&keysym))
+			.map(|(&keycode, _)| keycode)
+			.collect();
+		keycodes.sort_unstable_by_key(Keycode::unwrap);
+
+		keycodes
+	}
+
+	/// Returns the modifier which a [keycode] mapped to the given [keysym] is
+	/// mapped to, if the keyboard and modifier mappings are cached and there
+	/// is one.
+	///
+	/// This is used to find which modifier Num Lock is mapped to, for
+	/// example.
+	///
+	/// [keycode]: Keycode
+	/// [keysym]: Keysym
+	#[must_use]
+	pub fn modifier(&self, keysym: Keysym) -> Option<ModifierKeyMask> {
+		let keycodes = self.keycodes(keysym);
+
+		self.modifiers
+			.as_ref()?
+			.iter()
+			.position(|modifier| modifier.iter().any(|keycode| keycodes.contains(keycode)))
+			.map(|index| ModifierKeyMask::from_bits_truncate(1 << index))
+	}
+
+	/// Returns the cached [keycodes] mapped to each modifier, if the modifier
+	/// mapping is cached.
+	///
+	/// The modifiers are, in order: shift, caps lock, control, and Mod1 to
+	/// Mod5.
+	///
+	/// [keycodes]: Keycode
+	#[must_use]
+	pub const fn modifiers(&self) -> Option<&[Vec<Keycode>; 8]> {
+		self.modifiers.as_ref()
+	}
+
+	/// Returns the cached button mapping, if any.
+	#[must_use]
+	pub const fn buttons(&self) -> Option<&ButtonMapping> {
+		self.buttons.as_ref()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_mapping_state_refresh() {
+		let mut state = MappingState::new();
+
+		let request = GetKeyboardMapping {
+			range: Keycode::new(10)..=Keycode::new(11),
+		};
+		state.merge_keyboard(
+			&request,
+			reply::GetKeyboardMapping {
+				sequence: 1,
+				mappings: vec![vec![Keysym::new(0x61)], vec![Keysym::new(0x62)]],
+			},
+		);
+		assert_eq!(
+			state.keysyms(Keycode::new(11)),
+			Some(&[Keysym::new(0x62)][..])
+		);
+
+		let refresh = state.handle(&MappingChange {
+			sequence: 2,
+			request: MappingRequest::Keyboard,
+			first_keycode: Keycode::new(11),
+			count: 3,
+		});
+		assert_eq!(
+			refresh,
+			Some(Refresh::Keyboard(GetKeyboardMapping {
+				range: Keycode::new(11)..=Keycode::new(13),
+			})),
+		);
+		assert!(state.keysyms(Keycode::new(10)).is_some());
+		assert!(state.keysyms(Keycode::new(11)).is_none());
+
+		let refresh = state.handle(&MappingChange {
+			sequence: 3,
+			request: MappingRequest::Keyboard,
+			first_keycode: Keycode::new(11),
+			count: 0,
+		});
+		assert_eq!(refresh, None);
+
+		let refresh = state.handle(&MappingChange {
+			sequence: 4,
+			request: MappingRequest::Unknown(3),
+			first_keycode: Keycode::new(0),
+			count: 0,
+		});
+		assert_eq!(refresh, None);
+	}
+
+	#[test]
+	fn test_query_keyboard_diff() {
+		let mut previous = reply::QueryKeyboard {
+			sequence: 1,
+			keys: [0; 32],
+		};
+		previous.keys[1] = 0b0000_0101;
+
+		let mut current = reply::QueryKeyboard {
+			sequence: 2,
+			keys: [0; 32],
+		};
+		current.keys[1] = 0b0000_0110;
+
+		assert!(current.is_held(Keycode::new(9)));
+		assert_eq!(
+			current.diff(&previous).collect::<Vec<_>>(),
+			[(Keycode::new(8), false), (Keycode::new(9), true)],
+		);
+	}
+}
@@ -4,12 +4,134 @@
 
 //! Traits defining the format of messages sent via the X11 protocol.
 
+pub use any_reply::{expect_reply, AnyReply, Cookie, ExpectedReply, ReplySource, ResolveError};
+pub use opaque::Opaque;
+pub use sequence::SequenceTracker;
+
+use std::{fmt::Debug, ops::Range};
+
 use crate::x11::error;
-use xrbk::{Readable, Writable, X11Size};
+use xrbk::{
+	Buf,
+	BufMut,
+	ReadError,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteError,
+	WriteResult,
+	X11Size,
+};
+
+mod any_reply;
+mod opaque;
+mod sequence;
+
+/// Metadata common to every message sent via the X11 protocol.
+///
+/// This is implemented by every [request], [reply], [event], and [error], so
+/// that tooling such as loggers and proxies can describe messages uniformly.
+///
+/// [request]: Request
+/// [reply]: Reply
+/// [event]: Event
+/// [error]: Error
+pub trait Message {
+	/// The name of this type of message.
+	///
+	/// This is the name of the type implementing `Message`; for example,
+	/// `"GetGeometry"`.
+	fn name(&self) -> &'static str;
+
+	/// What kind of message this is, along with the opcode or code identifying
+	/// it.
+	fn kind(&self) -> MessageKind;
+
+	/// The name and value of each field of this message, in the order in which
+	/// they are defined.
+	///
+	/// Fields of tuple structs are named by their position, such as `"0"`.
+	/// Values which are only used to encode the message, such as the lengths
+	/// of lists, are not fields.
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)>;
+
+	/// The ranges of bytes within the encoding of this message which are
+	/// unused, measured from the start of the message.
+	///
+	/// This includes unused bytes in the message's header, such as an unused
+	/// metabyte position, and any padding at the end of the message.
+	fn unused(&self) -> Vec<Range<usize>>;
+}
+
+/// The kind of a [`Message`], along with the opcode or code identifying it.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum MessageKind {
+	/// A [`Request`].
+	Request {
+		/// The [major opcode] of the [`Request`].
+		///
+		/// [major opcode]: Request::MAJOR_OPCODE
+		major_opcode: u8,
+		/// The [minor opcode] of the [`Request`], if it has one.
+		///
+		/// [minor opcode]: Request::MINOR_OPCODE
+		minor_opcode: Option<u16>,
+	},
+
+	/// A [`Reply`].
+	Reply {
+		/// The [major opcode] of the [`Request`] which generates the
+		/// [`Reply`].
+		///
+		/// [major opcode]: Request::MAJOR_OPCODE
+		major_opcode: u8,
+		/// The [minor opcode] of the [`Request`] which generates the
+		/// [`Reply`], if it has one.
+		///
+		/// [minor opcode]: Request::MINOR_OPCODE
+		minor_opcode: Option<u16>,
+	},
+
+	/// An [`Event`].
+	Event {
+		/// The [code] of the [`Event`].
+		///
+		/// [code]: Event::CODE
+		code: u8,
+	},
+
+	/// An [`Error`].
+	Error {
+		/// The [code] of the [`Error`].
+		///
+		/// [code]: Error::CODE
+		code: u8,
+	},
+}
+
+impl MessageKind {
+	/// The [`MessageKind`] of the given [`Request`] type.
+	#[must_use]
+	pub const fn of_request<Req: Request>() -> Self {
+		Self::Request {
+			major_opcode: Req::MAJOR_OPCODE,
+			minor_opcode: Req::MINOR_OPCODE,
+		}
+	}
+
+	/// The [`MessageKind`] of the given [`Reply`] type.
+	#[must_use]
+	pub const fn of_reply<Rep: Reply>() -> Self {
+		Self::Reply {
+			major_opcode: <Rep::Request as Request>::MAJOR_OPCODE,
+			minor_opcode: <Rep::Request as Request>::MINOR_OPCODE,
+		}
+	}
+}
 
 /// A message sent from an X client to the X server.
 #[doc(notable_trait)]
-pub trait Request: X11Size + Writable {
+pub trait Request: Message + X11Size + Writable {
 	/// The type representing the [other possible errors][other-errors]
 	/// generated by this `Request`.
 	///
@@ -105,7 +227,7 @@ pub trait Request: X11Size + Writable {
 	/// use xrb::{ColorChannelMask, Colormap, visual::ColorId, message::Request, String8};
 	///
 	/// derive_xrb! {
-	///     #[derive(Debug, Hash, PartialEq, Eq, Readable, Writable, X11Size)]
+	///     #[derive(Debug, Hash, PartialEq, Eq, Readable, Writable, InfallibleWritable, X11Size)]
 	///     pub struct StoreNamedColor: Request(90) {
 	///         #[metabyte]
 	///         pub color_channel_mask: ColorChannelMask,
@@ -135,6 +257,94 @@ pub trait Request: X11Size + Writable {
 
 		(size / 4) as u16
 	}
+
+	/// Writes this `Request` to the given `buf`, first checking that it is no
+	/// longer than `max_request_len`.
+	///
+	/// `max_request_len` is measured in units of 4 bytes. It is usually the
+	/// [`maximum_request_length`] of the connection, or the maximum length
+	/// enabled by the BIG-REQUESTS extension. [`None`] means that the length
+	/// is not checked.
+	///
+	/// An X server responds to a `Request` which is too long with a [`Length`
+	/// error]; this allows that to be caught before the `Request` is sent.
+	///
+	/// # Errors
+	/// Returns [`WriteError::RequestTooLarge`] if this `Request` is longer than
+	/// `max_request_len`, or any [`WriteError`] generated while writing it.
+	///
+	/// [`maximum_request_length`]: crate::connection::ServerCapabilities::maximum_request_length
+	/// [`Length` error]: error::Length
+	fn write_checked(&self, buf: &mut impl BufMut, max_request_len: Option<u32>) -> WriteResult
+	where
+		Self: Sized,
+	{
+		if let Some(allowed) = max_request_len {
+			let needed = self.x11_size().div_ceil(4);
+			let allowed = allowed as usize;
+
+			if needed > allowed {
+				return Err(WriteError::RequestTooLarge { needed, allowed });
+			}
+		}
+
+		self.write_to(buf)
+	}
+}
+
+/// An object-safe view of a [`Request`].
+///
+/// [`Request`] has associated types and constants, so it can't be used as a
+/// trait object. `RawRequest` is implemented for every [`Request`], and offers
+/// what is needed to send one: its opcodes, its length, and its encoding. This
+/// allows [requests] of different types to be stored together, for example as
+/// `Vec<Box<dyn RawRequest>>`.
+///
+/// [requests]: Request
+pub trait RawRequest: Message {
+	/// The [major opcode] of this [request].
+	///
+	/// [major opcode]: Request::MAJOR_OPCODE
+	/// [request]: Request
+	fn major_opcode(&self) -> u8;
+	/// The [minor opcode] of this [request], if it has one.
+	///
+	/// [minor opcode]: Request::MINOR_OPCODE
+	/// [request]: Request
+	fn minor_opcode(&self) -> Option<u16>;
+
+	/// The [length] of this [request] in 4-byte units.
+	///
+	/// [length]: Request::length
+	/// [request]: Request
+	fn request_length(&self) -> u16;
+
+	/// Writes this [request] to the given `buf`.
+	///
+	/// # Errors
+	/// Returns a [`WriteError`] if this [request] could not be written.
+	///
+	/// [request]: Request
+	/// [`WriteError`]: xrbk::WriteError
+	fn write_request_to(&self, buf: &mut dyn BufMut) -> WriteResult;
+}
+
+impl<Req: Request> RawRequest for Req {
+	fn major_opcode(&self) -> u8 {
+		Req::MAJOR_OPCODE
+	}
+
+	fn minor_opcode(&self) -> Option<u16> {
+		Req::MINOR_OPCODE
+	}
+
+	fn request_length(&self) -> u16 {
+		self.length()
+	}
+
+	fn write_request_to(&self, mut buf: &mut dyn BufMut) -> WriteResult {
+		self.write_to(&mut buf)
+	}
 }
 
 /// The result of sending a [request].
@@ -209,10 +419,43 @@ pub enum RequestError<OtherErrors> {
 	Other(OtherErrors),
 }
 
+/// A [`Request`] with constraints which can be checked before it is sent.
+///
+/// The X server generates an [error] for a [request] which violates these
+/// constraints; validating a [request] first reports the offending field
+/// without a round trip to the X server.
+///
+/// [error]: Error
+/// [request]: Request
+pub trait Validate: Request {
+	/// Checks that this [request] satisfies the constraints of the X11
+	/// protocol which do not depend on the state of the X server.
+	///
+	/// # Errors
+	/// Returns a [`ValidationError`] naming the first field found to violate
+	/// a constraint.
+	///
+	/// [request]: Request
+	fn validate(&self) -> Result<(), ValidationError>;
+}
+
+/// An error generated when a [request] fails [validation].
+///
+/// [request]: Request
+/// [validation]: Validate
+#[derive(Clone, Debug, Hash, PartialEq, Eq, thiserror::Error)]
+#[error("`{field}` is invalid: {reason}")]
+pub struct ValidationError {
+	/// The name of the field which violates a constraint.
+	pub field: &'static str,
+	/// The constraint which was violated.
+	pub reason: &'static str,
+}
+
 /// A message sent from the X server to an X client in response to a
 /// [`Request`].
 #[doc(notable_trait)]
-pub trait Reply: X11Size + Readable {
+pub trait Reply: Message + X11Size + Readable {
 	/// The [request] that generates this `Reply`.
 	///
 	/// The type indicated here must implement [`Request`] with a
@@ -266,12 +509,12 @@ pub trait Reply: X11Size + Readable {
 	/// # use xrb::message::Request;
 	///
 	/// derive_xrb! {
-	///     # #[derive(Debug, Hash, PartialEq, Eq, Readable, Writable, X11Size)]
+	///     # #[derive(Debug, Hash, PartialEq, Eq, Readable, Writable, InfallibleWritable, X11Size)]
 	///     # pub struct GetGeometry: Request(14) -> GetGeometryReply {
 	///     #     pub drawable: xrb::Drawable,
 	///     # }
 	///     #
-	///     #[derive(Debug, Hash, Readable, Writable, X11Size)]
+	///     #[derive(Debug, Hash, Readable, Writable, InfallibleWritable, X11Size)]
 	///     pub struct GetGeometryReply: Reply for GetGeometry {
 	///         // Header is 8 bytes.
 	///
@@ -327,7 +570,7 @@ pub trait Reply: X11Size + Readable {
 /// [replies]: Reply
 /// [request]: Request
 #[doc(notable_trait)]
-pub trait Event: X11Size + Readable + Writable {
+pub trait Event: Message + X11Size + Readable + Writable {
 	/// The code uniquely identifying this `Event` (among other `Event`s).
 	///
 	/// Event codes 64 to 127 are reserved for extensions. The core X11 protocol
@@ -340,14 +583,104 @@ pub trait Event: X11Size + Readable + Writable {
 	///
 	/// [request]: Request
 	fn sequence(&self) -> Option<u16>;
+
+	/// Writes this `Event` as it is received by a client when it is sent with
+	/// a [`SendEvent` request]: with the [`SYNTHETIC_EVENT_FLAG`] set in its
+	/// code.
+	///
+	/// # Errors
+	/// Returns a [`WriteError`] if this `Event` could not be written.
+	///
+	/// [`SendEvent` request]: crate::x11::request::SendEvent
+	fn write_synthetic_to(&self, buf: &mut impl BufMut) -> WriteResult
+	where
+		Self: Sized,
+	{
+		let mut bytes = Vec::with_capacity(self.x11_size());
+		self.write_to(&mut bytes)?;
+
+		bytes[0] |= SYNTHETIC_EVENT_FLAG;
+		buf.put_slice(&bytes);
+
+		Ok(())
+	}
+
+	/// Reads this `Event`, including its code, recording whether it was sent
+	/// with a [`SendEvent` request].
+	///
+	/// Unlike [`read_from`], this reads the `Event`'s code too.
+	///
+	/// # Errors
+	/// Returns [`ReadError::UnrecognizedDiscriminant`] if the code, ignoring
+	/// the [`SYNTHETIC_EVENT_FLAG`], is not this `Event`'s [`CODE`], or any
+	/// [`ReadError`] generated while reading the rest of the `Event`.
+	///
+	/// [`SendEvent` request]: crate::x11::request::SendEvent
+	/// [`read_from`]: Readable::read_from
+	/// [`CODE`]: Event::CODE
+	fn read_received(buf: &mut impl Buf) -> ReadResult<ReceivedEvent<Self>>
+	where
+		Self: Sized,
+	{
+		let code = u8::read_from(buf)?;
+
+		if code & !SYNTHETIC_EVENT_FLAG != Self::CODE {
+			return Err(ReadError::UnrecognizedDiscriminant(usize::from(code)));
+		}
+
+		Ok(ReceivedEvent {
+			event: Self::read_from(buf)?,
+			synthetic: code & SYNTHETIC_EVENT_FLAG != 0,
+		})
+	}
+}
+
+/// The bit set in the code of an [`Event`] if it was sent with a
+/// [`SendEvent` request].
+///
+/// [`SendEvent` request]: crate::x11::request::SendEvent
+pub const SYNTHETIC_EVENT_FLAG: u8 = 0x80;
+
+/// An [`Event`] received by a client, along with whether it was sent with a
+/// [`SendEvent` request].
+///
+/// See [`Event::read_received`] for more information.
+///
+/// [`SendEvent` request]: crate::x11::request::SendEvent
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ReceivedEvent<E> {
+	/// The [`Event`] which was received.
+	pub event: E,
+	/// Whether the [`Event`] was sent with a [`SendEvent` request].
+	///
+	/// [`SendEvent` request]: crate::x11::request::SendEvent
+	pub synthetic: bool,
+}
+
+impl<E> ReceivedEvent<E> {
+	/// Whether the [`Event`] was sent by a client with a [`SendEvent`
+	/// request], rather than generated by the X server.
+	///
+	/// [`SendEvent` request]: crate::x11::request::SendEvent
+	#[must_use]
+	pub const fn is_synthetic(&self) -> bool {
+		self.synthetic
+	}
+
+	/// Unwraps the received [`Event`].
+	#[must_use]
+	pub fn into_event(self) -> E {
+		self.event
+	}
 }
 
 /// An error sent from the X server to an X client in response to a failed
 /// [request].
 ///
 /// [request]: Request
+#[doc(alias = "X11Error")]
 #[doc(notable_trait)]
-pub trait Error: X11Size + Readable {
+pub trait Error: Message + X11Size + Readable {
 	/// The code uniquely identifying this `Error` (among other `Error`s).
 	///
 	/// Error codes 128 to 255 are reserved for extensions.
@@ -369,4 +702,196 @@ pub trait Error: X11Size + Readable {
 	///
 	/// [major opcode]: Request::MAJOR_OPCODE
 	fn major_opcode(&self) -> u8;
+
+	/// The value in the [request] which caused this `Error`, if this `Error`
+	/// carries one.
+	///
+	/// For example, for a [`Window` error] this is the ID which did not refer
+	/// to a defined [window], and for a [`Value` error] it is the value which
+	/// fell outside of the accepted range.
+	///
+	/// [request]: Request
+	/// [window]: crate::Window
+	///
+	/// [`Window` error]: error::Window
+	/// [`Value` error]: error::Value
+	fn bad_value(&self) -> Option<u32> {
+		None
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use xrbk::InfallibleWritable;
+
+	use super::*;
+	use crate::{
+		set::WindowConfig,
+		unit::Px,
+		x11::{
+			event::{self, ClientMessage, ClientMessageData, Expose},
+			reply,
+			request,
+		},
+		Region,
+		VisibilityState,
+		Window,
+	};
+
+	#[test]
+	fn test_received_event() {
+		let message = ClientMessage {
+			sequence: 7,
+			window: Window::new(0x0020_0001),
+			r#type: crate::atom::WM_NAME,
+			data: ClientMessageData::I32([1, -2, 3, -4, 5]),
+		};
+		let mut bytes = vec![];
+		message.write_to(&mut bytes).unwrap();
+
+		// Events sent with `SendEvent` have the most significant bit of their
+		// code set.
+		let mut synthetic = vec![];
+		message.write_synthetic_to(&mut synthetic).unwrap();
+		assert_eq!(synthetic[0], ClientMessage::CODE | SYNTHETIC_EVENT_FLAG);
+		assert_eq!(&synthetic[1..], &bytes[1..]);
+
+		let received = ClientMessage::read_received(&mut &synthetic[..]).unwrap();
+		assert!(received.is_synthetic());
+		assert_eq!(received.into_event(), message);
+
+		let received = ClientMessage::read_received(&mut &bytes[..]).unwrap();
+		assert!(!received.is_synthetic());
+		assert_eq!(received.event, message);
+
+		assert!(Expose::read_received(&mut &bytes[..]).is_err());
+	}
+
+	#[test]
+	fn test_message_kind() {
+		let messages: [(&dyn Message, &str, MessageKind); 5] = [
+			(
+				&request::MapWindow {
+					target: Window::new(1),
+				},
+				"MapWindow",
+				MessageKind::Request {
+					major_opcode: 8,
+					minor_opcode: None,
+				},
+			),
+			(
+				&request::NoOp { unused_units: 0 },
+				"NoOp",
+				MessageKind::Request {
+					major_opcode: 127,
+					minor_opcode: None,
+				},
+			),
+			(
+				&reply::QueryColors {
+					sequence: 1,
+					colors: vec![],
+				},
+				"QueryColors",
+				MessageKind::Reply {
+					major_opcode: 91,
+					minor_opcode: None,
+				},
+			),
+			(
+				&event::Visibility {
+					sequence: 1,
+					window: Window::new(1),
+					visibility: VisibilityState::Unobscured,
+				},
+				"Visibility",
+				MessageKind::Event { code: 15 },
+			),
+			(
+				&error::Match {
+					sequence: 1,
+					minor_opcode: 0,
+					major_opcode: 8,
+				},
+				"Match",
+				MessageKind::Error { code: 8 },
+			),
+		];
+
+		for (message, name, kind) in messages {
+			assert_eq!(message.name(), name);
+			assert_eq!(message.kind(), kind);
+		}
+	}
+
+	#[test]
+	fn test_raw_request_queue() {
+		let queue: Vec<Box<dyn RawRequest>> = vec![
+			Box::new(request::MapWindow {
+				target: Window::new(1),
+			}),
+			Box::new(request::NoOp { unused_units: 2 }),
+		];
+
+		let mut bytes = Vec::new();
+		for request in &queue {
+			request.write_request_to(&mut bytes).unwrap();
+		}
+
+		assert_eq!(
+			queue
+				.iter()
+				.map(|request| (request.major_opcode(), request.request_length()))
+				.collect::<Vec<_>>(),
+			[(8, 2), (127, 3)],
+		);
+		assert_eq!(bytes.len(), (2 + 3) * 4);
+		assert_eq!((bytes[0], bytes[8]), (8, 127));
+	}
+
+	#[test]
+	fn test_write_checked() {
+		let request = request::NoOp { unused_units: 10 };
+		let mut bytes = Vec::new();
+
+		assert!(matches!(
+			request.write_checked(&mut bytes, Some(10)),
+			Err(WriteError::RequestTooLarge {
+				needed: 11,
+				allowed: 10,
+			}),
+		));
+		assert!(bytes.is_empty());
+
+		request.write_checked(&mut bytes, Some(11)).unwrap();
+		request.write_checked(&mut bytes, None).unwrap();
+		assert_eq!(bytes.len(), 2 * 11 * 4);
+	}
+
+	#[test]
+	fn test_write_infallible() {
+		let mut config = WindowConfig::builder();
+		config.x(Px(-10));
+
+		let request = request::ConfigureWindow {
+			target: Window::new(0x0020_0001),
+			config: config.build(),
+		};
+		let event = event::Expose {
+			sequence: 1,
+			window: Window::new(0x0020_0001),
+			region: Region::new(Px(0), Px(0), Px(64), Px(64)),
+			count: 0,
+		};
+
+		let mut bytes = Vec::new();
+		request.write_infallible(&mut bytes);
+		event.write_infallible(&mut bytes);
+
+		let mut expected = Vec::new();
+		request.write_to(&mut expected).unwrap();
+		event.write_to(&mut expected).unwrap();
+		assert_eq!(bytes, expected);
+	}
 }
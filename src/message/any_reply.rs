@@ -0,0 +1,429 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::{
+	any::Any,
+	fmt::{self, Debug, Formatter},
+	marker::PhantomData,
+	ops::Range,
+};
+
+use thiserror::Error;
+
+use super::{Message, MessageKind, Reply, Request};
+
+/// A [reply] which has been type-erased so that it can be returned by a
+/// connection before it is known which type of [reply] was expected.
+///
+/// The original [reply] can be recovered with [`downcast()`], usually by the
+/// [`ExpectedReply`] for the [request] which generated it.
+///
+/// [reply]: Reply
+/// [request]: Request
+///
+/// [`downcast()`]: AnyReply::downcast
+pub struct AnyReply {
+	reply: Box<dyn ErasedReply>,
+}
+
+/// A [`Reply`] which can be stored in an [`AnyReply`].
+trait ErasedReply: Any + Message + Debug + Send {
+	fn sequence(&self) -> u16;
+}
+
+impl<Rep: Reply + Debug + Send + 'static> ErasedReply for Rep {
+	fn sequence(&self) -> u16 {
+		Reply::sequence(self)
+	}
+}
+
+impl AnyReply {
+	/// Erases the type of the given `reply`.
+	pub fn new<Rep: Reply + Debug + Send + 'static>(reply: Rep) -> Self {
+		Self {
+			reply: Box::new(reply),
+		}
+	}
+
+	/// The sequence number of the [request] which generated this [reply].
+	///
+	/// See [`Reply::sequence`] for more information.
+	///
+	/// [request]: Request
+	/// [reply]: Reply
+	#[must_use]
+	pub fn sequence(&self) -> u16 {
+		self.reply.sequence()
+	}
+
+	/// Returns whether this is a [reply] of type `Rep`.
+	///
+	/// [reply]: Reply
+	#[must_use]
+	pub fn is<Rep: Reply + 'static>(&self) -> bool {
+		(&*self.reply as &dyn Any).is::<Rep>()
+	}
+
+	/// Returns a reference to the [reply] if it is of type `Rep`.
+	///
+	/// [reply]: Reply
+	#[must_use]
+	pub fn downcast_ref<Rep: Reply + 'static>(&self) -> Option<&Rep> {
+		(&*self.reply as &dyn Any).downcast_ref()
+	}
+
+	/// Recovers the [reply] if it is of type `Rep`.
+	///
+	/// # Errors
+	/// Returns this `AnyReply` unchanged if the [reply] is not of type `Rep`.
+	///
+	/// [reply]: Reply
+	#[allow(clippy::missing_panics_doc, reason = "the type is checked first")]
+	pub fn downcast<Rep: Reply + 'static>(self) -> Result<Rep, Self> {
+		if self.is::<Rep>() {
+			let reply: Box<dyn Any> = self.reply;
+
+			Ok(*reply.downcast().expect("the type was just checked"))
+		} else {
+			Err(self)
+		}
+	}
+}
+
+impl Message for AnyReply {
+	fn name(&self) -> &'static str {
+		self.reply.name()
+	}
+
+	fn kind(&self) -> MessageKind {
+		self.reply.kind()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		self.reply.fields()
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		self.reply.unused()
+	}
+}
+
+impl Debug for AnyReply {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		self.reply.fmt(f)
+	}
+}
+
+/// The [reply] expected for a [request] which has been sent.
+///
+/// This pairs the sequence number of the [request] with the type of its
+/// [reply], so that an [`AnyReply`] received from the X server can be
+/// [resolved] back into that type.
+///
+/// [reply]: Reply
+/// [request]: Request
+///
+/// [resolved]: ExpectedReply::resolve
+#[doc(alias = "Cookie")]
+#[derive(Debug, Hash, PartialEq, Eq)]
+#[must_use = "a request's reply will be ignored if its `ExpectedReply` is dropped"]
+pub struct ExpectedReply<Rep> {
+	sequence: u64,
+	_reply: PhantomData<fn() -> Rep>,
+}
+
+/// The name used for an [`ExpectedReply`] by XCB and libraries based on it.
+pub type Cookie<Rep> = ExpectedReply<Rep>;
+
+/// A connection to an X server from which the [replies] to sent [requests] can
+/// be received.
+///
+/// XRB does not connect to X servers itself; this trait allows libraries which
+/// do to resolve [`ExpectedReply`]s in the same way.
+///
+/// [replies]: Reply
+/// [requests]: Request
+pub trait ReplySource {
+	/// The error generated if a [reply] could not be received.
+	///
+	/// This includes an X11 [error] being generated instead of a [reply].
+	///
+	/// [reply]: Reply
+	/// [error]: super::Error
+	type Error;
+
+	/// Waits for the [reply] to the [request] sent with the given full
+	/// `sequence` number.
+	///
+	/// # Errors
+	/// Returns [`Self::Error`] if the [reply] could not be received.
+	///
+	/// [reply]: Reply
+	/// [request]: Request
+	fn wait_for_reply(&mut self, sequence: u64) -> Result<AnyReply, Self::Error>;
+
+	/// Returns the [reply] to the [request] sent with the given full
+	/// `sequence` number if it has already been received.
+	///
+	/// # Errors
+	/// Returns [`Self::Error`] if the [reply] could not be received.
+	///
+	/// [reply]: Reply
+	/// [request]: Request
+	fn poll_for_reply(&mut self, sequence: u64) -> Result<Option<AnyReply>, Self::Error>;
+}
+
+/// An error generated when an [`ExpectedReply`] could not be resolved by a
+/// [`ReplySource`].
+#[derive(Debug, Error)]
+pub enum ResolveError<E> {
+	/// The [`ReplySource`] could not receive the [reply].
+	///
+	/// [reply]: Reply
+	#[error("failed to receive the reply: {0}")]
+	Source(E),
+
+	/// The [`ReplySource`] returned a [reply] which was not the expected one.
+	///
+	/// [reply]: Reply
+	#[error("received an unexpected `{}` reply", .0.name())]
+	Unexpected(AnyReply),
+}
+
+/// Creates an [`ExpectedReply`] for the given `request`, which was sent with
+/// the given full `sequence` number.
+///
+/// The full `sequence` number is that returned by
+/// [`SequenceTracker::record_request`] when the `request` was sent.
+///
+/// [`SequenceTracker::record_request`]: super::SequenceTracker::record_request
+pub const fn expect_reply<Req>(_request: &Req, sequence: u64) -> ExpectedReply<Req::Reply>
+where
+	Req: Request,
+	Req::Reply: Reply + 'static,
+{
+	ExpectedReply::new(sequence)
+}
+
+impl<Rep: Reply + 'static> ExpectedReply<Rep> {
+	/// Creates an `ExpectedReply` for a [request] sent with the given full
+	/// `sequence` number.
+	///
+	/// [request]: Request
+	pub const fn new(sequence: u64) -> Self {
+		Self {
+			sequence,
+			_reply: PhantomData,
+		}
+	}
+
+	/// The full sequence number of the [request] which this [reply] is
+	/// expected for.
+	///
+	/// [request]: Request
+	/// [reply]: Reply
+	#[must_use]
+	pub const fn sequence(&self) -> u64 {
+		self.sequence
+	}
+
+	/// Returns whether the given `reply` is the [reply] this `ExpectedReply`
+	/// is expecting.
+	///
+	/// [reply]: Reply
+	#[must_use]
+	pub fn matches(&self, reply: &AnyReply) -> bool {
+		#[allow(clippy::cast_possible_truncation, reason = "truncation is intended")]
+		let sequence = self.sequence as u16;
+
+		reply.sequence() == sequence && reply.is::<Rep>()
+	}
+
+	/// Recovers the expected [reply] from the given `reply`.
+	///
+	/// # Errors
+	/// Returns the `reply` unchanged if it is not the [reply] this
+	/// `ExpectedReply` is expecting.
+	///
+	/// [reply]: Reply
+	pub fn resolve(self, reply: AnyReply) -> Result<Rep, AnyReply> {
+		if self.matches(&reply) {
+			reply.downcast()
+		} else {
+			Err(reply)
+		}
+	}
+
+	/// Waits for the expected [reply] to be received by the given `source`.
+	///
+	/// # Errors
+	/// Returns a [`ResolveError`] if the `source` could not receive the
+	/// [reply], or if it returned a different [reply].
+	///
+	/// [reply]: Reply
+	pub fn wait<Source: ReplySource>(
+		self, source: &mut Source,
+	) -> Result<Rep, ResolveError<Source::Error>> {
+		let reply = source
+			.wait_for_reply(self.sequence)
+			.map_err(ResolveError::Source)?;
+
+		self.resolve(reply).map_err(ResolveError::Unexpected)
+	}
+
+	/// Returns the expected [reply] if it has already been received by the
+	/// given `source`.
+	///
+	/// If it has not been received yet, this `ExpectedReply` is returned so
+	/// that it can be polled again.
+	///
+	/// # Errors
+	/// Returns a [`ResolveError`] if the `source` could not receive the
+	/// [reply], or if it returned a different [reply].
+	///
+	/// [reply]: Reply
+	pub fn poll<Source: ReplySource>(
+		self, source: &mut Source,
+	) -> Result<Result<Rep, Self>, ResolveError<Source::Error>> {
+		match source
+			.poll_for_reply(self.sequence)
+			.map_err(ResolveError::Source)?
+		{
+			Some(reply) => self
+				.resolve(reply)
+				.map(Ok)
+				.map_err(ResolveError::Unexpected),
+			None => Ok(Err(self)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::collections::HashMap;
+
+	use xrbk::{Readable, Writable};
+
+	use super::*;
+	use crate::{
+		x11::{reply, request},
+		Atom,
+		FocusWindow,
+		String8,
+		Window,
+	};
+
+	#[test]
+	fn test_expected_reply_resolve() {
+		let request = request::GetAtom {
+			no_creation: true,
+			name: String8::from_latin1("WM_NAME").unwrap(),
+		};
+		let expected = expect_reply(&request, 0x0001_0005);
+
+		let reply = AnyReply::new(reply::GetAtom {
+			sequence: 5,
+			atom: Some(Atom::new(39)),
+		});
+		assert_eq!(reply.name(), "GetAtom");
+		assert!(reply.downcast_ref::<reply::QueryColors>().is_none());
+
+		// A reply with the right sequence number but the wrong type is not
+		// the expected reply.
+		let other = AnyReply::new(reply::QueryColors {
+			sequence: 5,
+			colors: vec![],
+		});
+		assert!(!expected.matches(&other));
+
+		let reply = expected.resolve(reply).unwrap();
+		assert_eq!(reply.atom, Some(Atom::new(39)));
+	}
+
+	/// A [`ReplySource`] which has already received some replies.
+	struct Received(HashMap<u64, AnyReply>);
+
+	impl ReplySource for Received {
+		type Error = &'static str;
+
+		fn wait_for_reply(&mut self, sequence: u64) -> Result<AnyReply, Self::Error> {
+			self.0
+				.remove(&sequence)
+				.ok_or("the reply would never arrive")
+		}
+
+		fn poll_for_reply(&mut self, sequence: u64) -> Result<Option<AnyReply>, Self::Error> {
+			Ok(self.0.remove(&sequence))
+		}
+	}
+
+	#[test]
+	fn test_cookie_wait() {
+		let request = request::GetFocus;
+		let cookie: Cookie<reply::GetFocus> = expect_reply(&request, 7);
+
+		let mut source = Received(HashMap::new());
+		let cookie = cookie.poll(&mut source).unwrap().unwrap_err();
+
+		source.0.insert(
+			7,
+			AnyReply::new(reply::GetAtom {
+				sequence: 7,
+				atom: None,
+			}),
+		);
+		assert!(matches!(
+			cookie.wait(&mut source),
+			Err(ResolveError::Unexpected(_)),
+		));
+
+		let cookie = expect_reply(&request, 8);
+		assert!(matches!(
+			cookie.wait(&mut source),
+			Err(ResolveError::Source("the reply would never arrive")),
+		));
+	}
+
+	#[test]
+	fn test_cookie_resolves_decoded_reply() {
+		let request = request::GetFocus;
+		let focus = reply::GetFocus {
+			sequence: 7,
+			revert_to: request::RevertFocus::Parent,
+			focus: FocusWindow::Other(Window::new(0x0020_0001)),
+		};
+
+		// The reply is decoded from the bytes sent by the X server, not passed
+		// through as the same value.
+		let mut bytes = vec![];
+		focus.write_to(&mut bytes).unwrap();
+		// The first byte, which identifies the message as a reply, is read
+		// before the reply's reader is called.
+		let decoded = reply::GetFocus::read_from(&mut &bytes[1..]).unwrap();
+
+		let mut source = Received(HashMap::from([(0x0001_0007, AnyReply::new(decoded))]));
+
+		let cookie: Cookie<reply::GetFocus> = expect_reply(&request, 0x0001_0007);
+		let reply = cookie.wait(&mut source).unwrap();
+		assert_eq!(reply, focus);
+		assert_eq!(reply.sequence, 7);
+
+		let decoded = reply::GetFocus::read_from(&mut &bytes[1..]).unwrap();
+		source.0.insert(0x0001_0008, AnyReply::new(decoded));
+
+		// The sequence number in the reply is the low 16 bits of the full
+		// sequence number, so a reply to a different request is not resolved.
+		let cookie: Cookie<reply::GetFocus> = expect_reply(&request, 0x0001_0008);
+		assert!(matches!(
+			cookie.poll(&mut source),
+			Err(ResolveError::Unexpected(_)),
+		));
+
+		let decoded = reply::GetFocus::read_from(&mut &bytes[1..]).unwrap();
+		source.0.insert(0x0002_0007, AnyReply::new(decoded));
+
+		let cookie: Cookie<reply::GetFocus> = expect_reply(&request, 0x0002_0007);
+		assert_eq!(cookie.poll(&mut source).unwrap().unwrap(), focus);
+	}
+}
@@ -0,0 +1,428 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use xrbk::{BufMut, Writable, WriteResult, X11Size};
+
+/// A decoded message which remembers the exact bytes it was decoded from.
+///
+/// # Canonical encoding
+/// XRB always writes unused bytes as zero, and ignores the value of unused
+/// bytes when reading a message. That means decoding a message and then
+/// encoding it again reproduces the original bytes exactly if, and only if,
+/// every unused byte in the original was zero. Such messages are said to be
+/// _canonical_.
+///
+/// The X11 protocol does not require unused bytes to be zero, and some
+/// clients and servers do leave garbage in them. An `Opaque` message keeps
+/// the values of any bytes which its canonical encoding does not reproduce,
+/// and restores them when it is written, so that a proxy can forward the
+/// messages passing through it without altering them.
+///
+/// If the message is modified such that its encoding changes length, the
+/// preserved bytes can no longer be matched to their positions, and the
+/// canonical encoding is written instead. Otherwise, a preserved byte is only
+/// restored if the modification left its canonical encoding unchanged.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Opaque<M> {
+	message: M,
+	original: Vec<u8>,
+
+	/// The positions of the bytes in `original` which differ from the
+	/// canonical encoding of `message`, along with the canonical and original
+	/// values of those bytes.
+	noncanonical: Vec<(usize, u8, u8)>,
+	/// Whether `original` was the canonical encoding of `message`.
+	canonical: bool,
+}
+
+/// Encodes `message` in its canonical form.
+fn canonical<M: Writable>(message: &M) -> Result<Vec<u8>, xrbk::WriteError> {
+	let mut bytes = Vec::with_capacity(message.x11_size());
+	message.write_to(&mut bytes)?;
+
+	Ok(bytes)
+}
+
+impl<M: Writable> Opaque<M> {
+	/// Creates a new `Opaque` message from a decoded `message` and the
+	/// `original` bytes it was decoded from.
+	///
+	/// The `original` bytes must contain the entire message, including its
+	/// header, and nothing more.
+	///
+	/// # Errors
+	/// Returns a [`WriteError`] if the `message` could not be encoded.
+	///
+	/// [`WriteError`]: xrbk::WriteError
+	pub fn new(message: M, original: Vec<u8>) -> Result<Self, xrbk::WriteError> {
+		let encoded = canonical(&message)?;
+
+		let noncanonical: Vec<_> = encoded
+			.iter()
+			.zip(&original)
+			.enumerate()
+			.filter_map(|(index, (encoded, original))| {
+				(encoded != original).then_some((index, *encoded, *original))
+			})
+			.collect();
+		let canonical = noncanonical.is_empty() && encoded.len() == original.len();
+
+		Ok(Self {
+			message,
+			original,
+
+			noncanonical,
+			canonical,
+		})
+	}
+}
+
+impl<M> Opaque<M> {
+	/// Returns a reference to the decoded message.
+	#[must_use]
+	pub const fn message(&self) -> &M {
+		&self.message
+	}
+
+	/// Returns a mutable reference to the decoded message.
+	///
+	/// Bytes which were not canonical in the original message are still
+	/// restored when the modified message is written, provided its length
+	/// has not changed and the modification did not change those bytes'
+	/// canonical encoding.
+	#[must_use]
+	pub const fn message_mut(&mut self) -> &mut M {
+		&mut self.message
+	}
+
+	/// Unwraps the decoded message, discarding the original bytes.
+	#[must_use]
+	pub fn into_message(self) -> M {
+		self.message
+	}
+
+	/// Returns the original bytes the message was decoded from.
+	#[must_use]
+	pub fn original(&self) -> &[u8] {
+		&self.original
+	}
+
+	/// Whether the original bytes were the canonical encoding of the message.
+	///
+	/// See [canonical encoding] for more information.
+	///
+	/// [canonical encoding]: Opaque#canonical-encoding
+	#[must_use]
+	pub const fn is_canonical(&self) -> bool {
+		self.canonical
+	}
+}
+
+impl<M: X11Size> X11Size for Opaque<M> {
+	fn x11_size(&self) -> usize {
+		self.message.x11_size()
+	}
+}
+
+impl<M: Writable> Writable for Opaque<M> {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		let mut bytes = canonical(&self.message)?;
+
+		if bytes.len() == self.original.len() {
+			for &(index, canonical, original) in &self.noncanonical {
+				// If the byte's canonical encoding has changed, it belongs to a
+				// field which was modified, so the new value is kept.
+				if bytes[index] == canonical {
+					bytes[index] = original;
+				}
+			}
+		}
+
+		buf.put_slice(&bytes);
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use xrbk::Readable;
+
+	use super::*;
+	use crate::{
+		set::WindowConfig,
+		test_util::round_trip,
+		unit::Px,
+		x11::{error, event, registry, registry::Entry, reply, request, request::RevertFocus},
+		Char8,
+		CurrentableTime,
+		FocusWindow,
+		Region,
+		String8,
+		Window,
+	};
+
+	/// Asserts that every message in `entries` can be decoded and encoded
+	/// again to produce the same bytes.
+	///
+	/// Each message is decoded from the bytes given for it in `samples`, if
+	/// there are any, or otherwise from the shortest message produced by
+	/// `zeroed` which it can be decoded from. `zeroed` is given the message's
+	/// code and a length in 4-byte units, and returns a message of that length
+	/// with every byte other than its header set to zero.
+	fn assert_registered_round_trip(
+		entries: &[Entry], samples: &[(&str, &[u8])], zeroed: impl Fn(u8, usize) -> Vec<u8>,
+	) {
+		for entry in entries {
+			let (bytes, encoded) = match samples.iter().find(|(name, _)| *name == entry.name) {
+				Some((_, bytes)) => (
+					bytes.to_vec(),
+					(entry.reencode)(bytes)
+						.unwrap_or_else(|error| panic!("failed to decode {}: {error}", entry.name)),
+				),
+
+				None => (1..=64)
+					.map(|units| zeroed(entry.code, units))
+					.find_map(|bytes| {
+						let encoded = (entry.reencode)(&bytes).ok()?;
+
+						Some((bytes, encoded))
+					})
+					.unwrap_or_else(|| panic!("failed to decode {} from zeroes", entry.name)),
+			};
+
+			assert_eq!(encoded, bytes, "{} did not round-trip", entry.name);
+		}
+	}
+
+	fn name(name: &[u8]) -> String8 {
+		String8::from(name.iter().map(|&char| Char8(char)).collect::<Vec<_>>())
+	}
+
+	#[test]
+	fn test_request_round_trip() {
+		assert_registered_round_trip(
+			registry::REQUESTS,
+			&[
+				// A `format` of 8.
+				(
+					"ModifyProperty",
+					&[
+						18, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0,
+					],
+				),
+				// A `ClientMessage` event with a `format` of 32.
+				(
+					"SendEvent",
+					&[
+						25, 0, 0, 11, 0, 0, 0, 0, 0, 0, 0, 0, 33, 32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+						0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+					],
+				),
+				// A range of 1 keycode, starting at keycode 8.
+				("GetKeyboardMapping", &[101, 0, 0, 2, 8, 1, 0, 0]),
+				// An IPv4 address, which is 4 bytes long.
+				("ChangeHosts", &[109, 0, 0, 3, 0, 0, 0, 4, 127, 0, 0, 1]),
+			],
+			|code, units| {
+				let mut bytes = vec![0; units * 4];
+				bytes[0] = code;
+				bytes[2..4].copy_from_slice(&u16::try_from(units).unwrap().to_be_bytes());
+
+				bytes
+			},
+		);
+
+		round_trip(
+			&request::MapWindow {
+				target: Window::new(0x0020_0001),
+			},
+			1,
+		);
+
+		round_trip(
+			&request::GetAtom {
+				no_creation: true,
+				name: name(b"WM_NAME"),
+			},
+			1,
+		);
+
+		let mut config = WindowConfig::builder();
+		config.x(Px(-10)).width(Px(640));
+
+		round_trip(
+			&request::ConfigureWindow {
+				target: Window::new(0x0020_0001),
+				config: config.build(),
+			},
+			1,
+		);
+
+		round_trip(
+			&request::SetFocus {
+				revert_to: RevertFocus::Parent,
+				new_focus: FocusWindow::Other(Window::new(0x0020_0001)),
+				time: CurrentableTime::CurrentTime,
+			},
+			1,
+		);
+	}
+
+	#[test]
+	fn test_reply_round_trip() {
+		assert_registered_round_trip(
+			registry::REPLIES,
+			// A `class` of `InputOutput`.
+			&[(
+				"GetWindowAttributes",
+				&[
+					1, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+					0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+				],
+			)],
+			// Replies are at least 32 bytes long, and their length counts the
+			// units after those 32 bytes.
+			|_, units| {
+				let mut bytes = vec![0; 32 + (units - 1) * 4];
+				bytes[0] = 1;
+				bytes[4..8].copy_from_slice(&u32::try_from(units - 1).unwrap().to_be_bytes());
+
+				bytes
+			},
+		);
+
+		round_trip(
+			&reply::GetFocus {
+				sequence: 42,
+				revert_to: RevertFocus::CursorRoot,
+				focus: FocusWindow::None,
+			},
+			1,
+		);
+	}
+
+	#[test]
+	fn test_event_round_trip() {
+		assert_registered_round_trip(
+			registry::EVENTS,
+			// A `format` of 32.
+			&[(
+				"ClientMessage",
+				&[
+					33, 32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+					0, 0, 0, 0, 0, 0,
+				],
+			)],
+			|code, _| {
+				let mut bytes = vec![0; 32];
+				bytes[0] = code;
+
+				bytes
+			},
+		);
+
+		round_trip(
+			&event::Expose {
+				sequence: 7,
+				window: Window::new(0x0020_0001),
+				region: Region {
+					x: Px(1),
+					y: Px(2),
+					width: Px(3),
+					height: Px(4),
+				},
+				count: 0,
+			},
+			1,
+		);
+	}
+
+	#[test]
+	fn test_error_round_trip() {
+		assert_registered_round_trip(registry::ERRORS, &[], |code, _| {
+			let mut bytes = vec![0; 32];
+			bytes[1] = code;
+
+			bytes
+		});
+
+		round_trip(
+			&error::Value {
+				sequence: 3,
+				invalid_value: [0, 0, 1, 0],
+				minor_opcode: 0,
+				major_opcode: 16,
+			},
+			2,
+		);
+	}
+
+	#[test]
+	fn test_opaque_preserves_noncanonical_bytes() {
+		let mut original = round_trip(
+			&request::GetAtom {
+				no_creation: false,
+				name: name(b"WM_NAME"),
+			},
+			1,
+		);
+		// Fill in the unused bytes after the name's length and after the name.
+		original[6] = 0xaa;
+		original[7] = 0xbb;
+		original[15] = 0xcc;
+
+		let decoded = request::GetAtom::read_from(&mut &original[1..]).unwrap();
+		assert_ne!(canonical(&decoded).unwrap(), original);
+
+		let mut opaque = Opaque::new(decoded, original.clone()).unwrap();
+		assert!(!opaque.is_canonical());
+		assert_eq!(canonical(&opaque).unwrap(), original);
+
+		opaque.message_mut().no_creation = true;
+		let modified = canonical(&opaque).unwrap();
+		assert_eq!(modified[1], 1);
+		assert_eq!(&modified[2..], &original[2..]);
+	}
+
+	#[test]
+	fn test_opaque_keeps_modified_noncanonical_bytes() {
+		let mut original = round_trip(
+			&request::GetAtom {
+				no_creation: true,
+				name: name(b"WM_NAME"),
+			},
+			1,
+		);
+		// Encode `no_creation` as `2` rather than the canonical `1`.
+		original[1] = 2;
+
+		let decoded = request::GetAtom::read_from(&mut &original[1..]).unwrap();
+		assert!(decoded.no_creation);
+
+		let mut opaque = Opaque::new(decoded, original.clone()).unwrap();
+		assert!(!opaque.is_canonical());
+		assert_eq!(canonical(&opaque).unwrap(), original);
+
+		opaque.message_mut().no_creation = false;
+		let modified = canonical(&opaque).unwrap();
+		assert_eq!(modified[1], 0);
+		assert_eq!(&modified[2..], &original[2..]);
+
+		opaque.message_mut().no_creation = true;
+		assert_eq!(canonical(&opaque).unwrap(), original);
+	}
+
+	#[test]
+	fn test_opaque_canonical() {
+		let message = request::MapWindow {
+			target: Window::new(0x0020_0001),
+		};
+		let original = canonical(&message).unwrap();
+
+		let opaque = Opaque::new(message, original.clone()).unwrap();
+		assert!(opaque.is_canonical());
+		assert_eq!(canonical(&opaque).unwrap(), original);
+	}
+}
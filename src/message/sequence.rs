@@ -0,0 +1,140 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+/// Keeps track of the full sequence numbers of [requests] sent and messages
+/// received on a connection.
+///
+/// Every [request] sent on a connection is implicitly assigned a sequence
+/// number, starting at `1` for the first [request]. [Replies], [events], and
+/// [errors] only carry the least significant 16 bits of the sequence number
+/// of the [request] they relate to, however, so that sequence number wraps
+/// around every 65536 [requests]. A `SequenceTracker` expands those truncated
+/// sequence numbers back into full 64-bit sequence numbers.
+///
+/// Call [`record_request()`] whenever a [request] is sent, and [`expand()`]
+/// whenever a message is received from the X server.
+///
+/// # Ambiguity
+/// The X server processes [requests] in order, so each message received
+/// relates to a [request] no earlier than the last message received, and no
+/// later than the last [request] sent. As long as fewer than 65536 [requests]
+/// are sent between two messages being received, the full sequence number is
+/// unambiguous.
+///
+/// [`record_request()`]: SequenceTracker::record_request
+/// [`expand()`]: SequenceTracker::expand
+///
+/// [requests]: super::Request
+/// [request]: super::Request
+/// [Replies]: super::Reply
+/// [events]: super::Event
+/// [errors]: super::Error
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SequenceTracker {
+	/// The full sequence number of the last [request] sent.
+	///
+	/// [request]: super::Request
+	last_sent: u64,
+	/// The full sequence number of the last message received.
+	last_received: u64,
+}
+
+impl SequenceTracker {
+	/// Creates a new `SequenceTracker` for a connection on which no
+	/// [requests] have been sent.
+	///
+	/// [requests]: super::Request
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			last_sent: 0,
+			last_received: 0,
+		}
+	}
+
+	/// Records that a [request] has been sent, returning its full sequence
+	/// number.
+	///
+	/// [request]: super::Request
+	pub const fn record_request(&mut self) -> u64 {
+		self.last_sent += 1;
+
+		self.last_sent
+	}
+
+	/// The full sequence number of the last [request] sent.
+	///
+	/// This is `0` if no [requests][request] have been sent.
+	///
+	/// [request]: super::Request
+	#[must_use]
+	pub const fn last_sent(&self) -> u64 {
+		self.last_sent
+	}
+
+	/// The full sequence number of the last message received.
+	///
+	/// This is `0` if no messages have been received.
+	#[must_use]
+	pub const fn last_received(&self) -> u64 {
+		self.last_received
+	}
+
+	/// Expands the truncated `sequence` number of a received message into its
+	/// full sequence number, and records that message as received.
+	///
+	/// See [ambiguity] for the limitations of this expansion.
+	///
+	/// [ambiguity]: SequenceTracker#ambiguity
+	pub fn expand(&mut self, sequence: u16) -> u64 {
+		#[allow(clippy::cast_possible_truncation, reason = "truncation is intended")]
+		let after_received = sequence.wrapping_sub(self.last_received as u16);
+		let full = self.last_received + u64::from(after_received);
+
+		// If that is later than the last request sent, the message must relate
+		// to an earlier request than expected, so count back from the last
+		// request sent instead.
+		let full = if full > self.last_sent {
+			#[allow(clippy::cast_possible_truncation, reason = "truncation is intended")]
+			let before_sent = (self.last_sent as u16).wrapping_sub(sequence);
+
+			self.last_sent.saturating_sub(u64::from(before_sent))
+		} else {
+			full
+		};
+
+		self.last_received = full;
+
+		full
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_expand_wraps() {
+		let mut tracker = SequenceTracker::new();
+
+		for _ in 0..70_000 {
+			tracker.record_request();
+		}
+
+		assert_eq!(tracker.expand(10), 10);
+		assert_eq!(tracker.expand(10), 10);
+		assert_eq!(tracker.expand(65_535), 65_535);
+		assert_eq!(tracker.expand(0), 65_536);
+		assert_eq!(tracker.expand(4464), 70_000);
+	}
+
+	#[test]
+	fn test_expand_before_any_request() {
+		let mut tracker = SequenceTracker::new();
+
+		assert_eq!(tracker.expand(0), 0);
+		assert_eq!(tracker.record_request(), 1);
+		assert_eq!(tracker.expand(1), 1);
+	}
+}
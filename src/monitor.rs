@@ -0,0 +1,239 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A model of the monitors attached to a [screen], independent of the
+//! extension which reported them.
+//!
+//! Monitors may be listed by the RandR extension, either as monitors (from
+//! RandR 1.5) or as the CRTCs which drive them, or by the older Xinerama
+//! extension as screens. If neither extension is present, the whole [screen]
+//! can be treated as a single monitor. A [`Monitor`] can be constructed from
+//! the information given by each of these, so that multi-monitor code only
+//! has to handle one type.
+//!
+//! The RandR and Xinerama messages themselves are not implemented by XRB
+//! yet, so the constructors take the fields of their replies.
+//!
+//! [screen]: Screen
+
+use crate::{
+	unit::{Mm, Px},
+	visual::Screen,
+	Coords,
+	Rectangle,
+};
+
+/// The DPI which corresponds to a [`scale`] of `1.0`.
+///
+/// [`scale`]: Monitor::scale
+const BASE_DPI: f64 = 96.0;
+
+/// The number of millimeters in an inch.
+const MM_PER_INCH: f64 = 25.4;
+
+/// A monitor attached to a [screen].
+///
+/// See the [module-level documentation] for more information.
+///
+/// [screen]: Screen
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Monitor {
+	/// The name of the monitor, if it is known.
+	///
+	/// RandR monitors and outputs are named (such as `DP-1`); Xinerama
+	/// screens are not.
+	pub name: Option<String>,
+
+	/// The area of the root window which is shown on the monitor.
+	pub geometry: Rectangle,
+
+	/// Whether this is the primary monitor.
+	pub primary: bool,
+
+	/// The physical width and height of the monitor, if they are known.
+	pub physical_size: Option<(Mm<u32>, Mm<u32>)>,
+}
+
+impl Monitor {
+	/// Creates a `Monitor` covering the whole of the given `screen`.
+	///
+	/// This is the only monitor if neither the RandR nor the Xinerama
+	/// extension is present.
+	#[must_use]
+	pub fn from_screen(screen: &Screen) -> Self {
+		Self {
+			name: None,
+			geometry: Rectangle::new(Px(0), Px(0), screen.width_px, screen.height_px),
+			primary: true,
+
+			physical_size: Some((
+				screen.width_mm.map(u32::from),
+				screen.height_mm.map(u32::from),
+			)),
+		}
+	}
+
+	/// Creates a `Monitor` from a `ScreenInfo` in the reply to the Xinerama
+	/// extension's `QueryScreens` request.
+	///
+	/// Xinerama has no notion of a primary monitor, but by convention the
+	/// first screen (with a `screen_number` of zero) is treated as the
+	/// primary monitor.
+	#[must_use]
+	pub const fn from_xinerama(
+		screen_number: usize, x: i16, y: i16, width: u16, height: u16,
+	) -> Self {
+		Self {
+			name: None,
+			geometry: Rectangle::new(Px(x), Px(y), Px(width), Px(height)),
+			primary: screen_number == 0,
+
+			physical_size: None,
+		}
+	}
+
+	/// Creates a `Monitor` from a `MonitorInfo` in the reply to the RandR
+	/// extension's `GetMonitors` request.
+	///
+	/// The `name` is the name of the `MonitorInfo`'s `name` atom.
+	///
+	/// A `width_mm` or `height_mm` of zero means that the physical size of
+	/// the monitor is not known.
+	#[must_use]
+	pub fn from_randr_monitor(
+		name: impl Into<String>, primary: bool, geometry: Rectangle, width_mm: u32, height_mm: u32,
+	) -> Self {
+		Self {
+			name: Some(name.into()),
+			geometry,
+			primary,
+
+			physical_size: (width_mm != 0 && height_mm != 0)
+				.then_some((Mm(width_mm), Mm(height_mm))),
+		}
+	}
+
+	/// Creates a `Monitor` from the reply to the RandR extension's
+	/// `GetCrtcInfo` request for a CRTC driving the given `output`.
+	///
+	/// This is for X servers which support a version of RandR older than 1.5,
+	/// which has no `GetMonitors` request. The `geometry` is the CRTC's
+	/// position and size. The `output_name`, `width_mm`, and `height_mm` are
+	/// those in the reply to the `GetOutputInfo` request for the `output`, and
+	/// `primary` is whether the `output` is the one returned by the
+	/// `GetOutputPrimary` request.
+	///
+	/// A `width_mm` or `height_mm` of zero means that the physical size of
+	/// the monitor is not known.
+	#[must_use]
+	pub fn from_randr_crtc(
+		output_name: impl Into<String>, primary: bool, geometry: Rectangle, width_mm: u32,
+		height_mm: u32,
+	) -> Self {
+		Self::from_randr_monitor(output_name, primary, geometry, width_mm, height_mm)
+	}
+
+	/// Returns whether the given `coords`, relative to the root window, are
+	/// shown on this monitor.
+	#[must_use]
+	pub fn contains(&self, coords: Coords) -> bool {
+		let Rectangle {
+			x,
+			y,
+			width,
+			height,
+		} = self.geometry;
+
+		let (x, y) = (i32::from(x.0), i32::from(y.0));
+		let (coords_x, coords_y) = (i32::from(coords.x.0), i32::from(coords.y.0));
+
+		(x..x + i32::from(width.0)).contains(&coords_x)
+			&& (y..y + i32::from(height.0)).contains(&coords_y)
+	}
+
+	/// Returns the horizontal and vertical DPI (dots per inch) of the monitor,
+	/// if its physical size is known.
+	#[must_use]
+	pub fn dpi(&self) -> Option<(f64, f64)> {
+		let (Mm(width_mm), Mm(height_mm)) = self.physical_size?;
+
+		if width_mm == 0 || height_mm == 0 {
+			return None;
+		}
+
+		Some((
+			f64::from(self.geometry.width.0) * MM_PER_INCH / f64::from(width_mm),
+			f64::from(self.geometry.height.0) * MM_PER_INCH / f64::from(height_mm),
+		))
+	}
+
+	/// Returns a hint of how much content should be scaled on the monitor, if
+	/// its physical size is known.
+	///
+	/// This is the monitor's average DPI relative to 96 DPI, rounded to the
+	/// nearest quarter, and never less than `1.0`. Monitors often report
+	/// inaccurate physical sizes, so this is only a hint.
+	#[must_use]
+	pub fn scale(&self) -> Option<f64> {
+		let (horizontal, vertical) = self.dpi()?;
+		let scale = f64::midpoint(horizontal, vertical) / BASE_DPI;
+
+		Some(((scale * 4.0).round() / 4.0).max(1.0))
+	}
+}
+
+/// Returns the primary monitor among the given `monitors`, or the first
+/// monitor if none of them are primary.
+#[must_use]
+pub fn primary(monitors: &[Monitor]) -> Option<&Monitor> {
+	monitors
+		.iter()
+		.find(|monitor| monitor.primary)
+		.or_else(|| monitors.first())
+}
+
+/// Returns the first of the given `monitors` which shows the given `coords`,
+/// relative to the root window.
+#[must_use]
+pub fn monitor_at(monitors: &[Monitor], coords: Coords) -> Option<&Monitor> {
+	monitors.iter().find(|monitor| monitor.contains(coords))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_monitor_sources() {
+		let monitors = [
+			Monitor::from_xinerama(0, 0, 0, 1920, 1080),
+			Monitor::from_randr_monitor(
+				"DP-1",
+				true,
+				Rectangle::new(Px(1920), Px(0), Px(3840), Px(2160)),
+				600,
+				340,
+			),
+		];
+
+		assert_eq!(monitors[0].name, None);
+		assert_eq!(monitors[0].scale(), None);
+		assert_eq!(monitors[1].scale(), Some(1.75));
+
+		assert_eq!(primary(&monitors[..1]), Some(&monitors[0]));
+		assert_eq!(primary(&monitors[1..]), Some(&monitors[1]));
+
+		assert_eq!(
+			monitor_at(&monitors, Coords::new(Px(2000), Px(10))),
+			Some(&monitors[1])
+		);
+		assert_eq!(monitor_at(&monitors, Coords::new(Px(10), Px(1080))), None);
+
+		assert_eq!(
+			Monitor::from_randr_crtc("HDMI-1", false, monitors[0].geometry, 0, 0).physical_size,
+			None
+		);
+	}
+}
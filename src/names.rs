@@ -0,0 +1,250 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! [`FromStr`] and [`Display`] implementations for protocol enums and masks,
+//! for use in configuration files.
+//!
+//! Window manager and hotkey configuration files name things like
+//! [stack modes], [gravities], and [event masks]. With the `names` feature
+//! enabled, those types can be parsed from and displayed as their names, so
+//! that configuration parsers don't need their own tables of names.
+//!
+//! Enums are displayed as the name of their variant, such as `NorthWest`.
+//! Masks are displayed as the names of their flags separated by `|`, such as
+//! `SHIFT | MOD_4`, and an empty mask is displayed as an empty string.
+//!
+//! When parsing, names are matched ignoring ASCII case, underscores, hyphens,
+//! and spaces: `NorthWest`, `north_west`, and `north-west` are all parsed as
+//! [`WindowGravity::NorthWest`], and `Mod4` is parsed as
+//! [`ModifierMask::MOD_4`]. Mask flags may be separated by either `|` or `+`,
+//! such as `Mod4+Shift`.
+//!
+//! [stack modes]: StackMode
+//! [gravities]: WindowGravity
+//! [event masks]: EventMask
+
+use std::{
+	fmt::{Display, Formatter},
+	str::FromStr,
+};
+
+use thiserror::Error;
+
+use crate::{
+	visual::VisualClass,
+	x11::request::{
+		AllowEventsMode,
+		CirculateDirection,
+		ModifyPropertyMode,
+		RetainResourcesMode,
+		RevertFocus,
+	},
+	AnyModifierKeyMask,
+	BitGravity,
+	ColorChannelMask,
+	CursorEventMask,
+	DeviceEventMask,
+	EventMask,
+	FreezeMode,
+	GrabMode,
+	LedMask,
+	MaintainContents,
+	MapState,
+	ModifierKeyMask,
+	ModifierMask,
+	NamedFlags,
+	StackMode,
+	Toggle,
+	ToggleOrDefault,
+	VisibilityState,
+	WindowGravity,
+};
+
+/// An error generated when parsing a name which does not name any variant of
+/// an enum or flag of a mask.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+#[error("unknown {kind} name: {name:?}")]
+pub struct UnknownName {
+	/// The type which was being parsed.
+	pub kind: &'static str,
+	/// The name which was not recognized.
+	pub name: String,
+}
+
+/// Returns whether the given `name` matches the `canonical` name of a variant
+/// or flag, ignoring ASCII case, underscores, hyphens, and spaces.
+fn matches(name: &str, canonical: &str) -> bool {
+	let normalize = |name: &'_ str| {
+		name.chars()
+			.filter(|char| !matches!(char, '_' | '-' | ' '))
+			.map(|char| char.to_ascii_lowercase())
+			.collect::<Vec<_>>()
+	};
+
+	normalize(name) == normalize(canonical)
+}
+
+/// Implements [`FromStr`] and [`Display`] for enums with only unit variants.
+macro_rules! impl_enum_names {
+	($($Enum:ident { $($Variant:ident),+$(,)? })+) => {
+		$(
+			impl FromStr for $Enum {
+				type Err = UnknownName;
+
+				fn from_str(name: &str) -> Result<Self, Self::Err> {
+					$(
+						if matches(name.trim(), stringify!($Variant)) {
+							return Ok(Self::$Variant);
+						}
+					)+
+
+					Err(UnknownName {
+						kind: stringify!($Enum),
+						name: name.to_owned(),
+					})
+				}
+			}
+
+			impl Display for $Enum {
+				fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+					f.write_str(match self {
+						$(Self::$Variant => stringify!($Variant),)+
+					})
+				}
+			}
+		)+
+	};
+}
+
+/// Implements [`FromStr`] and [`Display`] for [masks with named flags].
+///
+/// [masks with named flags]: NamedFlags
+macro_rules! impl_mask_names {
+	($($Mask:ident),+$(,)?) => {
+		$(
+			impl FromStr for $Mask {
+				type Err = UnknownName;
+
+				fn from_str(names: &str) -> Result<Self, Self::Err> {
+					let mut mask = Self::empty();
+
+					if names.trim().is_empty() {
+						return Ok(mask);
+					}
+
+					for name in names.split(['|', '+']).map(str::trim) {
+						mask |= Self::FLAGS
+							.iter()
+							.find_map(|&(canonical, flag)| matches(name, canonical).then_some(flag))
+							.ok_or_else(|| UnknownName {
+								kind: Self::NAME,
+								name: name.to_owned(),
+							})?;
+					}
+
+					Ok(mask)
+				}
+			}
+
+			impl Display for $Mask {
+				fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+					let mut separator = "";
+
+					for &(name, flag) in Self::FLAGS {
+						if self.contains(flag) {
+							write!(f, "{separator}{name}")?;
+							separator = " | ";
+						}
+					}
+
+					Ok(())
+				}
+			}
+		)+
+	};
+}
+
+impl_enum_names! {
+	Toggle { Disabled, Enabled }
+	ToggleOrDefault { Disabled, Enabled, Default }
+	BitGravity {
+		Forget, NorthWest, North, NorthEast, West, Center, East, SouthWest, South, SouthEast,
+		Static,
+	}
+	WindowGravity {
+		Unmap, NorthWest, North, NorthEast, West, Center, East, SouthWest, South, SouthEast,
+		Static,
+	}
+	MaintainContents { Never, WhenMapped, Always }
+	MapState { Unmapped, Unviewable, Viewable }
+	VisibilityState { Unobscured, PartiallyObscured, FullyObscured }
+	GrabMode { Normal, Grab, Ungrab }
+	FreezeMode { Unfrozen, Frozen }
+	StackMode { Above, Below, TopIf, BottomIf, Opposite }
+	VisualClass { StaticGray, GrayScale, StaticColor, PseudoColor, TrueColor, DirectColor }
+	AllowEventsMode {
+		UnfreezeCursor, RefreezeCursor, ReplayCursor, UnfreezeKeyboard, RefreezeKeyboard,
+		ReplayKeyboard, UnfreezeBoth, RefreezeBoth,
+	}
+	RevertFocus { None, CursorRoot, Parent }
+	CirculateDirection { RaiseLowest, LowerHighest }
+	RetainResourcesMode { Destroy, RetainPermanently, RetainTemporarily }
+	ModifyPropertyMode { Replace, Prepend, Append }
+}
+
+impl_mask_names!(
+	ColorChannelMask,
+	EventMask,
+	CursorEventMask,
+	DeviceEventMask,
+	ModifierMask,
+	ModifierKeyMask,
+	AnyModifierKeyMask,
+	LedMask,
+);
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_enum_names() {
+		for name in ["NorthWest", "north_west", "north-west", " NORTHWEST "] {
+			assert_eq!(name.parse(), Ok(WindowGravity::NorthWest), "{name:?}");
+		}
+
+		assert_eq!("top_if".parse(), Ok(StackMode::TopIf));
+		assert_eq!(StackMode::BottomIf.to_string(), "BottomIf");
+
+		assert_eq!(
+			"Sideways".parse::<StackMode>(),
+			Err(UnknownName {
+				kind: "StackMode",
+				name: "Sideways".to_owned(),
+			})
+		);
+	}
+
+	#[test]
+	fn test_mask_names() {
+		assert_eq!(
+			"Mod4+Shift".parse(),
+			Ok(ModifierMask::MOD_4 | ModifierMask::SHIFT)
+		);
+		assert_eq!(
+			"STRUCTURE_NOTIFY | substructure-redirect".parse(),
+			Ok(EventMask::STRUCTURE_NOTIFY | EventMask::SUBSTRUCTURE_REDIRECT)
+		);
+		assert_eq!("".parse(), Ok(ModifierKeyMask::empty()));
+
+		assert_eq!(
+			(ModifierKeyMask::MOD_4 | ModifierKeyMask::SHIFT).to_string(),
+			"SHIFT | MOD_4"
+		);
+		assert_eq!(ModifierKeyMask::empty().to_string(), "");
+
+		assert!("Shift+Hyper".parse::<ModifierKeyMask>().is_err());
+		assert!("Shift+".parse::<ModifierKeyMask>().is_err());
+	}
+}
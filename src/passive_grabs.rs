@@ -0,0 +1,210 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Generating the [passive key grabs] for a list of keybindings.
+//!
+//! A [passive key grab] only activates when exactly its `modifiers` are held.
+//! Caps Lock and Num Lock are modifiers too, so a keybinding for `Super+Q`
+//! would not activate while either is on. Window managers work around this
+//! by grabbing each keybinding once for every combination of Caps Lock and
+//! Num Lock: [`grab_keys`] does exactly that.
+//!
+//! The modifier which Num Lock is mapped to differs between keyboard layouts,
+//! so it is looked up in a [`MappingState`], as are the [keycodes] which each
+//! keybinding's [keysym] is mapped to.
+//!
+//! [passive key grab]: GrabKey
+//! [passive key grabs]: GrabKey
+//! [keycodes]: Keycode
+//! [keysym]: Keysym
+
+use crate::{
+	mapping::MappingState,
+	x11::request::{GrabKey, UngrabKey},
+	Any,
+	AnyModifierKeyMask,
+	FreezeMode,
+	Keycode,
+	Keysym,
+	ModifierKeyMask,
+	Window,
+};
+
+/// The `Num_Lock` [keysym].
+///
+/// [keysym]: Keysym
+pub const NUM_LOCK: Keysym = Keysym::new(0xff7f);
+
+/// A [keysym] and the modifiers which must be held with it to activate a
+/// keybinding.
+///
+/// [keysym]: Keysym
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct KeyBinding {
+	/// The [keysym] which activates the keybinding.
+	///
+	/// [keysym]: Keysym
+	pub keysym: Keysym,
+	/// The modifiers which must be held for the keybinding to activate.
+	pub modifiers: ModifierKeyMask,
+}
+
+impl KeyBinding {
+	/// Creates a new `KeyBinding` for the given `keysym` and `modifiers`.
+	#[must_use]
+	pub const fn new(keysym: Keysym, modifiers: ModifierKeyMask) -> Self {
+		Self { keysym, modifiers }
+	}
+}
+
+/// Returns the modifiers which should not affect whether a keybinding
+/// activates: Caps Lock and, if it is mapped to a modifier, Num Lock.
+#[must_use]
+pub fn lock_modifiers(mapping: &MappingState) -> ModifierKeyMask {
+	ModifierKeyMask::LOCK | mapping.modifier(NUM_LOCK).unwrap_or_default()
+}
+
+/// Returns every combination of the given `binding`'s modifiers with any of
+/// the `ignored` modifiers.
+fn modifier_combinations(
+	binding: ModifierKeyMask, ignored: ModifierKeyMask,
+) -> impl Iterator<Item = AnyModifierKeyMask> {
+	let ignored = ignored.bits();
+
+	// Iterates over every subset of the bits in `ignored`, from the full set
+	// down to the empty set.
+	let mut subset = Some(ignored);
+	std::iter::from_fn(move || {
+		let current = subset?;
+		subset = current.checked_sub(1).map(|next| next & ignored);
+
+		Some(current)
+	})
+	.map(move |subset| (binding | ModifierKeyMask::from_bits_truncate(subset)).into())
+}
+
+/// Expands `bindings` into a `(key, modifiers)` pair for each [keycode] and
+/// modifier combination which must be grabbed.
+///
+/// [keycode]: Keycode
+fn expand<'a>(
+	mapping: &'a MappingState, bindings: &'a [KeyBinding],
+) -> impl Iterator<Item = (Keycode, AnyModifierKeyMask)> + 'a {
+	let ignored = lock_modifiers(mapping);
+
+	bindings.iter().flat_map(move |binding| {
+		mapping
+			.keycodes(binding.keysym)
+			.into_iter()
+			.flat_map(move |keycode| {
+				modifier_combinations(binding.modifiers - ignored, ignored)
+					.map(move |modifiers| (keycode, modifiers))
+			})
+	})
+}
+
+/// Generates the [`GrabKey` requests] for the given `bindings` on the given
+/// `grab_window`, including a variant of each for every combination of Caps
+/// Lock and Num Lock.
+///
+/// Keybindings whose [keysym] is not mapped to any [keycode] in `mapping` are
+/// skipped.
+///
+/// The generated grabs do not freeze [event] processing and do not report
+/// [events] to the `grab_window` as usual.
+///
+/// [`GrabKey` requests]: GrabKey
+/// [keysym]: Keysym
+/// [keycode]: Keycode
+/// [event]: crate::message::Event
+/// [events]: crate::message::Event
+pub fn grab_keys<'a>(
+	mapping: &'a MappingState, grab_window: Window, bindings: &'a [KeyBinding],
+) -> impl Iterator<Item = GrabKey> + 'a {
+	expand(mapping, bindings).map(move |(keycode, modifiers)| GrabKey {
+		owner_events: false,
+		grab_window,
+		modifiers,
+		key: Any::Other(keycode),
+		cursor_freeze: FreezeMode::Unfrozen,
+		keyboard_freeze: FreezeMode::Unfrozen,
+	})
+}
+
+/// Generates the [`UngrabKey` requests] which release the grabs generated by
+/// [`grab_keys`] for the same `bindings`.
+///
+/// [`UngrabKey` requests]: UngrabKey
+pub fn ungrab_keys<'a>(
+	mapping: &'a MappingState, grab_window: Window, bindings: &'a [KeyBinding],
+) -> impl Iterator<Item = UngrabKey> + 'a {
+	expand(mapping, bindings).map(move |(keycode, modifiers)| UngrabKey {
+		key: Any::Other(keycode),
+		grab_window,
+		modifiers,
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::x11::{reply, request::GetKeyboardMapping};
+
+	#[test]
+	fn test_grab_keys_lock_combinations() {
+		const Q: Keysym = Keysym::new(0x71);
+
+		let mut mapping = MappingState::new();
+		mapping.merge_keyboard(
+			&GetKeyboardMapping {
+				range: Keycode::new(24)..=Keycode::new(25),
+			},
+			reply::GetKeyboardMapping {
+				sequence: 1,
+				mappings: vec![vec![Q], vec![NUM_LOCK]],
+			},
+		);
+		mapping.merge_modifiers(reply::GetModifierMapping {
+			sequence: 2,
+			shift_keycodes: vec![],
+			capslock_keycodes: vec![],
+			ctrl_keycodes: vec![],
+			mod1_keycodes: vec![],
+			mod2_keycodes: vec![Keycode::new(25)],
+			mod3_keycodes: vec![],
+			mod4_keycodes: vec![],
+			mod5_keycodes: vec![],
+		});
+
+		assert_eq!(
+			lock_modifiers(&mapping),
+			ModifierKeyMask::LOCK | ModifierKeyMask::MOD_2
+		);
+
+		let bindings = [
+			KeyBinding::new(Q, ModifierKeyMask::MOD_4),
+			// Not mapped to any keycode.
+			KeyBinding::new(Keysym::new(0x72), ModifierKeyMask::MOD_4),
+		];
+		let window = Window::new(0x0000_0100);
+
+		let grabs: Vec<_> = grab_keys(&mapping, window, &bindings).collect();
+		let modifiers: Vec<_> = grabs.iter().map(|grab| grab.modifiers).collect();
+
+		assert_eq!(
+			modifiers,
+			[
+				AnyModifierKeyMask::MOD_4 | AnyModifierKeyMask::LOCK | AnyModifierKeyMask::MOD_2,
+				AnyModifierKeyMask::MOD_4 | AnyModifierKeyMask::MOD_2,
+				AnyModifierKeyMask::MOD_4 | AnyModifierKeyMask::LOCK,
+				AnyModifierKeyMask::MOD_4,
+			],
+		);
+		assert!(grabs
+			.iter()
+			.all(|grab| grab.key == Any::Other(Keycode::new(24))));
+
+		assert_eq!(ungrab_keys(&mapping, window, &bindings).count(), 4);
+	}
+}
@@ -0,0 +1,358 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Reading properties which are too long to be returned in a single
+//! [`GetProperty` reply].
+//!
+//! A [`GetProperty` request] returns at most `length` 4-byte units of a
+//! property's value, starting at `offset`, and the [reply] reports how many
+//! bytes remain after them. A [`PropertyReader`] keeps track of the `offset`,
+//! generates a [`GetProperty` request] for each chunk of the value, and
+//! assembles the chunks into the full value.
+//!
+//! [`GetProperty` request]: GetProperty
+//! [`GetProperty` reply]: reply::GetProperty
+//! [reply]: reply::GetProperty
+
+use thiserror::Error;
+
+use crate::{
+	x11::{
+		reply,
+		request::{DataFormat, DataList, GetProperty, PropertyData},
+	},
+	Any,
+	Atom,
+	Window,
+};
+
+/// A property's value, as assembled by a [`PropertyReader`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Property {
+	/// The actual type of the property.
+	pub r#type: Atom,
+	/// The property's full value.
+	pub value: DataList,
+}
+
+/// The next step of reading a property with a [`PropertyReader`].
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub enum PropertyRead {
+	/// The given [`GetProperty` request] must be sent to read the next chunk
+	/// of the property's value.
+	///
+	/// [`GetProperty` request]: GetProperty
+	Continue(GetProperty),
+
+	/// The property has been read in full.
+	///
+	/// This is [`None`] if the property does not exist.
+	Done(Option<Property>),
+}
+
+/// An error generated when a [`GetProperty` reply] cannot be assembled into a
+/// property's value.
+///
+/// [`GetProperty` reply]: reply::GetProperty
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidPropertyReply {
+	/// The property exists, but its type does not match the requested type.
+	#[error("expected a property of type {expected:?}, found {actual:?} ({size} bytes)")]
+	TypeMismatch {
+		/// The requested type.
+		expected: Atom,
+		/// The actual type of the property.
+		actual: Atom,
+		/// The size of the property's value in bytes.
+		size: u32,
+	},
+
+	/// The property's type changed between chunks, so it was modified while
+	/// it was being read.
+	#[error("the property's type changed from {expected:?} to {actual:?} while it was read")]
+	TypeChanged {
+		/// The type of the chunks read so far.
+		expected: Atom,
+		/// The type of the latest chunk.
+		actual: Option<Atom>,
+	},
+
+	/// The property's format changed between chunks, so it was modified while
+	/// it was being read.
+	#[error("the property's format changed from {expected:?} to {actual:?} while it was read")]
+	FormatChanged {
+		/// The format of the chunks read so far.
+		expected: DataFormat,
+		/// The format of the latest chunk.
+		actual: Option<DataFormat>,
+	},
+
+	/// A chunk which was not the last chunk did not end on a 4-byte boundary,
+	/// so the `offset` of the next chunk cannot be expressed.
+	#[error("a chunk of {0} bytes was followed by another chunk")]
+	UnalignedChunk(usize),
+}
+
+/// A state machine which reads a property's value in chunks.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct PropertyReader {
+	target: Window,
+	property: Atom,
+	r#type: Any<Atom>,
+	delete: bool,
+
+	chunk_length: u32,
+	offset: u32,
+
+	read: Option<Property>,
+}
+
+impl PropertyReader {
+	/// The default number of 4-byte units requested in each chunk.
+	pub const DEFAULT_CHUNK_LENGTH: u32 = 1024;
+
+	/// Creates a new `PropertyReader` which reads the given `property` of the
+	/// given `target` [window], if it is of the given `type`.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn new(target: Window, property: Atom, r#type: Any<Atom>) -> Self {
+		Self {
+			target,
+			property,
+			r#type,
+			delete: false,
+
+			chunk_length: Self::DEFAULT_CHUNK_LENGTH,
+			offset: 0,
+
+			read: None,
+		}
+	}
+
+	/// Sets the number of 4-byte units requested in each chunk.
+	///
+	/// A `chunk_length` of zero is treated as one.
+	#[must_use]
+	pub const fn chunk_length(mut self, chunk_length: u32) -> Self {
+		self.chunk_length = if chunk_length == 0 { 1 } else { chunk_length };
+
+		self
+	}
+
+	/// Sets whether the property is deleted once it has been read in full.
+	///
+	/// The X server only deletes the property in response to the request for
+	/// its last chunk.
+	#[must_use]
+	pub const fn delete(mut self, delete: bool) -> Self {
+		self.delete = delete;
+
+		self
+	}
+
+	/// Returns the [`GetProperty` request] which reads the next chunk of the
+	/// property's value.
+	///
+	/// [`GetProperty` request]: GetProperty
+	#[must_use]
+	pub const fn request(&self) -> GetProperty {
+		GetProperty {
+			delete: self.delete,
+			target: self.target,
+			property: self.property,
+			r#type: self.r#type,
+			offset: self.offset,
+			length: self.chunk_length,
+		}
+	}
+
+	/// Adds the chunk of the property's value in the given [reply] to the
+	/// value read so far, and returns what must be done next.
+	///
+	/// # Errors
+	/// Returns an [`InvalidPropertyReply`] error if the property's type does
+	/// not match the requested type, if the property was modified while it
+	/// was being read, or if a chunk which is not the last chunk does not end
+	/// on a 4-byte boundary.
+	///
+	/// [reply]: reply::GetProperty
+	pub fn handle(
+		&mut self, reply: reply::GetProperty,
+	) -> Result<PropertyRead, InvalidPropertyReply> {
+		let Some(actual) = reply.r#type else {
+			// If the property was deleted while it was being read, that is an
+			// error; otherwise, the property does not exist.
+			return self
+				.read
+				.as_ref()
+				.map_or(Ok(PropertyRead::Done(None)), |read| {
+					Err(InvalidPropertyReply::TypeChanged {
+						expected: read.r#type,
+						actual: None,
+					})
+				});
+		};
+
+		if let Any::Other(expected) = self.r#type {
+			// If the type does not match, the X server returns no value and
+			// reports the size of the property's value as remaining.
+			if expected != actual {
+				return Err(InvalidPropertyReply::TypeMismatch {
+					expected,
+					actual,
+					size: reply.bytes_remaining,
+				});
+			}
+		}
+
+		let chunk_size = match reply.format {
+			Some(DataFormat::I8) => reply.value.len(),
+			Some(DataFormat::I16) => reply.value.len() * 2,
+			Some(DataFormat::I32) => reply.value.len() * 4,
+
+			None => 0,
+		};
+
+		match &mut self.read {
+			None => {
+				self.read = Some(Property {
+					r#type: actual,
+					value: reply.value,
+				});
+			},
+
+			Some(read) => {
+				if read.r#type != actual {
+					return Err(InvalidPropertyReply::TypeChanged {
+						expected: read.r#type,
+						actual: Some(actual),
+					});
+				}
+
+				append(&mut read.value, reply.value)?;
+			},
+		}
+
+		if reply.bytes_remaining == 0 {
+			return Ok(PropertyRead::Done(self.read.take()));
+		}
+
+		if chunk_size % 4 != 0 {
+			return Err(InvalidPropertyReply::UnalignedChunk(chunk_size));
+		}
+
+		#[allow(
+			clippy::cast_possible_truncation,
+			reason = "a chunk is at most `chunk_length` 4-byte units"
+		)]
+		{
+			self.offset += (chunk_size / 4) as u32;
+		}
+
+		Ok(PropertyRead::Continue(self.request()))
+	}
+}
+
+/// Appends the `chunk` to the `value`, if they have the same format.
+fn append(value: &mut DataList, chunk: DataList) -> Result<(), InvalidPropertyReply> {
+	match (value, chunk) {
+		(DataList::I8(value), DataList::I8(chunk)) => value.extend(chunk),
+		(DataList::I16(value), DataList::I16(chunk)) => value.extend(chunk),
+		(DataList::I32(value), DataList::I32(chunk)) => value.extend(chunk),
+
+		(value, chunk) => {
+			return Err(InvalidPropertyReply::FormatChanged {
+				expected: value.format(),
+				actual: Some(chunk.format()),
+			})
+		},
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_property_reader_chunks() {
+		let mut reader = PropertyReader::new(
+			Window::new(0x0040_0001),
+			Atom::new(300),
+			Any::Other(crate::atom::STRING),
+		)
+		.chunk_length(1);
+
+		let reply = |bytes: &[u8], bytes_remaining| reply::GetProperty {
+			sequence: 1,
+			format: Some(DataFormat::I8),
+			r#type: Some(crate::atom::STRING),
+			bytes_remaining,
+			value: DataList::I8(bytes.iter().map(|&byte| byte as i8).collect()),
+		};
+
+		assert_eq!(reader.request().offset, 0);
+		assert_eq!(
+			reader.handle(reply(b"0123", 6)),
+			Ok(PropertyRead::Continue(GetProperty {
+				offset: 1,
+				..reader.request()
+			})),
+		);
+		assert!(matches!(
+			reader.handle(reply(b"4567", 2)),
+			Ok(PropertyRead::Continue(GetProperty { offset: 2, .. })),
+		));
+		assert_eq!(
+			reader.handle(reply(b"89", 0)),
+			Ok(PropertyRead::Done(Some(Property {
+				r#type: crate::atom::STRING,
+				value: DataList::I8(b"0123456789".iter().map(|&byte| byte as i8).collect()),
+			}))),
+		);
+	}
+
+	#[test]
+	fn test_property_reader_errors() {
+		let reader = PropertyReader::new(
+			Window::new(0x0040_0001),
+			Atom::new(300),
+			Any::Other(crate::atom::STRING),
+		);
+
+		let mut missing = reader.clone();
+		assert_eq!(
+			missing.handle(reply::GetProperty {
+				sequence: 1,
+				format: None,
+				r#type: None,
+				bytes_remaining: 0,
+				value: DataList::I8(vec![]),
+			}),
+			Ok(PropertyRead::Done(None)),
+		);
+
+		let mut mismatched = reader;
+		assert_eq!(
+			mismatched.handle(reply::GetProperty {
+				sequence: 1,
+				format: Some(DataFormat::I32),
+				r#type: Some(crate::atom::ATOM),
+				bytes_remaining: 8,
+				value: DataList::I32(vec![]),
+			}),
+			Err(InvalidPropertyReply::TypeMismatch {
+				expected: crate::atom::STRING,
+				actual: crate::atom::ATOM,
+				size: 8,
+			}),
+		);
+	}
+}
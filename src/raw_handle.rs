@@ -0,0 +1,205 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Conversions between XRB's [windows] and the handles used by
+//! [`raw-window-handle`].
+//!
+//! Graphics libraries such as `wgpu` and `glutin` accept [windows] in the form
+//! of [`raw-window-handle`] handles. A [`WindowContext`] can be converted into
+//! either an [`XlibWindowHandle`] or an [`XcbWindowHandle`], and a
+//! [`DisplayContext`] into the matching display handle.
+//!
+//! [windows]: Window
+//! [`raw-window-handle`]: raw_window_handle
+
+use std::{
+	ffi::{c_int, c_ulong, c_void},
+	num::NonZeroU32,
+	ptr::NonNull,
+};
+
+use raw_window_handle::{XcbDisplayHandle, XcbWindowHandle, XlibDisplayHandle, XlibWindowHandle};
+use thiserror::Error;
+
+use crate::{visual::VisualId, RawXid, Window, XidOutOfRange};
+
+/// A [window] and, optionally, the [visual] it was created with.
+///
+/// [window]: Window
+/// [visual]: VisualId
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct WindowContext {
+	/// The [window].
+	///
+	/// [window]: Window
+	pub window: Window,
+	/// The [visual] the `window` was created with, if it is known.
+	///
+	/// [visual]: VisualId
+	pub visual: Option<VisualId>,
+}
+
+/// The connection to the X server and the [screen] which [windows] are on.
+///
+/// XRB does not connect to X servers itself, so the `connection` is a pointer
+/// to whichever library's connection is in use: an Xlib `Display` for an
+/// [`XlibDisplayHandle`], or an `xcb_connection_t` for an
+/// [`XcbDisplayHandle`].
+///
+/// [screen]: crate::visual::Screen
+/// [windows]: Window
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DisplayContext {
+	/// A pointer to the connection to the X server, if there is one.
+	pub connection: Option<NonNull<c_void>>,
+	/// The index of the [screen] in the X server's list of roots.
+	///
+	/// [screen]: crate::visual::Screen
+	pub screen: c_int,
+}
+
+/// An error generated when a [window] of zero is converted into an
+/// [`XcbWindowHandle`].
+///
+/// Zero is used in the X11 protocol to mean no [window] at all.
+///
+/// [window]: Window
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+#[error("zero is not a window")]
+pub struct NullWindow;
+
+/// An error generated when converting a [`raw-window-handle`] handle into a
+/// [`WindowContext`].
+///
+/// [`raw-window-handle`]: raw_window_handle
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum FromHandleError {
+	/// The window's ID does not fit in a [`RawXid`].
+	#[error("the window's ID does not fit in 32 bits")]
+	TooLarge,
+	/// The window's ID has any of its top three bits set.
+	#[error(transparent)]
+	OutOfRange(#[from] XidOutOfRange),
+}
+
+/// Converts an Xlib ID into a [`RawXid`].
+fn xlib_xid(id: c_ulong) -> Result<RawXid, FromHandleError> {
+	RawXid::try_from(id).map_err(|_| FromHandleError::TooLarge)
+}
+
+impl From<WindowContext> for XlibWindowHandle {
+	fn from(context: WindowContext) -> Self {
+		let mut handle = Self::new(RawXid::from(context.window).into());
+
+		if let Some(visual) = context.visual {
+			handle.visual_id = visual.unwrap().into();
+		}
+
+		handle
+	}
+}
+
+impl TryFrom<WindowContext> for XcbWindowHandle {
+	type Error = NullWindow;
+
+	fn try_from(context: WindowContext) -> Result<Self, Self::Error> {
+		let window = NonZeroU32::new(context.window.into()).ok_or(NullWindow)?;
+
+		let mut handle = Self::new(window);
+		handle.visual_id = context
+			.visual
+			.and_then(|visual| NonZeroU32::new(visual.unwrap()));
+
+		Ok(handle)
+	}
+}
+
+impl TryFrom<XlibWindowHandle> for WindowContext {
+	type Error = FromHandleError;
+
+	fn try_from(handle: XlibWindowHandle) -> Result<Self, Self::Error> {
+		let visual = match xlib_xid(handle.visual_id)? {
+			0 => None,
+			visual => Some(VisualId::new(visual)),
+		};
+
+		Ok(Self {
+			window: Window::try_from(xlib_xid(handle.window)?)?,
+			visual,
+		})
+	}
+}
+
+impl TryFrom<XcbWindowHandle> for WindowContext {
+	type Error = FromHandleError;
+
+	fn try_from(handle: XcbWindowHandle) -> Result<Self, Self::Error> {
+		Ok(Self {
+			window: Window::try_from(handle.window.get())?,
+			visual: handle.visual_id.map(|visual| VisualId::new(visual.get())),
+		})
+	}
+}
+
+impl From<DisplayContext> for XlibDisplayHandle {
+	fn from(context: DisplayContext) -> Self {
+		Self::new(context.connection, context.screen)
+	}
+}
+
+impl From<DisplayContext> for XcbDisplayHandle {
+	fn from(context: DisplayContext) -> Self {
+		Self::new(context.connection, context.screen)
+	}
+}
+
+impl From<XlibDisplayHandle> for DisplayContext {
+	fn from(handle: XlibDisplayHandle) -> Self {
+		Self {
+			connection: handle.display,
+			screen: handle.screen,
+		}
+	}
+}
+
+impl From<XcbDisplayHandle> for DisplayContext {
+	fn from(handle: XcbDisplayHandle) -> Self {
+		Self {
+			connection: handle.connection,
+			screen: handle.screen,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_window_handle_round_trip() {
+		let context = WindowContext {
+			window: Window::new(0x0020_0001),
+			visual: Some(VisualId::new(0x21)),
+		};
+
+		let xlib = XlibWindowHandle::from(context);
+		assert_eq!((xlib.window, xlib.visual_id), (0x0020_0001, 0x21));
+		assert_eq!(WindowContext::try_from(xlib), Ok(context));
+
+		let xcb = XcbWindowHandle::try_from(context).unwrap();
+		assert_eq!(WindowContext::try_from(xcb), Ok(context));
+
+		assert_eq!(
+			XcbWindowHandle::try_from(WindowContext {
+				window: Window::new(0),
+				visual: None,
+			}),
+			Err(NullWindow),
+		);
+		assert_eq!(
+			WindowContext::try_from(XlibWindowHandle::new(0xe000_0000)),
+			Err(FromHandleError::OutOfRange(XidOutOfRange(0xe000_0000))),
+		);
+	}
+}
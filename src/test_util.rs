@@ -0,0 +1,36 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers shared by XRB's unit tests.
+
+use std::fmt::Debug;
+
+use xrbk::{Readable, Writable};
+
+/// Writes `value` and returns the bytes, checking that as many bytes were
+/// written as `value`'s [`x11_size`].
+///
+/// [`x11_size`]: xrbk::X11Size::x11_size
+pub fn encode(value: &impl Writable) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	value.write_to(&mut bytes).unwrap();
+
+	assert_eq!(bytes.len(), value.x11_size());
+	bytes
+}
+
+/// Writes `message`, checks that reading the bytes back gives the same
+/// `message`, and returns the bytes.
+///
+/// `header` is the number of bytes which are read before the message's reader
+/// is called: the major opcode of a request, or the first byte of a reply or
+/// event, and the first two bytes of an error.
+pub fn round_trip<M: Readable + Writable + PartialEq + Debug>(
+	message: &M, header: usize,
+) -> Vec<u8> {
+	let bytes = encode(message);
+	assert_eq!(&M::read_from(&mut &bytes[header..]).unwrap(), message);
+
+	bytes
+}
@@ -0,0 +1,463 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Helpers for testing message definitions against captured X11 traffic.
+//!
+//! Captures are written as hex dumps, so that traffic recorded by tools such
+//! as `xscope` or a packet capture can be pasted into a test. Each message in
+//! a capture can then be checked with [`check_request`], [`check_reply`],
+//! [`check_event`], or [`check_error`], which decode it with XRB and encode it
+//! again to ensure the same bytes are produced.
+//!
+//! This module is only available with the `testing` feature, which is
+//! intended to be enabled in `dev-dependencies`.
+//!
+//! # Capture format
+//! A capture is a list of messages. Each message starts with a line beginning
+//! with `>` for a message sent by the client, or `<` for a message sent by the
+//! X server. The rest of that line, and any lines following it which do not
+//! start a new message, are the message's bytes in hex.
+//!
+//! Hex digits may be grouped in any even number, so `0800 0200` is the same as
+//! `08 00 02 00`. A line may start with an offset ending in `:`, which is
+//! ignored, and anything following two consecutive spaces (such as the ASCII
+//! column of `xxd` output) is ignored too. Comments start with `#`.
+//!
+//! ```
+//! # use xrb::{testing::{self, Sender}, x11::request};
+//! let capture = testing::parse_capture(
+//!     "
+//!     > 08 00 00 02 # MapWindow
+//!       00 20 00 01
+//!     ",
+//! )?;
+//!
+//! assert_eq!(capture[0].sender, Sender::Client);
+//! let request: request::MapWindow = testing::check_request(&capture[0].bytes)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Canonical encoding
+//! XRB always writes unused bytes as zero. If the X server or client which
+//! was captured left other values in unused bytes, re-encoding the message
+//! does not reproduce them, and the check fails with
+//! [`ConformanceError::Mismatch`]. See [`Opaque`] for more information.
+//!
+//! [`Opaque`]: crate::message::Opaque
+
+use thiserror::Error;
+use xrbk::{check_remaining, Buf, ReadError, Readable, Writable, WriteError};
+
+use crate::message::{Error, Event, Reply, Request, SYNTHETIC_EVENT_FLAG};
+
+/// Which side of a connection sent a [`CapturedMessage`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Sender {
+	/// The message was sent by the client: it is a [request].
+	///
+	/// [request]: Request
+	Client,
+	/// The message was sent by the X server: it is a [reply], [event], or
+	/// [error].
+	///
+	/// [reply]: Reply
+	/// [event]: Event
+	/// [error]: Error
+	Server,
+}
+
+/// A message in a capture.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CapturedMessage {
+	/// Which side of the connection sent the message.
+	pub sender: Sender,
+	/// The bytes of the message.
+	pub bytes: Vec<u8>,
+}
+
+/// An error generated when a capture cannot be parsed.
+///
+/// Line numbers start at `1`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum CaptureError {
+	/// A group of digits was not valid hex.
+	#[error("line {line}: `{group}` is not valid hex")]
+	InvalidHex {
+		/// The line on which the group was found.
+		line: usize,
+		/// The group of digits which was not valid hex.
+		group: String,
+	},
+
+	/// A group of hex digits had an odd number of digits, so it could not be
+	/// split into bytes.
+	#[error("line {line}: `{group}` has an odd number of hex digits")]
+	OddDigits {
+		/// The line on which the group was found.
+		line: usize,
+		/// The group of hex digits.
+		group: String,
+	},
+
+	/// Bytes were found before the start of the first message.
+	#[error("line {line}: expected a message to start with `>` or `<`")]
+	MissingSender {
+		/// The line on which the bytes were found.
+		line: usize,
+	},
+}
+
+/// Parses the bytes from a hex dump.
+///
+/// See [capture format] for the format of the hex dump; the `>` and `<`
+/// markers are not allowed.
+///
+/// # Errors
+/// Returns a [`CaptureError`] if the hex dump is not valid.
+///
+/// [capture format]: self#capture-format
+pub fn parse_hex(text: &str) -> Result<Vec<u8>, CaptureError> {
+	let mut bytes = Vec::new();
+
+	for (index, line) in text.lines().enumerate() {
+		parse_hex_line(index + 1, line, &mut bytes)?;
+	}
+
+	Ok(bytes)
+}
+
+/// Parses a capture of multiple messages.
+///
+/// See [capture format] for the format of the capture.
+///
+/// # Errors
+/// Returns a [`CaptureError`] if the capture is not valid.
+///
+/// [capture format]: self#capture-format
+pub fn parse_capture(text: &str) -> Result<Vec<CapturedMessage>, CaptureError> {
+	let mut messages: Vec<CapturedMessage> = Vec::new();
+
+	for (index, line) in text.lines().enumerate() {
+		let line_number = index + 1;
+		let line = line.trim_start();
+
+		let (sender, hex) = match line.chars().next() {
+			Some('>') => (Some(Sender::Client), &line[1..]),
+			Some('<') => (Some(Sender::Server), &line[1..]),
+
+			_ => (None, line),
+		};
+
+		if let Some(sender) = sender {
+			messages.push(CapturedMessage {
+				sender,
+				bytes: Vec::new(),
+			});
+		}
+
+		let mut bytes = Vec::new();
+		parse_hex_line(line_number, hex, &mut bytes)?;
+
+		match messages.last_mut() {
+			Some(message) => message.bytes.append(&mut bytes),
+
+			None if bytes.is_empty() => {},
+			None => return Err(CaptureError::MissingSender { line: line_number }),
+		}
+	}
+
+	Ok(messages)
+}
+
+/// Parses the hex bytes in a single `line` of a hex dump, appending them to
+/// `bytes`.
+fn parse_hex_line(line_number: usize, line: &str, bytes: &mut Vec<u8>) -> Result<(), CaptureError> {
+	let line = line.split('#').next().unwrap_or_default().trim();
+	let line = line.split("  ").next().unwrap_or_default();
+
+	for (index, group) in line.split_whitespace().enumerate() {
+		if index == 0 && group.ends_with(':') {
+			continue;
+		}
+
+		if !group.chars().all(|digit| digit.is_ascii_hexdigit()) {
+			return Err(CaptureError::InvalidHex {
+				line: line_number,
+				group: group.to_owned(),
+			});
+		}
+
+		if group.len() % 2 != 0 {
+			return Err(CaptureError::OddDigits {
+				line: line_number,
+				group: group.to_owned(),
+			});
+		}
+
+		for pair in group.as_bytes().chunks_exact(2) {
+			let pair = std::str::from_utf8(pair).expect("hex digits are ASCII");
+			bytes.push(u8::from_str_radix(pair, 16).expect("hex digits were checked"));
+		}
+	}
+
+	Ok(())
+}
+
+/// An error generated when a message does not conform to its definition in
+/// XRB.
+#[derive(Debug, Error)]
+pub enum ConformanceError {
+	/// The message's opcode or code is not that of the expected type of
+	/// message.
+	#[error("expected the message's opcode or code to be {expected}, found {found}")]
+	UnexpectedCode {
+		/// The opcode or code of the expected type of message.
+		expected: u8,
+		/// The opcode or code of the message.
+		found: u8,
+	},
+
+	/// The message could not be read.
+	#[error("failed to read the message: {0}")]
+	Read(#[from] ReadError),
+	/// The message ended before all of its fields could be read.
+	#[error("the message ended before all of its fields could be read")]
+	Malformed,
+
+	/// The message could not be written.
+	#[error("failed to write the message: {0}")]
+	Write(#[from] WriteError),
+
+	/// The length of the decoded message is not the length of the original
+	/// bytes.
+	#[error(
+		"expected the message to be {expected} bytes long, but it was decoded as {found} bytes"
+	)]
+	Length {
+		/// The length of the original bytes.
+		expected: usize,
+		/// The length of the decoded message.
+		found: usize,
+	},
+
+	/// Encoding the decoded message again did not produce the original bytes.
+	#[error("byte {offset} of the re-encoded message was {found:#04x}, expected {expected:#04x}")]
+	Mismatch {
+		/// The offset of the first byte which differs.
+		offset: usize,
+		/// The original byte.
+		expected: u8,
+		/// The re-encoded byte.
+		found: u8,
+	},
+}
+
+/// Ensures that the opcode or code `found` is the one `expected`.
+const fn expect_code(expected: u8, found: u8) -> Result<(), ConformanceError> {
+	if expected == found {
+		Ok(())
+	} else {
+		Err(ConformanceError::UnexpectedCode { expected, found })
+	}
+}
+
+/// Decodes an `M` from `bytes`, skipping the `header` bytes which identify the
+/// message, and ensures that encoding it again produces the same `bytes`.
+fn check<M: Readable + Writable>(bytes: &[u8], header: usize) -> Result<M, ConformanceError> {
+	let mut body = bytes;
+	check_remaining(&body, header).map_err(|_| ConformanceError::Malformed)?;
+	body.advance(header);
+
+	let message = M::read_from(&mut body).map_err(|error| match error.root() {
+		ReadError::NotEnoughBytes { .. } => ConformanceError::Malformed,
+		_ => ConformanceError::Read(error),
+	})?;
+
+	if message.x11_size() != bytes.len() {
+		return Err(ConformanceError::Length {
+			expected: bytes.len(),
+			found: message.x11_size(),
+		});
+	}
+
+	let mut encoded = Vec::with_capacity(bytes.len());
+	message.write_to(&mut encoded)?;
+
+	if let Some((offset, (&expected, &found))) = bytes
+		.iter()
+		.zip(&encoded)
+		.enumerate()
+		.find(|(_, (expected, found))| expected != found)
+	{
+		return Err(ConformanceError::Mismatch {
+			offset,
+			expected,
+			found,
+		});
+	}
+
+	Ok(message)
+}
+
+/// Decodes a [request] from `bytes` and ensures that encoding it again
+/// produces the same `bytes`.
+///
+/// `bytes` must contain the entire [request], starting with its major opcode.
+///
+/// # Errors
+/// Returns a [`ConformanceError`] if the [request] is not of type `Req`, could
+/// not be decoded, or does not produce the same `bytes` when encoded again.
+///
+/// [request]: Request
+pub fn check_request<Req: Request + Readable>(bytes: &[u8]) -> Result<Req, ConformanceError> {
+	expect_code(
+		Req::MAJOR_OPCODE,
+		*bytes.first().ok_or(ConformanceError::Malformed)?,
+	)?;
+
+	check(bytes, 1)
+}
+
+/// Decodes a [reply] from `bytes` and ensures that encoding it again produces
+/// the same `bytes`.
+///
+/// `bytes` must contain the entire [reply], starting with its first byte
+/// (which is always `1`).
+///
+/// # Errors
+/// Returns a [`ConformanceError`] if the [reply] could not be decoded or does
+/// not produce the same `bytes` when encoded again.
+///
+/// [reply]: Reply
+pub fn check_reply<Rep: Reply + Writable>(bytes: &[u8]) -> Result<Rep, ConformanceError> {
+	expect_code(1, *bytes.first().ok_or(ConformanceError::Malformed)?)?;
+
+	check(bytes, 1)
+}
+
+/// Decodes an [event] from `bytes` and ensures that encoding it again
+/// produces the same `bytes`.
+///
+/// `bytes` must contain the entire [event], starting with its code. Events
+/// sent with a [`SendEvent` request] are allowed, but their
+/// [`SYNTHETIC_EVENT_FLAG`] is not checked when encoding them again.
+///
+/// # Errors
+/// Returns a [`ConformanceError`] if the [event] is not of type `E`, could not
+/// be decoded, or does not produce the same `bytes` when encoded again.
+///
+/// [event]: Event
+/// [`SendEvent` request]: crate::x11::request::SendEvent
+pub fn check_event<E: Event>(bytes: &[u8]) -> Result<E, ConformanceError> {
+	let code = *bytes.first().ok_or(ConformanceError::Malformed)?;
+	expect_code(E::CODE, code & !SYNTHETIC_EVENT_FLAG)?;
+
+	let mut bytes = bytes.to_vec();
+	bytes[0] = E::CODE;
+
+	check(&bytes, 1)
+}
+
+/// Decodes an [error] from `bytes` and ensures that encoding it again
+/// produces the same `bytes`.
+///
+/// `bytes` must contain the entire [error], starting with its first byte
+/// (which is always `0`).
+///
+/// # Errors
+/// Returns a [`ConformanceError`] if the [error] is not of type `E`, could not
+/// be decoded, or does not produce the same `bytes` when encoded again.
+///
+/// [error]: Error
+pub fn check_error<E: Error + Writable>(bytes: &[u8]) -> Result<E, ConformanceError> {
+	let code = *bytes.get(1).ok_or(ConformanceError::Malformed)?;
+	expect_code(E::CODE, code)?;
+
+	check(bytes, 2)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		x11::{error, reply, request},
+		Atom,
+		Window,
+	};
+
+	const CAPTURE: &str = "
+		# InternAtom, only-if-exists, `WM_NAME`
+		> 10 01 0004 0007 0000
+		  574d 5f4e 414d 4500  WM_NAME.
+
+		# The reply: atom 39
+		< 01 00 0005 00000000 00000027
+		  00000000 00000000 00000000 00000000 00000000
+		# A `Window` error for `MapWindow`
+		<0003 0006 00200001 0000 08 000000000000000000000000000000000000000000
+	";
+
+	#[test]
+	fn test_check_capture() {
+		let capture = parse_capture(CAPTURE).unwrap();
+		assert_eq!(
+			capture
+				.iter()
+				.map(|message| (message.sender, message.bytes.len()))
+				.collect::<Vec<_>>(),
+			[
+				(Sender::Client, 16),
+				(Sender::Server, 32),
+				(Sender::Server, 32)
+			],
+		);
+
+		let request: request::GetAtom = check_request(&capture[0].bytes).unwrap();
+		assert_eq!(request.name.to_latin1(), "WM_NAME");
+
+		let reply: reply::GetAtom = check_reply(&capture[1].bytes).unwrap();
+		assert_eq!(reply.atom, Some(Atom::new(39)));
+
+		let error: error::Window = check_error(&capture[2].bytes).unwrap();
+		assert_eq!(error.invalid_window_id, 0x0020_0001);
+
+		assert!(matches!(
+			check_request::<request::MapWindow>(&capture[0].bytes),
+			Err(ConformanceError::UnexpectedCode {
+				expected: 8,
+				found: 16,
+			}),
+		));
+	}
+
+	#[test]
+	fn test_check_noncanonical() {
+		let mut bytes = parse_hex("08 00 0002 00200001").unwrap();
+		let request: request::MapWindow = check_request(&bytes).unwrap();
+		assert_eq!(request.target, Window::new(0x0020_0001));
+
+		// The metabyte of `MapWindow` is unused.
+		bytes[1] = 0xff;
+		assert!(matches!(
+			check_request::<request::MapWindow>(&bytes),
+			Err(ConformanceError::Mismatch {
+				offset: 1,
+				expected: 0xff,
+				found: 0,
+			}),
+		));
+
+		assert!(matches!(
+			check_request::<request::MapWindow>(&bytes[..6]),
+			Err(ConformanceError::Malformed),
+		));
+		assert_eq!(
+			parse_hex("0000: 0800 002"),
+			Err(CaptureError::OddDigits {
+				line: 1,
+				group: "002".to_owned(),
+			}),
+		);
+	}
+}
@@ -0,0 +1,432 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A cache of the window tree which translates coordinates between [windows]
+//! without a [`ConvertCoordinates` request] for each translation.
+//!
+//! A [`WindowTree`] is built from the [replies] to [`QueryWindowTree`] and
+//! [`GetGeometry` requests], and is kept up to date with [`Configure`],
+//! [`Gravity`], [`Reparent`], and [`Destroy` events]. When the position of a
+//! [window] is no longer known, the [`GetGeometry` request] which must be sent
+//! to fetch it again is returned.
+//!
+//! [windows]: Window
+//! [window]: Window
+//! [replies]: crate::message::Reply
+//! [`ConvertCoordinates` request]: crate::x11::request::ConvertCoordinates
+//! [`QueryWindowTree`]: QueryWindowTree
+//! [`GetGeometry` request]: GetGeometry
+//! [`GetGeometry` requests]: GetGeometry
+//! [`Destroy` events]: Destroy
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+	unit::Px,
+	x11::{
+		event::{Configure, Destroy, Gravity, Reparent},
+		reply,
+		request::{GetGeometry, QueryWindowTree},
+	},
+	Coords,
+	Window,
+};
+
+/// What is known about a [window] in a [`WindowTree`].
+///
+/// [window]: Window
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Node {
+	/// The [window]'s parent, if it is known.
+	///
+	/// [window]: Window
+	parent: Option<Window>,
+	/// The coordinates of the outer corner of the [window]'s border relative
+	/// to its parent's origin, if they are known.
+	///
+	/// [window]: Window
+	coords: Option<Coords>,
+	/// The width of the [window]'s border, if it is known.
+	///
+	/// [window]: Window
+	border_width: Option<Px<u16>>,
+}
+
+impl Node {
+	/// Returns the position of the [window]'s origin (inside its border)
+	/// relative to its parent's origin, if it is known.
+	///
+	/// [window]: Window
+	fn origin(&self) -> Option<(i32, i32)> {
+		let (coords, border_width) = (self.coords?, i32::from(self.border_width?.0));
+
+		Some((
+			i32::from(coords.x.0) + border_width,
+			i32::from(coords.y.0) + border_width,
+		))
+	}
+}
+
+/// A cache of the window tree and the positions of [windows] within it.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [windows]: Window
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WindowTree {
+	roots: HashSet<Window>,
+	nodes: HashMap<Window, Node>,
+}
+
+impl WindowTree {
+	/// Creates a new, empty `WindowTree`.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Merges the [reply] to a [`QueryWindowTree` request] for the given
+	/// `window` into the cache.
+	///
+	/// This records the `window`'s root, parent, and children.
+	///
+	/// [reply]: reply::QueryWindowTree
+	/// [`QueryWindowTree` request]: QueryWindowTree
+	pub fn merge_tree(&mut self, window: Window, reply: &reply::QueryWindowTree) {
+		self.roots.insert(reply.root);
+
+		if let Some(parent) = reply.parent {
+			self.nodes.entry(window).or_default().parent = Some(parent);
+		}
+
+		for &child in &reply.children {
+			self.nodes.entry(child).or_default().parent = Some(window);
+		}
+	}
+
+	/// Merges the [reply] to a [`GetGeometry` request] for the given `window`
+	/// into the cache.
+	///
+	/// [reply]: reply::GetGeometry
+	/// [`GetGeometry` request]: GetGeometry
+	pub fn merge_geometry(&mut self, window: Window, reply: &reply::GetGeometry) {
+		self.roots.insert(reply.root);
+
+		let node = self.nodes.entry(window).or_default();
+
+		node.coords = Some(reply.geometry.as_coords());
+		node.border_width = Some(reply.border_width);
+	}
+
+	/// Forgets the position of the [window] which was configured in the given
+	/// [`Configure` event] and returns the [`GetGeometry` request] which must
+	/// be sent to fetch it again.
+	///
+	/// The position is not taken from the event itself, as window managers
+	/// send synthetic [`Configure` events] with coordinates relative to the
+	/// root window instead.
+	///
+	/// Returns [`None`] if the [window] is not in the cache.
+	///
+	/// [window]: Window
+	/// [`Configure` event]: Configure
+	/// [`Configure` events]: Configure
+	pub fn handle_configure(&mut self, event: &Configure) -> Option<GetGeometry> {
+		self.forget_position(event.window)
+	}
+
+	/// Forgets the position of the [window] which was moved because of its
+	/// parent's resizing in the given [`Gravity` event] and returns the
+	/// [`GetGeometry` request] which must be sent to fetch it again.
+	///
+	/// Like [`handle_configure`], the position is not taken from the event
+	/// itself.
+	///
+	/// Returns [`None`] if the [window] is not in the cache.
+	///
+	/// [window]: Window
+	/// [`Gravity` event]: Gravity
+	/// [`handle_configure`]: WindowTree::handle_configure
+	pub fn handle_gravity(&mut self, event: &Gravity) -> Option<GetGeometry> {
+		self.forget_position(event.window)
+	}
+
+	/// Forgets the position of the given `window` and returns the
+	/// [`GetGeometry` request] which must be sent to fetch it again.
+	///
+	/// Returns [`None`] if the `window` is not in the cache.
+	fn forget_position(&mut self, window: Window) -> Option<GetGeometry> {
+		let node = self.nodes.get_mut(&window)?;
+
+		node.coords = None;
+		node.border_width = None;
+
+		Some(GetGeometry {
+			target: window.into(),
+		})
+	}
+
+	/// Updates the parent and position of the [window] which was reparented in
+	/// the given [`Reparent` event].
+	///
+	/// The [window]'s `border_width` is not included in the event, so its
+	/// position remains unknown unless its `border_width` was already cached.
+	///
+	/// [window]: Window
+	/// [`Reparent` event]: Reparent
+	pub fn handle_reparent(&mut self, event: &Reparent) {
+		let node = self.nodes.entry(event.window).or_default();
+
+		node.parent = Some(event.new_parent);
+		node.coords = Some(event.coords);
+	}
+
+	/// Forgets the [window] destroyed in the given [`Destroy` event].
+	///
+	/// [window]: Window
+	/// [`Destroy` event]: Destroy
+	pub fn handle_destroy(&mut self, event: &Destroy) {
+		self.nodes.remove(&event.window);
+		self.roots.remove(&event.window);
+	}
+
+	/// Returns the offset of the given `window`'s origin from the origin of
+	/// its root window, if it is known.
+	///
+	/// Returns [`None`] if the cached parents form a cycle, which can happen if
+	/// the cache is out of date.
+	fn root_offset(&self, mut window: Window) -> Option<(i32, i32)> {
+		let (mut x, mut y) = (0, 0);
+
+		// Every window on the way to the root is in `nodes`, so a walk with
+		// more steps than that must have gone around a cycle.
+		for _ in 0..self.nodes.len() {
+			if self.roots.contains(&window) {
+				break;
+			}
+
+			let node = self.nodes.get(&window)?;
+			let (origin_x, origin_y) = node.origin()?;
+
+			x += origin_x;
+			y += origin_y;
+
+			window = node.parent?;
+		}
+
+		self.roots.contains(&window).then_some((x, y))
+	}
+
+	/// Translates the given `coords`, relative to the given `window`'s origin,
+	/// to coordinates relative to the root window.
+	///
+	/// Returns [`None`] if the position of the `window` or any of its
+	/// ancestors is not known.
+	#[must_use]
+	pub fn to_root(&self, window: Window, coords: Coords) -> Option<Coords> {
+		let (x, y) = self.root_offset(window)?;
+
+		Some(Coords::saturating_new(
+			i32::from(coords.x.0) + x,
+			i32::from(coords.y.0) + y,
+		))
+	}
+
+	/// Translates the given `coords`, relative to the root window, to
+	/// coordinates relative to the given `window`'s origin.
+	///
+	/// Returns [`None`] if the position of the `window` or any of its
+	/// ancestors is not known.
+	#[must_use]
+	pub fn from_root(&self, window: Window, coords: Coords) -> Option<Coords> {
+		let (x, y) = self.root_offset(window)?;
+
+		Some(Coords::saturating_new(
+			i32::from(coords.x.0) - x,
+			i32::from(coords.y.0) - y,
+		))
+	}
+
+	/// Returns the [requests] which must be sent to be able to translate
+	/// coordinates relative to the given `window`.
+	///
+	/// A [`QueryWindowTree` request] is returned for each [window] whose parent
+	/// is not known, and a [`GetGeometry` request] for each [window] whose
+	/// position is not known, walking up from the `window` until its root
+	/// window is reached or its parent is not known.
+	///
+	/// [requests]: crate::message::Request
+	/// [window]: Window
+	/// [`QueryWindowTree` request]: QueryWindowTree
+	/// [`GetGeometry` request]: GetGeometry
+	#[must_use]
+	pub fn missing(&self, mut window: Window) -> (Vec<QueryWindowTree>, Vec<GetGeometry>) {
+		let mut trees = Vec::new();
+		let mut geometries = Vec::new();
+
+		// As in `root_offset`, this stops if the cached parents form a cycle.
+		for _ in 0..=self.nodes.len() {
+			if self.roots.contains(&window) {
+				break;
+			}
+
+			let node = self.nodes.get(&window);
+
+			if node.and_then(Node::origin).is_none() {
+				geometries.push(GetGeometry {
+					target: window.into(),
+				});
+			}
+
+			let Some(parent) = node.and_then(|node| node.parent) else {
+				trees.push(QueryWindowTree { target: window });
+				break;
+			};
+
+			window = parent;
+		}
+
+		(trees, geometries)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::Rectangle;
+
+	const ROOT: Window = Window::new(0x0000_0100);
+	const FRAME: Window = Window::new(0x0020_0001);
+	const CLIENT: Window = Window::new(0x0040_0001);
+
+	fn geometry(x: i16, y: i16, border_width: u16) -> reply::GetGeometry {
+		reply::GetGeometry {
+			sequence: 1,
+			depth: 24,
+			root: ROOT,
+			geometry: Rectangle::new(Px(x), Px(y), Px(100), Px(100)),
+			border_width: Px(border_width),
+		}
+	}
+
+	#[test]
+	fn test_window_tree_translation() {
+		let mut tree = WindowTree::new();
+
+		assert_eq!(
+			tree.missing(CLIENT),
+			(
+				vec![QueryWindowTree { target: CLIENT }],
+				vec![GetGeometry {
+					target: CLIENT.into()
+				}],
+			),
+		);
+
+		tree.merge_tree(
+			CLIENT,
+			&reply::QueryWindowTree {
+				sequence: 1,
+				root: ROOT,
+				parent: Some(FRAME),
+				children: reply::ReplyList::new(),
+			},
+		);
+		tree.merge_tree(
+			FRAME,
+			&reply::QueryWindowTree {
+				sequence: 2,
+				root: ROOT,
+				parent: Some(ROOT),
+				children: vec![CLIENT].into(),
+			},
+		);
+		tree.merge_geometry(FRAME, &geometry(100, 50, 1));
+		tree.merge_geometry(CLIENT, &geometry(2, 20, 0));
+
+		assert_eq!(tree.missing(CLIENT), (vec![], vec![]));
+		assert_eq!(
+			tree.to_root(CLIENT, Coords::new(Px(5), Px(5))),
+			Some(Coords::new(Px(108), Px(76)))
+		);
+		assert_eq!(
+			tree.from_root(CLIENT, Coords::new(Px(108), Px(76))),
+			Some(Coords::new(Px(5), Px(5)))
+		);
+
+		let configure = Configure {
+			sequence: 3,
+			event_window: FRAME,
+			window: FRAME,
+			sibling_below: None,
+			geometry: Rectangle::new(Px(0), Px(0), Px(100), Px(100)),
+			border_width: Px(1),
+			override_redirect: false,
+		};
+		assert_eq!(
+			tree.handle_configure(&configure),
+			Some(GetGeometry {
+				target: FRAME.into()
+			})
+		);
+		assert_eq!(tree.to_root(CLIENT, Coords::new(Px(5), Px(5))), None);
+
+		tree.merge_geometry(FRAME, &geometry(100, 50, 1));
+		let gravity = Gravity {
+			sequence: 4,
+			event_window: CLIENT,
+			window: CLIENT,
+			coords: Coords::new(Px(2), Px(30)),
+		};
+		assert_eq!(
+			tree.handle_gravity(&gravity),
+			Some(GetGeometry {
+				target: CLIENT.into()
+			})
+		);
+		assert_eq!(tree.to_root(CLIENT, Coords::new(Px(5), Px(5))), None);
+
+		tree.merge_geometry(CLIENT, &geometry(2, 30, 0));
+		assert_eq!(
+			tree.to_root(CLIENT, Coords::new(Px(5), Px(5))),
+			Some(Coords::new(Px(108), Px(86)))
+		);
+
+		tree.handle_reparent(&Reparent {
+			sequence: 5,
+			event_window: CLIENT,
+			window: CLIENT,
+			new_parent: ROOT,
+			coords: Coords::new(Px(10), Px(10)),
+			override_redirect: false,
+		});
+		assert_eq!(
+			tree.to_root(CLIENT, Coords::new(Px(5), Px(5))),
+			Some(Coords::new(Px(15), Px(15)))
+		);
+	}
+
+	#[test]
+	fn test_window_tree_cycle() {
+		let mut tree = WindowTree::new();
+
+		// An out-of-date cache may have windows which are each other's parent.
+		for (window, new_parent) in [(FRAME, CLIENT), (CLIENT, FRAME)] {
+			tree.handle_reparent(&Reparent {
+				sequence: 1,
+				event_window: window,
+				window,
+				new_parent,
+				coords: Coords::new(Px(10), Px(10)),
+				override_redirect: false,
+			});
+			tree.merge_geometry(window, &geometry(10, 10, 0));
+		}
+
+		assert_eq!(tree.to_root(CLIENT, Coords::new(Px(5), Px(5))), None);
+		assert_eq!(tree.from_root(FRAME, Coords::new(Px(5), Px(5))), None);
+		assert_eq!(tree.missing(CLIENT), (vec![], vec![]));
+	}
+}
@@ -16,6 +16,7 @@ use derive_more::{
 	DivAssign,
 	Mul,
 	MulAssign,
+	Neg,
 	Rem,
 	RemAssign,
 	Sub,
@@ -28,6 +29,7 @@ use xrbk::{
 	Buf,
 	BufMut,
 	ConstantX11Size,
+	InfallibleWritable,
 	ReadResult,
 	Readable,
 	Wrap,
@@ -86,12 +88,19 @@ macro_rules! impl_xrbk_traits {
 				Ok(())
 			}
 		}
+
+		impl$(<$generic>)? InfallibleWritable for $type$(<$generic>)?
+		$(where
+			$generic: InfallibleWritable,)?
+		{
+		}
 	};
 }
 
 /// A value measured in pixels.
 #[derive(
 	Debug,
+	Default,
 	Hash,
 	Copy,
 	Clone,
@@ -140,6 +149,7 @@ impl_xrbk_traits!(Px<Num>(Num));
 /// A value measured in millimeters.
 #[derive(
 	Debug,
+	Default,
 	Hash,
 	Copy,
 	Clone,
@@ -188,6 +198,7 @@ impl_xrbk_traits!(Mm<Num>(Num));
 /// A value measured in milliseconds.
 #[derive(
 	Debug,
+	Default,
 	Hash,
 	Copy,
 	Clone,
@@ -236,6 +247,7 @@ impl_xrbk_traits!(Ms<Num>(Num));
 /// A value measured in seconds.
 #[derive(
 	Debug,
+	Default,
 	Hash,
 	Copy,
 	Clone,
@@ -305,6 +317,7 @@ impl_xrbk_traits!(Sec<Num>(Num));
 /// A value measured in hertz.
 #[derive(
 	Debug,
+	Default,
 	Hash,
 	Copy,
 	Clone,
@@ -351,7 +364,7 @@ where
 impl_xrbk_traits!(Hz<Num>(Num));
 
 /// A value measured as a percentage from 0% to 100%.
-#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Percentage(u8);
 
 impl Percentage {
@@ -429,7 +442,7 @@ impl PartialOrd<Percentage> for u8 {
 impl_xrbk_traits!(Percentage(u8));
 
 /// A value measured as a percentage from -100% to 100%.
-#[derive(Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SignedPercentage(i8);
 
 impl SignedPercentage {
@@ -507,3 +520,256 @@ impl PartialOrd<SignedPercentage> for i8 {
 }
 
 impl_xrbk_traits!(SignedPercentage(i8));
+
+/// The volume at which a bell is rung, relative to the keyboard's base
+/// [`bell_volume`].
+///
+/// The X11 protocol encodes this as a [`SignedPercentage`] whose meaning
+/// depends on its sign: a positive percentage moves the volume that far from
+/// the base volume towards 100%, and a negative percentage moves it that far
+/// from the base volume towards 0%. [`louder`], [`quieter`], and [`absolute`]
+/// construct a `Volume` in those terms, and [`resolve`] calculates the
+/// resulting volume for a given base volume.
+///
+/// [`bell_volume`]: crate::set::KeyboardOptions::bell_volume
+///
+/// [`louder`]: Volume::louder
+/// [`quieter`]: Volume::quieter
+/// [`absolute`]: Volume::absolute
+/// [`resolve`]: Volume::resolve
+#[derive(Debug, Default, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Volume(SignedPercentage);
+
+impl Volume {
+	/// The base volume, unchanged.
+	pub const BASE: Self = Self(SignedPercentage(0));
+
+	/// Creates a volume `percentage`% of the way from the base volume to 100%.
+	///
+	/// # Errors
+	/// Returns a [`ValueOutOfBounds`] error if the `percentage > 100`.
+	pub const fn louder(percentage: u8) -> Result<Self, ValueOutOfBounds<u8>> {
+		match Percentage::new(percentage) {
+			#[allow(clippy::cast_possible_wrap, reason = "`percentage` is at most 100")]
+			Ok(percentage) => Ok(Self(SignedPercentage(percentage.0 as i8))),
+			Err(error) => Err(error),
+		}
+	}
+
+	/// Creates a volume `percentage`% of the way from the base volume to 0%.
+	///
+	/// # Errors
+	/// Returns a [`ValueOutOfBounds`] error if the `percentage > 100`.
+	pub const fn quieter(percentage: u8) -> Result<Self, ValueOutOfBounds<u8>> {
+		match Percentage::new(percentage) {
+			#[allow(clippy::cast_possible_wrap, reason = "`percentage` is at most 100")]
+			Ok(percentage) => Ok(Self(SignedPercentage(-(percentage.0 as i8)))),
+			Err(error) => Err(error),
+		}
+	}
+
+	/// Creates a volume from the `percentage` as it is encoded in the X11
+	/// protocol.
+	///
+	/// A positive `percentage` is equivalent to [`louder`], and a negative
+	/// `percentage` is equivalent to [`quieter`].
+	///
+	/// # Errors
+	/// Returns a [`ValueOutOfBounds`] error if `percentage < -100` or
+	/// `percentage > 100`.
+	///
+	/// [`louder`]: Volume::louder
+	/// [`quieter`]: Volume::quieter
+	pub const fn absolute(percentage: i8) -> Result<Self, ValueOutOfBounds<i8>> {
+		match SignedPercentage::new(percentage) {
+			Ok(percentage) => Ok(Self(percentage)),
+			Err(error) => Err(error),
+		}
+	}
+
+	/// Returns the volume at which the bell is rung if the keyboard's base
+	/// [`bell_volume`] is `base`.
+	///
+	/// [`bell_volume`]: crate::set::KeyboardOptions::bell_volume
+	#[must_use]
+	pub const fn resolve(&self, base: Percentage) -> Percentage {
+		let (base, percentage) = (base.0 as i16, self.0 .0 as i16);
+
+		let volume = if percentage >= 0 {
+			base - (base * percentage) / 100 + percentage
+		} else {
+			base + (base * percentage) / 100
+		};
+
+		#[allow(
+			clippy::cast_possible_truncation,
+			clippy::cast_sign_loss,
+			reason = "the resolved volume is always between 0 and 100"
+		)]
+		Percentage(volume as u8)
+	}
+
+	/// Returns the wrapped [`SignedPercentage`], as it is encoded in the X11
+	/// protocol.
+	#[must_use]
+	pub const fn unwrap(&self) -> SignedPercentage {
+		self.0
+	}
+}
+
+impl From<SignedPercentage> for Volume {
+	fn from(percentage: SignedPercentage) -> Self {
+		Self(percentage)
+	}
+}
+
+impl From<Volume> for SignedPercentage {
+	fn from(volume: Volume) -> Self {
+		volume.0
+	}
+}
+
+impl Display for Volume {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self.0 .0 {
+			percentage if percentage >= 0 => write!(f, "{percentage}% louder"),
+			percentage => write!(f, "{}% quieter", percentage.unsigned_abs()),
+		}
+	}
+}
+
+impl_xrbk_traits!(Volume(SignedPercentage));
+
+/// An angle measured in 64ths of a degree.
+///
+/// This is how angles are encoded in the X11 protocol: for example, a right
+/// angle is `Angle(90 * 64)`, not `Angle(90)`. [`from_degrees`] and
+/// [`from_radians`] perform this conversion.
+///
+/// Positive angles are counterclockwise and negative angles are clockwise.
+///
+/// [`from_degrees`]: Angle::from_degrees
+/// [`from_radians`]: Angle::from_radians
+#[derive(
+	Debug,
+	Hash,
+	Default,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Add,
+	AddAssign,
+	Sub,
+	SubAssign,
+	Neg,
+)]
+pub struct Angle(pub i16);
+
+#[allow(
+	clippy::cast_possible_truncation,
+	reason = "angles which don't fit in an `i16` are saturated"
+)]
+impl Angle {
+	/// The number of units in one degree.
+	pub const UNITS_PER_DEGREE: i16 = 64;
+
+	/// A full circle: 360 degrees.
+	pub const FULL_CIRCLE: Self = Self(360 * Self::UNITS_PER_DEGREE);
+
+	/// Creates a new `Angle` from a number of degrees.
+	///
+	/// The angle is rounded to the nearest 64th of a degree. Angles of more
+	/// than ±511 degrees cannot be represented and are saturated.
+	///
+	/// # Examples
+	/// ```
+	/// # use xrb::unit::Angle;
+	/// assert_eq!(Angle::from_degrees(90.0), Angle(5760));
+	/// ```
+	#[must_use]
+	pub fn from_degrees(degrees: f64) -> Self {
+		Self((degrees * f64::from(Self::UNITS_PER_DEGREE)).round() as i16)
+	}
+
+	/// Creates a new `Angle` from a number of radians.
+	///
+	/// The angle is rounded to the nearest 64th of a degree. Angles of more
+	/// than ±511 degrees cannot be represented and are saturated.
+	#[must_use]
+	pub fn from_radians(radians: f64) -> Self {
+		Self::from_degrees(radians.to_degrees())
+	}
+
+	/// Returns this angle in degrees.
+	#[must_use]
+	pub fn degrees(self) -> f64 {
+		f64::from(self.0) / f64::from(Self::UNITS_PER_DEGREE)
+	}
+
+	/// Returns this angle in radians.
+	#[must_use]
+	pub fn radians(self) -> f64 {
+		self.degrees().to_radians()
+	}
+
+	/// Returns the wrapped number of 64ths of a degree.
+	#[must_use]
+	pub const fn unwrap(self) -> i16 {
+		self.0
+	}
+}
+
+impl Display for Angle {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}°", self.degrees())
+	}
+}
+
+impl_xrbk_traits!(Angle(i16));
+
+#[cfg(test)]
+mod test {
+	use std::f64::consts::PI;
+
+	use super::*;
+
+	#[test]
+	#[allow(clippy::float_cmp, reason = "these values are exactly representable")]
+	fn test_angle_conversions() {
+		assert_eq!(Angle::from_degrees(90.0), Angle(90 * 64));
+		assert_eq!(Angle::from_degrees(-360.0), -Angle::FULL_CIRCLE);
+		assert_eq!(Angle::from_radians(PI), Angle(180 * 64));
+		assert_eq!(Angle::from_degrees(1.0 / 64.0), Angle(1));
+
+		assert_eq!(Angle(5760).degrees(), 90.0);
+		assert_eq!(Angle(-1).degrees(), -1.0 / 64.0);
+		assert!((Angle(180 * 64).radians() - PI).abs() < f64::EPSILON);
+
+		// The largest representable angles are just under ±512 degrees.
+		assert_eq!(Angle::from_degrees(511.0), Angle(511 * 64));
+		assert_eq!(Angle::from_degrees(512.0), Angle(i16::MAX));
+		assert_eq!(Angle::from_degrees(-1000.0), Angle(i16::MIN));
+
+		assert_eq!(Angle::from_degrees(45.5).to_string(), "45.5°");
+	}
+
+	#[test]
+	fn test_volume_resolve() {
+		let base = Percentage::new(50).unwrap();
+
+		assert_eq!(Volume::BASE.resolve(base), 50);
+		assert_eq!(Volume::louder(50).unwrap().resolve(base), 75);
+		assert_eq!(Volume::louder(100).unwrap().resolve(base), 100);
+		assert_eq!(Volume::quieter(50).unwrap().resolve(base), 25);
+		assert_eq!(Volume::quieter(100).unwrap().resolve(base), 0);
+
+		assert_eq!(Volume::absolute(-20), Ok(Volume::quieter(20).unwrap()));
+		assert!(Volume::louder(101).is_err());
+		assert!(Volume::absolute(-101).is_err());
+
+		assert_eq!(Volume::quieter(20).unwrap().to_string(), "20% quieter");
+	}
+}
@@ -0,0 +1,188 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A type-state [`Handle`] which tracks the lifecycle of a [window].
+//!
+//! A [`Handle`] only offers the [requests] which are valid in the state the
+//! [window] is in: for example, a [`MapWindow` request] can only be created
+//! for an [`Unmapped`] [window], and once a [`DestroyWindow` request] has been
+//! created, the [`Handle`] is consumed.
+//!
+//! [`Handle`]s do not send anything themselves; the [requests] they return must
+//! be sent in the order they are returned.
+//!
+//! # Examples
+//! ```compile_fail
+//! # use xrb::window::{Handle, Mapped};
+//! # fn mapped() -> Handle<Mapped> { todo!() }
+//! // A mapped window cannot be mapped again.
+//! let (handle, request) = mapped().map();
+//! ```
+//!
+//! [window]: Window
+//! [requests]: crate::message::Request
+//!
+//! [`MapWindow` request]: MapWindow
+//! [`DestroyWindow` request]: DestroyWindow
+
+use std::marker::PhantomData;
+
+use crate::{
+	set::{Attributes, WindowConfig},
+	x11::request::{
+		ChangeWindowAttributes,
+		ConfigureWindow,
+		CreateWindow,
+		DestroyWindow,
+		MapWindow,
+		UnmapWindow,
+	},
+	Window,
+};
+
+mod sealed {
+	pub trait Sealed {}
+}
+
+/// The state of a [window] tracked by a [`Handle`].
+///
+/// This is implemented by [`Unmapped`] and [`Mapped`].
+///
+/// [window]: Window
+pub trait State: sealed::Sealed {}
+
+/// The state of a [window] which has been created but is not mapped.
+///
+/// [window]: Window
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Unmapped {}
+
+/// The state of a [window] which has been mapped.
+///
+/// A window manager may intercept the [`MapWindow` request] with
+/// [`SUBSTRUCTURE_REDIRECT`], in which case the [window] is not actually
+/// viewable until the window manager maps it.
+///
+/// [window]: Window
+///
+/// [`MapWindow` request]: MapWindow
+/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Mapped {}
+
+impl sealed::Sealed for Unmapped {}
+impl sealed::Sealed for Mapped {}
+
+impl State for Unmapped {}
+impl State for Mapped {}
+
+/// A handle to a [window] which tracks whether it is [`Unmapped`] or
+/// [`Mapped`].
+///
+/// See the [module-level documentation] for more information.
+///
+/// [window]: Window
+/// [module-level documentation]: self
+#[derive(Debug, Hash, PartialEq, Eq)]
+#[must_use = "a `Handle` represents a window which has not been destroyed"]
+pub struct Handle<S: State> {
+	window: Window,
+	_state: PhantomData<S>,
+}
+
+impl<S: State> Handle<S> {
+	/// The [window] this `Handle` refers to.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn window(&self) -> Window {
+		self.window
+	}
+
+	/// Creates a [`ConfigureWindow` request] which reconfigures this [window].
+	///
+	/// [window]: Window
+	/// [`ConfigureWindow` request]: ConfigureWindow
+	#[must_use]
+	pub const fn configure(&self, config: WindowConfig) -> ConfigureWindow {
+		ConfigureWindow {
+			target: self.window,
+			config,
+		}
+	}
+
+	/// Creates a [`ChangeWindowAttributes` request] which changes the
+	/// `attributes` of this [window].
+	///
+	/// [window]: Window
+	/// [`ChangeWindowAttributes` request]: ChangeWindowAttributes
+	#[must_use]
+	pub const fn change_attributes(&self, attributes: Attributes) -> ChangeWindowAttributes {
+		ChangeWindowAttributes {
+			target: self.window,
+			attributes,
+		}
+	}
+
+	/// Creates a [`DestroyWindow` request] which destroys this [window],
+	/// consuming this `Handle`.
+	///
+	/// [window]: Window
+	/// [`DestroyWindow` request]: DestroyWindow
+	#[must_use]
+	pub const fn destroy(self) -> DestroyWindow {
+		DestroyWindow {
+			target: self.window,
+		}
+	}
+
+	const fn with_state<T: State>(self) -> Handle<T> {
+		Handle {
+			window: self.window,
+			_state: PhantomData,
+		}
+	}
+}
+
+impl Handle<Unmapped> {
+	/// Creates a `Handle` to the [window] created by the given [`CreateWindow`
+	/// request].
+	///
+	/// [window]: Window
+	/// [`CreateWindow` request]: CreateWindow
+	pub const fn new(request: &CreateWindow) -> Self {
+		Self {
+			window: request.window_id,
+			_state: PhantomData,
+		}
+	}
+
+	/// Creates a [`MapWindow` request] which maps this [window], returning the
+	/// [`Mapped`] `Handle`.
+	///
+	/// [window]: Window
+	/// [`MapWindow` request]: MapWindow
+	pub const fn map(self) -> (Handle<Mapped>, MapWindow) {
+		let request = MapWindow {
+			target: self.window,
+		};
+
+		(self.with_state(), request)
+	}
+}
+
+impl Handle<Mapped> {
+	/// Creates an [`UnmapWindow` request] which unmaps this [window], returning
+	/// the [`Unmapped`] `Handle`.
+	///
+	/// [window]: Window
+	/// [`UnmapWindow` request]: UnmapWindow
+	pub const fn unmap(self) -> (Handle<Unmapped>, UnmapWindow) {
+		let request = UnmapWindow {
+			target: self.window,
+		};
+
+		(self.with_state(), request)
+	}
+}
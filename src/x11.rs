@@ -5,6 +5,9 @@
 //! Messages defined in the core X11 protocol: [requests], [replies], [events],
 //! and [errors].
 //!
+//! A [registry] of all of these messages, indexed by their opcodes and codes,
+//! is also provided.
+//!
 //! [requests]: request
 //! [replies]: reply
 //! [events]: event
@@ -12,5 +15,6 @@
 
 pub mod error;
 pub mod event;
+pub mod registry;
 pub mod reply;
 pub mod request;
@@ -11,6 +11,11 @@
 //! [request]: crate::message::Request
 //! [core X11 protocol]: super
 
+use std::{
+	fmt,
+	fmt::{Display, Formatter},
+};
+
 use crate::message::Error;
 
 use derivative::Derivative;
@@ -25,7 +30,7 @@ derive_xrb! {
 	/// [request]: crate::message::Request
 	/// [major opcode]: crate::message::Request::MAJOR_OPCODE
 	/// [minor opcode]: crate::message::Request::MINOR_OPCODE
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Request: Error(1) {
 		/// The sequence number identifying the [request] that was
@@ -72,7 +77,7 @@ derive_xrb! {
 	///
 	/// [request]: crate::message::Request
 	/// [error]: Error
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Value: Error(2) {
 		/// The sequence number identifying the [request] that was
@@ -120,7 +125,7 @@ derive_xrb! {
 	/// [`Window`]: crate::Window
 	/// [window]: crate::Window
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Window: Error(3) {
 		/// The sequence number identifying the [request] that was
@@ -168,7 +173,7 @@ derive_xrb! {
 	/// [`Pixmap`]: crate::Pixmap
 	/// [pixmap]: crate::Pixmap
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Pixmap: Error(4) {
 		/// The sequence number identifying the [request] that was
@@ -216,7 +221,7 @@ derive_xrb! {
 	/// [`Atom`]: crate::Atom
 	/// [atom]: crate::Atom
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Atom: Error(5) {
 		/// The sequence number identifying the [request] that was
@@ -264,7 +269,7 @@ derive_xrb! {
 	/// [`CursorAppearance`]: crate::CursorAppearance
 	/// [cursor appearance]: crate::CursorAppearance
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct CursorAppearance: Error(6) {
 		/// The sequence number identifying the [request] that was
@@ -313,7 +318,7 @@ derive_xrb! {
 	/// [`Font`]: crate::Font
 	/// [font]: crate::Font
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Font: Error(7) {
 		/// The sequence number identifying the [request] that was
@@ -370,7 +375,7 @@ derive_xrb! {
 	/// [drawable]: crate::Drawable
 	/// [request]: crate::message::Request
 	/// [graphics context]: crate::GraphicsContext
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Match: Error(8) {
 		/// The sequence number identifying the [request] that was
@@ -409,7 +414,7 @@ derive_xrb! {
 	/// [window]: crate::Window
 	/// [pixmap]: crate::Pixmap
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Drawable: Error(9) {
 		/// The sequence number identifying the [request] that was
@@ -475,7 +480,7 @@ derive_xrb! {
 	/// [select to receive]: crate::mask::EventMask
 	/// [button]: crate::Button
 	/// [colormap]: crate::Colormap
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Access: Error(10) {
 		/// The sequence number identifying the [request] that was
@@ -510,7 +515,7 @@ derive_xrb! {
 	/// resource.
 	///
 	/// [error]: Error
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Alloc: Error(11) {
 		/// The sequence number identifying the [request] that was
@@ -548,7 +553,7 @@ derive_xrb! {
 	/// [`Colormap`]: crate::Colormap
 	/// [colormap]: crate::Colormap
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Colormap: Error(12) {
 		/// The sequence number identifying the [request] that was
@@ -596,7 +601,7 @@ derive_xrb! {
 	/// [`GraphicsContext`]: crate::GraphicsContext
 	/// [graphics context]: crate::GraphicsContext
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GraphicsContext: Error(13) {
 		/// The sequence number identifying the [request] that was
@@ -641,7 +646,7 @@ derive_xrb! {
 	/// resource IDs assigned to the client, or the ID is already in use.
 	///
 	/// [error]: Error
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ResourceIdChoice: Error(14) {
 		/// The sequence number identifying the [request] that was
@@ -683,7 +688,7 @@ derive_xrb! {
 	/// [error]: Error
 	/// [request]: crate::message::Request
 	/// [font]: crate::Font
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Name: Error(15) {
 		/// The sequence number identifying the [request] that was
@@ -722,7 +727,7 @@ derive_xrb! {
 	///
 	/// [error]: Error
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Length: Error(16) {
 		/// The sequence number identifying the [request] that was
@@ -758,7 +763,7 @@ derive_xrb! {
 	///
 	/// [error]: Error
 	/// [request]: crate::message::Request
-	#[derive(Debug, Derivative, Writable, Readable, X11Size)]
+	#[derive(Debug, Derivative, Writable, InfallibleWritable, Readable, X11Size)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Implementation: Error(17) {
 		/// The sequence number identifying the [request] that was
@@ -789,3 +794,97 @@ derive_xrb! {
 		[_; ..],
 	}
 }
+
+/// Writes a description of the given `error`, referred to by the given
+/// `name`.
+fn describe(error: &impl Error, name: &str, f: &mut Formatter<'_>) -> fmt::Result {
+	write!(f, "{name} error")?;
+
+	if let Some(value) = error.bad_value() {
+		write!(f, " for value {value:#010x}")?;
+	}
+
+	write!(
+		f,
+		" (major opcode {}, minor opcode {}, sequence {})",
+		error.major_opcode(),
+		error.minor_opcode(),
+		error.sequence(),
+	)
+}
+
+/// Implements [`Display`] and [`std::error::Error`] for the given [errors].
+///
+/// [errors]: Error
+macro_rules! impl_display {
+	($($Error:ident),+$(,)?) => {
+		$(
+			impl Display for $Error {
+				fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+					describe(self, stringify!($Error), f)
+				}
+			}
+
+			impl std::error::Error for $Error {}
+		)+
+	};
+}
+
+impl_display!(
+	Request,
+	Value,
+	Window,
+	Pixmap,
+	Atom,
+	CursorAppearance,
+	Font,
+	Match,
+	Drawable,
+	Access,
+	Alloc,
+	Colormap,
+	GraphicsContext,
+	ResourceIdChoice,
+	Name,
+	Length,
+	Implementation,
+);
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_bad_value_display() {
+		let error = Window {
+			sequence: 42,
+			invalid_window_id: 0x0020_0001,
+			minor_opcode: 0,
+			major_opcode: 8,
+		};
+		assert_eq!(error.bad_value(), Some(0x0020_0001));
+		assert_eq!(
+			error.to_string(),
+			"Window error for value 0x00200001 (major opcode 8, minor opcode 0, sequence 42)",
+		);
+
+		let error = Value {
+			sequence: 1,
+			invalid_value: [0, 0, 1, 0],
+			minor_opcode: 0,
+			major_opcode: 1,
+		};
+		assert_eq!(error.bad_value(), Some(0x100));
+
+		let error = Match {
+			sequence: 1,
+			minor_opcode: 0,
+			major_opcode: 42,
+		};
+		assert_eq!(error.bad_value(), None);
+		assert_eq!(
+			error.to_string(),
+			"Match error (major opcode 42, minor opcode 0, sequence 1)",
+		);
+	}
+}
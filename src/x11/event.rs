@@ -15,12 +15,12 @@ use bitflags::bitflags;
 use derivative::Derivative;
 
 use xrbk::{Buf, ConstantX11Size, ReadResult, Readable, ReadableWithContext, X11Size};
-use xrbk_macro::{derive_xrb, ConstantX11Size, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
 	atom::Atom,
 	message::Event,
-	set::WindowConfigMask,
+	set::{WindowConfig, WindowConfigMask},
 	unit::Px,
 	Button,
 	Coords,
@@ -36,6 +36,9 @@ use crate::{
 	Window,
 };
 
+// Re-exported so that `x11::event::VisibilityState` keeps working.
+pub use crate::VisibilityState;
+
 derive_xrb! {
 	/// An [event] generated when a key is pressed.
 	///
@@ -48,7 +51,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`KEY_PRESS`]: crate::EventMask::KEY_PRESS
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct KeyPress: Event(2) {
 		/// The [sequence number] associated with the last [request] related
@@ -129,7 +132,7 @@ derive_xrb! {
 	///
 	/// [event]: Event
 	/// [`KEY_RELEASE`]: crate::EventMask::KEY_RELEASE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct KeyRelease: Event(3) {
 		/// The [sequence number] associated with the last [request] related
@@ -213,7 +216,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [mouse button]: Button
 	/// [`BUTTON_PRESS`]: crate::EventMask::BUTTON_PRESS
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ButtonPress: Event(4) {
 		/// The [sequence number] associated with the last [request] related
@@ -295,7 +298,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [mouse button]: Button
 	/// [`BUTTON_RELEASE`]: crate::EventMask::BUTTON_RELEASE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ButtonRelease: Event(5) {
 		/// The [sequence number] associated with the last [request] related
@@ -373,7 +376,19 @@ derive_xrb! {
 /// This is used in the [`Motion` event].
 ///
 /// [`Motion` event]: Motion
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Debug,
+	Hash,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum MotionNotificationType {
 	/// The [`Motion` event] was not one generated for a client selecting
 	/// [`MOTION_HINT`].
@@ -440,7 +455,7 @@ derive_xrb! {
 	///
 	/// [event]: Event
 	/// [window]: Window
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Motion: Event(6) {
 		/// The [sequence number] associated with the last [request] related
@@ -560,7 +575,19 @@ derive_xrb! {
 ///
 /// [event]: Event
 /// [window]: Window
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Debug,
+	Hash,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum EnterLeaveDetail {
 	/// Used for [`LeaveWindow` events] when the cursor leaves a [window] and
 	/// enters an ancestor of that [window], and for [`EnterWindow` events]
@@ -615,7 +642,7 @@ pub enum EnterLeaveDetail {
 
 bitflags! {
 	/// A bitmask used in the [`EnterWindow`] and [`LeaveWindow`] events.
-	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable)]
+	#[derive(Default, X11Size, Readable, ConstantX11Size, Writable, InfallibleWritable)]
 	pub struct EnterLeaveMask: u8 {
 		/// Whether the `event_window` is the focused [window] or a descendant
 		/// of the focused [window].
@@ -645,7 +672,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`ENTER_WINDOW`]: crate::EventMask::ENTER_WINDOW
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct EnterWindow: Event(7) {
 		/// The [sequence number] associated with the last [request] related
@@ -736,7 +763,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`LEAVE_WINDOW`]: crate::EventMask::LEAVE_WINDOW
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct LeaveWindow: Event(8) {
 		/// The [sequence number] associated with the last [request] related
@@ -941,7 +968,19 @@ derive_xrb! {
 ///
 /// [event]: Event
 /// [window]: Window
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Debug,
+	Hash,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum FocusDetail {
 	/// Used for [`Unfocus` events] for the [window] which has been unfocused if
 	/// the newly focused [window] is an ancestor of that [window], and for
@@ -990,7 +1029,19 @@ pub enum FocusDetail {
 
 /// Detail about how an [`Unfocus`] or [`Focus`] event was generated in relation
 /// to grabs.
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Debug,
+	Hash,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum FocusGrabMode {
 	/// Used for [`Unfocus`] and [`Focus`] events generated when the keyboard is
 	/// not grabbed.
@@ -1025,7 +1076,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`FOCUS_CHANGE`]: crate::EventMask::FOCUS_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Focus: Event(9) {
 		/// The [sequence number] associated with the last [request] related
@@ -1084,7 +1135,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`FOCUS_CHANGE`]: crate::EventMask::FOCUS_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Unfocus: Event(10) {
 		/// The [sequence number] associated with the last [request] related
@@ -1136,7 +1187,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`KEYBOARD_STATE`]: crate::EventMask::KEYBOARD_STATE
-	#[derive(Debug, Hash, X11Size, Readable, Writable)]
+	#[derive(Debug, Default, Hash, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct KeyboardState: Event(11) {
 		/// A bit vector representing the current keyboard state.
 		///
@@ -1178,7 +1229,7 @@ derive_xrb! {
 	/// [`WindowClass::InputOnly`]: crate::WindowClass::InputOnly
 	///
 	/// [`EXPOSURE`]: crate::EventMask::EXPOSURE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Expose: Event(12) {
 		/// The [sequence number] associated with the last [request] related
@@ -1217,7 +1268,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [`GraphicsContext`]: crate::GraphicsContext
 	/// [`graphics_exposure`]: crate::set::GraphicsOptions::graphics_exposure
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GraphicsExposure: Event(13) {
 		/// The [sequence number] associated with the last [request] related
@@ -1272,7 +1323,7 @@ derive_xrb! {
 	/// [`GraphicsExposure` events]: GraphicsExposure
 	/// [`GraphicsContext`]: crate::GraphicsContext
 	/// [`graphics_exposure`]: crate::set::GraphicsOptions::graphics_exposure
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct NoExposure: Event(14) {
 		/// The [sequence number] associated with the last [request] related
@@ -1309,42 +1360,6 @@ derive_xrb! {
 	}
 }
 
-/// The state of a [window]'s visibility.
-///
-/// This is used in the [`Visibility` event].
-///
-/// [window]: Window
-/// [`Visibility` event]: Visibility
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
-pub enum VisibilityState {
-	/// There is nothing obscuring the `window`.
-	///
-	/// This is used in the [`Visibility` event] when a [window] changes state
-	/// to be `Unobscured`.
-	///
-	/// [window]: Window
-	/// [`Visibility` event]: Visibility
-	Unobscured,
-
-	/// The `window` is partially, but not fully, obscured.
-	///
-	/// This is used in the [`Visibility` event] when a [window] changes state
-	/// to be `PartiallyObscured`.
-	///
-	/// [window]: Window
-	/// [`Visibility` event]: Visibility
-	PartiallyObscured,
-
-	/// The `window` is fully obscured.
-	///
-	/// This is used in the [`Visibility` event] when a [window] changes state
-	/// to be `FullyObscured`.
-	///
-	/// [window]: Window
-	/// [`Visibility` event]: Visibility
-	FullyObscured,
-}
-
 derive_xrb! {
 	/// An [event] generated when changes to a [window]'s visibility occur.
 	///
@@ -1352,17 +1367,17 @@ derive_xrb! {
 	///
 	/// When a [window] changes state from not viewable, [`PartiallyObscured`],
 	/// or [`FullyObscured`] to viewable and [`Unobscured`], a `Visibility`
-	/// event with [`VisibilityState::Unobscured`] is generated.
+	/// event with a `visibility` of [`Unobscured`] is generated.
 	///
 	/// When a [window] changes state from viewable and [`Unobscured`], viewable
 	/// and [`FullyObscured`], or not viewable, to viewable and
-	/// [`PartiallyObscured`], a `Visibility` event with
-	/// [`VisibilityState::PartiallyObscured`] is generated.
+	/// [`PartiallyObscured`], a `Visibility` event with a `visibility` of
+	/// [`PartiallyObscured`] is generated.
 	///
 	/// When a [window] changes state from viewable and [`Unobscured`], viewable
 	/// and [`PartiallyObscured`], or not viewable to viewable and
-	/// [`FullyObscured`], a `Visibility` event with
-	/// [`VisibilityState::FullyObscured`] is generated.
+	/// [`FullyObscured`], a `Visibility` event with a `visibility` of
+	/// [`FullyObscured`] is generated.
 	///
 	/// # Recipients
 	/// This [event] is reported to clients selecting [`VISIBILITY_CHANGE`] on
@@ -1376,7 +1391,7 @@ derive_xrb! {
 	/// [`FullyObscured`]: VisibilityState::FullyObscured
 	///
 	/// [`VISIBILITY_CHANGE`]: crate::EventMask::VISIBILITY_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Visibility: Event(15) {
 		/// The [sequence number] associated with the last [request] related
@@ -1405,7 +1420,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Create: Event(16) {
 		/// The [sequence number] associated with the last [request] related
@@ -1462,7 +1477,7 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Destroy: Event(17) {
 		/// The [sequence number] associated with the last [request] related
@@ -1502,7 +1517,7 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Unmap: Event(18) {
 		/// The [sequence number] associated with the last [request] related
@@ -1550,7 +1565,7 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Map: Event(19) {
 		/// The [sequence number] associated with the last [request] related
@@ -1605,7 +1620,7 @@ derive_xrb! {
 	/// [`MapWindow` request]: super::request::MapWindow
 	///
 	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct MapWindowRequest: Event(20) {
 		/// The [sequence number] associated with the last [request] related
@@ -1641,7 +1656,7 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Reparent: Event(21) {
 		/// The [sequence number] associated with the last [request] related
@@ -1699,7 +1714,7 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Configure: Event(22) {
 		/// The [sequence number] associated with the last [request] related
@@ -1781,7 +1796,7 @@ derive_xrb! {
 	/// [`ConfigureWindow` request]: super::request::ConfigureWindow
 	///
 	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ConfigureWindowRequest: Event(23) {
 		/// The [sequence number] associated with the last [request] related
@@ -1826,6 +1841,8 @@ derive_xrb! {
 		///
 		/// The `window`'s dimensions exclude its border.
 		pub geometry: Rectangle,
+		/// The width of the `window`'s border.
+		pub border_width: Px<u16>,
 
 		/// A bitmask representing which attributes were configured in the
 		/// [`ConfigureWindow` request].
@@ -1834,7 +1851,51 @@ derive_xrb! {
 		pub mask: WindowConfigMask,
 		[_; ..],
 	}
+}
+
+impl ConfigureWindowRequest {
+	/// Returns the [`WindowConfig`] containing only the options which were
+	/// configured in the [`ConfigureWindow` request].
+	///
+	/// This can be modified and then used in a [`ConfigureWindow` request] to
+	/// honor the client's request.
+	///
+	/// [`ConfigureWindow` request]: super::request::ConfigureWindow
+	#[must_use]
+	pub fn config(&self) -> WindowConfig {
+		let mut config = WindowConfig::builder();
+
+		if self.mask.contains(WindowConfigMask::X) {
+			config.x(self.geometry.x);
+		}
+		if self.mask.contains(WindowConfigMask::Y) {
+			config.y(self.geometry.y);
+		}
+		if self.mask.contains(WindowConfigMask::WIDTH) {
+			config.width(self.geometry.width);
+		}
+		if self.mask.contains(WindowConfigMask::HEIGHT) {
+			config.height(self.geometry.height);
+		}
+
+		if self.mask.contains(WindowConfigMask::BORDER_WIDTH) {
+			config.border_width(self.border_width);
+		}
+
+		if self.mask.contains(WindowConfigMask::SIBLING) {
+			if let Some(sibling) = self.sibling {
+				config.sibling(sibling);
+			}
+		}
+		if self.mask.contains(WindowConfigMask::STACK_MODE) {
+			config.stack_mode(self.stack_mode);
+		}
+
+		config.build()
+	}
+}
 
+derive_xrb! {
 	/// An [event] generated when a [window] is moved because its parent is
 	/// resized.
 	///
@@ -1846,7 +1907,7 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Gravity: Event(24) {
 		/// The [sequence number] associated with the last [request] related
@@ -1889,7 +1950,7 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`RESIZE_REDIRECT`]: crate::EventMask::RESIZE_REDIRECT
 	/// [`ConfigureWindow` request]: super::request::ConfigureWindow
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ResizeRequest: Event(25) {
 		/// The [sequence number] associated with the last [request] related
@@ -1929,7 +1990,9 @@ derive_xrb! {
 /// [window]: Window
 /// [`CirculateWindow` request]: super::request::CirculateWindow
 /// [`Circulate` events]: Circulate
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(
+	Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable, InfallibleWritable,
+)]
 pub enum Placement {
 	/// The `window` is now above all its siblings in the stack.
 	Top,
@@ -1951,7 +2014,7 @@ derive_xrb! {
 	///
 	/// [`STRUCTURE_NOTIFY`]: crate::EventMask::STRUCTURE_NOTIFY
 	/// [`SUBSTRUCTURE_NOTIFY`]: crate::EventMask::SUBSTRUCTURE_NOTIFY
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Circulate: Event(26) {
 		/// The [sequence number] associated with the last [request] related
@@ -1994,7 +2057,7 @@ derive_xrb! {
 	/// [window]: Window
 	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
 	/// [`CirculateWindow` request]: super::request::CirculateWindow
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct CirculateWindowRequest: Event(27) {
 		/// The [sequence number] associated with the last [request] related
@@ -2032,15 +2095,31 @@ derive_xrb! {
 /// Whether a `property` was [`Modified`] or [`Deleted`] in a [`Property`
 /// event].
 ///
+/// Any change which is not recognized is read as [`PropertyChange::Unknown`]
+/// rather than failing, so that a [`Property` event] from a newer X server can
+/// still be read.
+///
 /// [`Property` event]: Property
 /// [`Modified`]: PropertyChange::Modified
 /// [`Deleted`]: PropertyChange::Deleted
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(
+	Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable, InfallibleWritable,
+)]
+#[non_exhaustive]
 pub enum PropertyChange {
 	/// The `property` was added or its value was changed.
 	Modified,
 	/// The `property` was removed.
 	Deleted,
+
+	/// A change which is not known to XRB, with the given discriminant.
+	///
+	/// This only holds discriminants which are not recognized: it is written
+	/// as just its discriminant, so `Unknown(0)` is read back as [`Modified`].
+	///
+	/// [`Modified`]: PropertyChange::Modified
+	#[unknown]
+	Unknown(u8),
 }
 
 derive_xrb! {
@@ -2054,7 +2133,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [window]: Window
 	/// [`PROPERTY_CHANGE`]: crate::EventMask::PROPERTY_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Property: Event(28) {
 		/// The [sequence number] associated with the last [request] related
@@ -2093,7 +2172,7 @@ derive_xrb! {
 	///
 	/// [event]: Event
 	/// [`SetSelectionOwner` request]: super::request::SetSelectionOwner
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct SelectionClear: Event(29) {
 		/// The [sequence number] associated with the last [request] related
@@ -2131,7 +2210,7 @@ derive_xrb! {
 	/// [`ConvertSelection` request]: super::request::ConvertSelection
 	/// [`Selection` event]: Selection
 	/// [`SendEvent` request]: super::request::SendEvent
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ConvertSelectionRequest: Event(30) {
 		/// The [sequence number] associated with the last [request] related
@@ -2160,7 +2239,13 @@ derive_xrb! {
 		pub selection: Atom,
 		/// The type that the `selection` should be converted into.
 		pub target_type: Atom,
-		// TODO: undocumented in core protocol, check ICCCM
+		/// The property on the `requester` in which the converted `selection`
+		/// should be stored.
+		///
+		/// [`None`] is sent by obsolete clients; the [ICCCM] recommends that
+		/// the `target_type` is used as the property in that case.
+		///
+		/// [ICCCM]: https://x.org/releases/X11R7.7/doc/xorg-docs/icccm/icccm.html
 		pub property: Option<Atom>,
 		[_; ..],
 	}
@@ -2178,7 +2263,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [`ConvertSelection` request]: super::request::ConvertSelection
 	/// [`SendEvent` request]: super::request::SendEvent
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Selection: Event(31) {
 		/// The [sequence number] associated with the last [request] related
@@ -2207,7 +2292,10 @@ derive_xrb! {
 		///
 		/// The `selection` may or may not have been converted.
 		pub target_type: Atom,
-		// TODO: undocumented in core protocol, check ICCCM.
+		/// The property on the `requester` in which the converted `selection`
+		/// was stored.
+		///
+		/// This is [`None`] if the `selection` could not be converted.
 		pub property: Option<Atom>,
 		[_; ..],
 	}
@@ -2215,7 +2303,7 @@ derive_xrb! {
 	/// The reason why a [`Colormap` event] was generated.
 	///
 	/// [`Colormap` event]: Colormap
-	#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+	#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable)]
 	pub enum ColormapDetail {
 		/// The `window`'s [`colormap` attribute] was changed.
 		///
@@ -2231,7 +2319,7 @@ derive_xrb! {
 	///
 	/// [window]: Window
 	/// [colormap]: crate::Colormap
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable)]
 	pub enum ColormapState {
 		/// The [window]'s [colormap] is not currently installed.
 		///
@@ -2258,7 +2346,7 @@ derive_xrb! {
 	/// [`colormap` attribute]: crate::Attributes::colormap
 	///
 	/// [`COLORMAP_CHANGE`]: crate::EventMask::COLORMAP_CHANGE
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct Colormap: Event(32) {
 		/// The [sequence number] associated with the last [request] related
@@ -2297,7 +2385,19 @@ derive_xrb! {
 /// `i8` values, 10 `i16` values, or 5 `i32` values.
 ///
 /// [`ClientMessage` event]: ClientMessage
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Debug,
+	Hash,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum ClientMessageFormat {
 	/// 20 `i8` values: [`ClientMessageData::I8`].
 	I8 = 8,
@@ -2310,7 +2410,7 @@ pub enum ClientMessageFormat {
 /// The `data` contained in a [`ClientMessage` event].
 ///
 /// [`ClientMessage` event]: ClientMessage
-#[derive(Clone, Eq, PartialEq, Hash, Debug, Writable)]
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Writable, InfallibleWritable)]
 #[no_discrim]
 pub enum ClientMessageData {
 	/// Data comprised of 20 `i8` values.
@@ -2356,7 +2456,7 @@ derive_xrb! {
 	/// [event]: Event
 	/// [`SendEvent` request]: super::request::SendEvent
 	/// [window]: Window
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ClientMessage: Event(33) {
 		/// The [sequence number] associated with the last [request] related
@@ -2390,11 +2490,29 @@ derive_xrb! {
 	}
 }
 
+// Every event is 32 bytes, but `ClientMessage` and `Selection` are the only
+// ones which are commonly sent with a `SendEvent` request, which requires
+// `ConstantX11Size`.
+impl ConstantX11Size for ClientMessage {
+	const X11_SIZE: usize = 32;
+}
+
+impl ConstantX11Size for Selection {
+	const X11_SIZE: usize = 32;
+}
+
 /// Detail about which [request] generated a [`MappingChange` event].
 ///
+/// Any [request] which is not recognized is read as
+/// [`MappingRequest::Unknown`] rather than failing, so that a
+/// [`MappingChange` event] from a newer X server can still be read.
+///
 /// [request]: crate::message::Request
 /// [`MappingChange` event]: MappingChange
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable)]
+#[derive(
+	Copy, Clone, Eq, PartialEq, Debug, Hash, X11Size, Readable, Writable, InfallibleWritable,
+)]
+#[non_exhaustive]
 pub enum MappingRequest {
 	/// The [`MappingChange` event] was generated by a
 	/// [`SetModifierMapping` request].
@@ -2416,6 +2534,16 @@ pub enum MappingRequest {
 	/// [`MappingChange` event]: MappingChange
 	/// [`ChangeKeyboardMapping` request]: super::request::ChangeKeyboardMapping
 	Cursor,
+
+	/// A [request] which is not known to XRB, with the given discriminant.
+	///
+	/// This only holds discriminants which are not recognized: it is written
+	/// as just its discriminant, so `Unknown(0)` is read back as [`Modifier`].
+	///
+	/// [request]: crate::message::Request
+	/// [`Modifier`]: MappingRequest::Modifier
+	#[unknown]
+	Unknown(u8),
 }
 
 derive_xrb! {
@@ -2429,7 +2557,7 @@ derive_xrb! {
 	/// [`SetModifierMapping`]: super::request::SetModifierMapping
 	/// [`ChangeKeyboardMapping`]: super::request::ChangeKeyboardMapping
 	/// [`SetCursorMapping`]: super::request::SetButtonMapping
-	#[derive(Debug, Derivative, X11Size, Readable, Writable)]
+	#[derive(Debug, Derivative, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct MappingChange: Event(34) {
 		/// The [sequence number] associated with the last [request] related
@@ -2464,3 +2592,49 @@ derive_xrb! {
 		[_; ..],
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_util::round_trip;
+
+	#[test]
+	fn test_configure_window_request() {
+		let request = ConfigureWindowRequest {
+			sequence: 7,
+			stack_mode: StackMode::Above,
+			parent: Window::new(0x0000_0100),
+			window: Window::new(0x0020_0001),
+			sibling: None,
+			geometry: Rectangle::new(Px(-10), Px(0), Px(640), Px(480)),
+			border_width: Px(2),
+			mask: WindowConfigMask::X | WindowConfigMask::WIDTH | WindowConfigMask::BORDER_WIDTH,
+		};
+		let bytes = round_trip(&request, 1);
+		assert_eq!(&bytes[24..28], &[0x00, 0x02, 0x00, 0x15]);
+
+		let mut config = WindowConfig::builder();
+		config.x(Px(-10)).width(Px(640)).border_width(Px(2));
+		assert_eq!(request.config(), config.build());
+	}
+
+	#[test]
+	fn test_unknown_event_details() {
+		let property = Property {
+			sequence: 1,
+			window: Window::new(0x0020_0001),
+			property: Atom::new(39),
+			time: Timestamp::new(1000),
+			change: PropertyChange::Unknown(2),
+		};
+		assert_eq!(round_trip(&property, 1)[16], 2);
+
+		let mapping = MappingChange {
+			sequence: 2,
+			request: MappingRequest::Unknown(3),
+			first_keycode: Keycode::new(0),
+			count: 0,
+		};
+		assert_eq!(round_trip(&mapping, 1)[4], 3);
+	}
+}
@@ -0,0 +1,598 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A registry of the messages defined in the [core X11 protocol], indexed by
+//! their opcodes and codes.
+//!
+//! The registry is built directly from the [`MAJOR_OPCODE`s] and [`CODE`s]
+//! of the message types themselves, so it can never disagree with them. It is
+//! used by the [dissector] to find the name of a message and to decode its
+//! body.
+//!
+//! [core X11 protocol]: super
+//! [dissector]: crate::dissect
+//!
+//! [`MAJOR_OPCODE`s]: crate::message::Request::MAJOR_OPCODE
+//! [`CODE`s]: crate::message::Event::CODE
+
+use std::{fmt::Debug, ops::Range};
+
+use xrbk::{check_remaining, Buf, ReadResult, Readable, TrackingBuf};
+
+use crate::{
+	message::{Error, Event, Message, MessageKind, Request, SYNTHETIC_EVENT_FLAG},
+	x11::{error, event, reply, request},
+	DestinationWindow,
+	EventMask,
+};
+
+/// A function which decodes the body of a message from its complete bytes.
+///
+/// The bytes given must begin at the very start of the message (that is, they
+/// must include the message's opcode or code) and must contain the entire
+/// message. They are read through a [`TrackingBuf`] so that the [dissector]
+/// can tell where the message's fields begin and where decoding failed.
+///
+/// [dissector]: crate::dissect
+pub type Decoder = fn(&mut TrackingBuf<&[u8]>) -> ReadResult<Box<dyn DecodedMessage>>;
+
+/// A message decoded by a [`Decoder`].
+///
+/// Its fields can be listed with [`Message::fields`], and it can be formatted
+/// as a whole with [`Debug`].
+pub trait DecodedMessage: Message + Debug {}
+
+impl<T: Message + Debug> DecodedMessage for T {}
+
+/// An entry in the registry describing a single message type.
+#[derive(Clone, Copy)]
+pub struct Entry {
+	/// The name of the message type.
+	pub name: &'static str,
+	/// The opcode or code of the message type.
+	///
+	/// For [requests] and [replies], this is the [major opcode] of the
+	/// [request]. For [events] and [errors], this is their [code].
+	///
+	/// [requests]: Request
+	/// [request]: Request
+	/// [replies]: crate::message::Reply
+	/// [events]: Event
+	/// [errors]: Error
+	///
+	/// [major opcode]: Request::MAJOR_OPCODE
+	/// [code]: Event::CODE
+	pub code: u8,
+	/// Decodes the message.
+	pub decode: Decoder,
+
+	/// Decodes the message from its complete bytes and encodes it again, so
+	/// that every message in the registry can be round-tripped in tests.
+	#[cfg(test)]
+	pub(crate) reencode: fn(&[u8]) -> ReadResult<Vec<u8>>,
+}
+
+impl Debug for Entry {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Entry")
+			.field("name", &self.name)
+			.field("code", &self.code)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Decodes a `T` from `bytes`, skipping the `HEADER` bytes which have already
+/// been interpreted to identify the message.
+fn decode<T: Readable + Message + Debug + 'static, const HEADER: usize>(
+	buf: &mut TrackingBuf<&[u8]>,
+) -> ReadResult<Box<dyn DecodedMessage>> {
+	check_remaining(buf, HEADER)?;
+	buf.advance(HEADER);
+
+	Ok(Box::new(T::read_from(buf)?))
+}
+
+/// Decodes a `T` from `bytes`, skipping the `HEADER` bytes, and encodes it
+/// again.
+#[cfg(test)]
+fn reencode<T: Readable + xrbk::Writable, const HEADER: usize>(
+	bytes: &[u8],
+) -> ReadResult<Vec<u8>> {
+	let message = T::read_from(&mut &bytes[HEADER..])?;
+
+	let mut encoded = vec![];
+	xrbk::Writable::write_to(&message, &mut encoded)
+		.expect("a decoded message should be encodable");
+
+	Ok(encoded)
+}
+
+/// The decoded body of a [`SendEvent` request], the `event` of which is
+/// itself decoded with the registry.
+///
+/// [`SendEvent` request]: request::SendEvent
+#[derive(Debug)]
+struct SendEvent {
+	propagate: bool,
+	destination: DestinationWindow,
+	event_mask: EventMask,
+	event: Option<Box<dyn DecodedMessage>>,
+}
+
+impl Message for SendEvent {
+	fn name(&self) -> &'static str {
+		"SendEvent"
+	}
+
+	fn kind(&self) -> MessageKind {
+		// `request::SendEvent` is generic, so its `MAJOR_OPCODE` cannot be named
+		// without choosing an event type.
+		MessageKind::Request {
+			major_opcode: 25,
+			minor_opcode: None,
+		}
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("propagate", &self.propagate),
+			("destination", &self.destination),
+			("event_mask", &self.event_mask),
+			("event", &self.event),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		// The event begins after the request's 12 bytes of other fields.
+		self.event.as_ref().map_or_else(Vec::new, |event| {
+			event
+				.unused()
+				.into_iter()
+				.map(|range| (range.start + 12)..(range.end + 12))
+				.collect()
+		})
+	}
+}
+
+fn decode_send_event(buf: &mut TrackingBuf<&[u8]>) -> ReadResult<Box<dyn DecodedMessage>> {
+	// The major opcode.
+	check_remaining(buf, 1)?;
+	buf.advance(1);
+	let propagate = bool::read_from(buf)?;
+	// The length of the request, which is always 11.
+	check_remaining(buf, 2)?;
+	buf.advance(2);
+
+	let destination = DestinationWindow::read_from(buf)?;
+	let event_mask = EventMask::read_from(buf)?;
+
+	let event = match buf
+		.chunk()
+		.first()
+		.and_then(|code| event(code & !SYNTHETIC_EVENT_FLAG))
+	{
+		Some(entry) => Some((entry.decode)(buf)?),
+		None => None,
+	};
+
+	Ok(Box::new(SendEvent {
+		propagate,
+		destination,
+		event_mask,
+		event,
+	}))
+}
+
+macro_rules! registry {
+	(
+		$(#[$meta:meta])*
+		$vis:vis static $NAME:ident: $Trait:ident::$CODE:ident in $module:ident, skip $header:literal {
+			$($Type:ident $(<$Arg:ty>($code:literal) => $decoder:expr)?),+$(,)?
+		}
+	) => {
+		$(#[$meta])*
+		$vis static $NAME: &[Entry] = &[$(
+			registry!(@entry $Trait::$CODE, $module::$Type, $header $(, <$Arg>($code) => $decoder)?)
+		),+];
+	};
+
+	(@entry $Trait:ident::$CODE:ident, $module:ident::$Type:ident, $header:literal) => {
+		Entry {
+			name: stringify!($Type),
+			code: <$module::$Type as $Trait>::$CODE,
+			decode: decode::<$module::$Type, $header>,
+
+			#[cfg(test)]
+			reencode: reencode::<$module::$Type, $header>,
+		}
+	};
+	// Generic message types cannot name their code without choosing a type
+	// argument, so their code and decoder must be given explicitly. The type
+	// argument is only used to round-trip the message in tests.
+	(
+		@entry $Trait:ident::$CODE:ident, $module:ident::$Type:ident, $header:literal,
+		<$Arg:ty>($code:literal) => $decoder:expr
+	) => {
+		Entry {
+			name: stringify!($Type),
+			code: $code,
+			decode: $decoder,
+
+			#[cfg(test)]
+			reencode: reencode::<$module::$Type<$Arg>, $header>,
+		}
+	};
+}
+
+registry! {
+	/// Every [request] defined in the core X11 protocol, in order of their
+	/// [major opcodes].
+	///
+	/// [request]: Request
+	/// [major opcodes]: Request::MAJOR_OPCODE
+	#[allow(deprecated, reason = "deprecated requests are still part of the protocol")]
+	pub static REQUESTS: Request::MAJOR_OPCODE in request, skip 1 {
+		CreateWindow,
+		ChangeWindowAttributes,
+		GetWindowAttributes,
+		DestroyWindow,
+		DestroyChildren,
+		ChangeSavedWindows,
+		ReparentWindow,
+		MapWindow,
+		MapChildren,
+		UnmapWindow,
+		UnmapChildren,
+		ConfigureWindow,
+		CirculateWindow,
+		GetGeometry,
+		QueryWindowTree,
+		GetAtom,
+		GetAtomName,
+		ModifyProperty,
+		DeleteProperty,
+		GetProperty,
+		ListProperties,
+		SetSelectionOwner,
+		GetSelectionOwner,
+		ConvertSelection,
+		SendEvent<event::ClientMessage>(25) => decode_send_event,
+		GrabCursor,
+		UngrabCursor,
+		GrabButton,
+		UngrabButton,
+		ChangeActiveCursorGrab,
+		GrabKeyboard,
+		UngrabKeyboard,
+		GrabKey,
+		UngrabKey,
+		AllowEvents,
+		GrabServer,
+		UngrabServer,
+		QueryCursorLocation,
+		GetMotionHistory,
+		ConvertCoordinates,
+		WarpCursor,
+		SetFocus,
+		GetFocus,
+		QueryKeyboard,
+		AssignFont,
+		UnassignFont,
+		QueryFont,
+		QueryTextExtents,
+		ListFonts,
+		ListFontsWithInfo,
+		SetFontSearchDirectories,
+		GetFontSearchDirectories,
+		CreatePixmap,
+		FreePixmap,
+		CreateGraphicsContext,
+		ChangeGraphicsOptions,
+		CopyGraphicsOptions,
+		SetDashes,
+		SetClipRectangles,
+		DestroyGraphicsContext,
+		ClearArea,
+		CopyArea,
+		CopyBitPlane,
+		DrawPoints,
+		DrawPath,
+		DrawLines,
+		DrawRectangles,
+		DrawArcs,
+		FillPolygon,
+		FillRectangles,
+		FillArcs,
+		PlaceImage,
+		CaptureImage,
+		DrawText8,
+		DrawText16,
+		ImageText8,
+		ImageText16,
+		CreateColormap,
+		DestroyColormap,
+		MoveColormap,
+		InstallColormap,
+		UninstallColormap,
+		ListInstalledColormaps,
+		AllocateColor,
+		AllocateNamedColor,
+		AllocateColorCells,
+		AllocateColorPlanes,
+		DestroyColormapEntries,
+		StoreColors,
+		StoreNamedColor,
+		QueryColors,
+		GetNamedColor,
+		CreateCursorAppearance,
+		CreateGlyphCursorAppearance,
+		DestroyCursorAppearance,
+		RecolorCursorAppearance,
+		QueryIdealDimensions,
+		QueryExtension,
+		ListExtensions,
+		GetKeyboardMapping,
+		ChangeKeyboardOptions,
+		GetKeyboardOptions,
+		RingBell,
+		ChangeCursorOptions,
+		GetCursorOptions,
+		SetScreenSaver,
+		GetScreenSaver,
+		ChangeHosts,
+		QueryAccessControl,
+		SetAccessControl,
+		SetRetainResourcesMode,
+		KillClient,
+		RotateProperties,
+		ForceScreenSaver,
+		SetButtonMapping,
+		GetButtonMapping,
+		SetModifierMapping,
+		GetModifierMapping,
+		NoOp,
+	}
+}
+
+macro_rules! replies {
+	(
+		$(#[$meta:meta])*
+		$vis:vis static $NAME:ident {
+			$($Type:ident),+$(,)?
+		}
+	) => {
+		$(#[$meta])*
+		$vis static $NAME: &[Entry] = &[$(
+			Entry {
+				name: stringify!($Type),
+				code: <request::$Type as Request>::MAJOR_OPCODE,
+				decode: decode::<reply::$Type, 1>,
+
+				#[cfg(test)]
+				reencode: reencode::<reply::$Type, 1>,
+			}
+		),+];
+	};
+}
+
+replies! {
+	/// Every [reply] defined in the core X11 protocol, indexed by the
+	/// [major opcode] of the [request] which generates it.
+	///
+	/// [reply]: crate::message::Reply
+	/// [request]: Request
+	/// [major opcode]: Request::MAJOR_OPCODE
+	#[allow(deprecated, reason = "deprecated requests are still part of the protocol")]
+	pub static REPLIES {
+		GetWindowAttributes,
+		GetGeometry,
+		QueryWindowTree,
+		GetAtom,
+		GetAtomName,
+		GetProperty,
+		ListProperties,
+		GetSelectionOwner,
+		GrabCursor,
+		GrabKeyboard,
+		QueryCursorLocation,
+		GetMotionHistory,
+		ConvertCoordinates,
+		GetFocus,
+		QueryKeyboard,
+		QueryFont,
+		QueryTextExtents,
+		ListFonts,
+		ListFontsWithInfo,
+		GetFontSearchDirectories,
+		CaptureImage,
+		ListInstalledColormaps,
+		AllocateColor,
+		AllocateNamedColor,
+		AllocateColorCells,
+		AllocateColorPlanes,
+		QueryColors,
+		GetNamedColor,
+		QueryIdealDimensions,
+		QueryExtension,
+		ListExtensions,
+		GetKeyboardMapping,
+		GetKeyboardOptions,
+		GetCursorOptions,
+		GetScreenSaver,
+		QueryAccessControl,
+		SetButtonMapping,
+		GetButtonMapping,
+		SetModifierMapping,
+		GetModifierMapping,
+	}
+}
+
+registry! {
+	/// Every [event] defined in the core X11 protocol, in order of their
+	/// [codes].
+	///
+	/// [event]: Event
+	/// [codes]: Event::CODE
+	pub static EVENTS: Event::CODE in event, skip 1 {
+		KeyPress,
+		KeyRelease,
+		ButtonPress,
+		ButtonRelease,
+		Motion,
+		EnterWindow,
+		LeaveWindow,
+		Focus,
+		Unfocus,
+		KeyboardState,
+		Expose,
+		GraphicsExposure,
+		NoExposure,
+		Visibility,
+		Create,
+		Destroy,
+		Unmap,
+		Map,
+		MapWindowRequest,
+		Reparent,
+		Configure,
+		ConfigureWindowRequest,
+		Gravity,
+		ResizeRequest,
+		Circulate,
+		CirculateWindowRequest,
+		Property,
+		SelectionClear,
+		ConvertSelectionRequest,
+		Selection,
+		Colormap,
+		ClientMessage,
+		MappingChange,
+	}
+}
+
+registry! {
+	/// Every [error] defined in the core X11 protocol, in order of their
+	/// [codes].
+	///
+	/// [error]: Error
+	/// [codes]: Error::CODE
+	pub static ERRORS: Error::CODE in error, skip 2 {
+		Request,
+		Value,
+		Window,
+		Pixmap,
+		Atom,
+		CursorAppearance,
+		Font,
+		Match,
+		Drawable,
+		Access,
+		Alloc,
+		Colormap,
+		GraphicsContext,
+		ResourceIdChoice,
+		Name,
+		Length,
+		Implementation,
+	}
+}
+
+fn find(entries: &'static [Entry], code: u8) -> Option<&'static Entry> {
+	entries.iter().find(|entry| entry.code == code)
+}
+
+/// Returns the [entry] for the [request] with the given [major opcode], if
+/// there is one.
+///
+/// [entry]: Entry
+/// [request]: Request
+/// [major opcode]: Request::MAJOR_OPCODE
+#[must_use]
+pub fn request(major_opcode: u8) -> Option<&'static Entry> {
+	find(REQUESTS, major_opcode)
+}
+
+/// Returns the [entry] for the [reply] generated by the [request] with the
+/// given [major opcode], if there is one.
+///
+/// [entry]: Entry
+/// [reply]: crate::message::Reply
+/// [request]: Request
+/// [major opcode]: Request::MAJOR_OPCODE
+#[must_use]
+pub fn reply(major_opcode: u8) -> Option<&'static Entry> {
+	find(REPLIES, major_opcode)
+}
+
+/// Returns the [entry] for the [event] with the given [code], if there is one.
+///
+/// [entry]: Entry
+/// [event]: Event
+/// [code]: Event::CODE
+#[must_use]
+pub fn event(code: u8) -> Option<&'static Entry> {
+	find(EVENTS, code)
+}
+
+/// Returns the [entry] for the [error] with the given [code], if there is one.
+///
+/// [entry]: Entry
+/// [error]: Error
+/// [code]: Error::CODE
+#[must_use]
+pub fn error(code: u8) -> Option<&'static Entry> {
+	find(ERRORS, code)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Asserts that the codes of `entries` are strictly increasing, and thus
+	/// that no two message types share a code.
+	fn assert_unique(entries: &[Entry]) {
+		for pair in entries.windows(2) {
+			assert!(
+				pair[0].code < pair[1].code,
+				"{} ({}) and {} ({}) are out of order or share a code",
+				pair[0].name,
+				pair[0].code,
+				pair[1].name,
+				pair[1].code,
+			);
+		}
+	}
+
+	#[test]
+	fn test_codes_are_unique() {
+		assert_unique(REQUESTS);
+		assert_unique(REPLIES);
+		assert_unique(EVENTS);
+		assert_unique(ERRORS);
+	}
+
+	#[test]
+	fn test_request_opcodes_are_complete() {
+		// `ChangeKeyboardMapping` cannot be read, so it is not in the registry.
+		let change_keyboard_mapping = <request::ChangeKeyboardMapping<1> as Request>::MAJOR_OPCODE;
+
+		let expected: Vec<u8> = (1..=119)
+			.filter(|&opcode| opcode != change_keyboard_mapping)
+			.chain([127])
+			.collect();
+		let opcodes: Vec<u8> = REQUESTS.iter().map(|entry| entry.code).collect();
+
+		assert_eq!(change_keyboard_mapping, 100);
+		assert_eq!(opcodes, expected);
+	}
+
+	#[test]
+	fn test_event_and_error_codes_are_complete() {
+		let codes: Vec<u8> = EVENTS.iter().map(|entry| entry.code).collect();
+		assert_eq!(codes, (2..=34).collect::<Vec<_>>());
+
+		let codes: Vec<u8> = ERRORS.iter().map(|entry| entry.code).collect();
+		assert_eq!(codes, (1..=17).collect::<Vec<_>>());
+	}
+}
@@ -29,3 +29,197 @@ pub mod input;
 pub mod meta;
 pub mod miscellaneous;
 pub mod window;
+
+use std::{
+	fmt::{self, Debug, Formatter},
+	ops::{Deref, DerefMut},
+};
+
+use xrbk::{
+	Buf,
+	BufMut,
+	InfallibleWritable,
+	ReadResult,
+	Readable,
+	ReadableWithContext,
+	Writable,
+	WriteResult,
+	X11Size,
+};
+
+#[cfg(not(feature = "smallvec"))]
+type ReplyListStorage<T> = Vec<T>;
+#[cfg(feature = "smallvec")]
+type ReplyListStorage<T> = smallvec::SmallVec<[T; 8]>;
+
+/// A list in [replies] which is usually short, such as the children of a
+/// window.
+///
+/// A `ReplyList` dereferences to a slice, and can be converted into a [`Vec`]
+/// with [`into_vec`]. If the `smallvec` feature is enabled, up to 8 elements
+/// are stored without allocating. This avoids allocating for most of these
+/// [replies], which can add up in an event loop.
+///
+/// A `ReplyList` can be created from a [`Vec`] or a slice with [`From`], or
+/// from an iterator with [`FromIterator`].
+///
+/// `ReplyList` is used whether or not the `smallvec` feature is enabled, so
+/// that enabling the feature doesn't change the types of [reply][replies]
+/// fields.
+///
+/// [replies]: crate::message::Reply
+/// [`into_vec`]: ReplyList::into_vec
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub struct ReplyList<T>(ReplyListStorage<T>);
+
+impl<T> ReplyList<T> {
+	/// Creates a new, empty `ReplyList`.
+	#[must_use]
+	#[allow(
+		clippy::missing_const_for_fn,
+		reason = "`SmallVec::new` is not a `const fn`"
+	)]
+	pub fn new() -> Self {
+		Self(ReplyListStorage::new())
+	}
+
+	/// Converts this `ReplyList` into a [`Vec`].
+	///
+	/// This does not allocate unless the `smallvec` feature is enabled and
+	/// the elements were stored without allocating.
+	#[must_use]
+	pub fn into_vec(self) -> Vec<T> {
+		#[cfg(not(feature = "smallvec"))]
+		return self.0;
+
+		#[cfg(feature = "smallvec")]
+		return self.0.into_vec();
+	}
+}
+
+impl<T> Default for ReplyList<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Debug> Debug for ReplyList<T> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		Debug::fmt(&**self, f)
+	}
+}
+
+impl<T> Deref for ReplyList<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &[T] {
+		&self.0
+	}
+}
+
+impl<T> DerefMut for ReplyList<T> {
+	fn deref_mut(&mut self) -> &mut [T] {
+		&mut self.0
+	}
+}
+
+impl<T: PartialEq> PartialEq<Vec<T>> for ReplyList<T> {
+	fn eq(&self, other: &Vec<T>) -> bool {
+		**self == **other
+	}
+}
+
+impl<T> From<Vec<T>> for ReplyList<T> {
+	fn from(vec: Vec<T>) -> Self {
+		#[cfg(not(feature = "smallvec"))]
+		return Self(vec);
+
+		#[cfg(feature = "smallvec")]
+		return Self(smallvec::SmallVec::from_vec(vec));
+	}
+}
+
+impl<T: Clone> From<&[T]> for ReplyList<T> {
+	fn from(slice: &[T]) -> Self {
+		Self(slice.into())
+	}
+}
+
+impl<T> From<ReplyList<T>> for Vec<T> {
+	fn from(list: ReplyList<T>) -> Self {
+		list.into_vec()
+	}
+}
+
+impl<T> FromIterator<T> for ReplyList<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		Self(iter.into_iter().collect())
+	}
+}
+
+impl<T> IntoIterator for ReplyList<T> {
+	type IntoIter = std::vec::IntoIter<T>;
+	type Item = T;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.into_vec().into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a ReplyList<T> {
+	type IntoIter = std::slice::Iter<'a, T>;
+	type Item = &'a T;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<T: X11Size> X11Size for ReplyList<T> {
+	fn x11_size(&self) -> usize {
+		self.0.x11_size()
+	}
+}
+
+impl<T: Readable> ReadableWithContext for ReplyList<T> {
+	type Context = usize;
+
+	fn read_with(reader: &mut impl Buf, context: &Self::Context) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		Ok(Self(<ReplyListStorage<T>>::read_with(reader, context)?))
+	}
+}
+
+impl<T: Writable> Writable for ReplyList<T> {
+	fn write_to(&self, writer: &mut impl BufMut) -> WriteResult {
+		self.0.write_to(writer)
+	}
+}
+
+impl<T: InfallibleWritable> InfallibleWritable for ReplyList<T> {}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_reply_list() {
+		let list: ReplyList<u16> = (1..=10).collect();
+		assert_eq!(list.len(), 10);
+		assert_eq!(
+			format!("{list:?}"),
+			format!("{:?}", (1..=10).collect::<Vec<u16>>())
+		);
+
+		let mut bytes = vec![];
+		list.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), list.x11_size());
+
+		let read = ReplyList::<u16>::read_with(&mut &bytes[..], &10).unwrap();
+		assert_eq!(read, list);
+		assert_eq!(read, (1..=10).collect::<Vec<_>>());
+		assert_eq!(read.into_vec(), (1..=10).collect::<Vec<_>>());
+	}
+}
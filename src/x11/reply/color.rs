@@ -17,14 +17,25 @@
 extern crate self as xrb;
 
 use derivative::Derivative;
-use xrbk::{Buf, BufMut, ConstantX11Size, ReadResult, Readable, Writable, WriteResult, X11Size};
+use std::{fmt::Debug, ops::Range};
+use xrbk::{
+	check_remaining,
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteResult,
+	X11Size,
+};
 
 use xrbk_macro::derive_xrb;
 
 use crate::{
-	message::Reply,
+	message::{Message, MessageKind, Reply},
 	visual::{ColorId, RgbColor},
-	x11::request,
+	x11::{reply::ReplyList, request},
 	Colormap,
 };
 
@@ -34,7 +45,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`ListInstalledColormaps` request]: request::ListInstalledColormaps
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ListInstalledColormaps: Reply for request::ListInstalledColormaps {
 		/// The sequence number identifying the [request] that generated this
@@ -66,7 +77,7 @@ derive_xrb! {
 		/// [colormaps]: Colormaps
 		/// [screen]: crate::visual::Screen
 		#[context(colormaps_len => usize::from(*colormaps_len))]
-		pub colormaps: Vec<Colormap>,
+		pub colormaps: ReplyList<Colormap>,
 	}
 
 	/// The [reply] to an [`AllocateColor` request].
@@ -75,7 +86,7 @@ derive_xrb! {
 	///
 	/// [`AllocateColor` request]: request::AllocateColor
 	#[doc(alias("AllocColor"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct AllocateColor: Reply for request::AllocateColor {
 		/// The sequence number identifying the [request] that generated this
@@ -109,7 +120,7 @@ derive_xrb! {
 	///
 	/// [`AllocateNamedColor` request]: request::AllocateNamedColor
 	#[doc(alias("AllocNamedColor"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct AllocateNamedColor: Reply for request::AllocateNamedColor {
 		/// The sequence number identifying the [request] that generated this
@@ -138,7 +149,7 @@ derive_xrb! {
 	///
 	/// [`AllocateColorCells` request]: request::AllocateColorCells
 	#[doc(alias("AllocColorCells"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct AllocateColorCells: Reply for request::AllocateColorCells {
 		/// The sequence number identifying the [request] that generated this
@@ -196,7 +207,7 @@ derive_xrb! {
 	///
 	/// [`AllocateColorPlanes` request]: request::AllocateColorPlanes
 	#[doc(alias("AllocColorPlanes"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct AllocateColorPlanes: Reply for request::AllocateColorPlanes {
 		/// The sequence number identifying the [request] that generated this
@@ -281,23 +292,58 @@ impl Reply for QueryColors {
 	}
 }
 
+impl Message for QueryColors {
+	fn name(&self) -> &'static str {
+		"QueryColors"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_reply::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![("sequence", &self.sequence), ("colors", &self.colors)]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		const HEADER: usize = 32;
+		const COLOR_SIZE: usize = RgbColor::X11_SIZE + 2;
+
+		let mut unused = vec![1..2, 10..32];
+		// Each color is followed by 2 unused bytes.
+		unused.extend((1..=self.colors.len()).map(|count| {
+			let end = HEADER + (count * COLOR_SIZE);
+
+			(end - 2)..end
+		}));
+
+		unused
+	}
+}
+
 impl X11Size for QueryColors {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 8;
+		// Each color is followed by 2 unused bytes.
+		const COLOR_SIZE: usize = RgbColor::X11_SIZE + 2;
 
-		HEADER + u16::X11_SIZE + 22 + self.colors.x11_size()
+		HEADER + u16::X11_SIZE + 22 + (self.colors.len() * COLOR_SIZE)
 	}
 }
 
 impl Readable for QueryColors {
 	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+		check_remaining(buf, 1)?;
 		buf.advance(1);
-		let sequence = buf.get_u16();
+		let sequence = u16::read_from(buf)?;
 
-		let length = (buf.get_u32() as usize) * 4;
-		let buf = &mut buf.take(length - 8);
+		// The length doesn't include the first 32 bytes, 8 of which have been
+		// read.
+		let length = ((u32::read_from(buf)? as usize) * 4) + (32 - 8);
+		let buf = &mut buf.take(length);
 
-		let colors_len = buf.get_u16();
+		let colors_len = u16::read_from(buf)?;
+		check_remaining(buf, 22)?;
 		buf.advance(22);
 
 		let colors = {
@@ -305,6 +351,7 @@ impl Readable for QueryColors {
 
 			for _ in 0..colors_len {
 				colors.push(RgbColor::read_from(buf)?);
+				check_remaining(buf, 2)?;
 				buf.advance(2);
 			}
 
@@ -318,7 +365,7 @@ impl Readable for QueryColors {
 impl Writable for QueryColors {
 	#[allow(clippy::cast_possible_truncation)]
 	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
-		let buf = &mut buf.limit((self.length() as usize) * 4);
+		let buf = &mut buf.limit(self.x11_size());
 
 		buf.put_u8(1);
 		buf.put_u8(0);
@@ -344,7 +391,7 @@ derive_xrb! {
 	///
 	/// [`GetNamedColor` request]: request::GetNamedColor
 	#[doc(alias("LookupColor"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetNamedColor: Reply for request::GetNamedColor {
 		/// The sequence number identifying the [request] that generated this
@@ -17,8 +17,10 @@
 extern crate self as xrb;
 
 use derivative::Derivative;
+use std::{fmt::Debug, ops::Range};
 
 use xrbk::{
+	check_remaining,
 	pad,
 	Buf,
 	BufMut,
@@ -30,14 +32,33 @@ use xrbk::{
 	WriteResult,
 	X11Size,
 };
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
-
-use crate::{message::Reply, x11::request, Atom, LengthString8, String8};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
+
+use crate::{
+	message::{Message, MessageKind, Reply},
+	x11::request,
+	Atom,
+	Char16,
+	LengthString8,
+	String8,
+};
 
 /// A property of a font.
 ///
 /// The value of this property is uninterpreted by XRB.
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub struct FontProperty {
 	/// The name of the font property.
 	pub name: Atom,
@@ -52,7 +73,21 @@ pub struct FontProperty {
 /// Information about a particular character within a font.
 ///
 /// For a nonexistent character, all of these fields are zero.
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[doc(alias = "CharInfo")]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub struct CharacterInfo {
 	/// The extent of this character's appearance beyond its left edge.
 	///
@@ -85,10 +120,6 @@ pub struct CharacterInfo {
 	pub attributes: u16,
 }
 
-impl ConstantX11Size for CharacterInfo {
-	const X11_SIZE: usize = 12;
-}
-
 /// A hint as to whether most [`CharacterInfo`]s in a font have a positive or
 /// negative width.
 ///
@@ -97,7 +128,19 @@ impl ConstantX11Size for CharacterInfo {
 ///
 /// [`LeftToRight`]: DrawDirection::LeftToRight
 /// [`RightToLeft`]: DrawDirection::RightToLeft
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum DrawDirection {
 	/// Most [`CharacterInfo`]s in the font have a positive width.
 	LeftToRight,
@@ -105,17 +148,13 @@ pub enum DrawDirection {
 	RightToLeft,
 }
 
-impl ConstantX11Size for DrawDirection {
-	const X11_SIZE: usize = 1;
-}
-
 derive_xrb! {
 	/// The [reply] to a [`QueryFont` request].
 	///
 	/// [reply]: Reply
 	///
 	/// [`QueryFont` request]: request::QueryFont
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryFont: Reply for request::QueryFont {
 		/// The sequence number identifying the [request] that generated this
@@ -267,7 +306,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`QueryTextExtents` request]: request::QueryTextExtents
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryTextExtents: Reply for request::QueryTextExtents {
 		/// The sequence number identifying the [request] that generated this
@@ -325,7 +364,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`ListFonts` request]: request::ListFonts
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ListFonts: Reply for request::ListFonts {
 		/// The sequence number identifying the [request] that generated this
@@ -354,6 +393,195 @@ derive_xrb! {
 	}
 }
 
+/// The extents of a string of text, computed client-side with
+/// [`QueryFont::text_extents`].
+///
+/// These are the same extents returned by the X server in a
+/// [`QueryTextExtents` reply].
+///
+/// [`QueryTextExtents` reply]: QueryTextExtents
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct TextExtents {
+	/// The highest individual `ascent` of any character in the text.
+	pub overall_ascent: i16,
+	/// The largest individual `descent` of any character in the text.
+	///
+	/// `descent` is measured downwards from the baseline, so this is the
+	/// distance below the baseline reached by the lowest character.
+	pub overall_descent: i16,
+
+	/// The sum of the `width`s of each character in the text.
+	pub overall_width: i32,
+
+	/// If the 'left side' of each character is the sum of the `width`s of
+	/// all characters before it plus its `left_side_bearing`, this is the
+	/// leftmost left side.
+	pub overall_left: i32,
+	/// If the 'right side' of each character is the sum of the `width`s of
+	/// all characters before it, plus its `right_side_bearing`, this is the
+	/// rightmost right side.
+	pub overall_right: i32,
+}
+
+impl FontProperty {
+	/// Interprets the `value` of this property as a `u32` value.
+	#[must_use]
+	pub const fn as_u32(&self) -> u32 {
+		u32::from_be_bytes(self.value)
+	}
+
+	/// Interprets the `value` of this property as an [`Atom`].
+	///
+	/// Many font properties, such as `FAMILY_NAME`, have an [`Atom`] as their
+	/// value, the name of which is the value of the property.
+	#[must_use]
+	pub const fn as_atom(&self) -> Atom {
+		Atom::new(self.as_u32())
+	}
+}
+
+impl CharacterInfo {
+	/// The width of the ink of this character - that is, the distance between
+	/// its `left_side_bearing` and its `right_side_bearing`.
+	#[must_use]
+	pub const fn ink_width(&self) -> i32 {
+		self.right_side_bearing as i32 - self.left_side_bearing as i32
+	}
+
+	/// The height of this character - that is, the sum of its `ascent` and
+	/// its `descent`.
+	#[must_use]
+	pub const fn height(&self) -> i32 {
+		self.ascent as i32 + self.descent as i32
+	}
+
+	/// Whether this `CharacterInfo` represents a nonexistent character.
+	///
+	/// A nonexistent character has all of its fields set to zero.
+	#[must_use]
+	pub const fn is_nonexistent(&self) -> bool {
+		self.left_side_bearing == 0
+			&& self.right_side_bearing == 0
+			&& self.width == 0
+			&& self.ascent == 0
+			&& self.descent == 0
+			&& self.attributes == 0
+	}
+}
+
+impl QueryFont {
+	/// Returns the [`CharacterInfo`] for the given `character`, or [`None`] if
+	/// that character does not exist in the font.
+	///
+	/// For fonts indexed by a single byte, the `character` should be
+	/// a [`Char8`] or have a first byte of zero.
+	///
+	/// [`Char8`]: crate::Char8
+	#[must_use]
+	pub fn character_info(&self, character: impl Into<Char16>) -> Option<&CharacterInfo> {
+		let (major, minor) = character.into().unwrap();
+
+		let first = self.first_character_or_min_minor_index;
+		let last = self.last_character_or_max_minor_index;
+
+		let index = if self.min_major_index == 0 && self.max_major_index == 0 {
+			// The font is indexed linearly.
+			let character = u16::from_be_bytes([major, minor]);
+
+			if !(first..=last).contains(&character) {
+				return None;
+			}
+
+			usize::from(character - first)
+		} else {
+			// The font is indexed by a major index and a minor index.
+			let minor = u16::from(minor);
+
+			if !(self.min_major_index..=self.max_major_index).contains(&major)
+				|| !(first..=last).contains(&minor)
+			{
+				return None;
+			}
+
+			let major_index_range = usize::from(last - first) + 1;
+
+			usize::from(major - self.min_major_index) * major_index_range
+				+ usize::from(minor - first)
+		};
+
+		let info = if self.character_infos.is_empty() {
+			// If there are no `character_infos`, every character has the same
+			// metrics, so `min_bounds` and `max_bounds` are identical.
+			&self.max_bounds
+		} else {
+			self.character_infos.get(index)?
+		};
+
+		(!info.is_nonexistent()).then_some(info)
+	}
+
+	/// Returns the [`CharacterInfo`] for the `fallback_character`, or [`None`]
+	/// if the `fallback_character` does not exist in the font.
+	#[must_use]
+	pub fn fallback_character_info(&self) -> Option<&CharacterInfo> {
+		self.character_info(Char16::from(self.fallback_character))
+	}
+
+	/// Returns the [`FontProperty`] with the given `name`, if there is one.
+	#[must_use]
+	pub fn property(&self, name: Atom) -> Option<&FontProperty> {
+		self.properties
+			.iter()
+			.find(|property| property.name == name)
+	}
+
+	/// Computes the [extents] of the given `text` in this font.
+	///
+	/// This produces the same result as a [`QueryTextExtents` request]
+	/// without a round trip to the X server: characters which do not exist in
+	/// the font are replaced by the `fallback_character`, and characters are
+	/// ignored if the `fallback_character` does not exist either.
+	///
+	/// [extents]: TextExtents
+	/// [`QueryTextExtents` request]: request::QueryTextExtents
+	#[must_use]
+	pub fn text_extents<C: Into<Char16>>(&self, text: impl IntoIterator<Item = C>) -> TextExtents {
+		let mut infos = text.into_iter().filter_map(|character| {
+			self.character_info(character)
+				.or_else(|| self.fallback_character_info())
+		});
+
+		let Some(first) = infos.next() else {
+			return TextExtents::default();
+		};
+
+		let extents = TextExtents {
+			overall_ascent: first.ascent,
+			overall_descent: first.descent,
+			overall_width: i32::from(first.width),
+			overall_left: i32::from(first.left_side_bearing),
+			overall_right: i32::from(first.right_side_bearing),
+		};
+
+		infos.fold(extents, |extents, info| {
+			// Each character is drawn where the previous character ended.
+			let origin = extents.overall_width;
+
+			TextExtents {
+				overall_ascent: extents.overall_ascent.max(info.ascent),
+				overall_descent: extents.overall_descent.max(info.descent),
+				overall_width: origin + i32::from(info.width),
+				overall_left: extents
+					.overall_left
+					.min(origin + i32::from(info.left_side_bearing)),
+				overall_right: extents
+					.overall_right
+					.max(origin + i32::from(info.right_side_bearing)),
+			}
+		})
+	}
+}
+
 /// The [reply] to a [`ListFontsWithInfo` request].
 ///
 /// The [`ListFontsWithInfo` request] is unique in that it has a series of
@@ -362,6 +590,7 @@ derive_xrb! {
 /// [reply]: Reply
 ///
 /// [`ListFontsWithInfo` request]: request::ListFontsWithInfo
+#[derive(Debug)]
 pub enum ListFontsWithInfo {
 	/// Information about one of the available fonts.
 	Font(FontWithInfo),
@@ -383,6 +612,42 @@ impl Reply for ListFontsWithInfo {
 	}
 }
 
+impl Message for ListFontsWithInfo {
+	fn name(&self) -> &'static str {
+		"ListFontsWithInfo"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_reply::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		match self {
+			Self::Font(reply) => vec![("Font", reply)],
+			Self::Terminate(last) => vec![("Terminate", last)],
+		}
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		match self {
+			Self::Font(reply) => {
+				let size = reply.x11_size();
+				let padding = pad(&reply.name);
+
+				// 4 unused bytes follow both `min_bounds` and `max_bounds`.
+				let mut unused = vec![20..24, 36..40];
+				if padding > 0 {
+					unused.push((size - padding)..size);
+				}
+
+				unused
+			},
+
+			Self::Terminate(last) => vec![8..last.x11_size()],
+		}
+	}
+}
+
 impl X11Size for ListFontsWithInfo {
 	fn x11_size(&self) -> usize {
 		match self {
@@ -397,8 +662,8 @@ impl Readable for ListFontsWithInfo {
 	where
 		Self: Sized,
 	{
-		let name_len = buf.get_u8();
-		let sequence = buf.get_u16();
+		let name_len = u8::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
 
 		Ok(match name_len {
 			zero if zero == 0 => Self::Terminate(<_>::read_with(buf, &sequence)?),
@@ -607,14 +872,16 @@ impl ReadableWithContext for FontWithInfo {
 		//   `ListFontsWithInfo` reply
 
 		// Read the length - take away the 8 bytes we've already read.
-		let length = ((buf.get_u32() as usize) * 4) + (32 - 8);
+		let length = ((u32::read_from(buf)? as usize) * 4) + (32 - 8);
 		// Limit `buf` by the read `length`.
 		let buf = &mut buf.take(length);
 
 		let min_bounds = CharacterInfo::read_from(buf)?;
+		check_remaining(buf, 4)?;
 		buf.advance(4); // 4 unused bytes
 
 		let max_bounds = CharacterInfo::read_from(buf)?;
+		check_remaining(buf, 4)?;
 		buf.advance(4); // 4 unused bytes
 
 		let first_character_or_min_minor_index = u16::read_from(buf)?;
@@ -639,6 +906,7 @@ impl ReadableWithContext for FontWithInfo {
 		let properties = <Vec<FontProperty>>::read_with(buf, &properties_len)?;
 
 		let name = String8::read_with(buf, &name_len)?;
+		check_remaining(buf, pad(&name))?;
 		buf.advance(pad(&name));
 
 		Ok(Self {
@@ -784,9 +1052,11 @@ impl ReadableWithContext for TerminateListFontsWithInfo {
 
 		// Then we skip the length because we know what it is meant to be... should
 		// probably verify that...
+		check_remaining(buf, 4)?;
 		buf.advance(4);
 
 		// And then skip the 52 remaining unused bytes.
+		check_remaining(buf, 52)?;
 		buf.advance(52);
 
 		Ok(Self {
@@ -802,7 +1072,7 @@ derive_xrb! {
 	///
 	/// [`GetFontSearchDirectories` request]: request::GetFontSearchDirectories
 	#[doc(alias = "GetFontPath")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetFontSearchDirectories: Reply for request::GetFontSearchDirectories {
 		/// The sequence number identifying the [request] that generated this
@@ -830,3 +1100,150 @@ derive_xrb! {
 		[_; directories => pad(directories)],
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::Char8;
+
+	const fn info(left_side_bearing: i16, right_side_bearing: i16, width: i16) -> CharacterInfo {
+		CharacterInfo {
+			left_side_bearing,
+			right_side_bearing,
+			width,
+			ascent: width,
+			descent: 1,
+			attributes: 0,
+		}
+	}
+
+	const NONEXISTENT: CharacterInfo = CharacterInfo {
+		descent: 0,
+		..info(0, 0, 0)
+	};
+
+	fn font(min_major_index: u8, max_major_index: u8) -> QueryFont {
+		QueryFont {
+			sequence: 0,
+			min_bounds: info(-1, 2, 2),
+			max_bounds: info(0, 5, 4),
+			first_character_or_min_minor_index: 0x20,
+			last_character_or_max_minor_index: 0x22,
+			fallback_character: 0x22,
+			draw_direction: DrawDirection::LeftToRight,
+			min_major_index,
+			max_major_index,
+			all_characters_exist: false,
+			font_ascent: 4,
+			font_descent: 1,
+			properties: vec![FontProperty {
+				name: crate::atom::WM_NAME,
+				value: [0, 0, 0, 1],
+			}],
+			character_infos: vec![
+				info(-1, 2, 2),
+				NONEXISTENT,
+				info(0, 5, 4),
+				info(0, 3, 3),
+				NONEXISTENT,
+				info(1, 4, 3),
+			],
+		}
+	}
+
+	#[test]
+	fn test_character_info_linear() {
+		let font = font(0, 0);
+
+		assert_eq!(font.character_info(Char8(0x20)), Some(&info(-1, 2, 2)));
+		assert_eq!(font.character_info(Char8(0x22)), Some(&info(0, 5, 4)));
+		assert_eq!(font.character_info(Char8(0x23)), None);
+		assert_eq!(font.character_info(Char8(0x1f)), None);
+
+		assert!(font.property(crate::atom::WM_NAME).is_some());
+		assert!(font.property(crate::atom::WM_CLASS).is_none());
+	}
+
+	#[test]
+	fn test_character_info_matrix() {
+		let font = font(1, 2);
+
+		assert_eq!(font.character_info(Char16(1, 0x20)), Some(&info(-1, 2, 2)));
+		assert_eq!(font.character_info(Char16(2, 0x20)), Some(&info(0, 3, 3)));
+		assert_eq!(font.character_info(Char16(2, 0x21)), None);
+		assert_eq!(font.character_info(Char16(2, 0x22)), Some(&info(1, 4, 3)));
+		assert_eq!(font.character_info(Char16(3, 0x20)), None);
+	}
+
+	/// A [`QueryFont` reply] for a font with a single `FONT` property and two
+	/// characters, `0x41` and the nonexistent `0x42`.
+	///
+	/// [`QueryFont` reply]: QueryFont
+	#[rustfmt::skip]
+	const QUERY_FONT_REPLY: [u8; 92] = [
+		// reply, unused, sequence, length
+		1, 0, 0, 7, 0, 0, 0, 15,
+		// min_bounds, unused
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 0,
+		// max_bounds, unused
+		0, 1, 0, 6, 0, 7, 0, 9, 0, 2, 0, 0,
+		0, 0, 0, 0,
+		// first, last, and fallback characters, properties_len
+		0, 0x41, 0, 0x42, 0, 0x41, 0, 1,
+		// draw_direction, min and max major indexes, all_characters_exist
+		1, 0, 0, 0,
+		// font_ascent, font_descent, character_infos_len
+		0, 9, 0, 2, 0, 0, 0, 2,
+		// properties
+		0, 0, 0, 18, 0, 0, 1, 44,
+		// character_infos
+		0, 1, 0, 6, 0, 7, 0, 9, 0, 2, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+	];
+
+	#[test]
+	fn test_query_font_fixture() {
+		let reply = QueryFont::read_from(&mut &QUERY_FONT_REPLY[1..]).unwrap();
+
+		assert_eq!(reply.sequence, 7);
+		assert_eq!(reply.draw_direction, DrawDirection::RightToLeft);
+		assert!(!reply.all_characters_exist);
+
+		let property = reply.property(crate::atom::FONT).unwrap();
+		assert_eq!(property.as_atom(), Atom::new(300));
+
+		let info = reply.character_info(Char8(0x41)).unwrap();
+		assert_eq!((info.ink_width(), info.height()), (5, 11));
+		assert_eq!(info, &reply.max_bounds);
+
+		assert!(reply.character_infos[1].is_nonexistent());
+		assert_eq!(reply.character_info(Char8(0x42)), None);
+
+		let mut bytes = Vec::new();
+		reply.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, QUERY_FONT_REPLY);
+	}
+
+	#[test]
+	fn test_text_extents() {
+		let font = font(0, 0);
+
+		assert_eq!(
+			font.text_extents(Vec::<Char8>::new()),
+			TextExtents::default()
+		);
+
+		// The nonexistent `0x21` and `0x30` are replaced by `0x22`.
+		assert_eq!(
+			font.text_extents([0x20, 0x21, 0x30].map(Char8)),
+			TextExtents {
+				overall_ascent: 4,
+				overall_descent: 1,
+				overall_width: 10,
+				overall_left: -1,
+				overall_right: 11,
+			},
+		);
+	}
+}
@@ -32,7 +32,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`CaptureImage` request]: request::CaptureImage
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct CaptureImage: Reply for request::CaptureImage {
 		/// The sequence number identifying the [request] that generated this
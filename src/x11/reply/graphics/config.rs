@@ -16,7 +16,7 @@ derive_xrb! {
 	///
 	/// [`QueryIdealDimensions` request]: request::QueryIdealDimensions
 	#[doc(alias("QueryBestSize"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryIdealDimensions: Reply for request::QueryIdealDimensions {
 		/// The sequence number identifying the [request] that generated this
@@ -16,18 +16,33 @@
 
 extern crate self as xrb;
 
-use array_init::array_init;
+use array_init::try_array_init;
 use derivative::Derivative;
-use xrbk::{Buf, BufMut, ConstantX11Size, ReadResult, Readable, Writable, WriteResult, X11Size};
+use std::{fmt::Debug, ops::Range};
+use xrbk::{
+	check_remaining,
+	pad,
+	Buf,
+	BufMut,
+	ConstantX11Size,
+	ReadError,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteResult,
+	X11Size,
+};
 
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
-	message::Reply,
+	message::{Message, MessageKind, Reply},
+	set::AutoRepeatMode,
 	unit::{Hz, Ms, Percentage, Px},
 	x11::{
+		reply::ReplyList,
 		request,
-		request::{Fraction, RevertFocus},
+		request::{AccelerationFactor, RevertFocus},
 	},
 	Button,
 	Coords,
@@ -35,9 +50,9 @@ use crate::{
 	GrabStatus,
 	Keycode,
 	Keysym,
+	LedMask,
 	ModifierMask,
 	Timestamp,
-	Toggle,
 	Window,
 };
 
@@ -48,7 +63,7 @@ derive_xrb! {
 	///
 	/// [`GrabCursor` request]: request::GrabCursor
 	#[doc(alias = "GrabPointer")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GrabCursor: Reply for request::GrabCursor {
 		/// The sequence number identifying the [request] that generated this
@@ -79,7 +94,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`GrabKeyboard` request]: request::GrabKeyboard
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GrabKeyboard: Reply for request::GrabKeyboard {
 		/// The sequence number identifying the [request] that generated this
@@ -112,7 +127,7 @@ derive_xrb! {
 	///
 	/// [`QueryCursorLocation` request]: request::QueryCursorLocation
 	#[doc(alias("QueryPointer, QueryCursor, GetCursorPos, GetCursorLocation"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryCursorLocation: Reply for request::QueryCursorLocation {
 		/// The sequence number identifying the [request] that generated this
@@ -173,7 +188,20 @@ derive_xrb! {
 /// [time]: Timestamp
 ///
 /// [`GetMotionHistory` reply]: GetMotionHistory
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[doc(alias = "TimeCoord")]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub struct TimeCoords {
 	/// The [time] at which the cursor was at the `coords`.
 	///
@@ -190,7 +218,7 @@ derive_xrb! {
 	///
 	/// [`GetMotionHistory` request]: request::GetMotionHistory
 	#[doc(alias = "GetMotionEvents")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetMotionHistory: Reply for request::GetMotionHistory {
 		/// The sequence number identifying the [request] that generated this
@@ -225,7 +253,7 @@ derive_xrb! {
 	///
 	/// [`ConvertCoordinates` request]: request::ConvertCoordinates
 	#[doc(alias = "TranslateCoordinates")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ConvertCoordinates: Reply for request::ConvertCoordinates {
 		/// The sequence number identifying the [request] that generated this
@@ -277,7 +305,7 @@ derive_xrb! {
 	///
 	/// [`GetFocus` request]: request::GetFocus
 	#[doc(alias = "GetInputFocus")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetFocus: Reply for request::GetFocus {
 		/// The sequence number identifying the [request] that generated this
@@ -293,7 +321,7 @@ derive_xrb! {
 		#[derivative(Hash = "ignore", PartialEq = "ignore")]
 		pub sequence: u16,
 
-		/// What the focus will retain to if the focused [window] becomes
+		/// What the focus will revert to if the focused [window] becomes
 		/// unviewable.
 		///
 		/// [window]: Window
@@ -311,7 +339,7 @@ derive_xrb! {
 	///
 	/// [`QueryKeyboard` request]: request::QueryKeyboard
 	#[doc(alias = "QueryKeymap")]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryKeyboard: Reply for request::QueryKeyboard {
 		/// The sequence number identifying the [request] that generated this
@@ -336,6 +364,32 @@ derive_xrb! {
 	}
 }
 
+impl QueryKeyboard {
+	/// Returns whether the given [keycode] is held.
+	///
+	/// [keycode]: Keycode
+	#[must_use]
+	pub fn is_held(&self, keycode: Keycode) -> bool {
+		let keycode = keycode.unwrap();
+
+		self.keys[usize::from(keycode / 8)] & (1 << (keycode % 8)) != 0
+	}
+
+	/// Returns the [keycodes] which are held or not held in this reply, but
+	/// were not or were in the `previous` reply.
+	///
+	/// Each [keycode] is returned with whether it is now held.
+	///
+	/// [keycode]: Keycode
+	/// [keycodes]: Keycode
+	pub fn diff<'a>(&'a self, previous: &'a Self) -> impl Iterator<Item = (Keycode, bool)> + 'a {
+		(0..=u8::MAX)
+			.map(Keycode::new)
+			.map(move |keycode| (keycode, self.is_held(keycode)))
+			.filter(move |&(keycode, held)| held != previous.is_held(keycode))
+	}
+}
+
 /// The [keysyms] mapped to a particular [keycode].
 ///
 /// [keysyms]: Keysym
@@ -377,6 +431,24 @@ impl Reply for GetKeyboardMapping {
 	}
 }
 
+impl Message for GetKeyboardMapping {
+	fn name(&self) -> &'static str {
+		"GetKeyboardMapping"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_reply::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![("sequence", &self.sequence), ("mappings", &self.mappings)]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		vec![8..32]
+	}
+}
+
 impl X11Size for GetKeyboardMapping {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 8;
@@ -391,8 +463,6 @@ impl Readable for GetKeyboardMapping {
 	where
 		Self: Sized,
 	{
-		const HEADER: usize = 8;
-
 		// Header {{{
 
 		// FIXME: actually, replies need to have their first 4 bytes read before
@@ -400,21 +470,23 @@ impl Readable for GetKeyboardMapping {
 		//        and `sequence` should be context for `ReadableWithContext`.
 		//
 		// FIXME: This is a change that needs to be done for all replies...
-		buf.advance(1);
-		let keysyms_per_keycode = buf.get_u8();
-		let sequence = buf.get_u16();
+		let keysyms_per_keycode = u8::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
 
-		let length = (buf.get_u32() as usize) * 4;
-		let buf = &mut buf.take(length - HEADER);
+		// The length doesn't include the first 32 bytes, 8 of which have been
+		// read.
+		let length = ((u32::read_from(buf)? as usize) * 4) + (32 - 8);
+		let buf = &mut buf.take(length);
 
 		// }}}
 
 		// 24 unused bytes.
+		check_remaining(buf, 24)?;
 		buf.advance(24);
 
 		let mappings = {
 			let mapping_size = usize::from(keysyms_per_keycode) * Keysym::X11_SIZE;
-			let mappings_len = buf.remaining() / mapping_size;
+			let mappings_len = buf.remaining().checked_div(mapping_size).unwrap_or(0);
 
 			let mut mappings = vec![];
 
@@ -445,8 +517,7 @@ impl Writable for GetKeyboardMapping {
 		// Indicates that this is a reply.
 		buf.put_u8(1);
 		// The number of keysyms in each mapping.
-		let mapping_size = self.mappings.x11_size() / self.mappings.len();
-		let keysyms_per_keycode = (mapping_size / Keysym::X11_SIZE) as u8;
+		let keysyms_per_keycode = self.mappings.first().map_or(0, Vec::len) as u8;
 		keysyms_per_keycode.write_to(buf)?;
 		// The sequence number.
 		self.sequence.write_to(buf)?;
@@ -472,7 +543,7 @@ derive_xrb! {
 	///
 	/// [`GetKeyboardOptions` request]: request::GetKeyboardOptions
 	#[doc(alias("GetKeyboardControl"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetKeyboardOptions: Reply for request::GetKeyboardOptions {
 		/// The sequence number identifying the [request] that generated this
@@ -497,7 +568,7 @@ derive_xrb! {
 		/// [auto repeat mode]: crate::set::KeyboardOptions::auto_repeat_mode
 		#[doc(alias("global_auto_repeat"))]
 		#[metabyte]
-		pub global_auto_repeat_mode: Toggle,
+		pub global_auto_repeat_mode: AutoRepeatMode,
 
 		/// A bitmask representing whether each [LED] is lit.
 		///
@@ -505,7 +576,7 @@ derive_xrb! {
 		/// significant bit represents the state of [LED] 32.
 		///
 		/// [LED]: crate::set::Led
-		pub led_mask: u32,
+		pub led_mask: LedMask,
 
 		/// The volume of key clicks.
 		///
@@ -559,7 +630,7 @@ derive_xrb! {
 	///
 	/// [`GetCursorOptions` request]: request::GetCursorOptions
 	#[doc(alias("GetPointerControl", "GetPointerOptions", "GetCursorControl"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetCursorOptions: Reply for request::GetCursorOptions {
 		/// The sequence number identifying the [request] that generated this
@@ -579,7 +650,8 @@ derive_xrb! {
 		/// [`threshold`] is exceeded.
 		///
 		/// [`threshold`]: GetCursorOptions::threshold
-		pub acceleration: Fraction<Px<u16>>,
+		#[doc(alias("acceleration_numerator", "acceleration_denominator"))]
+		pub acceleration: AccelerationFactor,
 		/// The threshold speed which the cursor must exceed for the
 		/// [`acceleration`] multiplier to be applied.
 		///
@@ -595,12 +667,26 @@ derive_xrb! {
 ///
 /// [`SetButtonMapping` request]: request::SetButtonMapping
 /// [`SetButtonMapping` reply]: SetButtonMapping
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum SetButtonMappingStatus {
 	/// The [`SetButtonMapping` request] was successful.
 	///
 	/// [`SetButtonMapping` request]: request::SetButtonMapping
-	Success,
+	#[default]
+	Success = 0,
 
 	/// The [`SetButtonMapping` request] was unsuccessful because it specified
 	/// buttons which are currently held.
@@ -608,7 +694,7 @@ pub enum SetButtonMappingStatus {
 	/// The mapping of mouse buttons cannot be changed while they are held.
 	///
 	/// [`SetButtonMapping` request]: request::SetButtonMapping
-	Busy,
+	Busy = 1,
 }
 
 derive_xrb! {
@@ -618,7 +704,7 @@ derive_xrb! {
 	///
 	/// [`SetButtonMapping` request]: request::SetButtonMapping
 	#[doc(alias("SetPointerMapping", "SetCursorMapping"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct SetButtonMapping: Reply for request::SetButtonMapping {
 		/// The sequence number identifying the [request] that generated this
@@ -639,6 +725,7 @@ derive_xrb! {
 		/// See [`SetButtonMappingStatus`] for more information.
 		///
 		/// [`SetButtonMapping` request]: request::SetButtonMapping
+		#[metabyte]
 		pub status: SetButtonMappingStatus,
 		[_; ..],
 	}
@@ -649,7 +736,7 @@ derive_xrb! {
 	///
 	/// [`GetButtonMapping` request]: request::GetButtonMapping
 	#[doc(alias("GetPointerMapping", "GetCursorMapping"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetButtonMapping: Reply for request::GetButtonMapping {
 		/// The sequence number identifying the [request] that generated this
@@ -681,7 +768,24 @@ derive_xrb! {
 		/// [mouse button]: Button
 		/// [button]: Button
 		#[context(mappings_len => usize::from(*mappings_len))]
-		pub mappings: Vec<Option<Button>>,
+		pub mappings: ReplyList<Option<Button>>,
+		[_; mappings => pad(mappings)],
+	}
+}
+
+impl SetButtonMapping {
+	/// Whether the [`SetButtonMapping` request] failed because [buttons] which
+	/// it remapped were held.
+	///
+	/// The [request] may be retried once the [buttons] are released.
+	///
+	/// [buttons]: Button
+	/// [request]: request::SetButtonMapping
+	///
+	/// [`SetButtonMapping` request]: request::SetButtonMapping
+	#[must_use]
+	pub const fn is_busy(&self) -> bool {
+		matches!(self.status, SetButtonMappingStatus::Busy)
 	}
 }
 
@@ -693,12 +797,26 @@ derive_xrb! {
 ///
 /// [`SetModifierMapping` request]: request::SetModifierMapping
 /// [`SetModifierMapping` reply]: SetModifierMapping
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum SetModifierMappingStatus {
 	/// The [`SetModifierMapping` request] was successful.
 	///
 	/// [`SetModifierMapping` request]: request::SetModifierMapping
-	Success,
+	#[default]
+	Success = 0,
 
 	/// The [`SetModifierMapping` request] failed because either the currently
 	/// mapped modifier keys or specified new modifier keys are currently held.
@@ -708,7 +826,7 @@ pub enum SetModifierMappingStatus {
 	/// the new modifier keys are not held.
 	///
 	/// [`SetModifierMapping` request]: request::SetModifierMapping
-	Busy,
+	Busy = 1,
 	/// The [`SetModifierMapping` request] failed because the X server rejected
 	/// it.
 	///
@@ -718,7 +836,8 @@ pub enum SetModifierMappingStatus {
 	/// supported or because auto-repeat cannot be disabled for certain keys.
 	///
 	/// [`SetModifierMapping` request]: request::SetModifierMapping
-	Rejected,
+	#[doc(alias = "Failed")]
+	Rejected = 2,
 }
 
 derive_xrb! {
@@ -727,7 +846,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`SetModifierMapping` request]: request::SetModifierMapping
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct SetModifierMapping: Reply for request::SetModifierMapping {
 		/// The sequence number identifying the [request] that generated this
@@ -842,6 +961,34 @@ impl Reply for GetModifierMapping {
 	}
 }
 
+impl Message for GetModifierMapping {
+	fn name(&self) -> &'static str {
+		"GetModifierMapping"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_reply::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("sequence", &self.sequence),
+			("shift_keycodes", &self.shift_keycodes),
+			("capslock_keycodes", &self.capslock_keycodes),
+			("ctrl_keycodes", &self.ctrl_keycodes),
+			("mod1_keycodes", &self.mod1_keycodes),
+			("mod2_keycodes", &self.mod2_keycodes),
+			("mod3_keycodes", &self.mod3_keycodes),
+			("mod4_keycodes", &self.mod4_keycodes),
+			("mod5_keycodes", &self.mod5_keycodes),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		vec![8..32]
+	}
+}
+
 impl X11Size for GetModifierMapping {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 8;
@@ -858,33 +1005,29 @@ impl Readable for GetModifierMapping {
 	where
 		Self: Sized,
 	{
-		const HEADER: usize = 8;
-		const ALIGNMENT: usize = 4;
-
-		// FIXME: the first 4 bytes of the header should be read separately, with the
-		// metabyte        position and sequence being given as context. That applies to
-		// all replies.
-		buf.advance(1);
+		let keycodes_per_modifier = u8::read_from(buf)?;
+		let sequence = u16::read_from(buf)?;
 
-		let keycodes_per_modifier = buf.get_u8();
-		let sequence = buf.get_u16();
-
-		let total_size = ((buf.get_u32() as usize) * ALIGNMENT) - HEADER;
-		let buf = &mut buf.take(total_size);
+		// The message length, which is given by `keycodes_per_modifier`.
+		check_remaining(buf, 4)?;
+		buf.advance(4);
+		// 24 unused bytes.
+		check_remaining(buf, 24)?;
+		buf.advance(24);
 
 		let [shift_keycodes, capslock_keycodes, ctrl_keycodes, mod1_keycodes, mod2_keycodes, mod3_keycodes, mod4_keycodes, mod5_keycodes] =
-			array_init(|_| {
+			try_array_init(|_| {
 				let mut keycodes = vec![];
 
 				for _ in 0..keycodes_per_modifier {
-					match buf.get_u8() {
+					match u8::read_from(buf)? {
 						0 => {},
 						code => keycodes.push(Keycode(code)),
 					}
 				}
 
-				keycodes
-			});
+				Ok::<_, ReadError>(keycodes)
+			})?;
 
 		Ok(Self {
 			sequence,
@@ -901,3 +1044,155 @@ impl Readable for GetModifierMapping {
 		})
 	}
 }
+
+impl Writable for GetModifierMapping {
+	#[allow(clippy::cast_possible_truncation)]
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		let keycodes_per_modifier = self.max_keycodes_len();
+
+		// Indicates that this is a reply.
+		buf.put_u8(1);
+		(keycodes_per_modifier as u8).write_to(buf)?;
+		self.sequence.write_to(buf)?;
+
+		// The message length.
+		self.length().write_to(buf)?;
+		// 24 unused bytes.
+		buf.put_bytes(0, 24);
+
+		for keycodes in [
+			&self.shift_keycodes,
+			&self.capslock_keycodes,
+			&self.ctrl_keycodes,
+			&self.mod1_keycodes,
+			&self.mod2_keycodes,
+			&self.mod3_keycodes,
+			&self.mod4_keycodes,
+			&self.mod5_keycodes,
+		] {
+			keycodes.write_to(buf)?;
+			// Modifiers with fewer keycodes are padded with zero keycodes.
+			buf.put_bytes(0, keycodes_per_modifier - keycodes.len());
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_util::round_trip;
+
+	#[test]
+	fn test_grab_status() {
+		let bytes = round_trip(
+			&GrabCursor {
+				sequence: 42,
+				grab_status: GrabStatus::Frozen,
+			},
+			1,
+		);
+		assert_eq!(bytes[1], 4);
+
+		// Replies whose fields all have defaults can be constructed from
+		// just the fields which matter.
+		let reply = GrabCursor {
+			sequence: 42,
+			..Default::default()
+		};
+		assert_eq!(reply.grab_status, GrabStatus::Success);
+		assert_eq!(round_trip(&reply, 1)[1], 0);
+	}
+
+	#[test]
+	fn test_cursor_options_above_int16() {
+		// The numerator and denominator are `CARD16`s in the reply.
+		let mut bytes = [0; 32];
+		bytes[..14].copy_from_slice(&[1, 0, 0, 42, 0, 0, 0, 0, 0x9c, 0x40, 0xff, 0xff, 0, 4]);
+
+		let reply = GetCursorOptions::read_from(&mut &bytes[1..]).unwrap();
+		assert_eq!(reply.acceleration.numerator(), 40_000);
+		assert_eq!(reply.acceleration.denominator(), u16::MAX);
+		assert_eq!(round_trip(&reply, 1), bytes);
+	}
+
+	#[test]
+	fn test_keyboard_options() {
+		let bytes = round_trip(
+			&GetKeyboardOptions {
+				sequence: 42,
+				global_auto_repeat_mode: AutoRepeatMode::On,
+				led_mask: LedMask::LED_1 | LedMask::LED_3,
+				key_click_volume: Percentage::new(50).unwrap(),
+				bell_volume: Percentage::new(100).unwrap(),
+				bell_pitch: Hz(400),
+				bell_duration: Ms(100),
+				auto_repeat_modes: [0xff; 32],
+			},
+			1,
+		);
+		assert_eq!(bytes[1], 1);
+		// LEDs 1 and 3 are the first and third bits of the mask.
+		assert_eq!(&bytes[8..12], &[0, 0, 0, 0b101]);
+	}
+
+	#[test]
+	fn test_keyboard_mapping() {
+		let bytes = round_trip(
+			&GetKeyboardMapping {
+				sequence: 42,
+				mappings: vec![
+					vec![Keysym::new(0x61), Keysym::new(0x41)],
+					vec![Keysym::new(0x62), Keysym::new(0x42)],
+				],
+			},
+			1,
+		);
+		// 2 keysyms per keycode, and 4 keysyms after the first 32 bytes.
+		assert_eq!(&bytes[..8], &[1, 2, 0, 42, 0, 0, 0, 4]);
+		assert_eq!(bytes.len(), 32 + 16);
+
+		// A reply without any mappings has no keysyms per keycode.
+		let bytes = round_trip(
+			&GetKeyboardMapping {
+				sequence: 42,
+				mappings: vec![],
+			},
+			1,
+		);
+		assert_eq!(bytes[1], 0);
+	}
+
+	#[test]
+	fn test_modifier_mapping() {
+		let bytes = round_trip(
+			&GetModifierMapping {
+				sequence: 42,
+				shift_keycodes: vec![Keycode(50), Keycode(62)],
+				capslock_keycodes: vec![Keycode(66)],
+				ctrl_keycodes: vec![],
+				mod1_keycodes: vec![],
+				mod2_keycodes: vec![],
+				mod3_keycodes: vec![],
+				mod4_keycodes: vec![Keycode(133)],
+				mod5_keycodes: vec![],
+			},
+			1,
+		);
+		// 2 keycodes per modifier, which is 16 bytes after the first 32.
+		assert_eq!(&bytes[..8], &[1, 2, 0, 42, 0, 0, 0, 4]);
+		// Modifiers with fewer keycodes are padded with zeroes.
+		assert_eq!(&bytes[32..36], &[50, 62, 66, 0]);
+	}
+
+	#[test]
+	fn test_set_button_mapping_status() {
+		let reply = SetButtonMapping {
+			sequence: 42,
+			status: SetButtonMappingStatus::Busy,
+		};
+		assert!(reply.is_busy());
+		assert_eq!(round_trip(&reply, 1)[1], 1);
+	}
+}
@@ -28,7 +28,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`QueryExtension` request]: request::QueryExtension
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryExtension: Reply for request::QueryExtension {
 		/// The sequence number identifying the [request] that generated this
@@ -64,6 +64,7 @@ derive_xrb! {
 		/// [errors]: crate::message::Error
 		/// [event code]: crate::message::Event::CODE
 		pub first_error_code: Option<u8>,
+		[_; ..],
 	}
 
 	/// The [reply] to a [`ListExtensions` request].
@@ -71,7 +72,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`ListExtensions` request]: request::ListExtensions
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ListExtensions: Reply for request::ListExtensions {
 		/// The sequence number identifying the [request] that generated this
@@ -105,7 +106,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`GetScreenSaver` request]: request::GetScreenSaver
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetScreenSaver: Reply for request::GetScreenSaver {
 		/// The sequence number identifying the [request] that generated this
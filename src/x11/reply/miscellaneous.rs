@@ -23,7 +23,10 @@ use xrbk_macro::derive_xrb;
 
 use crate::{
 	message::Reply,
-	x11::request::{self, DataFormat, DataList},
+	x11::{
+		reply::ReplyList,
+		request::{self, DataFormat, DataList},
+	},
 	Atom,
 	String8,
 	Window,
@@ -36,7 +39,7 @@ derive_xrb! {
 	///
 	/// [`GetAtom` request]: request::GetAtom
 	#[doc(alias("InternAtom", "CreateAtom"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetAtom: Reply for request::GetAtom {
 		/// The sequence number identifying the [request] that generated this
@@ -67,7 +70,7 @@ derive_xrb! {
 	/// [reply]: crate::message
 	///
 	/// [`GetAtomName` request]: request::GetAtomName
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetAtomName: Reply for request::GetAtomName {
 		/// The sequence number identifying the [request] that generated this
@@ -101,7 +104,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`GetProperty` request]: request::GetProperty
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetProperty: Reply for request::GetProperty {
 		/// The sequence number identifying the [request] that generated this
@@ -161,7 +164,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`ListProperties` request]: request::ListProperties
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct ListProperties: Reply for request::ListProperties {
 		/// The sequence number identifying the [request] that generated this
@@ -187,7 +190,7 @@ derive_xrb! {
 		/// [window]: Window
 		#[doc(alias = "atoms")]
 		#[context(properties_len => usize::from(*properties_len))]
-		pub properties: Vec<Atom>,
+		pub properties: ReplyList<Atom>,
 	}
 
 	/// The [reply] to a [`GetSelectionOwner` request].
@@ -195,7 +198,7 @@ derive_xrb! {
 	/// [reply]: Reply
 	///
 	/// [`GetSelectionOwner` request]: request::GetSelectionOwner
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, Default, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetSelectionOwner: Reply for request::GetSelectionOwner {
 		/// The sequence number identifying the [request] that generated this
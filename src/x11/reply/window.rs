@@ -18,13 +18,13 @@ extern crate self as xrb;
 
 use derivative::Derivative;
 
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::derive_xrb;
 
 use crate::{
 	message::Reply,
 	unit::Px,
 	visual::{ColorId, VisualId},
-	x11::request,
+	x11::{reply::ReplyList, request},
 	BitGravity,
 	Colormap,
 	DeviceEventMask,
@@ -36,34 +36,19 @@ use crate::{
 	WindowGravity,
 };
 
-/// The state of the [window] regarding how it is mapped.
-///
-/// [window]: Window
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
-pub enum MapState {
-	/// The [window] is not mapped.
-	///
-	/// [window]: Window
-	Unmapped,
-
-	/// The [window] is mapped but one of its ancestors is unmapped.
-	///
-	/// [window]: Window
-	Unviewable,
-
-	/// The [window] is mapped and all of its ancestors are mapped.
-	///
-	/// [window]: Window
-	Viewable,
-}
+// Re-exported so that `x11::reply::window::MapState` keeps working.
+pub use crate::MapState;
 
+// Unlike most replies, these don't implement `Default`: each has an ID (the
+// window's `visual` or its `root`) for which there is no meaningful default.
+// Each has a builder which takes that ID instead.
 derive_xrb! {
 	/// The [reply] to a [`GetWindowAttributes` request].
 	///
 	/// [reply]: Reply
 	///
 	/// [`GetWindowAttributes` request]: request::GetWindowAttributes
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetWindowAttributes: Reply for request::GetWindowAttributes {
 		/// The sequence number identifying the [request] that generated this
@@ -124,7 +109,7 @@ derive_xrb! {
 		///
 		/// [`Attributes::window_gravity`]: crate::set::Attributes::window_gravity
 		#[doc(alias = "win_gravity")]
-		pub window_graivty: WindowGravity,
+		pub window_gravity: WindowGravity,
 
 		/// Defines which bit planes of the [window] hold dynamic data which is
 		/// maintained for `maintain_contents` and `maintain_windows_under`.
@@ -241,7 +226,7 @@ derive_xrb! {
 	///
 	/// [`GetGeometry` request]: request::GetGeometry
 	#[doc(alias("GetX", "GetY", "GetWidth", "GetHeight", "GetBorderWidth"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct GetGeometry: Reply for request::GetGeometry {
 		/// The sequence number identifying the [request] that generated this
@@ -299,7 +284,7 @@ derive_xrb! {
 	#[doc(alias("QueryTree", "GetTree", "GetWindowTree"))]
 	#[doc(alias("QueryParent", "QueryChildren", "QueryRoot"))]
 	#[doc(alias("GetParent", "GetChildren", "GetRoot"))]
-	#[derive(Derivative, Debug, X11Size, Readable, Writable)]
+	#[derive(Derivative, Debug, X11Size, Readable, Writable, InfallibleWritable)]
 	#[derivative(Hash, PartialEq, Eq)]
 	pub struct QueryWindowTree: Reply for request::QueryWindowTree {
 		/// The sequence number identifying the [request] that generated this
@@ -333,6 +318,552 @@ derive_xrb! {
 		///
 		/// [window]: Window
 		#[context(children_len => usize::from(*children_len))]
-		pub children: Vec<Window>,
+		pub children: ReplyList<Window>,
+	}
+}
+
+/// The [events] selected on a [window], as returned in a
+/// [`GetWindowAttributes` reply].
+///
+/// [events]: crate::message::Event
+/// [window]: Window
+/// [`GetWindowAttributes` reply]: GetWindowAttributes
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct EventSelection {
+	/// All of the [events] selected by all clients on the [window].
+	///
+	/// [events]: crate::message::Event
+	/// [window]: Window
+	pub all: EventMask,
+	/// The [events] selected by you on the [window].
+	///
+	/// [events]: crate::message::Event
+	/// [window]: Window
+	pub yours: EventMask,
+	/// The [events] which are not propagated to ancestors of the [window].
+	///
+	/// [events]: crate::message::Event
+	/// [window]: Window
+	pub do_not_propagate: DeviceEventMask,
+}
+
+/// A [window]'s [colormap] and whether it is installed, as returned in a
+/// [`GetWindowAttributes` reply].
+///
+/// [window]: Window
+/// [colormap]: Colormap
+/// [`GetWindowAttributes` reply]: GetWindowAttributes
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ColormapState {
+	/// The [window]'s [colormap], if it has one.
+	///
+	/// [window]: Window
+	/// [colormap]: Colormap
+	pub colormap: Option<Colormap>,
+	/// Whether the `colormap` is installed.
+	pub installed: bool,
+}
+
+/// The attributes of a [window] which window managers commonly check, taken
+/// from a [`GetWindowAttributes` reply].
+///
+/// [window]: Window
+/// [`GetWindowAttributes` reply]: GetWindowAttributes
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct WindowAttributesInfo {
+	/// The [window]'s [class].
+	///
+	/// [window]: Window
+	/// [class]: WindowClass
+	pub class: WindowClass,
+	/// The [window]'s [map state].
+	///
+	/// [window]: Window
+	/// [map state]: MapState
+	pub map_state: MapState,
+	/// Whether the [window] is not meant to be managed by a window manager.
+	///
+	/// See [`GetWindowAttributes::override_redirect`] for more information.
+	///
+	/// [window]: Window
+	pub override_redirect: bool,
+	/// The [events] selected on the [window].
+	///
+	/// [events]: crate::message::Event
+	/// [window]: Window
+	pub events: EventSelection,
+	/// The [window]'s [colormap] and whether it is installed.
+	///
+	/// [window]: Window
+	/// [colormap]: Colormap
+	pub colormap: ColormapState,
+}
+
+impl WindowAttributesInfo {
+	/// Returns whether the [window] is mapped.
+	///
+	/// A mapped [window] is not necessarily [viewable]: one of its ancestors
+	/// may be unmapped.
+	///
+	/// [window]: Window
+	/// [viewable]: WindowAttributesInfo::is_viewable
+	#[must_use]
+	pub const fn is_mapped(&self) -> bool {
+		!matches!(self.map_state, MapState::Unmapped)
+	}
+
+	/// Returns whether the [window] and all of its ancestors are mapped.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn is_viewable(&self) -> bool {
+		matches!(self.map_state, MapState::Viewable)
+	}
+
+	/// Returns whether the [window] is [viewable] and any client has selected
+	/// key or button presses or releases on it.
+	///
+	/// [window]: Window
+	/// [viewable]: WindowAttributesInfo::is_viewable
+	#[must_use]
+	pub const fn accepts_input(&self) -> bool {
+		const INPUT: EventMask = EventMask::KEY_PRESS
+			.union(EventMask::KEY_RELEASE)
+			.union(EventMask::BUTTON_PRESS)
+			.union(EventMask::BUTTON_RELEASE);
+
+		self.is_viewable() && self.events.all.intersects(INPUT)
+	}
+
+	/// Returns whether a window manager should manage the [window].
+	///
+	/// A window manager should manage [windows][window] which are
+	/// [`InputOutput`] and not `override_redirect`.
+	///
+	/// [window]: Window
+	/// [`InputOutput`]: WindowClass::InputOutput
+	#[must_use]
+	pub const fn is_manageable(&self) -> bool {
+		!self.override_redirect && matches!(self.class, WindowClass::InputOutput)
+	}
+}
+
+impl GetWindowAttributes {
+	/// Returns the attributes in this reply which window managers commonly
+	/// check.
+	#[must_use]
+	pub const fn info(&self) -> WindowAttributesInfo {
+		WindowAttributesInfo {
+			class: self.class,
+			map_state: self.map_state,
+			override_redirect: self.override_redirect,
+			events: EventSelection {
+				all: self.all_event_masks,
+				yours: self.your_event_mask,
+				do_not_propagate: self.do_not_propagate_mask,
+			},
+			colormap: ColormapState {
+				colormap: self.colormap,
+				installed: self.map_installed,
+			},
+		}
+	}
+}
+
+impl From<&GetWindowAttributes> for WindowAttributesInfo {
+	fn from(reply: &GetWindowAttributes) -> Self {
+		reply.info()
+	}
+}
+
+impl GetWindowAttributes {
+	/// Returns a new [`GetWindowAttributesBuilder`] with which a
+	/// `GetWindowAttributes` reply can be created for the given `visual`.
+	///
+	/// There is no meaningful default for the window's `visual`, so it must be
+	/// given here; every other field starts with its default value.
+	#[must_use]
+	pub fn builder(visual: VisualId) -> GetWindowAttributesBuilder {
+		GetWindowAttributesBuilder::new(visual)
+	}
+}
+
+/// A builder used to construct a new [`GetWindowAttributes` reply].
+///
+/// Every field other than the `visual` starts with its default value, and
+/// can be configured with the methods on this builder. When the builder is
+/// configured, [`build()`] can be used to construct the resulting
+/// [`GetWindowAttributes`].
+///
+/// [`build()`]: GetWindowAttributesBuilder::build
+/// [`GetWindowAttributes` reply]: GetWindowAttributes
+#[derive(Debug)]
+pub struct GetWindowAttributesBuilder {
+	reply: GetWindowAttributes,
+}
+
+impl GetWindowAttributesBuilder {
+	/// Creates a new `GetWindowAttributesBuilder` for the given `visual`.
+	///
+	/// Every other field starts with its default value, and can be configured
+	/// with the other methods on this builder. When the builder is configured,
+	/// [`build()`] can be used to build the resulting [`GetWindowAttributes`].
+	///
+	/// [`build()`]: GetWindowAttributesBuilder::build
+	#[must_use]
+	pub fn new(visual: VisualId) -> Self {
+		Self {
+			reply: GetWindowAttributes {
+				sequence: 0,
+				maintain_contents: MaintainContents::default(),
+				visual,
+				class: WindowClass::InputOutput,
+				bit_gravity: BitGravity::default(),
+				window_gravity: WindowGravity::default(),
+				maintained_planes: !0,
+				maintenance_fallback_color: ColorId::new(0),
+				maintain_windows_under: false,
+				map_installed: false,
+				map_state: MapState::default(),
+				override_redirect: false,
+				colormap: None,
+				all_event_masks: EventMask::empty(),
+				your_event_mask: EventMask::empty(),
+				do_not_propagate_mask: DeviceEventMask::empty(),
+			},
+		}
+	}
+
+	/// Constructs the resulting [`GetWindowAttributes` reply] with the
+	/// configured fields.
+	///
+	/// [`GetWindowAttributes` reply]: GetWindowAttributes
+	#[must_use]
+	pub const fn build(self) -> GetWindowAttributes {
+		self.reply
+	}
+}
+
+impl GetWindowAttributesBuilder {
+	/// Configures the reply's [`sequence`](GetWindowAttributes::sequence).
+	pub const fn sequence(&mut self, sequence: u16) -> &mut Self {
+		self.reply.sequence = sequence;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`maintain_contents`](GetWindowAttributes::maintain_contents).
+	pub const fn maintain_contents(&mut self, maintain_contents: MaintainContents) -> &mut Self {
+		self.reply.maintain_contents = maintain_contents;
+		self
+	}
+
+	/// Configures the reply's [`class`](GetWindowAttributes::class).
+	pub const fn class(&mut self, class: WindowClass) -> &mut Self {
+		self.reply.class = class;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`bit_gravity`](GetWindowAttributes::bit_gravity).
+	pub const fn bit_gravity(&mut self, bit_gravity: BitGravity) -> &mut Self {
+		self.reply.bit_gravity = bit_gravity;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`window_gravity`](GetWindowAttributes::window_gravity).
+	pub const fn window_gravity(&mut self, window_gravity: WindowGravity) -> &mut Self {
+		self.reply.window_gravity = window_gravity;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`maintained_planes`](GetWindowAttributes::maintained_planes).
+	pub const fn maintained_planes(&mut self, maintained_planes: u32) -> &mut Self {
+		self.reply.maintained_planes = maintained_planes;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`maintenance_fallback_color`](GetWindowAttributes::maintenance_fallback_color).
+	pub const fn maintenance_fallback_color(
+		&mut self, maintenance_fallback_color: ColorId,
+	) -> &mut Self {
+		self.reply.maintenance_fallback_color = maintenance_fallback_color;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`maintain_windows_under`](GetWindowAttributes::maintain_windows_under).
+	pub const fn maintain_windows_under(&mut self, maintain_windows_under: bool) -> &mut Self {
+		self.reply.maintain_windows_under = maintain_windows_under;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`map_installed`](GetWindowAttributes::map_installed).
+	pub const fn map_installed(&mut self, map_installed: bool) -> &mut Self {
+		self.reply.map_installed = map_installed;
+		self
+	}
+
+	/// Configures the reply's [`map_state`](GetWindowAttributes::map_state).
+	pub const fn map_state(&mut self, map_state: MapState) -> &mut Self {
+		self.reply.map_state = map_state;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`override_redirect`](GetWindowAttributes::override_redirect).
+	pub const fn override_redirect(&mut self, override_redirect: bool) -> &mut Self {
+		self.reply.override_redirect = override_redirect;
+		self
+	}
+
+	/// Configures the reply's [`colormap`](GetWindowAttributes::colormap).
+	pub const fn colormap(&mut self, colormap: Option<Colormap>) -> &mut Self {
+		self.reply.colormap = colormap;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`all_event_masks`](GetWindowAttributes::all_event_masks).
+	pub const fn all_event_masks(&mut self, all_event_masks: EventMask) -> &mut Self {
+		self.reply.all_event_masks = all_event_masks;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`your_event_mask`](GetWindowAttributes::your_event_mask).
+	pub const fn your_event_mask(&mut self, your_event_mask: EventMask) -> &mut Self {
+		self.reply.your_event_mask = your_event_mask;
+		self
+	}
+
+	/// Configures the reply's
+	/// [`do_not_propagate_mask`](GetWindowAttributes::do_not_propagate_mask).
+	pub const fn do_not_propagate_mask(
+		&mut self, do_not_propagate_mask: DeviceEventMask,
+	) -> &mut Self {
+		self.reply.do_not_propagate_mask = do_not_propagate_mask;
+		self
+	}
+}
+
+impl GetGeometry {
+	/// Returns a new [`GetGeometryBuilder`] with which a `GetGeometry` reply
+	/// can be created for the given `root`.
+	///
+	/// There is no meaningful default for the drawable's `root` window, so it
+	/// must be given here; every other field starts with its default value.
+	#[must_use]
+	pub fn builder(root: Window) -> GetGeometryBuilder {
+		GetGeometryBuilder::new(root)
+	}
+}
+
+/// A builder used to construct a new [`GetGeometry` reply].
+///
+/// Every field other than the `root` starts with its default value, and
+/// can be configured with the methods on this builder. When the builder is
+/// configured, [`build()`] can be used to construct the resulting
+/// [`GetGeometry`].
+///
+/// [`build()`]: GetGeometryBuilder::build
+/// [`GetGeometry` reply]: GetGeometry
+#[derive(Debug)]
+pub struct GetGeometryBuilder {
+	reply: GetGeometry,
+}
+
+impl GetGeometryBuilder {
+	/// Creates a new `GetGeometryBuilder` for the given `root`.
+	///
+	/// Every other field starts with its default value, and can be configured
+	/// with the other methods on this builder. When the builder is configured,
+	/// [`build()`] can be used to build the resulting [`GetGeometry`].
+	///
+	/// [`build()`]: GetGeometryBuilder::build
+	#[must_use]
+	pub fn new(root: Window) -> Self {
+		Self {
+			reply: GetGeometry {
+				sequence: 0,
+				depth: 0,
+				root,
+				geometry: Rectangle::default(),
+				border_width: Px(0),
+			},
+		}
+	}
+
+	/// Constructs the resulting [`GetGeometry` reply] with the configured
+	/// fields.
+	///
+	/// [`GetGeometry` reply]: GetGeometry
+	#[must_use]
+	pub const fn build(self) -> GetGeometry {
+		self.reply
+	}
+}
+
+impl GetGeometryBuilder {
+	/// Configures the reply's [`sequence`](GetGeometry::sequence).
+	pub const fn sequence(&mut self, sequence: u16) -> &mut Self {
+		self.reply.sequence = sequence;
+		self
+	}
+
+	/// Configures the reply's [`depth`](GetGeometry::depth).
+	pub const fn depth(&mut self, depth: u8) -> &mut Self {
+		self.reply.depth = depth;
+		self
+	}
+
+	/// Configures the reply's [`geometry`](GetGeometry::geometry).
+	pub const fn geometry(&mut self, geometry: Rectangle) -> &mut Self {
+		self.reply.geometry = geometry;
+		self
+	}
+
+	/// Configures the reply's [`border_width`](GetGeometry::border_width).
+	pub const fn border_width(&mut self, border_width: Px<u16>) -> &mut Self {
+		self.reply.border_width = border_width;
+		self
+	}
+}
+
+impl QueryWindowTree {
+	/// Returns a new [`QueryWindowTreeBuilder`] with which a `QueryWindowTree`
+	/// reply can be created for the given `root`.
+	///
+	/// There is no meaningful default for the window's `root` window, so it
+	/// must be given here; every other field starts with its default value.
+	#[must_use]
+	pub fn builder(root: Window) -> QueryWindowTreeBuilder {
+		QueryWindowTreeBuilder::new(root)
+	}
+}
+
+/// A builder used to construct a new [`QueryWindowTree` reply].
+///
+/// Every field other than the `root` starts with its default value, and
+/// can be configured with the methods on this builder. When the builder is
+/// configured, [`build()`] can be used to construct the resulting
+/// [`QueryWindowTree`].
+///
+/// [`build()`]: QueryWindowTreeBuilder::build
+/// [`QueryWindowTree` reply]: QueryWindowTree
+#[derive(Debug)]
+pub struct QueryWindowTreeBuilder {
+	reply: QueryWindowTree,
+}
+
+impl QueryWindowTreeBuilder {
+	/// Creates a new `QueryWindowTreeBuilder` for the given `root`.
+	///
+	/// Every other field starts with its default value, and can be configured
+	/// with the other methods on this builder. When the builder is configured,
+	/// [`build()`] can be used to build the resulting [`QueryWindowTree`].
+	///
+	/// [`build()`]: QueryWindowTreeBuilder::build
+	#[must_use]
+	pub fn new(root: Window) -> Self {
+		Self {
+			reply: QueryWindowTree {
+				sequence: 0,
+				root,
+				parent: None,
+				children: ReplyList::default(),
+			},
+		}
+	}
+
+	/// Constructs the resulting [`QueryWindowTree` reply] with the configured
+	/// fields.
+	///
+	/// [`QueryWindowTree` reply]: QueryWindowTree
+	#[must_use]
+	pub fn build(self) -> QueryWindowTree {
+		self.reply
+	}
+}
+
+impl QueryWindowTreeBuilder {
+	/// Configures the reply's [`sequence`](QueryWindowTree::sequence).
+	pub const fn sequence(&mut self, sequence: u16) -> &mut Self {
+		self.reply.sequence = sequence;
+		self
+	}
+
+	/// Configures the reply's [`parent`](QueryWindowTree::parent).
+	pub const fn parent(&mut self, parent: Option<Window>) -> &mut Self {
+		self.reply.parent = parent;
+		self
+	}
+
+	/// Configures the reply's [`children`](QueryWindowTree::children).
+	pub fn children(&mut self, children: impl Into<ReplyList<Window>>) -> &mut Self {
+		self.reply.children = children.into();
+		self
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_window_attributes_info() {
+		let mut builder = GetWindowAttributes::builder(VisualId::new(0x21));
+		builder
+			.sequence(1)
+			.map_installed(true)
+			.map_state(MapState::Viewable)
+			.colormap(Some(Colormap::new(0x20)))
+			.all_event_masks(EventMask::KEY_PRESS | EventMask::EXPOSURE)
+			.your_event_mask(EventMask::EXPOSURE);
+		let reply = builder.build();
+		assert_eq!(reply.class, WindowClass::InputOutput);
+		assert_eq!(reply.window_gravity, WindowGravity::NorthWest);
+
+		let info = reply.info();
+		assert!(info.is_mapped());
+		assert!(info.is_viewable());
+		assert!(info.accepts_input());
+		assert!(info.is_manageable());
+		assert!(info.colormap.installed);
+
+		let info = WindowAttributesInfo {
+			map_state: MapState::Unviewable,
+			override_redirect: true,
+			..info
+		};
+		assert!(info.is_mapped());
+		assert!(!info.accepts_input());
+		assert!(!info.is_manageable());
+	}
+
+	#[test]
+	fn test_window_reply_builders() {
+		let root = Window::new(0x0000_0100);
+
+		let mut builder = GetGeometry::builder(root);
+		builder
+			.depth(24)
+			.geometry(Rectangle::new(Px(-5), Px(10), Px(640), Px(480)));
+		let reply = builder.build();
+		assert_eq!(reply.root, root);
+		assert_eq!(reply.depth, 24);
+		assert_eq!(reply.border_width, Px(0));
+
+		let mut builder = QueryWindowTree::builder(root);
+		builder
+			.parent(Some(root))
+			.children(vec![Window::new(0x0020_0001)]);
+		let reply = builder.build();
+		assert_eq!(reply.parent, Some(root));
+		assert_eq!(&*reply.children, [Window::new(0x0020_0001)]);
 	}
 }
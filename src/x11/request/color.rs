@@ -12,7 +12,7 @@
 extern crate self as xrb;
 
 use xrbk::{pad, ConstantX11Size};
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
 	message::Request,
@@ -74,7 +74,9 @@ request_error! {
 /// [all entries allocated]: InitialColormapAllocation::All
 ///
 /// [colormap]: Colormap
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable,
+)]
 pub enum InitialColormapAllocation {
 	/// The [colormap] initially has no entries, or those initial entries are
 	/// defined elsewhere.
@@ -139,7 +141,7 @@ derive_xrb! {
 	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
 	/// [`Window` error]: error::Window
 	/// [`Match` error]: error::Match
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct CreateColormap: Request(78, CreateColormapError) {
 		/// Whether this [colormap] begins with [no entries allocated] or
 		/// [all entries allocated].
@@ -229,7 +231,7 @@ derive_xrb! {
 	///
 	/// [`Colormap` error]: error::Colormap
 	#[doc(alias("FreeColormap"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct DestroyColormap: Request(79, error::Colormap) {
 		/// The [colormap] which is to be deleted.
 		///
@@ -270,7 +272,7 @@ derive_xrb! {
 	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
 	/// [`Colormap` error]: error::Colormap
 	#[doc(alias("CopyColormapAndFree"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct MoveColormap: Request(80, MoveColormapError) {
 		/// The [`Colormap` ID] that will be associated with the new [colormap].
 		///
@@ -345,7 +347,7 @@ derive_xrb! {
 	/// [`Colormap` event]: crate::x11::event::Colormap
 	///
 	/// [`Colormap` error]: error::Colormap
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct InstallColormap: Request(81, error::Colormap) {
 		/// The [colormap] that is to be installed.
 		///
@@ -402,7 +404,7 @@ derive_xrb! {
 	/// [`Colormap` event]: crate::x11::event::Colormap
 	///
 	/// [`Colormap` error]: error::Colormap
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct UninstallColormap: Request(82, error::Colormap) {
 		/// The [colormap] that is to be uninstalled.
 		///
@@ -434,7 +436,7 @@ derive_xrb! {
 	/// [`ListInstalledColormaps` reply]: reply::ListInstalledColormaps
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ListInstalledColormaps: Request(83, error::Window) -> reply::ListInstalledColormaps {
 		/// The [window] for which this [request] returns its installed
 		/// [colormaps].
@@ -478,7 +480,7 @@ derive_xrb! {
 	///
 	/// [`Colormap` error]: error::Colormap
 	#[doc(alias("AllocColor"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct AllocateColor: Request(84, error::Colormap) -> reply::AllocateColor {
 		/// The [colormap] for which the [colormap] entry is allocated.
 		///
@@ -525,7 +527,7 @@ derive_xrb! {
 	/// [`Colormap` error]: error::Colormap
 	/// [`Name` error]: error::Name
 	#[doc(alias("AllocNamedColor"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct AllocateNamedColor: Request(
 		85,
 		AllocateNamedColorError,
@@ -601,7 +603,7 @@ derive_xrb! {
 	/// [`Colormap` error]: error::Colormap
 	/// [`Value` error]: error::Value
 	#[doc(alias("AllocColorCells"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct AllocateColorCells: Request(
 		86,
 		AllocateColorCellsError,
@@ -699,7 +701,7 @@ derive_xrb! {
 	///
 	/// [`RequestError::Alloc`]: crate::message::RequestError::Alloc
 	#[doc(alias("AllocColorPlanes"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct AllocateColorPlanes: Request(
 		87,
 		AllocateColorPlanesError,
@@ -794,7 +796,7 @@ derive_xrb! {
 	/// [`Value` error]: error::Value
 	// TODO: rename all Destroy* requests to Delete*
 	#[doc(alias("FreeColors"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct DestroyColormapEntries: Request(88, DestroyColormapEntriesError) {
 		/// The [colormap] for which the [colormap] entries are deleted.
 		///
@@ -838,7 +840,7 @@ derive_xrb! {
 	/// [colormap]: Colormap
 	///
 	/// [`StoreColors` request]: StoreColors
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ColormapEntryChange {
 		/// The [`ColorId`] of the changed [colormap] entry.
 		///
@@ -855,15 +857,6 @@ derive_xrb! {
 		_,
 	}
 
-	impl ConstantX11Size for ColormapEntryChange {
-		const X11_SIZE: usize = {
-			ColorId::X11_SIZE
-			+ RgbColor::X11_SIZE
-			+ ColorChannelMask::X11_SIZE
-			+ 1
-		};
-	}
-
 	/// A [request] that changes the [RGB values] of the given [colormap]
 	/// entries.
 	///
@@ -886,7 +879,7 @@ derive_xrb! {
 	/// [`Access` error]: error::Access
 	/// [`Colormap` error]: error::Colormap
 	/// [`Value` error]: error::Value
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct StoreColors: Request(89, StoreColorsError) {
 		/// The [colormap] for which the [colormap] entries are changed.
 		///
@@ -956,12 +949,13 @@ derive_xrb! {
 	/// [`Colormap` error]: error::Colormap
 	/// [`Value` error]: error::Value
 	/// [`Name` error]: error::Name
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct StoreNamedColor: Request(90, StoreNamedColorError) {
 		/// The mask for which of the [colormap] entry's color channels are
 		/// changed.
 		///
 		/// [colormap]: Colormap
+		#[metabyte]
 		pub mask: ColorChannelMask,
 
 		/// The [colormap] for which the [colormap] entry is changed.
@@ -1031,7 +1025,7 @@ derive_xrb! {
 	///
 	/// [`Colormap` error]: error::Colormap
 	/// [`Value` error]: error::Value
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct QueryColors: Request(91, QueryColorsError) -> reply::QueryColors {
 		/// The [colormap] on which the [RGB values] of the given [colormap]
 		/// entries are queried.
@@ -1095,7 +1089,7 @@ derive_xrb! {
 	/// [`Colormap` error]: error::Colormap
 	/// [`Name` error]: error::Name
 	#[doc(alias("LookupColor"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct GetNamedColor: Request(92, GetNamedColorError) -> reply::GetNamedColor {
 		/// The [colormap] whose [screen] defines the requested color.
 		///
@@ -69,7 +69,7 @@ derive_xrb! {
 	///
 	/// [request]: Request
 	#[doc(alias("OpenFont", "CreateFont", "LoadFont", "AddFont"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct AssignFont: Request(45, AssignFontError) {
 		/// The [`Font` ID] to associate with the font specified by `name`.
 		///
@@ -99,7 +99,7 @@ derive_xrb! {
 	/// [request]: Request
 	/// [`Font` ID]: Font
 	#[doc(alias("CloseFont", "DeleteFont", "UnloadFont", "RemoveFont"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct UnassignFont: Request(46) {
 		/// The [`Font` ID] which is having its association with a font removed.
 		///
@@ -124,7 +124,7 @@ derive_xrb! {
 	/// [`QueryFont` reply]: reply::QueryFont
 	///
 	/// [`Font` error]: error::Font
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct QueryFont: Request(47, error::Font) -> reply::QueryFont {
 		/// The font which this [request] returns information about.
 		///
@@ -173,10 +173,11 @@ derive_xrb! {
 	/// [`QueryTextExtents` reply]: reply::QueryTextExtents
 	///
 	/// [`Font` error]: error::Font
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct QueryTextExtents: Request(48, error::Font) -> reply::QueryTextExtents {
-		// Whether `text` is of odd length. Is it is, it has 2 bytes of padding
-		// following it.
+		// Whether `text` is of odd length. If it is, it has 2 bytes of padding
+		// following it. This is derived from `text` when writing the request, so
+		// it can't disagree with it.
 		#[metabyte]
 		let odd_length: bool = text => text.len() % 2 != 0,
 
@@ -218,7 +219,7 @@ derive_xrb! {
 	/// [font search path]: SetFontSearchDirectories
 	///
 	/// [`ListFonts` reply]: reply::ListFonts
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ListFonts: Request(49) -> reply::ListFonts {
 		/// The maximum number of names that will appear in the returned font
 		/// `names`.
@@ -257,7 +258,7 @@ derive_xrb! {
 	///
 	/// [`ListFontsWithInfo` replies]: reply::ListFontsWithInfo
 	/// [`QueryFont` reply]: reply::QueryFont
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ListFontsWithInfo: Request(50) -> reply::ListFontsWithInfo {
 		/// The maximum number of [`FontWithInfo` replies] that will be returned.
 		///
@@ -293,7 +294,7 @@ derive_xrb! {
 	///
 	/// [`Value` error]: error::Value
 	#[doc(alias = "SetFontPath")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct SetFontSearchDirectories: Request(51, error::Value) {
 		// The length of `directories`.
 		#[allow(clippy::cast_possible_truncation)]
@@ -316,6 +317,41 @@ derive_xrb! {
 	/// See also: [`SetFontSearchDirectories`].
 	///
 	/// [request]: Request
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetFontSearchDirectories: Request(52) -> reply::GetFontSearchDirectories;
 }
+
+#[cfg(test)]
+mod test {
+	use xrbk::{Readable, Writable, X11Size};
+
+	use super::*;
+
+	#[test]
+	fn test_query_text_extents_odd_length() {
+		let request = |text: &str| QueryTextExtents {
+			font: Font::new(0x0040_0001).into(),
+			text: String16::try_from(text).unwrap(),
+		};
+
+		let mut bytes = Vec::new();
+		request("abc").write_to(&mut bytes).unwrap();
+		assert_eq!(
+			bytes,
+			[48, 1, 0, 4, 0, 0x40, 0, 1, 0, b'a', 0, b'b', 0, b'c', 0, 0],
+		);
+		assert_eq!(request("abc").x11_size(), bytes.len());
+		assert_eq!(
+			QueryTextExtents::read_from(&mut &bytes[1..]).unwrap(),
+			request("abc"),
+		);
+
+		let mut bytes = Vec::new();
+		request("ab").write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, [48, 0, 0, 3, 0, 0x40, 0, 1, 0, b'a', 0, b'b']);
+		assert_eq!(
+			QueryTextExtents::read_from(&mut &bytes[1..]).unwrap(),
+			request("ab"),
+		);
+	}
+}
@@ -15,13 +15,16 @@ pub use config::*;
 
 extern crate self as xrb;
 
+use std::{fmt::Debug, iter, ops::Range};
 use thiserror::Error;
 
 use xrbk::{
+	check_remaining,
 	pad,
 	Buf,
 	BufMut,
 	ConstantX11Size,
+	ReadError,
 	ReadResult,
 	Readable,
 	ReadableWithContext,
@@ -29,13 +32,15 @@ use xrbk::{
 	WriteResult,
 	X11Size,
 };
-use xrbk_macro::{derive_xrb, ConstantX11Size, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
-	message::Request,
+	message::{Message, MessageKind, Request},
 	unit::Px,
 	x11::{error, reply},
 	Arc,
+	Char16,
+	Char8,
 	Coords,
 	Dimensions,
 	Drawable,
@@ -44,6 +49,7 @@ use crate::{
 	Rectangle,
 	String16,
 	String8,
+	UnencodableCharacter,
 	Window,
 };
 
@@ -110,7 +116,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	/// [`Match` error]: error::Match
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct ClearArea: Request(61, ClearAreaError) {
 		/// Whether [`GraphicsExposure` events] should be generated for regions
 		/// of the `area` which are visible or maintained.
@@ -209,7 +215,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	/// [`Match` error]: error::Match
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct CopyArea: Request(62, CopyAreaError) {
 		/// The [drawable] from which the area is copied.
 		///
@@ -347,7 +353,7 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	/// [`Value` error]: error::Value
 	#[doc(alias("CopyPlane"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct CopyBitPlane: Request(63, CopyBitPlaneError) {
 		/// The [drawable] used as the source in this graphics operation.
 		///
@@ -459,7 +465,19 @@ request_error! {
 ///
 /// [coordinates]: Coords
 /// [drawable]: Drawable
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum CoordinateMode {
 	/// [Coordinates] are relative to the top-left corner of the [drawable].
 	///
@@ -517,7 +535,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	/// [`Match` error]: error::Match
 	#[doc(alias("PolyPoint", "DrawPoint"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct DrawPoints: Request(64, DrawPointsError) {
 		/// Whether the `points` are drawn relative to the `target` or the
 		/// previously drawn point.
@@ -657,7 +675,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyLine", "DrawLines", "DrawLine"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct DrawPath: Request(65, DrawPathError) {
 		/// Whether the [coordinates] of each point in `points` are relative to
 		/// the `target` or to the previous point.
@@ -717,7 +735,19 @@ request_error! {
 
 /// A line from the given `start` point to the given `end` point.
 #[doc(alias("Segment"))]
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+	ConstantX11Size,
+)]
 pub struct Line {
 	/// The start of the line.
 	pub start: Coords,
@@ -792,7 +822,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolySegment", "DrawSegment"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct DrawLines: Request(66, DrawLinesError) {
 		/// The [drawable] on which the given `lines` are drawn.
 		///
@@ -908,7 +938,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyRectangle"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct DrawRectangles: Request(67, DrawRectanglesError) {
 		/// The [drawable] on which the `rectangles`' outlines are drawn.
 		///
@@ -1023,7 +1053,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyArc"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct DrawArcs: Request(68, DrawArcsError) {
 		/// The [drawable] on which the [arcs] are drawn.
 		///
@@ -1080,7 +1110,9 @@ request_error! {
 /// This is used in the [`FillPolygon` request].
 ///
 /// [`FillPolygon` request]: FillPolygon
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable,
+)]
 pub enum ShapeMode {
 	/// The shape may intersect itself.
 	Complex,
@@ -1157,7 +1189,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("FillPoly"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct FillPolygon: Request(69, FillPolygonError) {
 		/// The [drawable] on which the filled polygon is drawn.
 		///
@@ -1286,7 +1318,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyFillRectangle"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct FillRectangles: Request(70, FillRectanglesError) {
 		/// The [drawable] on which the [rectangles] are filled.
 		///
@@ -1399,7 +1431,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("PolyFillArc"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct FillArcs: Request(71, FillArcsError) {
 		/// The [drawable] on which the [arcs] are filled.
 		///
@@ -1438,6 +1470,216 @@ derive_xrb! {
 	}
 }
 
+/// Returns the number of elements of the given `element_size` which fit in a
+/// graphics [request] no longer than `max_request_words`.
+///
+/// Each of these [requests] has a 12-byte header. At least one element is
+/// always returned.
+///
+/// [request]: Request
+/// [requests]: Request
+const fn elements_per_request(max_request_words: usize, element_size: usize) -> usize {
+	const HEADER_SIZE: usize = 12;
+
+	let elements = (max_request_words * 4).saturating_sub(HEADER_SIZE) / element_size;
+
+	if elements == 0 {
+		1
+	} else {
+		elements
+	}
+}
+
+/// Returns the [coordinates] of `point` offset by `origin`.
+///
+/// [coordinates]: Coords
+const fn offset(origin: Coords, point: Coords) -> Coords {
+	Coords {
+		x: Px(origin.x.0.wrapping_add(point.x.0)),
+		y: Px(origin.y.0.wrapping_add(point.y.0)),
+	}
+}
+
+/// Splits `points` into chunks of at most `capacity` points.
+///
+/// If `overlap` is true, each chunk after the first starts with the last point
+/// of the previous chunk, so that a path drawn through every chunk is
+/// continuous.
+///
+/// The first point of each chunk is always relative to the [drawable], so for
+/// [`CoordinateMode::Previous`], the first point of each chunk after the first
+/// is replaced with its position relative to the [drawable].
+///
+/// [drawable]: Drawable
+fn split_points(
+	points: &[Coords], capacity: usize, overlap: bool, coordinate_mode: CoordinateMode,
+) -> Vec<Vec<Coords>> {
+	let step = if overlap { capacity - 1 } else { capacity };
+
+	let mut chunks = Vec::new();
+	// The position relative to the drawable of the point which `points[start]`
+	// is relative to in `CoordinateMode::Previous`.
+	let mut origin = Coords::new(Px(0), Px(0));
+	let mut start = 0;
+
+	loop {
+		let end = (start + capacity).min(points.len());
+
+		let mut chunk = points[start..end].to_vec();
+		if coordinate_mode == CoordinateMode::Previous {
+			chunk[0] = offset(origin, chunk[0]);
+		}
+
+		if end == points.len() {
+			chunks.push(chunk);
+
+			break chunks;
+		}
+
+		if coordinate_mode == CoordinateMode::Previous {
+			origin = points[start..start + step]
+				.iter()
+				.fold(origin, |origin, &point| offset(origin, point));
+		}
+
+		chunks.push(chunk);
+		start += step;
+	}
+}
+
+impl DrawPoints {
+	/// Splits this [request] into [requests] no longer than
+	/// `max_request_words`, measured in units of 4 bytes.
+	///
+	/// `max_request_words` is usually the [`maximum_request_length`] of the
+	/// connection.
+	///
+	/// If the `coordinate_mode` is [`CoordinateMode::Previous`], the first
+	/// point of each [request] is adjusted to be relative to the `target`, so
+	/// the same points are drawn.
+	///
+	/// [request]: Request
+	/// [requests]: Request
+	///
+	/// [`maximum_request_length`]: crate::connection::ConnectionSuccess::maximum_request_length
+	#[must_use]
+	pub fn split_for_max_len(self, max_request_words: usize) -> Vec<Self> {
+		let capacity = elements_per_request(max_request_words, Coords::X11_SIZE);
+
+		if self.points.len() <= capacity {
+			return vec![self];
+		}
+
+		split_points(&self.points, capacity, false, self.coordinate_mode)
+			.into_iter()
+			.map(|points| Self {
+				coordinate_mode: self.coordinate_mode,
+				target: self.target,
+				graphics_context: self.graphics_context,
+				points,
+			})
+			.collect()
+	}
+}
+
+impl DrawPath {
+	/// Splits this [request] into [requests] no longer than
+	/// `max_request_words`, measured in units of 4 bytes.
+	///
+	/// `max_request_words` is usually the [`maximum_request_length`] of the
+	/// connection.
+	///
+	/// Each [request] after the first starts at the last point of the previous
+	/// [request], so the path remains connected. The path is not joined at
+	/// those points, however: the [`cap_style`] is used instead of the
+	/// [`join_style`].
+	///
+	/// If the `coordinate_mode` is [`CoordinateMode::Previous`], the first
+	/// point of each [request] is adjusted to be relative to the `target`, so
+	/// the same path is drawn.
+	///
+	/// [request]: Request
+	/// [requests]: Request
+	///
+	/// [`cap_style`]: crate::set::GraphicsOptions::cap_style
+	/// [`join_style`]: crate::set::GraphicsOptions::join_style
+	/// [`maximum_request_length`]: crate::connection::ConnectionSuccess::maximum_request_length
+	#[must_use]
+	pub fn split_for_max_len(self, max_request_words: usize) -> Vec<Self> {
+		// At least two points are needed for the path to progress.
+		let capacity = elements_per_request(max_request_words, Coords::X11_SIZE).max(2);
+
+		if self.points.len() <= capacity {
+			return vec![self];
+		}
+
+		split_points(&self.points, capacity, true, self.coordinate_mode)
+			.into_iter()
+			.map(|points| Self {
+				coordinate_mode: self.coordinate_mode,
+				target: self.target,
+				graphics_context: self.graphics_context,
+				points,
+			})
+			.collect()
+	}
+}
+
+/// Implements `split_for_max_len` for graphics [requests] with a list of
+/// independent elements.
+///
+/// [requests]: Request
+macro_rules! impl_split_for_max_len {
+	($($Request:ident { $elements:ident: $Element:ty }),+$(,)?) => {
+		$(
+			impl $Request {
+				#[doc = concat!(
+					"Splits this [request] into [requests] no longer than `max_request_words`, ",
+					"measured in units of 4 bytes.",
+				)]
+				#[doc = ""]
+				#[doc = concat!(
+					"`max_request_words` is usually the [`maximum_request_length`] of the ",
+					"connection.",
+				)]
+				#[doc = ""]
+				#[doc = "[request]: Request"]
+				#[doc = "[requests]: Request"]
+				#[doc = ""]
+				#[doc = concat!(
+					"[`maximum_request_length`]: ",
+					"crate::connection::ConnectionSuccess::maximum_request_length",
+				)]
+				#[must_use]
+				pub fn split_for_max_len(self, max_request_words: usize) -> Vec<Self> {
+					let capacity = elements_per_request(max_request_words, <$Element>::X11_SIZE);
+
+					if self.$elements.len() <= capacity {
+						return vec![self];
+					}
+
+					self.$elements
+						.chunks(capacity)
+						.map(|$elements| Self {
+							target: self.target,
+							graphics_context: self.graphics_context,
+							$elements: $elements.to_vec(),
+						})
+						.collect()
+				}
+			}
+		)+
+	};
+}
+
+impl_split_for_max_len! {
+	DrawLines { lines: Line },
+	DrawRectangles { rectangles: Rectangle },
+	DrawArcs { arcs: Arc },
+	FillRectangles { rectangles: Rectangle },
+	FillArcs { arcs: Arc },
+}
+
 request_error! {
 	#[doc(alias("PutImageError"))]
 	pub enum PlaceImageError for PlaceImage {
@@ -1452,7 +1694,9 @@ request_error! {
 ///
 /// [`PlaceImage` request]: PlaceImage
 #[doc(alias("PutImageFormat"))]
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable,
+)]
 pub enum PlaceImageFormat {
 	/// The image must be in XY format.
 	///
@@ -1532,7 +1776,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	/// [`Match` error]: error::Match
 	#[doc(alias("PutImage"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct PlaceImage: Request(72, PlaceImageError) {
 		/// The [image format] used.
 		///
@@ -1641,7 +1885,9 @@ request_error! {
 /// [`CaptureImage` request]: CaptureImage
 /// [`CaptureImage` reply]: reply::CaptureImage
 #[doc(alias("GetImageFormat"))]
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable,
+)]
 pub enum CaptureImageFormat {
 	/// The image is returned in XY format.
 	XyPixmap,
@@ -1697,7 +1943,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`Match` error]: error::Match
 	#[doc(alias("GetImage"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct CaptureImage: Request(73, CaptureImageError) -> reply::CaptureImage {
 		/// The [image format] of the image that is returned in the
 		/// [`CaptureImage` reply].
@@ -1787,8 +2033,8 @@ impl Readable for TextItem8 {
 	where
 		Self: Sized,
 	{
-		Ok(match buf.get_u8() {
-			font_shift if font_shift == 255 => Self::Font(Font::new(buf.get_u32())),
+		Ok(match u8::read_from(buf)? {
+			font_shift if font_shift == 255 => Self::Font(Font::read_from(buf)?),
 			string_len => Self::Text(Box::new(Text8::read_with(buf, &string_len)?)),
 		})
 	}
@@ -1836,18 +2082,38 @@ pub struct TextTooLong {
 	pub found: usize,
 }
 
+/// An error returned when the given text cannot be used in a [text item].
+///
+/// [text item]: TextItem8
+#[derive(Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidText {
+	/// The text contained a character which cannot be encoded.
+	#[error(transparent)]
+	Unencodable(#[from] UnencodableCharacter),
+
+	/// The text was too long.
+	#[error(transparent)]
+	TooLong(#[from] TextTooLong),
+}
+
 impl Text8 {
+	/// The maximum number of characters in a `Text8`.
+	pub const MAX_STRING_LEN: u8 = 254;
+
 	/// Creates a new `Text8` with the given `horizontal_offset` and `string`.
 	///
 	/// `horizontal_offset` specifies the offset that is applied to the start of
 	/// the `string`.
 	///
 	/// # Errors
-	/// A [`TextTooLong`] error is returned if `string.len() > 255`.
+	/// A [`TextTooLong`] error is returned if `string.len() > 254`: a length
+	/// of 255 is reserved to indicate a font change in a [text item].
+	///
+	/// [text item]: TextItem8
 	pub fn new(horizontal_offset: Px<i8>, string: String8) -> Result<Self, TextTooLong> {
-		if string.len() > 255 {
+		if string.len() > usize::from(Self::MAX_STRING_LEN) {
 			Err(TextTooLong {
-				max: 255,
+				max: Self::MAX_STRING_LEN,
 				found: string.len(),
 			})
 		} else {
@@ -1858,6 +2124,19 @@ impl Text8 {
 		}
 	}
 
+	/// Creates a new `Text8` with the given `horizontal_offset`, encoding
+	/// `text` as Latin-1 (ISO 8859-1).
+	///
+	/// See [`String8::from_latin1`] for more information.
+	///
+	/// # Errors
+	/// An [`InvalidText::Unencodable`] error is returned if `text` contains a
+	/// character which cannot be encoded, and an [`InvalidText::TooLong`]
+	/// error is returned if `text` contains more than 254 characters.
+	pub fn from_latin1(horizontal_offset: Px<i8>, text: &str) -> Result<Self, InvalidText> {
+		Ok(Self::new(horizontal_offset, String8::from_latin1(text)?)?)
+	}
+
 	/// The horizontal offset applied to the start of the [`string`].
 	///
 	/// [`string`]: Text8::string
@@ -2034,6 +2313,37 @@ impl Request for DrawText8 {
 	const MINOR_OPCODE: Option<u16> = None;
 }
 
+impl Message for DrawText8 {
+	fn name(&self) -> &'static str {
+		"DrawText8"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_request::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("target", &self.target),
+			("graphics_context", &self.graphics_context),
+			("coordinates", &self.coordinates),
+			("text_items", &self.text_items),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		let size = self.x11_size();
+		let padding = pad(&self.text_items);
+
+		let mut unused = vec![1..2];
+		if padding > 0 {
+			unused.push((size - padding)..size);
+		}
+
+		unused
+	}
+}
+
 impl X11Size for DrawText8 {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 4;
@@ -2063,10 +2373,13 @@ impl Readable for DrawText8 {
 		// major opcode is already read
 
 		// Metabyte position is unused.
+		check_remaining(buf, 1)?;
 		buf.advance(1);
 
 		// Read the length and bound buf to not read more than it.
-		let length = (usize::from(buf.get_u16()) * 4) - 2;
+		let length = (usize::from(u16::read_from(buf)?) * 4)
+			.checked_sub(2)
+			.ok_or_else(|| ReadError::Other(Box::new("the request's length is zero")))?;
 		let buf = &mut buf.take(length);
 
 		let target = Drawable::read_from(buf)?;
@@ -2084,6 +2397,7 @@ impl Readable for DrawText8 {
 		};
 
 		// Advance the padding bytes at the end.
+		check_remaining(buf, pad(&text_items))?;
 		buf.advance(pad(&text_items));
 
 		Ok(Self {
@@ -2160,8 +2474,8 @@ impl Readable for TextItem16 {
 	where
 		Self: Sized,
 	{
-		Ok(match buf.get_u8() {
-			font_shift if font_shift == 255 => Self::Font(Font::new(buf.get_u32())),
+		Ok(match u8::read_from(buf)? {
+			font_shift if font_shift == 255 => Self::Font(Font::read_from(buf)?),
 			string_len => Self::Text(Box::new(Text16::read_with(buf, &string_len)?)),
 		})
 	}
@@ -2200,17 +2514,23 @@ pub struct Text16 {
 }
 
 impl Text16 {
+	/// The maximum number of characters in a `Text16`.
+	pub const MAX_STRING_LEN: u8 = 254;
+
 	/// Creates a new `Text16` with the given `horizontal_offset` and `string`.
 	///
 	/// `horizontal_offset` specifies the offset that is applied to the start of
 	/// the `string`.
 	///
 	/// # Errors
-	/// A [`TextTooLong`] error is returned if `string.len() > 255`.
+	/// A [`TextTooLong`] error is returned if `string.len() > 254`: a length
+	/// of 255 is reserved to indicate a font change in a [text item].
+	///
+	/// [text item]: TextItem16
 	pub fn new(horizontal_offset: Px<i8>, string: String16) -> Result<Self, TextTooLong> {
-		if string.len() > 255 {
+		if string.len() > usize::from(Self::MAX_STRING_LEN) {
 			Err(TextTooLong {
-				max: 255,
+				max: Self::MAX_STRING_LEN,
 				found: string.len(),
 			})
 		} else {
@@ -2221,6 +2541,19 @@ impl Text16 {
 		}
 	}
 
+	/// Creates a new `Text16` with the given `horizontal_offset`, encoding
+	/// `text` as big-endian two-byte characters.
+	///
+	/// See [`String16::from_ucs2`] for more information.
+	///
+	/// # Errors
+	/// An [`InvalidText::Unencodable`] error is returned if `text` contains a
+	/// character which cannot be encoded, and an [`InvalidText::TooLong`]
+	/// error is returned if `text` contains more than 254 characters.
+	pub fn from_ucs2(horizontal_offset: Px<i8>, text: &str) -> Result<Self, InvalidText> {
+		Ok(Self::new(horizontal_offset, String16::from_ucs2(text)?)?)
+	}
+
 	/// The horizontal offset applied to the start of the [`string`].
 	///
 	/// [`string`]: Text16::string
@@ -2402,6 +2735,37 @@ impl Request for DrawText16 {
 	const MINOR_OPCODE: Option<u16> = None;
 }
 
+impl Message for DrawText16 {
+	fn name(&self) -> &'static str {
+		"DrawText16"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_request::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("target", &self.target),
+			("graphics_context", &self.graphics_context),
+			("coordinates", &self.coordinates),
+			("text_items", &self.text_items),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		let size = self.x11_size();
+		let padding = pad(&self.text_items);
+
+		let mut unused = vec![1..2];
+		if padding > 0 {
+			unused.push((size - padding)..size);
+		}
+
+		unused
+	}
+}
+
 impl X11Size for DrawText16 {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 4;
@@ -2431,10 +2795,13 @@ impl Readable for DrawText16 {
 		// major opcode is already read
 
 		// Metabyte position is unused.
+		check_remaining(buf, 1)?;
 		buf.advance(1);
 
 		// Read the length and bound buf to not read more than it.
-		let length = (usize::from(buf.get_u16()) * 4) - 2;
+		let length = (usize::from(u16::read_from(buf)?) * 4)
+			.checked_sub(2)
+			.ok_or_else(|| ReadError::Other(Box::new("the request's length is zero")))?;
 		let buf = &mut buf.take(length);
 
 		let target = Drawable::read_from(buf)?;
@@ -2452,6 +2819,7 @@ impl Readable for DrawText16 {
 		};
 
 		// Advance the padding bytes at the end.
+		check_remaining(buf, pad(&text_items))?;
 		buf.advance(pad(&text_items));
 
 		Ok(Self {
@@ -2578,7 +2946,7 @@ derive_xrb! {
 	///
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ImageText8: Request(76, ImageText8Error) {
 		// The length of `string`.
 		#[metabyte]
@@ -2715,7 +3083,7 @@ derive_xrb! {
 	///
 	/// [`Drawable` error]: error::Drawable
 	/// [`GraphicsContext` error]: error::GraphicsContext
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ImageText16: Request(77, ImageText16Error) {
 		// The length of `string`.
 		#[metabyte]
@@ -2761,3 +3129,264 @@ derive_xrb! {
 		[_; string => pad(string)],
 	}
 }
+
+/// Splits `characters` into chunks of at most `max_len` characters, each paired
+/// with the [coordinates] at which that chunk is drawn.
+///
+/// Each chunk is drawn where the previous chunk ended, according to the metrics
+/// of the given `font`. The chunks stop once the next chunk would begin beyond
+/// the range of an `i16` `x` coordinate: it could not be drawn within any
+/// [drawable] anyway.
+///
+/// [coordinates]: Coords
+/// [drawable]: Drawable
+fn split_image_text<'font, C, S>(
+	characters: Vec<C>, max_len: usize, coordinates: Coords, font: &'font reply::QueryFont,
+) -> impl Iterator<Item = (Coords, S)> + 'font
+where
+	C: Copy + Into<Char16> + 'font,
+	S: From<Vec<C>>,
+{
+	let mut characters = characters.into_iter();
+	let mut next_coordinates = Some(coordinates);
+
+	iter::from_fn(move || {
+		let coordinates = next_coordinates?;
+
+		let chunk: Vec<C> = characters.by_ref().take(max_len).collect();
+		if chunk.is_empty() {
+			return None;
+		}
+
+		let width = font.text_extents(chunk.iter().copied()).overall_width;
+		next_coordinates = i16::try_from(i32::from(coordinates.x.0) + width)
+			.ok()
+			.map(|x| Coords::new(Px(x), coordinates.y));
+
+		Some((coordinates, S::from(chunk)))
+	})
+}
+
+impl ImageText8 {
+	/// The maximum number of characters that can be drawn by a single
+	/// `ImageText8` request.
+	pub const MAX_STRING_LEN: u8 = u8::MAX;
+
+	/// Creates a sequence of `ImageText8` requests which draw the given
+	/// `string`, no matter its length.
+	///
+	/// The `string` is split into chunks of at most [`MAX_STRING_LEN`]
+	/// characters. Each chunk is drawn where the previous chunk ended, as
+	/// measured by the metrics of the given `font`, which should be the
+	/// [`QueryFont` reply] for the `graphics_context`'s [font]. Any chunks
+	/// which would begin beyond an `x` coordinate of [`i16::MAX`] are not
+	/// drawn.
+	///
+	/// [`MAX_STRING_LEN`]: ImageText8::MAX_STRING_LEN
+	/// [font]: Font
+	///
+	/// [`QueryFont` reply]: reply::QueryFont
+	pub fn split(
+		target: Drawable, graphics_context: GraphicsContext, coordinates: Coords, string: String8,
+		font: &reply::QueryFont,
+	) -> impl Iterator<Item = Self> + '_ {
+		let characters: Vec<Char8> = string.into();
+
+		split_image_text(
+			characters,
+			usize::from(Self::MAX_STRING_LEN),
+			coordinates,
+			font,
+		)
+		.map(move |(coordinates, string)| Self {
+			target,
+			graphics_context,
+			coordinates,
+			string,
+		})
+	}
+}
+
+impl ImageText16 {
+	/// The maximum number of characters that can be drawn by a single
+	/// `ImageText16` request.
+	pub const MAX_STRING_LEN: u8 = u8::MAX;
+
+	/// Creates a sequence of `ImageText16` requests which draw the given
+	/// `string`, no matter its length.
+	///
+	/// The `string` is split into chunks of at most [`MAX_STRING_LEN`]
+	/// characters. Each chunk is drawn where the previous chunk ended, as
+	/// measured by the metrics of the given `font`, which should be the
+	/// [`QueryFont` reply] for the `graphics_context`'s [font]. Any chunks
+	/// which would begin beyond an `x` coordinate of [`i16::MAX`] are not
+	/// drawn.
+	///
+	/// [`MAX_STRING_LEN`]: ImageText16::MAX_STRING_LEN
+	/// [font]: Font
+	///
+	/// [`QueryFont` reply]: reply::QueryFont
+	pub fn split(
+		target: Drawable, graphics_context: GraphicsContext, coordinates: Coords, string: String16,
+		font: &reply::QueryFont,
+	) -> impl Iterator<Item = Self> + '_ {
+		let characters: Vec<Char16> = string.into();
+
+		split_image_text(
+			characters,
+			usize::from(Self::MAX_STRING_LEN),
+			coordinates,
+			font,
+		)
+		.map(move |(coordinates, string)| Self {
+			target,
+			graphics_context,
+			coordinates,
+			string,
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::x11::reply::{CharacterInfo, DrawDirection};
+
+	#[test]
+	fn test_image_text_split() {
+		let info = CharacterInfo {
+			left_side_bearing: 0,
+			right_side_bearing: 6,
+			width: 6,
+			ascent: 8,
+			descent: 2,
+			attributes: 0,
+		};
+
+		// Every character in a font without `character_infos` has the same
+		// metrics.
+		let font = reply::QueryFont {
+			sequence: 0,
+			min_bounds: info,
+			max_bounds: info,
+			first_character_or_min_minor_index: 0,
+			last_character_or_max_minor_index: 255,
+			fallback_character: 0,
+			draw_direction: DrawDirection::LeftToRight,
+			min_major_index: 0,
+			max_major_index: 0,
+			all_characters_exist: true,
+			font_ascent: 8,
+			font_descent: 2,
+			properties: vec![],
+			character_infos: vec![],
+		};
+
+		let string = String8::from(vec![Char8(b'a'); 600]);
+		let requests: Vec<_> = ImageText8::split(
+			Drawable::new(1),
+			GraphicsContext::new(2),
+			Coords::new(Px(10), Px(20)),
+			string,
+			&font,
+		)
+		.collect();
+
+		assert_eq!(
+			requests
+				.iter()
+				.map(|request| (request.coordinates.x, request.string.len()))
+				.collect::<Vec<_>>(),
+			[
+				(Px(10), 255),
+				(Px(10 + 6 * 255), 255),
+				(Px(10 + 6 * 510), 90)
+			],
+		);
+
+		// The second chunk would begin beyond `i16::MAX`.
+		let requests: Vec<_> = ImageText8::split(
+			Drawable::new(1),
+			GraphicsContext::new(2),
+			Coords::new(Px(32_000), Px(20)),
+			String8::from(vec![Char8(b'a'); 600]),
+			&font,
+		)
+		.collect();
+		assert_eq!(requests.len(), 1);
+		assert_eq!(requests[0].coordinates.x, Px(32_000));
+
+		assert!(Text8::new(Px(0), String8::from(vec![Char8(b'a'); 254])).is_ok());
+		assert!(Text8::new(Px(0), String8::from(vec![Char8(b'a'); 255])).is_err());
+	}
+
+	#[test]
+	fn test_split_for_max_len() {
+		let coords = |x, y| Coords::new(Px(x), Px(y));
+		// Relative to the drawable: (1, 1), (3, 1), (3, 4), (7, 8), (8, 9).
+		let points = vec![
+			coords(1, 1),
+			coords(2, 0),
+			coords(0, 3),
+			coords(4, 4),
+			coords(1, 1),
+		];
+
+		// 5 words leaves room for 2 points after the header.
+		let requests = DrawPoints {
+			coordinate_mode: CoordinateMode::Previous,
+			target: Drawable::new(1),
+			graphics_context: GraphicsContext::new(2),
+			points: points.clone(),
+		}
+		.split_for_max_len(5);
+
+		assert!(requests.iter().all(|request| request.x11_size() <= 5 * 4));
+		assert_eq!(
+			requests
+				.into_iter()
+				.map(|request| request.points)
+				.collect::<Vec<_>>(),
+			[
+				vec![coords(1, 1), coords(2, 0)],
+				vec![coords(3, 4), coords(4, 4)],
+				vec![coords(8, 9)],
+			],
+		);
+
+		// Each path starts where the previous one ended.
+		let requests = DrawPath {
+			coordinate_mode: CoordinateMode::Previous,
+			target: Drawable::new(1),
+			graphics_context: GraphicsContext::new(2),
+			points,
+		}
+		.split_for_max_len(6);
+
+		assert_eq!(
+			requests
+				.into_iter()
+				.map(|request| request.points)
+				.collect::<Vec<_>>(),
+			[
+				vec![coords(1, 1), coords(2, 0), coords(0, 3)],
+				vec![coords(3, 4), coords(4, 4), coords(1, 1)],
+			],
+		);
+
+		let requests = FillRectangles {
+			target: Drawable::new(1),
+			graphics_context: GraphicsContext::new(2),
+			rectangles: vec![Rectangle::new(Px(0), Px(0), Px(1), Px(1)); 5],
+		}
+		.split_for_max_len(7);
+
+		assert_eq!(
+			requests
+				.iter()
+				.map(|request| request.rectangles.len())
+				.collect::<Vec<_>>(),
+			[2, 2, 1],
+		);
+	}
+}
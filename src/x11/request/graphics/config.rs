@@ -13,10 +13,10 @@
 extern crate self as xrb;
 
 use xrbk::{pad, ConstantX11Size};
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
-	message::Request,
+	message::{Request, Validate, ValidationError},
 	set::{GraphicsOptions, GraphicsOptionsMask},
 	unit::Px,
 	visual::RgbColor,
@@ -93,7 +93,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
 	/// [`Value` error]: error::Value
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct CreatePixmap: Request(53, CreatePixmapError) {
 		/// The depth of the [pixmap].
 		///
@@ -161,7 +161,7 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`Pixmap` error]: error::Pixmap
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct FreePixmap: Request(54, error::Pixmap) {
 		/// The [pixmap] which is to have its association with its ID removed.
 		///
@@ -177,6 +177,27 @@ derive_xrb! {
 	}
 }
 
+impl Validate for CreatePixmap {
+	/// Checks that the `width` and `height` are non-zero.
+	fn validate(&self) -> Result<(), ValidationError> {
+		if self.width == Px(0) {
+			return Err(ValidationError {
+				field: "width",
+				reason: "the width must be non-zero",
+			});
+		}
+
+		if self.height == Px(0) {
+			return Err(ValidationError {
+				field: "height",
+				reason: "the height must be non-zero",
+			});
+		}
+
+		Ok(())
+	}
+}
+
 request_error! {
 	pub enum CreateGraphicsContextError for CreateGraphicsContext {
 		Drawable,
@@ -209,7 +230,7 @@ derive_xrb! {
 	/// [`ResourceIdChoice` error]: error::ResourceIdChoice
 	/// [`Drawable` error]: error::Drawable
 	#[doc(alias("CreateGc", "CreateGC", "CreateGcontext", "CreateGContext"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct CreateGraphicsContext: Request(55, CreateGraphicsContextError) {
 		/// The [`GraphicsContext` ID] which is to be assigned to the
 		/// [`GraphicsContext`].
@@ -301,7 +322,7 @@ derive_xrb! {
 	///
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("ChangeGc", "ChangeGC", "ChangeGraphicsContext"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ChangeGraphicsOptions: Request(56, ChangeGraphicsOptionsError) {
 		/// The [`GraphicsContext`] for which this [request] changes its
 		/// [graphics options].
@@ -355,7 +376,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	/// [`Match` error]: error::Match
 	#[doc(alias("CopyGc", "CopyGC", "CopyGraphicsContext", "CopyGcontext"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct CopyGraphicsOptions: Request(57, CopyGraphicsOptionsError) {
 		/// The [`GraphicsContext`] from which the [options] specified in
 		/// `options_mask` are copied.
@@ -430,7 +451,7 @@ derive_xrb! {
 	/// [`ChangeGraphicsOptions` request]: ChangeGraphicsOptions
 	///
 	/// [`GraphicsContext` error]: error::GraphicsContext
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct SetDashes: Request(58, SetDashesError) {
 		/// The [`GraphicsContext`] on which this [request] configures its
 		/// dashes.
@@ -474,7 +495,19 @@ request_error! {
 /// [rectangles]: Rectangle
 ///
 /// [`SetClipRectangles` request]: SetClipRectangles
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum ClipRectanglesOrdering {
 	/// No particular order is specified.
 	///
@@ -484,7 +517,8 @@ pub enum ClipRectanglesOrdering {
 	/// [rectangles]: Rectangle
 	///
 	/// [`SetClipRectangles` request]: SetClipRectangles
-	Unsorted,
+	#[doc(alias = "UnSorted")]
+	Unsorted = 0,
 
 	/// [Rectangles][rectangles] are ordered by their y coordinate.
 	///
@@ -498,7 +532,8 @@ pub enum ClipRectanglesOrdering {
 	/// [rectangles]: Rectangle
 	///
 	/// [`SetClipRectangles` request]: SetClipRectangles
-	SortedByY,
+	#[doc(alias = "YSorted")]
+	SortedByY = 1,
 
 	/// [Rectangles][rectangles] are ordered primarily by their y coordinate,
 	/// and secondarily by their x coordinate.
@@ -511,7 +546,8 @@ pub enum ClipRectanglesOrdering {
 	/// [rectangles]: Rectangle
 	///
 	/// [`SetClipRectangles` request]: SetClipRectangles
-	SortedByYx,
+	#[doc(alias = "YXSorted")]
+	SortedByYx = 2,
 
 	/// [Rectangles][rectangles] are ordered primarily by their y coordinate,
 	/// secondarily by their x coordinate, and each one which intersects a given
@@ -527,7 +563,8 @@ pub enum ClipRectanglesOrdering {
 	/// [rectangles]: Rectangle
 	///
 	/// [`SetClipRectangles` request]: SetClipRectangles
-	BandedByYx,
+	#[doc(alias = "YXBanded")]
+	BandedByYx = 3,
 }
 
 derive_xrb! {
@@ -549,7 +586,7 @@ derive_xrb! {
 	/// [`clip_y`]: GraphicsOptions::clip_y
 	///
 	/// [`GraphicsContext` error]: error::GraphicsContext
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct SetClipRectangles: Request(59, SetClipRectanglesError) {
 		/// Specifies the ordering of [rectangles] within `clip_rectangles`.
 		///
@@ -624,7 +661,7 @@ derive_xrb! {
 	/// [`GraphicsContext` error]: error::GraphicsContext
 	#[doc(alias("FreeGc", "FreeGcontext", "FreeGraphicsContext"))]
 	#[doc(alias("DestroyGc", "DestroyGcontext"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct DestroyGraphicsContext: Request(60, error::GraphicsContext) {
 		/// The [`GraphicsContext`] which is to be deleted.
 		///
@@ -697,7 +734,7 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	/// [`Pixmap` error]: error::Pixmap
 	#[doc(alias("CreateCursor"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct CreateCursorAppearance: Request(93, CreateCursorAppearanceError) {
 		/// The [`CursorAppearance` ID] which is to be assigned to the
 		/// [`CursorAppearance`].
@@ -872,7 +909,7 @@ derive_xrb! {
 	/// [`Font` error]: error::Font
 	/// [`Value` error]: error::Value
 	#[doc(alias("CreateGlyphCursor"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct CreateGlyphCursorAppearance: Request(94, CreateGlyphCursorAppearanceError) {
 		/// The [`CursorAppearance` ID] which is to be assigned to the
 		/// [`CursorAppearance`].
@@ -1003,7 +1040,7 @@ derive_xrb! {
 	/// [`CursorAppearance` ID]: CursorAppearance
 	///
 	/// [`CursorAppearance` error]: error::CursorAppearance
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct DestroyCursorAppearance: Request(95, error::CursorAppearance) {
 		/// The [`CursorAppearance`] that is to be deleted.
 		///
@@ -1029,7 +1066,7 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`CursorAppearance` error]: error::CursorAppearance
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct RecolorCursorAppearance: Request(96, error::CursorAppearance) {
 		/// The [`CursorAppearance`] which is to be recolored.
 		///
@@ -1081,7 +1118,9 @@ request_error! {
 ///
 /// [`QueryIdealDimension` request]: QueryIdealDimensions
 #[doc(alias("QueryBestSizeClass", "QueryIdealDimensionsClass"))]
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable,
+)]
 pub enum DimensionClass {
 	/// The largest [`CursorAppearance`] [dimensions] that can be fully
 	/// displayed are returned.
@@ -1137,7 +1176,7 @@ derive_xrb! {
 	/// [`Drawable` error]: error::Drawable
 	/// [`Match` error]: error::Match
 	#[doc(alias("QueryBestSize"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct QueryIdealDimensions: Request(
 		97,
 		QueryIdealDimensionsError,
@@ -1183,3 +1222,26 @@ derive_xrb! {
 		pub dimensions: Dimensions,
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_create_pixmap_validation() {
+		let mut request = CreatePixmap {
+			depth: 24,
+			pixmap_id: Pixmap::new(0x0020_0001),
+			drawable: Drawable::new(0x0000_0100),
+			width: Px(64),
+			height: Px(32),
+		};
+		assert_eq!(request.validate(), Ok(()));
+
+		request.height = Px(0);
+		assert_eq!(request.validate().unwrap_err().field, "height");
+
+		request.width = Px(0);
+		assert_eq!(request.validate().unwrap_err().field, "width");
+	}
+}
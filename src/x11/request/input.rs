@@ -13,10 +13,12 @@
 extern crate self as xrb;
 
 use xrbk::{
+	check_remaining,
 	pad,
 	Buf,
 	BufMut,
 	ConstantX11Size,
+	InfallibleWritable,
 	ReadError,
 	ReadResult,
 	Readable,
@@ -24,16 +26,19 @@ use xrbk::{
 	WriteResult,
 	X11Size,
 };
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
-use array_init::array_init;
-use std::ops::RangeInclusive;
+use array_init::try_array_init;
+use std::{
+	fmt::Debug,
+	ops::{Range, RangeInclusive},
+};
 use thiserror::Error;
 
 use crate::{
-	message::Request,
-	set::KeyboardOptions,
-	unit::{Px, SignedPercentage},
+	message::{Message, MessageKind, Request, Validate, ValidationError},
+	set::{DurationOrDefault, KeyboardOptions, Led, LedMode, PercentOrDefault, PitchOrDefault},
+	unit::{Px, ValueOutOfBounds, Volume},
 	x11::{error, reply},
 	Any,
 	AnyModifierKeyMask,
@@ -44,6 +49,7 @@ use crate::{
 	CursorEventMask,
 	FocusWindow,
 	FreezeMode,
+	GeometryOutOfBounds,
 	Keycode,
 	Keysym,
 	Window,
@@ -114,7 +120,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`CursorAppearance` error]: error::CursorAppearance
 	#[doc(alias = "GrabPointer")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GrabCursor: Request(26, GrabCursorError) -> reply::GrabCursor {
 		/// Whether cursor [events] which would normally be reported to this
 		/// client are reported normally.
@@ -227,7 +233,7 @@ derive_xrb! {
 	/// [`EnterWindow`]: crate::x11::event::EnterWindow
 	/// [`LeaveWindow`]: crate::x11::event::LeaveWindow
 	#[doc(alias = "UngrabPointer")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct UngrabCursor: Request(27) {
 		/// The [time] at which the grab is recorded as having been released.
 		///
@@ -278,7 +284,7 @@ derive_xrb! {
 	/// [`Access` error]: error::Access
 	/// [`Window` error]: error::Window
 	/// [`CursorAppearance` error]: error::CursorAppearance
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GrabButton: Request(28, GrabButtonError) {
 		/// Whether cursor [events] which would normally be reported to this
 		/// client are reported normally.
@@ -396,6 +402,45 @@ derive_xrb! {
 	}
 }
 
+impl Validate for GrabButton {
+	/// Checks that:
+	/// - the `event_mask` only selects cursor [events], which it may not if it
+	///   was read from bytes;
+	/// - the `button`, if it is not [`Any`], is not zero, which would be sent
+	///   as [`Any`];
+	/// - the `confine_to` [window], if there is one, does not have the ID zero,
+	///   which would be sent as [`None`].
+	///
+	/// [events]: crate::message::Event
+	/// [window]: Window
+	///
+	/// [`Any`]: Any::Any
+	fn validate(&self) -> Result<(), ValidationError> {
+		if CursorEventMask::from_bits(self.event_mask.bits()).is_none() {
+			return Err(ValidationError {
+				field: "event_mask",
+				reason: "only cursor events can be selected for a cursor grab",
+			});
+		}
+
+		if self.button == Any::Other(Button::new(0)) {
+			return Err(ValidationError {
+				field: "button",
+				reason: "a button of zero means any button",
+			});
+		}
+
+		if self.confine_to == Some(Window::new(0)) {
+			return Err(ValidationError {
+				field: "confine_to",
+				reason: "a window ID of zero means no window",
+			});
+		}
+
+		Ok(())
+	}
+}
+
 request_error! {
 	pub enum UngrabButtonError for UngrabButton {
 		Value,
@@ -417,7 +462,7 @@ derive_xrb! {
 	/// [passive button grab]: GrabButton
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct UngrabButton: Request(29, UngrabButtonError) {
 		/// The [button] which the [passive button grab] was established for.
 		///
@@ -486,7 +531,7 @@ derive_xrb! {
 	///
 	/// [`CursorAppearance` error]: error::CursorAppearance
 	#[doc(alias = "ChangeActivePointerGrab")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct ChangeActiveCursorGrab: Request(30, ChangeActiveCursorGrabError) {
 		/// Optionally overrides the [appearance of the cursor], no matter which
 		/// [window] it is within, for the duration of the grab.
@@ -553,7 +598,7 @@ derive_xrb! {
 	/// [`GrabKeyboard` reply]: reply::GrabKeyboard
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GrabKeyboard: Request(31, GrabKeyboardError) -> reply::GrabKeyboard {
 		/// Whether key [events] which would normally be reported to this client
 		/// are reported normally.
@@ -632,7 +677,7 @@ derive_xrb! {
 	///
 	/// [`Focus`]: crate::x11::event::Focus
 	/// [`Unfocus`]: crate::x11::event::Unfocus
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct UngrabKeyboard: Request(32) {
 		/// The [time] at which the grab is recorded as having been released.
 		///
@@ -679,7 +724,7 @@ derive_xrb! {
 	///
 	/// [`Access` error]: error::Access
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GrabKey: Request(33, GrabKeyError) {
 		/// Whether key [events] which would normally be reported to this client
 		/// are reported normally.
@@ -784,7 +829,7 @@ derive_xrb! {
 	/// [passive key grab]: GrabKey
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct UngrabKey: Request(34, UngrabKeyError) {
 		/// The key which the [passive key grab] was established for.
 		///
@@ -834,10 +879,23 @@ derive_xrb! {
 /// [`AllowEvents` request].
 ///
 /// [`AllowEvents` request]: AllowEvents
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum AllowEventsMode {
 	/// Unfreezes the cursor if it is frozen and you have active grab on the
 	/// cursor.
+	#[doc(alias = "AsyncPointer")]
 	UnfreezeCursor,
 	/// Unfreezes the cursor, but freezes it again after the next
 	/// [`ButtonPress`] or [`ButtonRelease`].
@@ -850,6 +908,7 @@ pub enum AllowEventsMode {
 	///
 	/// [`ButtonPress`]: crate::x11::event::ButtonPress
 	/// [`ButtonRelease`]: crate::x11::event::ButtonRelease
+	#[doc(alias = "SyncPointer")]
 	RefreezeCursor,
 	/// If the cursor is frozen as a result of the activation of a passive grab
 	/// or [`RefreezeCursor`] mode from your client, the grab is released and
@@ -858,13 +917,15 @@ pub enum AllowEventsMode {
 	/// [`RefreezeCursor`]: AllowEventsMode::RefreezeCursor
 	///
 	/// [event]: crate::message::Event
+	#[doc(alias = "ReplayPointer")]
 	ReplayCursor,
 
 	/// Unfreezes the keyboard if it is frozen and you have an active grab on
 	/// the keyboard.
+	#[doc(alias = "AsyncKeyboard")]
 	UnfreezeKeyboard,
 	/// Unfreezes the keyboard, but freezes it again after the next
-	/// [`KeyPress`] or [`KeyPress`].
+	/// [`KeyPress`] or [`KeyRelease`].
 	///
 	/// Your client must have an active grab on the keyboard.
 	///
@@ -874,6 +935,7 @@ pub enum AllowEventsMode {
 	///
 	/// [`KeyPress`]: crate::x11::event::KeyPress
 	/// [`KeyRelease`]: crate::x11::event::KeyRelease
+	#[doc(alias = "SyncKeyboard")]
 	RefreezeKeyboard,
 	/// If the keyboard is frozen as a result of the activation of a passive
 	/// grab or [`RefreezeKeyboard`] mode from your client, the grab is released
@@ -886,6 +948,7 @@ pub enum AllowEventsMode {
 
 	/// If both the cursor and the keyboard are frozen by your client, both are
 	/// unfrozen.
+	#[doc(alias = "AsyncBoth")]
 	UnfreezeBoth,
 	/// If both the cursor and the keyboard are frozen by your client, both are
 	/// unfrozen but are both frozen again on the next button or key press or
@@ -900,6 +963,7 @@ pub enum AllowEventsMode {
 	///
 	/// [`KeyPress`]: crate::x11::event::KeyPress
 	/// [`KeyRelease`]: crate::x11::event::KeyRelease
+	#[doc(alias = "SyncBoth")]
 	RefreezeBoth,
 }
 
@@ -909,7 +973,7 @@ derive_xrb! {
 	///
 	/// [frozen]: FreezeMode::Frozen
 	/// [request]: Request
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct AllowEvents: Request(35, error::Value) {
 		/// The conditions under which the queued [events] are released.
 		///
@@ -934,16 +998,89 @@ derive_xrb! {
 	/// connection closes on all other clients' connections.
 	///
 	/// [request]: Request
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GrabServer: Request(36);
 
 	/// A [request] that unfreezes processing of [requests][request] and
 	/// connection closes on all other clients' connections.
 	///
 	/// [request]: Request
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct UngrabServer: Request(37);
+}
 
+/// A guard which keeps the X server [grabbed] until it is dropped.
+///
+/// A `ServerGrabGuard` pairs a [`GrabServer` request] with its
+/// [`UngrabServer` request], so that the server is always ungrabbed when the
+/// guard goes out of scope, even if the code holding the grab returns early
+/// or panics.
+///
+/// The guard is independent of any particular transport: it is created with a
+/// function to send the [`UngrabServer` request], which is called exactly once
+/// - either by [`release()`] or when the guard is dropped.
+///
+/// [grabbed]: GrabServer
+/// [`release()`]: ServerGrabGuard::release
+///
+/// [`GrabServer` request]: GrabServer
+/// [`UngrabServer` request]: UngrabServer
+#[must_use = "the server is ungrabbed as soon as the guard is dropped"]
+pub struct ServerGrabGuard<Ungrab: FnOnce(UngrabServer) -> Output, Output = ()> {
+	ungrab: Option<Ungrab>,
+}
+
+impl<Ungrab: FnOnce(UngrabServer) -> Output, Output> ServerGrabGuard<Ungrab, Output> {
+	/// Grabs the server by sending a [`GrabServer` request] with `grab`,
+	/// returning a guard which sends an [`UngrabServer` request] with `ungrab`
+	/// when it is dropped.
+	///
+	/// # Errors
+	/// Returns the error returned by `grab`, if any. If `grab` fails, `ungrab`
+	/// is not called.
+	///
+	/// [`GrabServer` request]: GrabServer
+	/// [`UngrabServer` request]: UngrabServer
+	pub fn grab<Error>(
+		grab: impl FnOnce(GrabServer) -> Result<(), Error>, ungrab: Ungrab,
+	) -> Result<Self, Error> {
+		grab(GrabServer)?;
+
+		Ok(Self {
+			ungrab: Some(ungrab),
+		})
+	}
+
+	/// Ungrabs the server now, returning the result of sending the
+	/// [`UngrabServer` request].
+	///
+	/// When a `ServerGrabGuard` is dropped without calling `release()`, the
+	/// result of sending the [`UngrabServer` request] is discarded.
+	///
+	/// [`UngrabServer` request]: UngrabServer
+	#[allow(
+		clippy::missing_panics_doc,
+		reason = "`ungrab` is only taken by `release()` and `drop()`"
+	)]
+	pub fn release(mut self) -> Output {
+		let ungrab = self
+			.ungrab
+			.take()
+			.expect("the server is only ungrabbed once");
+
+		ungrab(UngrabServer)
+	}
+}
+
+impl<Ungrab: FnOnce(UngrabServer) -> Output, Output> Drop for ServerGrabGuard<Ungrab, Output> {
+	fn drop(&mut self) {
+		if let Some(ungrab) = self.ungrab.take() {
+			ungrab(UngrabServer);
+		}
+	}
+}
+
+derive_xrb! {
 	/// A [request] that gets the current location of the cursor.
 	///
 	/// # Errors
@@ -955,7 +1092,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[doc(alias("QueryPointer, QueryCursor, GetCursorPos, GetCursorLocation"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct QueryCursorLocation: Request(38, error::Window) -> reply::QueryCursorLocation {
 		/// Specifies a [window] to receive relative coordinates of the cursor
 		/// in relation to, if the cursor is on the same screen.
@@ -985,7 +1122,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[doc(alias = "GetMotionEvents")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetMotionHistory: Request(39, error::Window) -> reply::GetMotionHistory {
 		/// The [window] for which the motion history is returned.
 		///
@@ -1022,7 +1159,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[doc(alias = "TranslateCoordinates")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct ConvertCoordinates: Request(40, error::Window) -> reply::ConvertCoordinates {
 		/// The [window] which the `original_coords` are relative to.
 		///
@@ -1057,6 +1194,7 @@ derive_xrb! {
 		/// are relative to the `output` [window].
 		///
 		/// [window]: Window
+		#[doc(alias("src_x", "src_y", "source_coords"))]
 		pub original_coords: Coords,
 	}
 }
@@ -1094,7 +1232,7 @@ impl Readable for WarpSourceDimension {
 	where
 		Self: Sized,
 	{
-		Ok(match buf.get_u16() {
+		Ok(match u16::read_from(buf)? {
 			zero if zero == 0 => Self::FillRemaining,
 			other => Self::Other(other),
 		})
@@ -1112,6 +1250,8 @@ impl Writable for WarpSourceDimension {
 	}
 }
 
+impl InfallibleWritable for WarpSourceDimension {}
+
 derive_xrb! {
 	/// A [request] that instantly moves the cursor to a new location.
 	///
@@ -1124,7 +1264,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[doc(alias = "WarpPointer")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct WarpCursor: Request(41, error::Window) {
 		/// The [window] which the cursor is being warped from.
 		///
@@ -1185,6 +1325,44 @@ derive_xrb! {
 	}
 }
 
+impl WarpCursor {
+	/// Creates a `WarpCursor` request which unconditionally moves the cursor to
+	/// the given `coords`.
+	///
+	/// If `destination` is [`Some`], the `coords` are relative to that
+	/// [window]. Otherwise, the cursor is offset by the `coords`.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn to(destination: Option<Window>, coords: Coords) -> Self {
+		Self {
+			source: None,
+			destination,
+
+			source_coords: Coords::new(Px(0), Px(0)),
+			source_width: WarpSourceDimension::FillRemaining,
+			source_height: WarpSourceDimension::FillRemaining,
+
+			coords,
+		}
+	}
+
+	/// Creates a `WarpCursor` request which unconditionally moves the cursor to
+	/// the given coordinates, returning an error if they cannot be represented
+	/// in the X11 protocol.
+	///
+	/// See [`WarpCursor::to`] for more information.
+	///
+	/// # Errors
+	/// Returns [`GeometryOutOfBounds`] if `x` or `y` does not fit within an
+	/// [`i16`].
+	pub fn try_to(
+		destination: Option<Window>, x: i32, y: i32,
+	) -> Result<Self, GeometryOutOfBounds> {
+		Ok(Self::to(destination, Coords::try_new(x, y)?))
+	}
+}
+
 request_error! {
 	pub enum SetFocusError for SetFocus {
 		Match,
@@ -1195,12 +1373,27 @@ request_error! {
 
 /// What the focus should revert to if the focused [window] becomes unviewable.
 ///
-/// This is used in the [`SetFocus` request].
+/// This is used in the [`SetFocus` request] and returned in the
+/// [`GetFocus` reply].
 ///
 /// [window]: Window
 ///
 /// [`SetFocus` request]: SetFocus
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+/// [`GetFocus` reply]: reply::GetFocus
+#[doc(alias = "RevertTo")]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum RevertFocus {
 	/// Revert the focus to no [window].
 	///
@@ -1245,7 +1438,7 @@ derive_xrb! {
 	/// [`Match` error]: error::Match
 	/// [`Window` error]: error::Window
 	#[doc(alias("SetInputFocus", "Focus", "FocusWindow"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct SetFocus: Request(42, SetFocusError) {
 		/// What the focus should revert to if the focused [window] becomes
 		/// unviewable.
@@ -1281,7 +1474,7 @@ derive_xrb! {
 	///
 	/// [`GetFocus` reply]: reply::GetFocus
 	#[doc(alias = "GetInputFocus")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetFocus: Request(43) -> reply::GetFocus;
 
 	/// A [request] that returns a bit vector of the currently held keys on the
@@ -1294,7 +1487,7 @@ derive_xrb! {
 	///
 	/// [`QueryKeyboard` reply]: reply::QueryKeyboard
 	#[doc(alias = "QueryKeymap")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct QueryKeyboard: Request(44) -> reply::QueryKeyboard;
 }
 
@@ -1416,6 +1609,27 @@ impl<const KEYSYMS_PER_KEYCODE: usize> Request for ChangeKeyboardMapping<KEYSYMS
 	const MINOR_OPCODE: Option<u16> = None;
 }
 
+impl<const KEYSYMS_PER_KEYCODE: usize> Message for ChangeKeyboardMapping<KEYSYMS_PER_KEYCODE> {
+	fn name(&self) -> &'static str {
+		"ChangeKeyboardMapping"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_request::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("first_keycode", &self.first_keycode),
+			("mappings", &self.mappings),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		vec![6..8]
+	}
+}
+
 impl<const KEYSYMS_PER_KEYCODE: usize> X11Size for ChangeKeyboardMapping<KEYSYMS_PER_KEYCODE> {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 4;
@@ -1543,6 +1757,24 @@ impl Request for GetKeyboardMapping {
 	const MINOR_OPCODE: Option<u16> = None;
 }
 
+impl Message for GetKeyboardMapping {
+	fn name(&self) -> &'static str {
+		"GetKeyboardMapping"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_request::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![("range", &self.range)]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		vec![1..2, 6..8]
+	}
+}
+
 impl ConstantX11Size for GetKeyboardMapping {
 	const X11_SIZE: usize = {
 		const HEADER: usize = 4;
@@ -1565,20 +1797,33 @@ impl Readable for GetKeyboardMapping {
 		const HEADER: usize = 4;
 
 		// Unused metabyte.
+		check_remaining(buf, 1)?;
 		buf.advance(1);
 
 		// The message length.
-		let length = usize::from(buf.get_u16()) * 4;
-		let buf = &mut buf.take(length - HEADER);
+		let length = usize::from(u16::read_from(buf)?) * 4;
+		let buf = &mut buf.take(
+			length
+				.checked_sub(HEADER)
+				.ok_or_else(|| ReadError::Other(Box::new("the request's length is zero")))?,
+		);
 
 		let first_keycode = Keycode::read_from(buf)?;
-		let keycode_count = buf.get_u8();
+		let keycode_count = u8::read_from(buf)?;
+		check_remaining(buf, 2)?;
 		buf.advance(2);
 
 		Ok(Self {
 			range: RangeInclusive::new(
 				first_keycode,
-				Keycode::new(first_keycode.unwrap() + keycode_count - 1),
+				Keycode::new(
+					keycode_count
+						.checked_sub(1)
+						.and_then(|count| first_keycode.unwrap().checked_add(count))
+						.ok_or_else(|| {
+							ReadError::Other(Box::new("the keycode range is empty or too long"))
+						})?,
+				),
 			),
 		})
 	}
@@ -1620,7 +1865,7 @@ derive_xrb! {
 	///
 	/// [options]: KeyboardOptions
 	#[doc(alias("ChangeKeyboardControl"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ChangeKeyboardOptions: Request(102, ChangeKeyboardOptionsError) {
 		/// The changes that are made to the [keyboard options].
 		///
@@ -1631,7 +1876,62 @@ derive_xrb! {
 		#[doc(alias("keyboard_options"))]
 		pub changed_options: KeyboardOptions,
 	}
+}
 
+impl ChangeKeyboardOptions {
+	/// Creates a [request] which enables or disables auto-repeat for the given
+	/// `key`.
+	///
+	/// [request]: Request
+	#[must_use]
+	pub fn set_auto_repeat(key: Keycode, enabled: bool) -> Self {
+		let mut options = KeyboardOptions::builder();
+		options.auto_repeated_key(key).auto_repeat_mode(enabled);
+
+		Self {
+			changed_options: options.build(),
+		}
+	}
+
+	/// Creates a [request] which turns the given `led` on or off.
+	///
+	/// [request]: Request
+	#[must_use]
+	pub fn set_led(led: Led, on: bool) -> Self {
+		let mut options = KeyboardOptions::builder();
+		options
+			.led(led)
+			.led_mode(if on { LedMode::On } else { LedMode::Off });
+
+		Self {
+			changed_options: options.build(),
+		}
+	}
+
+	/// Creates a [request] which changes the `volume`, `pitch`, and `duration`
+	/// of the bell.
+	///
+	/// The bell can then be rung with a [`RingBell` request].
+	///
+	/// [request]: Request
+	/// [`RingBell` request]: RingBell
+	#[must_use]
+	pub fn bell_with(
+		volume: PercentOrDefault, pitch: PitchOrDefault, duration: DurationOrDefault,
+	) -> Self {
+		let mut options = KeyboardOptions::builder();
+		options
+			.bell_volume(volume)
+			.bell_pitch(pitch)
+			.bell_duration(duration);
+
+		Self {
+			changed_options: options.build(),
+		}
+	}
+}
+
+derive_xrb! {
 	/// A [request] that returns the current [keyboard options].
 	///
 	/// # Replies
@@ -1642,51 +1942,28 @@ derive_xrb! {
 	///
 	/// [`GetKeyboardOptions` reply]: reply::GetKeyboardOptions
 	#[doc(alias("GetKeyboardControl"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct GetKeyboardOptions: Request(103) -> reply::GetKeyboardOptions;
 
 	/// A [request] that rings the bell on the keyboard at the given volume.
 	///
-	/// The given volume is relative to the base [`bell_volume`] of the
-	/// keyboard.
-	///
-	/// The volume at which the bell is rung is decided by (where `base_volume`
-	/// is the configured [`bell_volume`], and `volume` is the volume specified
-	/// in this [request]):
-	/// ```
-	/// # use xrb::unit::SignedPercentage;
-	/// #
-	/// # fn main() -> Result<(), xrb::unit::ValueOutOfBounds<i8>> {
-	/// #     let base_volume = SignedPercentage::new(100)?;
-	/// #     let volume = SignedPercentage::new(50)?;
-	/// #
-	/// let delta =
-	///     ((isize::from(base_volume.unwrap()) * isize::from(volume.unwrap())) / 100) as i8;
-	///
-	/// #     let _ =
-	/// if volume >= 0 {
-	///     SignedPercentage::new(base_volume.unwrap() - delta + volume.unwrap())?
-	/// } else {
-	///     SignedPercentage::new(base_volume.unwrap() + delta)?
-	/// }
-	/// #     ;
-	/// #
-	/// #     Ok(())
-	/// # }
-	/// ```
+	/// The given [`Volume`] is relative to the base [`bell_volume`] of the
+	/// keyboard: [`Volume::resolve`] calculates the volume at which the bell is
+	/// rung.
 	///
 	/// [request]: Request
 	///
 	/// [`bell_volume`]: KeyboardOptions::bell_volume
 	#[doc(alias("Bell"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct RingBell: Request(104, error::Value) {
 		/// The volume at which the bell is rung relative to the base
 		/// [`bell_volume`].
 		///
 		/// [`bell_volume`]: KeyboardOptions::bell_volume
+		#[metabyte]
 		#[doc(alias("percent"))]
-		pub volume: SignedPercentage,
+		pub volume: Volume,
 	}
 }
 
@@ -1714,7 +1991,7 @@ impl Readable for OrDefault<Px<u8>> {
 	where
 		Self: Sized,
 	{
-		match buf.get_i16() {
+		match i16::read_from(buf)? {
 			default if default == -1 => Ok(Self::Default),
 
 			other => match u8::try_from(other) {
@@ -1736,66 +2013,271 @@ impl Writable for OrDefault<Px<u8>> {
 	}
 }
 
-/// A fraction with a numerator and a denominator.
+impl InfallibleWritable for OrDefault<Px<u8>> {}
+
+/// An error generated when an [`AccelerationFactor`] is not valid.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidAccelerationFactor {
+	/// The denominator was zero.
+	#[error("denominator was specified as 0; cannot divide by zero")]
+	DivideByZero,
+
+	/// The numerator or denominator was greater than
+	/// [`AccelerationFactor::MAX`].
+	#[error(transparent)]
+	OutOfBounds(#[from] ValueOutOfBounds<u16>),
+}
+
+/// A multiplier applied to the acceleration of the cursor, expressed as a
+/// fraction.
+///
+/// Both the numerator and the denominator are encoded as `INT16` values in a
+/// [`ChangeCursorOptions` request], so neither may be greater than
+/// [`AccelerationFactor::MAX`]. The denominator may not be zero.
+///
+/// [`From<f32>`] finds the closest fraction to a given factor.
 ///
-/// The denominator may not be zero.
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
-pub struct Fraction<T: X11Size + Readable + Writable>(T, T);
+/// An `AccelerationFactor` which is read is not checked, so that every message
+/// can be read and written again unchanged: a [`ChangeCursorOptions` request]
+/// may restore only the numerator or only the denominator to its default by
+/// specifying it as `-1` (`0xffff`), and may specify `0 / 0` if it does not
+/// [`do_acceleration`], while a [`GetCursorOptions` reply] encodes the
+/// numerator and denominator as `CARD16` values which may be greater than
+/// [`AccelerationFactor::MAX`].
+///
+/// [`ChangeCursorOptions` request]: ChangeCursorOptions
+/// [`do_acceleration`]: ChangeCursorOptions::do_acceleration
+/// [`GetCursorOptions` reply]: reply::GetCursorOptions
+#[doc(alias("acceleration_numerator", "acceleration_denominator"))]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct AccelerationFactor {
+	numerator: u16,
+	denominator: u16,
+}
+
+#[allow(
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss,
+	reason = "`i16::MAX` fits in a `u16`"
+)]
+impl AccelerationFactor {
+	/// The maximum value of the numerator and the denominator.
+	pub const MAX: u16 = i16::MAX as u16;
+
+	/// A factor of one: the acceleration of the cursor is unchanged.
+	pub const ONE: Self = Self {
+		numerator: 1,
+		denominator: 1,
+	};
 
-impl<T: X11Size + Readable + Writable> Fraction<T> {
-	/// Returns the fraction's numerator.
+	/// Creates a new `AccelerationFactor` with the given `numerator` and
+	/// `denominator`.
+	///
+	/// # Errors
+	/// An [`InvalidAccelerationFactor`] error is returned if the `denominator`
+	/// is zero or if either the `numerator` or `denominator` is greater than
+	/// [`AccelerationFactor::MAX`].
+	pub const fn new(numerator: u16, denominator: u16) -> Result<Self, InvalidAccelerationFactor> {
+		if denominator == 0 {
+			return Err(InvalidAccelerationFactor::DivideByZero);
+		}
+
+		let found = if numerator > denominator {
+			numerator
+		} else {
+			denominator
+		};
+
+		if found > Self::MAX {
+			return Err(InvalidAccelerationFactor::OutOfBounds(ValueOutOfBounds {
+				min: 0,
+				max: Self::MAX,
+				found,
+			}));
+		}
+
+		Ok(Self {
+			numerator,
+			denominator,
+		})
+	}
+
+	/// Returns the factor's numerator.
 	#[must_use]
-	pub const fn numerator(&self) -> &T {
-		&self.0
+	pub const fn numerator(&self) -> u16 {
+		self.numerator
 	}
 
-	/// Returns the fraction's denominator.
+	/// Returns the factor's denominator.
 	#[must_use]
-	pub const fn denominator(&self) -> &T {
-		&self.1
+	pub const fn denominator(&self) -> u16 {
+		self.denominator
 	}
 
-	/// Returns a pair of the fraction's numerator (first) and denominator
-	/// (second).
+	/// Returns the factor as an `f32`.
+	///
+	/// This is not finite if the factor was read with a denominator of zero.
 	#[must_use]
-	pub const fn pair(&self) -> (&T, &T) {
-		(&self.0, &self.1)
+	pub fn to_f32(&self) -> f32 {
+		f32::from(self.numerator) / f32::from(self.denominator)
 	}
 }
 
-/// A denominator of zero was provided to a [`Fraction`].
-#[derive(Error, Debug)]
-#[error("denominator was specified as 0; cannot divide by zero")]
-pub struct DivideByZero;
+impl Default for AccelerationFactor {
+	fn default() -> Self {
+		Self::ONE
+	}
+}
 
-impl Fraction<OrDefault<Px<u8>>> {
-	/// Creates a new [`Fraction`] with the given `numerator` and `denominator`.
-	///
-	/// # Errors
-	/// A [`DivideByZero`] error is returned if the denominator is zero.
-	pub const fn new(
-		numerator: OrDefault<Px<u8>>, denominator: OrDefault<Px<u8>>,
-	) -> Result<Self, DivideByZero> {
-		match denominator {
-			OrDefault::Other(Px(0)) => Err(DivideByZero),
-			denominator => Ok(Self(numerator, denominator)),
+impl From<f32> for AccelerationFactor {
+	/// Returns the closest `AccelerationFactor` to the given `factor`.
+	///
+	/// Factors which are not positive, including `NaN`, are treated as zero,
+	/// and factors greater than [`AccelerationFactor::MAX`] are saturated.
+	#[allow(
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss,
+		reason = "the terms are checked against `AccelerationFactor::MAX`"
+	)]
+	fn from(factor: f32) -> Self {
+		let max = f64::from(Self::MAX);
+		let factor = f64::from(factor);
+
+		if factor.is_nan() || factor <= 0.0 {
+			return Self {
+				numerator: 0,
+				denominator: 1,
+			};
 		}
+
+		if factor >= max {
+			return Self {
+				numerator: Self::MAX,
+				denominator: 1,
+			};
+		}
+
+		// The convergents of the factor's continued fraction are its best
+		// rational approximations: take the last one which fits.
+		let (mut numerator, mut denominator) = (1.0, 0.0);
+		let (mut previous_numerator, mut previous_denominator) = (0.0, 1.0);
+		let mut remainder = factor;
+
+		loop {
+			let term = remainder.floor();
+
+			let next_numerator = term.mul_add(numerator, previous_numerator);
+			let next_denominator = term.mul_add(denominator, previous_denominator);
+
+			if next_numerator > max || next_denominator > max {
+				break;
+			}
+
+			(previous_numerator, previous_denominator) = (numerator, denominator);
+			(numerator, denominator) = (next_numerator, next_denominator);
+
+			let fractional = remainder - term;
+			if fractional < f64::from(f32::EPSILON) {
+				break;
+			}
+
+			remainder = fractional.recip();
+		}
+
+		Self {
+			numerator: numerator as u16,
+			denominator: denominator as u16,
+		}
+	}
+}
+
+impl From<AccelerationFactor> for f32 {
+	fn from(factor: AccelerationFactor) -> Self {
+		factor.to_f32()
 	}
 }
 
-impl Fraction<Px<u16>> {
-	/// Creates a new [`Fraction`] with the given `numerator` and `denominator`.
+impl ConstantX11Size for AccelerationFactor {
+	const X11_SIZE: usize = 2 * u16::X11_SIZE;
+}
+
+impl X11Size for AccelerationFactor {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for AccelerationFactor {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		Ok(Self {
+			numerator: u16::read_from(buf)?,
+			denominator: u16::read_from(buf)?,
+		})
+	}
+}
+
+impl Writable for AccelerationFactor {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		buf.put_u16(self.numerator);
+		buf.put_u16(self.denominator);
+
+		Ok(())
+	}
+}
+
+impl InfallibleWritable for AccelerationFactor {}
+
+impl ConstantX11Size for OrDefault<AccelerationFactor> {
+	const X11_SIZE: usize = AccelerationFactor::X11_SIZE;
+}
+
+impl X11Size for OrDefault<AccelerationFactor> {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl Readable for OrDefault<AccelerationFactor> {
+	/// Reads an `OrDefault<AccelerationFactor>`.
 	///
-	/// # Errors
-	/// A [`DivideByZero`] error is returned if the denominator is zero.
-	pub const fn new(numerator: Px<u16>, denominator: Px<u16>) -> Result<Self, DivideByZero> {
-		match denominator {
-			Px(0) => Err(DivideByZero),
-			denominator => Ok(Self(numerator, denominator)),
+	/// This is [`OrDefault::Default`] if both the numerator and denominator are
+	/// `-1`. If only one of them is, that one is restored to its default by
+	/// itself: it is read as `0xffff` in an [`OrDefault::Other`] so that it is
+	/// written the same way.
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let factor = AccelerationFactor::read_from(buf)?;
+
+		if factor.numerator == u16::MAX && factor.denominator == u16::MAX {
+			Ok(Self::Default)
+		} else {
+			Ok(Self::Other(factor))
+		}
+	}
+}
+
+impl Writable for OrDefault<AccelerationFactor> {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		match self {
+			Self::Default => {
+				buf.put_i16(-1);
+				buf.put_i16(-1);
+			},
+
+			Self::Other(factor) => factor.write_to(buf)?,
 		}
+
+		Ok(())
 	}
 }
 
+impl InfallibleWritable for OrDefault<AccelerationFactor> {}
+
 derive_xrb! {
 	/// A [request] that changes the options configured for the cursor.
 	///
@@ -1803,14 +2285,14 @@ derive_xrb! {
 	///
 	/// [request]: Request
 	#[doc(alias("ChangePointerControl", "ChangePointerOptions", "ChangeCursorControl"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ChangeCursorOptions: Request(105, error::Value) {
 		/// A multiplier applied to the acceleration of the cursor when the
 		/// [`threshold`] is exceeded.
 		///
 		/// [`threshold`]: ChangeCursorOptions::threshold
 		#[doc(alias("acceleration_numerator", "acceleration_denominator"))]
-		pub acceleration: Fraction<OrDefault<Px<u8>>>,
+		pub acceleration: OrDefault<AccelerationFactor>,
 		/// The threshold speed which the cursor must exceed for the
 		/// [`acceleration`] multiplier to be applied.
 		///
@@ -1830,7 +2312,7 @@ derive_xrb! {
 	/// [cursor options]: ChangeCursorOptions
 	/// [request]: Request
 	#[doc(alias("GetPointerControl", "GetPointerOptions", "GetCursorControl"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct GetCursorOptions: Request(106) -> reply::GetCursorOptions;
 
 	/// A [request] that changes the mapping of the [mouse buttons].
@@ -1867,7 +2349,7 @@ derive_xrb! {
 	///
 	/// [`Value` error]: error::Value
 	#[doc(alias("SetPointerMapping", "SetCursorMapping"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct SetButtonMapping: Request(116, error::Value) -> reply::SetButtonMapping {
 		// The length of `mappings`.
 		#[metabyte]
@@ -1912,10 +2394,209 @@ derive_xrb! {
 	///
 	/// [`GetButtonMapping` reply]: reply::GetButtonMapping
 	#[doc(alias("GetPointerMapping", "GetCursorMapping"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct GetButtonMapping: Request(117) -> reply::GetButtonMapping;
 }
 
+/// An error returned when a [`ButtonMapping`] is invalid.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidButtonMapping {
+	/// The same logical [button] is mapped to by more than one physical
+	/// [button].
+	///
+	/// [button]: Button
+	#[error("button {0:?} is mapped to more than once")]
+	Duplicate(Button),
+
+	/// The [`ButtonMapping`] maps more [buttons] than can be encoded.
+	///
+	/// [buttons]: Button
+	#[error("a button mapping can map at most 255 buttons, found {0}")]
+	TooLong(usize),
+
+	/// The [`ButtonMapping`] does not map the same number of [buttons] as
+	/// the device has.
+	///
+	/// [buttons]: Button
+	#[error("the device has {expected} buttons, but {found} were mapped")]
+	LengthMismatch {
+		/// The number of [buttons] the device has.
+		///
+		/// [buttons]: Button
+		expected: usize,
+		/// The number of [buttons] which were mapped.
+		///
+		/// [buttons]: Button
+		found: usize,
+	},
+}
+
+/// A mapping of physical [mouse buttons] to logical [mouse buttons].
+///
+/// The physical [button] at position `n`, starting at 1, is mapped to the
+/// logical [button] at index `n - 1`. [`None`] means that physical [button]
+/// is disabled.
+///
+/// A `ButtonMapping` can be received in a [`GetButtonMapping` reply] and sent
+/// in a [`SetButtonMapping` request].
+///
+/// [mouse buttons]: Button
+/// [button]: Button
+///
+/// [`GetButtonMapping` reply]: reply::GetButtonMapping
+/// [`SetButtonMapping` request]: SetButtonMapping
+#[doc(alias("PointerMapping", "CursorMapping"))]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ButtonMapping(Vec<Option<Button>>);
+
+impl ButtonMapping {
+	/// Creates a new `ButtonMapping` from the given `mappings`.
+	///
+	/// # Errors
+	/// Returns an [`InvalidButtonMapping`] error if there are more than 255
+	/// `mappings` or if the same logical [button] occurs more than once.
+	///
+	/// [button]: Button
+	pub fn new(mappings: Vec<Option<Button>>) -> Result<Self, InvalidButtonMapping> {
+		if mappings.len() > usize::from(u8::MAX) {
+			return Err(InvalidButtonMapping::TooLong(mappings.len()));
+		}
+
+		for (i, button) in mappings.iter().enumerate() {
+			if let Some(button) = button {
+				if mappings[..i].contains(&Some(*button)) {
+					return Err(InvalidButtonMapping::Duplicate(*button));
+				}
+			}
+		}
+
+		Ok(Self(mappings))
+	}
+
+	/// Creates a `ButtonMapping` which maps each of `button_count` physical
+	/// [buttons] to the logical [button] of the same number.
+	///
+	/// This is the default mapping.
+	///
+	/// [button]: Button
+	/// [buttons]: Button
+	#[must_use]
+	pub fn identity(button_count: u8) -> Self {
+		Self(
+			(1..=button_count)
+				.map(|button| Some(Button::new(button)))
+				.collect(),
+		)
+	}
+
+	/// Creates a `ButtonMapping` of `button_count` [buttons] with the primary
+	/// and secondary [buttons], 1 and 3, swapped, as is commonly done for
+	/// left-handed users.
+	///
+	/// [buttons]: Button
+	#[must_use]
+	pub fn swapped_left_right(button_count: u8) -> Self {
+		let mut mapping = Self::identity(button_count);
+		mapping.swap_left_right();
+
+		mapping
+	}
+
+	/// Creates a `ButtonMapping` from the mappings in the given
+	/// [`GetButtonMapping` reply].
+	///
+	/// [`GetButtonMapping` reply]: reply::GetButtonMapping
+	#[must_use]
+	pub fn from_reply(reply: &reply::GetButtonMapping) -> Self {
+		Self(reply.mappings.to_vec())
+	}
+
+	/// Swaps the mappings of the primary and secondary [buttons], 1 and 3.
+	///
+	/// This has no effect if there are fewer than three [buttons].
+	///
+	/// [buttons]: Button
+	pub fn swap_left_right(&mut self) {
+		if self.0.len() >= 3 {
+			self.0.swap(0, 2);
+		}
+	}
+
+	/// The number of physical [buttons] mapped.
+	///
+	/// [buttons]: Button
+	#[must_use]
+	pub const fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Whether there are no [buttons] mapped.
+	///
+	/// [buttons]: Button
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns the logical [button] which the given `physical` [button] is
+	/// mapped to.
+	///
+	/// Returns [`None`] if the `physical` [button] is disabled or not mapped.
+	///
+	/// [button]: Button
+	#[must_use]
+	pub fn get(&self, physical: Button) -> Option<Button> {
+		let index = usize::from(physical.unwrap()).checked_sub(1)?;
+
+		self.0.get(index).copied().flatten()
+	}
+
+	/// The mappings, with the logical [button] for physical [button] `n` at
+	/// index `n - 1`.
+	///
+	/// [button]: Button
+	#[must_use]
+	pub fn as_slice(&self) -> &[Option<Button>] {
+		&self.0
+	}
+
+	/// Creates a [`SetButtonMapping` request] for a device with the given
+	/// `button_count`, which is the length of the mappings returned in a
+	/// [`GetButtonMapping` reply].
+	///
+	/// # Errors
+	/// Returns an [`InvalidButtonMapping::LengthMismatch`] error if this
+	/// `ButtonMapping` does not map exactly `button_count` [buttons], which
+	/// would otherwise generate a [`Value` error].
+	///
+	/// [buttons]: Button
+	///
+	/// [`SetButtonMapping` request]: SetButtonMapping
+	/// [`GetButtonMapping` reply]: reply::GetButtonMapping
+	///
+	/// [`Value` error]: error::Value
+	pub fn to_request(
+		&self, button_count: usize,
+	) -> Result<SetButtonMapping, InvalidButtonMapping> {
+		if self.0.len() == button_count {
+			Ok(SetButtonMapping {
+				mappings: self.0.clone(),
+			})
+		} else {
+			Err(InvalidButtonMapping::LengthMismatch {
+				expected: button_count,
+				found: self.0.len(),
+			})
+		}
+	}
+}
+
+impl From<ButtonMapping> for SetButtonMapping {
+	fn from(ButtonMapping(mappings): ButtonMapping) -> Self {
+		Self { mappings }
+	}
+}
+
 /// A [request] that sets the mapping of [keycodes] for each modifier.
 ///
 /// Each modifier has zero or more [keycodes] mapped to it. For example, the
@@ -2021,6 +2702,33 @@ impl Request for SetModifierMapping {
 	const MINOR_OPCODE: Option<u16> = None;
 }
 
+impl Message for SetModifierMapping {
+	fn name(&self) -> &'static str {
+		"SetModifierMapping"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_request::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("shift_keycodes", &self.shift_keycodes),
+			("capslock_keycodes", &self.capslock_keycodes),
+			("ctrl_keycodes", &self.ctrl_keycodes),
+			("mod1_keycodes", &self.mod1_keycodes),
+			("mod2_keycodes", &self.mod2_keycodes),
+			("mod3_keycodes", &self.mod3_keycodes),
+			("mod4_keycodes", &self.mod4_keycodes),
+			("mod5_keycodes", &self.mod5_keycodes),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		Vec::new()
+	}
+}
+
 impl X11Size for SetModifierMapping {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 4;
@@ -2036,26 +2744,31 @@ impl Readable for SetModifierMapping {
 	where
 		Self: Sized,
 	{
+		const HEADER: usize = 4;
 		const ALIGNMENT: usize = 4;
 
-		let keycodes_per_modifier = buf.get_u8();
+		let keycodes_per_modifier = u8::read_from(buf)?;
 
-		let total_size = usize::from(buf.get_u16()) * ALIGNMENT;
-		let buf = &mut buf.take(total_size);
+		let total_size = usize::from(u16::read_from(buf)?) * ALIGNMENT;
+		let buf = &mut buf.take(
+			total_size
+				.checked_sub(HEADER)
+				.ok_or_else(|| ReadError::Other(Box::new("the request's length is zero")))?,
+		);
 
 		let [shift_keycodes, capslock_keycodes, ctrl_keycodes, mod1_keycodes, mod2_keycodes, mod3_keycodes, mod4_keycodes, mod5_keycodes] =
-			array_init(|_| {
+			try_array_init(|_| {
 				let mut keycodes = vec![];
 
 				for _ in 0..keycodes_per_modifier {
-					match buf.get_u8() {
+					match u8::read_from(buf)? {
 						0 => {},
 						code => keycodes.push(Keycode(code)),
 					}
 				}
 
-				keycodes
-			});
+				Ok::<_, ReadError>(keycodes)
+			})?;
 
 		Ok(Self {
 			shift_keycodes,
@@ -2072,6 +2785,7 @@ impl Readable for SetModifierMapping {
 }
 
 impl Writable for SetModifierMapping {
+	#[allow(clippy::cast_possible_truncation)]
 	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
 		const HEADER: usize = 4;
 
@@ -2080,6 +2794,11 @@ impl Writable for SetModifierMapping {
 
 		let buf = &mut buf.limit(HEADER + (8 * keycodes_size));
 
+		Self::MAJOR_OPCODE.write_to(buf)?;
+		// The number of keycodes for each modifier.
+		(max_keycodes_len as u8).write_to(buf)?;
+		self.length().write_to(buf)?;
+
 		// For each keycodes field, we want to make sure that they are written
 		// as the same length as the longest list. Fortunately, that is easy to
 		// do, because (a) the order of each list does not matter, and (b) a `0`
@@ -2128,6 +2847,248 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`GetModifierMapping` reply]: reply::GetModifierMapping
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct GetModifierMapping: Request(119) -> reply::GetModifierMapping;
 }
+
+#[cfg(test)]
+mod test {
+	use std::{cell::Cell, convert::Infallible};
+
+	use super::*;
+	use crate::test_util::round_trip;
+
+	#[test]
+	fn test_acceleration_factor() {
+		assert_eq!(
+			AccelerationFactor::new(1, 0),
+			Err(InvalidAccelerationFactor::DivideByZero)
+		);
+		assert!(AccelerationFactor::new(AccelerationFactor::MAX + 1, 1).is_err());
+
+		let factor =
+			|numerator, denominator| AccelerationFactor::new(numerator, denominator).unwrap();
+
+		assert_eq!(AccelerationFactor::from(2.0), factor(2, 1));
+		assert_eq!(AccelerationFactor::from(1.5), factor(3, 2));
+		assert_eq!(AccelerationFactor::from(0.1), factor(1, 10));
+		assert_eq!(AccelerationFactor::from(-1.0), factor(0, 1));
+		assert_eq!(AccelerationFactor::from(f32::NAN), factor(0, 1));
+		assert_eq!(
+			AccelerationFactor::from(1e9),
+			factor(AccelerationFactor::MAX, 1)
+		);
+
+		let pi = AccelerationFactor::from(std::f32::consts::PI);
+		assert!((pi.to_f32() - std::f32::consts::PI).abs() < 1e-6);
+	}
+
+	#[test]
+	fn test_acceleration_factor_or_default() {
+		let mut buf: &[u8] = &[0xff, 0xff, 0xff, 0xff];
+		assert_eq!(
+			<OrDefault<AccelerationFactor>>::read_from(&mut buf).unwrap(),
+			OrDefault::Default
+		);
+
+		let mut bytes = vec![];
+		OrDefault::Other(AccelerationFactor::new(3, 2).unwrap())
+			.write_to(&mut bytes)
+			.unwrap();
+		OrDefault::<AccelerationFactor>::Default
+			.write_to(&mut bytes)
+			.unwrap();
+		assert_eq!(bytes, [0, 3, 0, 2, 0xff, 0xff, 0xff, 0xff]);
+	}
+
+	#[test]
+	fn test_change_cursor_options_unchecked() {
+		// Reads `bytes` after the major opcode, then checks that the request
+		// is written the same way.
+		let reread = |bytes: &[u8]| {
+			let request = ChangeCursorOptions::read_from(&mut &bytes[1..]).unwrap();
+			assert_eq!(round_trip(&request, 1), bytes);
+
+			request
+		};
+
+		// `0 / 0` may be specified if `do_acceleration` is false.
+		let request = reread(&[105, 0, 0, 3, 0, 0, 0, 0, 0, 4, 0, 1]);
+		assert!(!request.do_acceleration);
+		assert!(matches!(
+			request.acceleration,
+			OrDefault::Other(factor) if factor.denominator() == 0
+		));
+
+		// Just the numerator is restored to its default.
+		let request = reread(&[105, 0, 0, 3, 0xff, 0xff, 0, 4, 0, 2, 1, 0]);
+		assert!(matches!(
+			request.acceleration,
+			OrDefault::Other(factor) if (factor.numerator(), factor.denominator()) == (0xffff, 4)
+		));
+	}
+
+	#[test]
+	fn test_warp_cursor_try_to() {
+		let request = WarpCursor::try_to(None, -32768, 32767).unwrap();
+		assert_eq!(request.coords, Coords::new(Px(i16::MIN), Px(i16::MAX)));
+
+		assert_eq!(WarpCursor::try_to(None, 32768, 0).unwrap_err().field, "x");
+		assert_eq!(WarpCursor::try_to(None, 0, -32769).unwrap_err().field, "y");
+	}
+
+	#[test]
+	fn test_negative_coords() {
+		// Coordinates are signed, so negative coordinates must survive a round
+		// trip.
+		let bytes = round_trip(
+			&ConvertCoordinates {
+				original: Window::new(0x0020_0001),
+				output: Window::new(0x0000_0001),
+				original_coords: Coords::new(Px(-1), Px(-32768)),
+			},
+			1,
+		);
+		assert_eq!(&bytes[12..16], &[0xff, 0xff, 0x80, 0x00]);
+
+		let bytes = round_trip(&WarpCursor::to(None, Coords::new(Px(-5), Px(5))), 1);
+		assert_eq!(&bytes[20..24], &[0xff, 0xfb, 0x00, 0x05]);
+	}
+
+	#[test]
+	fn test_grab_button_validation() {
+		let mut request = GrabButton {
+			owner_events: false,
+			grab_window: Window::new(0x0000_0100),
+			event_mask: CursorEventMask::BUTTON_PRESS,
+			cursor_freeze: FreezeMode::Unfrozen,
+			keyboard_freeze: FreezeMode::Unfrozen,
+			confine_to: None,
+			cursor_appearance: None,
+			button: Any::Any,
+			modifiers: AnyModifierKeyMask::ANY_MODIFIER,
+		};
+		assert_eq!(request.validate(), Ok(()));
+
+		request.confine_to = Some(Window::new(0x0020_0001));
+		assert_eq!(request.validate(), Ok(()));
+
+		request.confine_to = Some(Window::new(0));
+		assert_eq!(request.validate().unwrap_err().field, "confine_to");
+		request.confine_to = None;
+
+		request.button = Any::Other(Button::PRIMARY);
+		assert_eq!(request.validate(), Ok(()));
+
+		request.button = Any::Other(Button::new(0));
+		assert_eq!(request.validate().unwrap_err().field, "button");
+		request.button = Any::Any;
+
+		// `KEY_PRESS` can't be selected for a cursor grab, but it can still be
+		// read from bytes.
+		let mut bytes = round_trip(&request, 1);
+		bytes[8..10].copy_from_slice(&0x0001_u16.to_be_bytes());
+
+		let request = GrabButton::read_from(&mut &bytes[1..]).unwrap();
+		assert_eq!(request.validate().unwrap_err().field, "event_mask");
+	}
+
+	#[test]
+	fn test_set_led() {
+		// LED 3 on: a mask of `LED | LED_MODE`, then the LED, then the mode.
+		let bytes = round_trip(
+			&ChangeKeyboardOptions::set_led(Led::new(3).unwrap(), true),
+			1,
+		);
+		assert_eq!(&bytes[4..], &[0, 0, 0, 0x30, 0, 0, 0, 3, 0, 0, 0, 1]);
+	}
+
+	#[test]
+	fn test_button_mapping() {
+		let mapping = ButtonMapping::swapped_left_right(5);
+		let reply = reply::GetButtonMapping {
+			sequence: 42,
+			mappings: mapping.as_slice().into(),
+		};
+		// 32 bytes, then 5 buttons, padded to a multiple of 4 bytes.
+		let bytes = round_trip(&reply, 1);
+		assert_eq!(&bytes[32..], &[3, 2, 1, 4, 5, 0, 0, 0]);
+
+		assert_eq!(ButtonMapping::from_reply(&reply), mapping);
+		assert_eq!(mapping.get(Button::new(1)), Some(Button::new(3)));
+
+		assert!(mapping.to_request(3).is_err());
+		let bytes = round_trip(&mapping.to_request(5).unwrap(), 1);
+		assert_eq!(&bytes[4..], &[3, 2, 1, 4, 5, 0, 0, 0]);
+
+		assert_eq!(
+			ButtonMapping::new(vec![Some(Button::new(1)), None, Some(Button::new(1))]),
+			Err(InvalidButtonMapping::Duplicate(Button::new(1))),
+		);
+	}
+
+	#[test]
+	fn test_set_modifier_mapping() {
+		let bytes = round_trip(
+			&SetModifierMapping {
+				shift_keycodes: vec![Keycode(50), Keycode(62)],
+				capslock_keycodes: vec![Keycode(66)],
+				ctrl_keycodes: vec![],
+				mod1_keycodes: vec![],
+				mod2_keycodes: vec![],
+				mod3_keycodes: vec![],
+				mod4_keycodes: vec![Keycode(133)],
+				mod5_keycodes: vec![],
+			},
+			1,
+		);
+		// Two keycodes per modifier, so the request is 5 units long.
+		assert_eq!(&bytes[..4], &[118, 2, 0, 5]);
+		// Modifiers with fewer keycodes are padded with zeroes.
+		assert_eq!(&bytes[4..8], &[50, 62, 66, 0]);
+	}
+
+	#[test]
+	fn test_server_grab_guard() {
+		let grabs = Cell::new(0);
+		let ungrabs = Cell::new(0);
+
+		let grab = |GrabServer| {
+			grabs.set(grabs.get() + 1);
+			Ok::<_, Infallible>(())
+		};
+		let ungrab = |UngrabServer| ungrabs.set(ungrabs.get() + 1);
+
+		// The server is ungrabbed when the guard is dropped.
+		{
+			let _guard = ServerGrabGuard::grab(grab, ungrab).unwrap();
+			assert_eq!((grabs.get(), ungrabs.get()), (1, 0));
+		}
+		assert_eq!((grabs.get(), ungrabs.get()), (1, 1));
+
+		// The server is ungrabbed if the code holding the grab returns early
+		// with an error.
+		let result = (|| -> Result<(), &str> {
+			let _guard = ServerGrabGuard::grab(grab, ungrab).unwrap();
+			Err("failed while grabbed")?;
+
+			Ok(())
+		})();
+		assert_eq!(result, Err("failed while grabbed"));
+		assert_eq!((grabs.get(), ungrabs.get()), (2, 2));
+
+		// `release()` ungrabs the server once, returning the result.
+		let guard = ServerGrabGuard::grab(grab, |UngrabServer| {
+			ungrabs.set(ungrabs.get() + 1);
+			"ungrabbed"
+		})
+		.unwrap();
+		assert_eq!(guard.release(), "ungrabbed");
+		assert_eq!((grabs.get(), ungrabs.get()), (3, 3));
+
+		// If grabbing fails, the server is not ungrabbed.
+		let failed = ServerGrabGuard::grab(|GrabServer| Err(()), |UngrabServer| unreachable!());
+		assert!(failed.is_err());
+		assert_eq!(ungrabs.get(), 3);
+	}
+}
@@ -12,23 +12,28 @@
 
 extern crate self as xrb;
 
-use std::convert::Infallible;
+use std::{convert::Infallible, fmt::Debug, ops::Range};
+use thiserror::Error;
 use xrbk::{
+	check_remaining,
 	pad,
 	Buf,
 	BufMut,
 	ConstantX11Size,
+	ReadError,
 	ReadError::FailedConversion,
 	ReadResult,
 	Readable,
 	Writable,
+	WriteError,
 	WriteResult,
 	X11Size,
 };
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
-	message::Request,
+	connection::ConnectionSuccess,
+	message::{Message, MessageKind, Request},
 	unit::Sec,
 	x11::{error, reply},
 	Host,
@@ -79,14 +84,125 @@ request_error! {
 }
 
 /// Whether something is added or removed.
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[doc(alias = "EditMode")]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum AddOrRemove {
 	/// The thing is added.
+	#[doc(alias = "Insert")]
 	Add,
 	/// The thing is removed.
+	#[doc(alias = "Delete")]
 	Remove,
 }
 
+impl AddOrRemove {
+	/// Returns whether this is [`AddOrRemove::Add`].
+	#[must_use]
+	pub const fn is_add(&self) -> bool {
+		matches!(self, Self::Add)
+	}
+
+	/// Returns whether this is [`AddOrRemove::Remove`].
+	#[must_use]
+	pub const fn is_remove(&self) -> bool {
+		matches!(self, Self::Remove)
+	}
+
+	/// Returns the opposite mode: [`Remove`] for [`Add`], and [`Add`] for
+	/// [`Remove`].
+	///
+	/// [`Add`]: AddOrRemove::Add
+	/// [`Remove`]: AddOrRemove::Remove
+	#[must_use]
+	pub const fn inverse(&self) -> Self {
+		match self {
+			Self::Add => Self::Remove,
+			Self::Remove => Self::Add,
+		}
+	}
+}
+
+impl From<bool> for AddOrRemove {
+	/// Converts `true` to [`AddOrRemove::Add`] and `false` to
+	/// [`AddOrRemove::Remove`].
+	fn from(add: bool) -> Self {
+		if add {
+			Self::Add
+		} else {
+			Self::Remove
+		}
+	}
+}
+
+/// An error returned when a [window] created by this client is used where a
+/// [window] created by another client is required.
+///
+/// [window]: Window
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Error)]
+#[error("window {window:?} was created by this client")]
+pub struct OwnWindow {
+	/// The [window] which was created by this client.
+	///
+	/// [window]: Window
+	pub window: Window,
+}
+
+/// A [window] which was not created by this client.
+///
+/// Only [windows] created by other clients can be saved with a
+/// [`ChangeSavedWindows` request], so that [request] only accepts
+/// `ForeignWindow`s.
+///
+/// [window]: Window
+/// [windows]: Window
+/// [request]: Request
+///
+/// [`ChangeSavedWindows` request]: ChangeSavedWindows
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ForeignWindow(Window);
+
+impl ForeignWindow {
+	/// Checks that the given `window` was not created by the client with the
+	/// given `connection`.
+	///
+	/// # Errors
+	/// Returns an [`OwnWindow`] error if the `window`'s resource ID is within
+	/// the range of resource IDs assigned to this client.
+	pub const fn new(window: Window, connection: &ConnectionSuccess) -> Result<Self, OwnWindow> {
+		if connection.owns_resource(window.unwrap()) {
+			Err(OwnWindow { window })
+		} else {
+			Ok(Self(window))
+		}
+	}
+
+	/// Returns the wrapped [window].
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn unwrap(self) -> Window {
+		self.0
+	}
+}
+
+impl From<ForeignWindow> for Window {
+	fn from(window: ForeignWindow) -> Self {
+		window.0
+	}
+}
+
 derive_xrb! {
 	/// A [request] that [adds] or [removes] the specified [window] from the
 	/// set of [windows][window] which you have chosen to save.
@@ -114,7 +230,7 @@ derive_xrb! {
 	///
 	/// [reparented]: super::ReparentWindow
 	#[doc(alias = "ChangeSaveSet")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct ChangeSavedWindows: Request(6, ChangeSavedWindowsError) {
 		#[metabyte]
 		/// Whether the `window` is added to or removed from your saved
@@ -139,7 +255,42 @@ derive_xrb! {
 		/// [`Window` error]: error::Window
 		pub window: Window,
 	}
+}
+
+impl ChangeSavedWindows {
+	/// Creates a [request] which changes whether the given `window` is saved.
+	///
+	/// [request]: Request
+	#[must_use]
+	pub const fn new(change_mode: AddOrRemove, window: ForeignWindow) -> Self {
+		Self {
+			change_mode,
+			window: window.unwrap(),
+		}
+	}
+
+	/// Creates a [request] which adds the given `window` to your saved
+	/// [windows].
+	///
+	/// [request]: Request
+	/// [windows]: Window
+	#[must_use]
+	pub const fn add(window: ForeignWindow) -> Self {
+		Self::new(AddOrRemove::Add, window)
+	}
 
+	/// Creates a [request] which removes the given `window` from your saved
+	/// [windows].
+	///
+	/// [request]: Request
+	/// [windows]: Window
+	#[must_use]
+	pub const fn remove(window: ForeignWindow) -> Self {
+		Self::new(AddOrRemove::Remove, window)
+	}
+}
+
+derive_xrb! {
 	/// A [request] that returns whether the specified extension is present and
 	/// the message codes associated with it if it is.
 	///
@@ -149,7 +300,7 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`QueryExtension` reply]: reply::QueryExtension
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct QueryExtension: Request(98) -> reply::QueryExtension {
 		// Length of `name`.
 		#[allow(clippy::cast_possible_truncation)]
@@ -173,15 +324,22 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`ListExtensions` reply]: reply::ListExtensions
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct ListExtensions: Request(99) -> reply::ListExtensions;
 }
 
 /// The delay used for `timeout` and `interval` in the
 /// [`SetScreenSaver` request].
 ///
+/// [`Default`] is encoded as `-1` and [`Disabled`] as `0`. [`Enabled`] delays
+/// must be no greater than [`Delay::MAX`].
+///
+/// [`Default`]: Delay::Default
+/// [`Disabled`]: Delay::Disabled
+/// [`Enabled`]: Delay::Enabled
+///
 /// [`SetScreenSaver` request]: SetScreenSaver
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum Delay {
 	/// The default option is used.
 	Default,
@@ -189,7 +347,27 @@ pub enum Delay {
 	Disabled,
 
 	/// The option is enabled after the given delay.
-	Enabled(Sec<u8>),
+	Enabled(Sec<u16>),
+}
+
+impl Delay {
+	/// The longest delay which can be encoded.
+	#[allow(clippy::cast_sign_loss, reason = "`i16::MAX` is positive")]
+	pub const MAX: Sec<u16> = Sec(i16::MAX as u16);
+
+	/// Creates a `Delay` from the given delay in seconds, as reported in a
+	/// [`GetScreenSaver` reply].
+	///
+	/// [`None`] is [`Delay::Disabled`].
+	///
+	/// [`GetScreenSaver` reply]: reply::GetScreenSaver
+	#[must_use]
+	pub const fn from_reply(delay: Option<Sec<u16>>) -> Self {
+		match delay {
+			Some(delay) => Self::Enabled(delay),
+			None => Self::Disabled,
+		}
+	}
 }
 
 impl ConstantX11Size for Delay {
@@ -207,11 +385,11 @@ impl Readable for Delay {
 	where
 		Self: Sized,
 	{
-		match buf.get_i16() {
+		match i16::read_from(buf)? {
 			-1 => Ok(Self::Default),
 			0 => Ok(Self::Disabled),
 
-			other => match u8::try_from(other) {
+			other => match u16::try_from(other) {
 				Ok(sec) => Ok(Self::Enabled(Sec(sec))),
 				Err(error) => Err(FailedConversion(Box::new(error))),
 			},
@@ -225,13 +403,88 @@ impl Writable for Delay {
 			Self::Default => buf.put_i16(-1),
 			Self::Disabled => buf.put_i16(0),
 
-			Self::Enabled(Sec(sec)) => i16::from(*sec).write_to(buf)?,
+			Self::Enabled(Sec(sec)) => match i16::try_from(*sec) {
+				Ok(sec) => sec.write_to(buf)?,
+				Err(error) => return Err(WriteError::FailedConversion(Box::new(error))),
+			},
 		}
 
 		Ok(())
 	}
 }
 
+/// Screensaver settings, as returned in a [`GetScreenSaver` reply] and set
+/// with a [`SetScreenSaver` request].
+///
+/// [`GetScreenSaver` reply]: reply::GetScreenSaver
+/// [`SetScreenSaver` request]: SetScreenSaver
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ScreenSaverSettings {
+	/// How long without input before the screensaver is activated.
+	///
+	/// [`None`] means the screensaver is disabled.
+	pub timeout: Option<Sec<u16>>,
+	/// A hint for screensavers with periodic changes as to the interval
+	/// between those changes.
+	///
+	/// [`None`] hints that no periodic change should be made.
+	pub interval: Option<Sec<u16>>,
+
+	/// Whether it is preferred that displays that support blanking go blank
+	/// when the screensaver is activated.
+	pub prefer_blanking: Toggle,
+	/// Whether screensavers which generate [`Expose` events] are allowed.
+	///
+	/// [`Expose` events]: crate::x11::event::Expose
+	pub allow_expose_events: Toggle,
+}
+
+impl ScreenSaverSettings {
+	/// Returns whether the screensaver is enabled.
+	#[must_use]
+	pub const fn is_enabled(&self) -> bool {
+		self.timeout.is_some()
+	}
+
+	/// Returns these settings with the screensaver disabled.
+	#[must_use]
+	pub const fn disabled(self) -> Self {
+		Self {
+			timeout: None,
+			..self
+		}
+	}
+}
+
+impl From<&reply::GetScreenSaver> for ScreenSaverSettings {
+	fn from(reply: &reply::GetScreenSaver) -> Self {
+		Self {
+			timeout: reply.timeout,
+			interval: reply.interval,
+
+			prefer_blanking: reply.prefer_blanking,
+			allow_expose_events: reply.allow_expose_events,
+		}
+	}
+}
+
+impl From<ScreenSaverSettings> for SetScreenSaver {
+	fn from(settings: ScreenSaverSettings) -> Self {
+		let toggle = |toggle| match toggle {
+			Toggle::Disabled => ToggleOrDefault::Disabled,
+			Toggle::Enabled => ToggleOrDefault::Enabled,
+		};
+
+		Self {
+			timeout: Delay::from_reply(settings.timeout),
+			interval: Delay::from_reply(settings.interval),
+
+			prefer_blanking: toggle(settings.prefer_blanking),
+			allow_expose_events: toggle(settings.allow_expose_events),
+		}
+	}
+}
+
 derive_xrb! {
 	/// A [request] that configures options for the screensaver.
 	///
@@ -299,10 +552,33 @@ derive_xrb! {
 	/// [request]: Request
 	///
 	/// [`GetScreenSaver` reply]: reply::GetScreenSaver
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetScreenSaver: Request(108) -> reply::GetScreenSaver;
 }
 
+impl SetScreenSaver {
+	/// A `SetScreenSaver` [request] which restores the default value of every
+	/// option.
+	///
+	/// [request]: Request
+	pub const DEFAULT: Self = Self {
+		timeout: Delay::Default,
+		interval: Delay::Default,
+
+		prefer_blanking: ToggleOrDefault::Default,
+		allow_expose_events: ToggleOrDefault::Default,
+	};
+
+	/// A `SetScreenSaver` [request] which disables the screensaver, leaving
+	/// the other options as their defaults.
+	///
+	/// [request]: Request
+	pub const DISABLED: Self = Self {
+		timeout: Delay::Disabled,
+		..Self::DEFAULT
+	};
+}
+
 request_error! {
 	pub enum ChangeHostsError for ChangeHosts {
 		Access,
@@ -377,10 +653,131 @@ derive_xrb! {
 	/// [`QueryAccessControl` reply]: reply::QueryAccessControl
 	#[doc(alias("ListHosts"))]
 	#[deprecated(note = "more secure forms of authentication are preferred.")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct QueryAccessControl: Request(110) -> reply::QueryAccessControl;
 }
 
+/// The [hosts] on an access control list.
+///
+/// A `HostList` can be [compared] with the access control list the client
+/// wants the X server to have, producing the [`ChangeHosts` requests] which
+/// bring the X server's access control list in line with it.
+///
+/// [hosts]: Host
+/// [compared]: HostList::diff
+///
+/// [`ChangeHosts` requests]: ChangeHosts
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct HostList {
+	hosts: Vec<Host>,
+}
+
+impl HostList {
+	/// Creates a new `HostList` containing the given `hosts`.
+	///
+	/// Duplicate [hosts] are only included once.
+	///
+	/// [hosts]: Host
+	#[must_use]
+	pub fn new(hosts: impl IntoIterator<Item = Host>) -> Self {
+		let mut list = Self::default();
+
+		for host in hosts {
+			list.insert(host);
+		}
+
+		list
+	}
+
+	/// Creates a `HostList` containing the [hosts] returned in a
+	/// [`QueryAccessControl` reply].
+	///
+	/// [hosts]: Host
+	///
+	/// [`QueryAccessControl` reply]: reply::QueryAccessControl
+	#[must_use]
+	pub fn from_reply(reply: &reply::QueryAccessControl) -> Self {
+		Self::new(reply.hosts.iter().cloned())
+	}
+
+	/// Adds the given `host` to this list, returning whether it was not
+	/// already present.
+	pub fn insert(&mut self, host: Host) -> bool {
+		if self.contains(&host) {
+			false
+		} else {
+			self.hosts.push(host);
+
+			true
+		}
+	}
+
+	/// Removes the given `host` from this list, returning whether it was
+	/// present.
+	pub fn remove(&mut self, host: &Host) -> bool {
+		let len = self.hosts.len();
+		self.hosts.retain(|other| other != host);
+
+		self.hosts.len() != len
+	}
+
+	/// Returns whether the given `host` is in this list.
+	#[must_use]
+	pub fn contains(&self, host: &Host) -> bool {
+		self.hosts.contains(host)
+	}
+
+	/// The [hosts] in this list.
+	///
+	/// [hosts]: Host
+	#[must_use]
+	pub fn hosts(&self) -> &[Host] {
+		&self.hosts
+	}
+
+	/// Returns the [`ChangeHosts` requests] which change an access control
+	/// list matching this `HostList` into one matching `desired`.
+	///
+	/// [Hosts][host] which are not in `desired` are [removed] first, followed
+	/// by [adding][added] the [hosts][host] which are missing from this list.
+	/// [Hosts][host] in both lists are left untouched.
+	///
+	/// [host]: Host
+	/// [removed]: AddOrRemove::Remove
+	/// [added]: AddOrRemove::Add
+	///
+	/// [`ChangeHosts` requests]: ChangeHosts
+	#[must_use]
+	#[allow(
+		deprecated,
+		reason = "the access control list is still part of the protocol"
+	)]
+	pub fn diff(&self, desired: &Self) -> Vec<ChangeHosts> {
+		let change = |mode, hosts: &Self, others: &Self| {
+			hosts
+				.hosts
+				.iter()
+				.filter(|host| !others.contains(host))
+				.map(move |host| ChangeHosts {
+					mode,
+					host: host.clone(),
+				})
+				.collect::<Vec<_>>()
+		};
+
+		let mut requests = change(AddOrRemove::Remove, self, desired);
+		requests.extend(change(AddOrRemove::Add, desired, self));
+
+		requests
+	}
+}
+
+impl FromIterator<Host> for HostList {
+	fn from_iter<I: IntoIterator<Item = Host>>(hosts: I) -> Self {
+		Self::new(hosts)
+	}
+}
+
 request_error! {
 	pub enum SetAccessControlError for SetAccessControl {
 		Access,
@@ -400,7 +797,7 @@ derive_xrb! {
 	/// [enabled]: Toggle::Enabled
 	/// [disabled]: Toggle::Disabled
 	#[deprecated(note = "more secure forms of authentication are preferred.")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct SetAccessControl: Request(111, SetAccessControlError) {
 		/// Whether access control is [enabled] or [disabled].
 		///
@@ -418,7 +815,19 @@ derive_xrb! {
 ///
 /// [`Destroy`]: RetainResourcesMode::Destroy
 #[doc(alias("CloseDownMode"))]
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum RetainResourcesMode {
 	/// All of the client's resources are destroyed immediately.
 	///
@@ -437,20 +846,20 @@ pub enum RetainResourcesMode {
 	/// [chosen to be saved]: ChangeSavedWindows
 	///
 	/// [`ChangeSavedWindows` request]: ChangeSavedWindows
-	Destroy,
+	Destroy = 0,
 
 	/// All of the client's resources are marked as permanently retained.
 	///
 	/// Ending a connection with [`RetainResourcesMode::RetainPermanently`] will
 	/// not cause the X server to reset.
 	#[doc(alias("RetainPermanent"))]
-	RetainPermanently,
+	RetainPermanently = 1,
 	/// All of the client's resources are marked as temporarily retained.
 	///
 	/// Ending a connection with [`RetainResourcesMode::RetainTemporarily`] will
 	/// not cause the X server to reset.
 	#[doc(alias("RetainTemporary"))]
-	RetainTemporarily,
+	RetainTemporarily = 2,
 }
 
 derive_xrb! {
@@ -463,7 +872,7 @@ derive_xrb! {
 	///
 	/// [request]: Request
 	#[doc(alias("SetCloseDownMode"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct SetRetainResourcesMode: Request(112, error::Value) {
 		/// The [`RetainResourcesMode`] set for your client.
 		///
@@ -486,7 +895,7 @@ derive_xrb! {
 	/// with [`RetainResourcesMode::RetainTemporarily`] are destroyed.
 	///
 	/// [request]: Request
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct KillClient: Request(113, error::Value) {
 		/// The target of this `KillClient` [request].
 		///
@@ -497,18 +906,119 @@ derive_xrb! {
 	}
 }
 
+/// A guard which keeps your client's [`RetainResourcesMode`] set to
+/// [`RetainTemporarily`] until it is dropped, when it is restored to
+/// [`Destroy`].
+///
+/// Session management tools use this around restarting a window manager, so
+/// that the resources they create survive if their connection ends before the
+/// restart is complete.
+///
+/// The guard is independent of any particular transport: it is created with a
+/// function to send the [`SetRetainResourcesMode` request] which restores
+/// [`Destroy`], which is called exactly once - either by [`release()`] or when
+/// the guard is dropped.
+///
+/// [`RetainTemporarily`]: RetainResourcesMode::RetainTemporarily
+/// [`Destroy`]: RetainResourcesMode::Destroy
+/// [`release()`]: RetainResourcesGuard::release
+///
+/// [`SetRetainResourcesMode` request]: SetRetainResourcesMode
+#[doc(alias("CloseDownModeGuard"))]
+#[must_use = "the retain resources mode is restored as soon as the guard is dropped"]
+pub struct RetainResourcesGuard<Restore: FnOnce(SetRetainResourcesMode) -> Output, Output = ()> {
+	restore: Option<Restore>,
+}
+
+impl<Restore: FnOnce(SetRetainResourcesMode) -> Output, Output>
+	RetainResourcesGuard<Restore, Output>
+{
+	/// Sets your client's [`RetainResourcesMode`] to [`RetainTemporarily`] by
+	/// sending a [`SetRetainResourcesMode` request] with `retain`, returning a
+	/// guard which restores [`Destroy`] with `restore` when it is dropped.
+	///
+	/// # Errors
+	/// Returns the error returned by `retain`, if any. If `retain` fails,
+	/// `restore` is not called.
+	///
+	/// [`RetainTemporarily`]: RetainResourcesMode::RetainTemporarily
+	/// [`Destroy`]: RetainResourcesMode::Destroy
+	///
+	/// [`SetRetainResourcesMode` request]: SetRetainResourcesMode
+	pub fn retain<Error>(
+		retain: impl FnOnce(SetRetainResourcesMode) -> Result<(), Error>, restore: Restore,
+	) -> Result<Self, Error> {
+		retain(SetRetainResourcesMode {
+			mode: RetainResourcesMode::RetainTemporarily,
+		})?;
+
+		Ok(Self {
+			restore: Some(restore),
+		})
+	}
+
+	/// Restores [`Destroy`] now, returning the result of sending the
+	/// [`SetRetainResourcesMode` request].
+	///
+	/// When a `RetainResourcesGuard` is dropped without calling `release()`,
+	/// the result of sending the [`SetRetainResourcesMode` request] is
+	/// discarded.
+	///
+	/// [`Destroy`]: RetainResourcesMode::Destroy
+	///
+	/// [`SetRetainResourcesMode` request]: SetRetainResourcesMode
+	#[allow(
+		clippy::missing_panics_doc,
+		reason = "`restore` is only taken by `release()` and `drop()`"
+	)]
+	pub fn release(mut self) -> Output {
+		let restore = self
+			.restore
+			.take()
+			.expect("the retain resources mode is only restored once");
+
+		restore(SetRetainResourcesMode {
+			mode: RetainResourcesMode::Destroy,
+		})
+	}
+}
+
+impl<Restore: FnOnce(SetRetainResourcesMode) -> Output, Output> Drop
+	for RetainResourcesGuard<Restore, Output>
+{
+	fn drop(&mut self) {
+		if let Some(restore) = self.restore.take() {
+			restore(SetRetainResourcesMode {
+				mode: RetainResourcesMode::Destroy,
+			});
+		}
+	}
+}
+
 /// Whether a [`ForceScreenSaver` request] [resets the activation timer] or
 /// [activates the screensaver]
 ///
 /// [resets the activation timer]: ForceScreenSaverMode::Reset
 /// [activates the screensaver]: ForceScreenSaverMode::Activate
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum ForceScreenSaverMode {
 	/// If the screensaver is currently [enabled], the activation timer (i.e.
 	/// the time left before its activation) is reset and, if the screensaver is
 	/// active, the screensaver is deactivated.
 	///
-	/// [enabled]: ToggleOrDefault::Enabled
+	/// [enabled]: Delay::Enabled
 	Reset,
 
 	/// If the screensaver is not currently active, it is forcibly activated.
@@ -516,7 +1026,7 @@ pub enum ForceScreenSaverMode {
 	/// The screensaver is activated even if [`timeout`] is [`Disabled`].
 	///
 	/// [`timeout`]: SetScreenSaver::timeout
-	/// [`Disabled`]: ToggleOrDefault::Disabled
+	/// [`Disabled`]: Delay::Disabled
 	Activate,
 }
 
@@ -531,7 +1041,7 @@ derive_xrb! {
 	///
 	/// [reset]: ForceScreenSaverMode::Reset
 	/// [activate]: ForceScreenSaverMode::Activate
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ForceScreenSaver: Request(115, error::Value) {
 		/// Whether the screensaver's [activation timer is reset] or the
 		/// screensaver is [forcibly activated].
@@ -569,6 +1079,29 @@ impl Request for NoOp {
 	const MINOR_OPCODE: Option<u16> = None;
 }
 
+impl Message for NoOp {
+	fn name(&self) -> &'static str {
+		"NoOp"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_request::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![("unused_units", &self.unused_units)]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		let mut unused = vec![1..2];
+		if self.unused_units > 0 {
+			unused.push(4..self.x11_size());
+		}
+
+		unused
+	}
+}
+
 impl X11Size for NoOp {
 	fn x11_size(&self) -> usize {
 		const HEADER: usize = 4;
@@ -583,10 +1116,13 @@ impl Readable for NoOp {
 		const ALIGNMENT: usize = 4;
 
 		// Unused metabyte.
+		check_remaining(buf, 1)?;
 		buf.advance(1);
 
 		// One unit is subtracted for the header.
-		let unused_units = buf.get_u16() - 1;
+		let unused_units = u16::read_from(buf)?
+			.checked_sub(1)
+			.ok_or_else(|| ReadError::Other(Box::new("the request's length is zero")))?;
 
 		let buf = &mut buf.take(usize::from(unused_units) * ALIGNMENT);
 		// Unused bytes.
@@ -614,3 +1150,189 @@ impl Writable for NoOp {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use std::cell::RefCell;
+
+	use super::*;
+	use crate::test_util::round_trip;
+
+	#[test]
+	fn test_retain_resources_mode_discriminants() {
+		for (mode, byte) in [
+			(RetainResourcesMode::Destroy, 0),
+			(RetainResourcesMode::RetainPermanently, 1),
+			(RetainResourcesMode::RetainTemporarily, 2),
+		] {
+			let mut bytes = vec![];
+			mode.write_to(&mut bytes).unwrap();
+
+			assert_eq!(bytes, [byte]);
+			assert_eq!(
+				RetainResourcesMode::read_from(&mut &bytes[..]).unwrap(),
+				mode
+			);
+		}
+	}
+
+	#[test]
+	fn test_retain_resources_guard() {
+		let sent = RefCell::new(vec![]);
+		let send = |request: SetRetainResourcesMode| sent.borrow_mut().push(request.mode);
+
+		{
+			let _guard = RetainResourcesGuard::retain(
+				|request| {
+					send(request);
+					Ok::<_, Infallible>(())
+				},
+				send,
+			)
+			.unwrap();
+
+			assert_eq!(*sent.borrow(), [RetainResourcesMode::RetainTemporarily]);
+		}
+
+		assert_eq!(
+			*sent.borrow(),
+			[
+				RetainResourcesMode::RetainTemporarily,
+				RetainResourcesMode::Destroy
+			]
+		);
+
+		let failed = RetainResourcesGuard::retain(|_| Err(()), |_| unreachable!());
+		assert!(failed.is_err());
+	}
+
+	#[test]
+	fn test_screen_saver_settings() {
+		let settings = ScreenSaverSettings {
+			timeout: Some(Sec(600)),
+			interval: None,
+			prefer_blanking: Toggle::Enabled,
+			allow_expose_events: Toggle::Disabled,
+		};
+		let bytes = round_trip(&SetScreenSaver::from(settings), 1);
+		assert_eq!(&bytes[4..10], &[0x02, 0x58, 0, 0, 1, 0]);
+
+		let bytes = round_trip(&SetScreenSaver::DEFAULT, 1);
+		assert_eq!(&bytes[4..10], &[0xff, 0xff, 0xff, 0xff, 2, 2]);
+	}
+
+	#[test]
+	#[allow(
+		deprecated,
+		reason = "the access control list is still part of the protocol"
+	)]
+	fn test_host_list_diff() {
+		use std::net::IpAddr;
+
+		let local = Host::from(IpAddr::from([127, 0, 0, 1]));
+		let remote = Host::from(IpAddr::from([0xfe80, 0, 0, 0, 0, 0, 0, 1]));
+		let named = Host::hostname("example").unwrap();
+
+		assert_eq!(local.address.ip_addr(), Some(IpAddr::from([127, 0, 0, 1])));
+		assert_eq!(named.address.as_hostname(), Some("example"));
+		assert_eq!(named.address.ip_addr(), None);
+
+		let current = HostList::new([local.clone(), remote.clone(), local.clone()]);
+		assert_eq!(current.hosts().len(), 2);
+
+		let desired = HostList::new([local, named.clone()]);
+		let requests = current.diff(&desired);
+		assert_eq!(
+			requests,
+			[
+				ChangeHosts {
+					mode: AddOrRemove::Remove,
+					host: remote,
+				},
+				ChangeHosts {
+					mode: AddOrRemove::Add,
+					host: named,
+				},
+			],
+		);
+		assert!(desired.diff(&desired).is_empty());
+
+		// `hostname`, a null byte, then `example`: 16 bytes, so no padding.
+		let bytes = round_trip(&requests[1], 1);
+		assert_eq!(&bytes[4..8], &[5, 0, 0, 16]);
+		assert_eq!(&bytes[8..], b"hostname\0example");
+	}
+
+	#[test]
+	fn test_foreign_window() {
+		use crate::{
+			connection::{ConnectionSuccess, ImageEndianness},
+			Keycode,
+			String8,
+		};
+
+		let connection = ConnectionSuccess {
+			protocol_major_version: 11,
+			protocol_minor_version: 0,
+			release_number: 0,
+			resource_id_base: 0x0040_0000,
+			resource_id_mask: 0x001f_ffff,
+			motion_buffer_size: 0,
+			maximum_request_length: u16::MAX,
+			image_byte_order: ImageEndianness::LittleEndian,
+			bitmap_format_bit_order: ImageEndianness::LittleEndian,
+			bitmap_format_scanline_unit: 32,
+			bitmap_format_scanline_padding: 32,
+			min_keycode: Keycode(8),
+			max_keycode: Keycode(255),
+			vendor: String8::from_latin1("").unwrap(),
+			pixmap_formats: vec![],
+			roots: vec![],
+		};
+
+		let own = Window::new(0x005f_ffff);
+		assert_eq!(
+			ForeignWindow::new(own, &connection),
+			Err(OwnWindow { window: own })
+		);
+
+		let window = Window::new(0x0060_0000);
+		let foreign = ForeignWindow::new(window, &connection).unwrap();
+		assert_eq!(Window::from(foreign), window);
+
+		let add = ChangeSavedWindows::add(foreign);
+		assert_eq!((add.change_mode, add.window), (AddOrRemove::Add, window));
+
+		let remove = ChangeSavedWindows::remove(foreign);
+		assert_eq!(
+			(remove.change_mode, remove.window),
+			(AddOrRemove::Remove, window)
+		);
+	}
+
+	#[test]
+	fn test_read_error_context() {
+		use xrbk::ReadError;
+
+		// The major opcode has already been read. A `change_mode` of 2 is
+		// neither `Add` nor `Remove`.
+		let error = ChangeSavedWindows::read_from(&mut &[2, 0, 2, 0, 0x40, 0, 1][..]).unwrap_err();
+
+		assert!(
+			matches!(
+				error,
+				ReadError::Context {
+					field: "change_mode",
+					offset: 1,
+					..
+				}
+			),
+			"{error}"
+		);
+		assert!(error.to_string().contains("ChangeSavedWindows"), "{error}");
+		assert!(matches!(
+			error.root(),
+			ReadError::UnrecognizedDiscriminant(2)
+		));
+	}
+}
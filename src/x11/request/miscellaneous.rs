@@ -14,24 +14,29 @@
 
 extern crate self as xrb;
 
+use std::{fmt::Debug, ops::Range};
+
 use xrbk::{
+	check_remaining,
 	pad,
 	Buf,
 	BufMut,
 	ConstantX11Size,
+	InfallibleWritable,
 	ReadError,
 	ReadError::UnrecognizedDiscriminant,
 	ReadResult,
+	Readable,
 	ReadableWithContext,
 	Wrap,
 	Writable,
 	WriteResult,
 	X11Size,
 };
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
-	message::{Event, Request},
+	message::{Event, Message, MessageKind, Request},
 	x11::{error, reply},
 	Any,
 	Atom,
@@ -89,7 +94,7 @@ derive_xrb! {
 	///
 	/// [`GetAtom` reply]: reply::GetAtom
 	#[doc(alias("InternAtom", "CreateAtom"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct GetAtom: Request(16, error::Value) -> reply::GetAtom {
 		#[metabyte]
 		/// Whether the X server should avoid creating a new [atom] for an
@@ -138,7 +143,7 @@ derive_xrb! {
 	/// [`GetAtomName` reply]: reply::GetAtomName
 	///
 	/// [`Atom` error]: error::Atom
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetAtomName: Request(17, error::Atom) -> reply::GetAtomName {
 		/// The [atom] for which this [request] gets its name.
 		///
@@ -173,7 +178,9 @@ request_error! {
 ///
 /// [window]: Window
 #[doc(alias = "ChangePropertyMode")]
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable,
+)]
 pub enum ModifyPropertyMode {
 	/// The property replaces an existing property; the previous value is
 	/// discarded.
@@ -188,7 +195,19 @@ pub enum ModifyPropertyMode {
 
 /// Whether a [`DataList`] is formatted as a list of `i8` values, `i16` values,
 /// or `i32` values.
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Hash,
+	PartialEq,
+	Eq,
+	X11Size,
+	ConstantX11Size,
+	Readable,
+	Writable,
+	InfallibleWritable,
+)]
 pub enum DataFormat {
 	/// The list is formatted as `i8` values.
 	I8 = 8,
@@ -198,10 +217,6 @@ pub enum DataFormat {
 	I32 = 32,
 }
 
-impl ConstantX11Size for DataFormat {
-	const X11_SIZE: usize = 1;
-}
-
 impl Wrap for DataFormat {
 	type Integer = u8;
 }
@@ -306,117 +321,321 @@ impl Writable for DataList {
 	}
 }
 
-derive_xrb! {
-	/// A [request] that modifies the given `property` for the [window].
-	///
-	/// A [`Property` event] is generated on the `target` [window].
+impl InfallibleWritable for DataList {}
+
+/// Data which can be used as the value of a property in a
+/// [`ModifyProperty` request].
+///
+/// Unlike [`DataList`], implementors need not own their data: slices of
+/// integers and [atoms] can be written directly.
+///
+/// Strings may be written as the `&[u8]` bytes of their encoding, or as a
+/// [`String8`] for Latin-1.
+///
+/// [`ModifyProperty` request]: ModifyProperty
+/// [atoms]: Atom
+pub trait PropertyData: X11Size + Writable + Debug {
+	/// Whether the data is formatted as 8-bit, 16-bit, or 32-bit values.
+	fn format(&self) -> DataFormat;
+
+	/// The number of values in the data.
 	///
-	/// # Errors
-	/// A [`Window` error] is generated if `target` does not refer to a defined
-	/// [window].
+	/// This is how many values there are - not the number of bytes.
+	fn len(&self) -> usize;
+
+	/// Whether there are no values in the data.
+	fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl PropertyData for DataList {
+	fn format(&self) -> DataFormat {
+		match self {
+			Self::I8(_) => DataFormat::I8,
+			Self::I16(_) => DataFormat::I16,
+			Self::I32(_) => DataFormat::I32,
+		}
+	}
+
+	fn len(&self) -> usize {
+		self.len()
+	}
+}
+
+impl PropertyData for String8 {
+	fn format(&self) -> DataFormat {
+		DataFormat::I8
+	}
+
+	fn len(&self) -> usize {
+		self.len()
+	}
+}
+
+impl<T: PropertyData> PropertyData for &T {
+	fn format(&self) -> DataFormat {
+		(*self).format()
+	}
+
+	fn len(&self) -> usize {
+		(*self).len()
+	}
+}
+
+macro_rules! impl_property_data {
+	($($format:ident => $($type:ty),+;)+) => {
+		$($(
+			impl PropertyData for &[$type] {
+				fn format(&self) -> DataFormat {
+					DataFormat::$format
+				}
+
+				fn len(&self) -> usize {
+					<[$type]>::len(self)
+				}
+			}
+
+			impl PropertyData for Vec<$type> {
+				fn format(&self) -> DataFormat {
+					DataFormat::$format
+				}
+
+				fn len(&self) -> usize {
+					Vec::len(self)
+				}
+			}
+		)+)+
+	};
+}
+
+impl_property_data! {
+	I8 => u8, i8;
+	I16 => u16, i16;
+	I32 => u32, i32, Atom, Window;
+}
+
+/// A [request] that modifies the given `property` for the [window].
+///
+/// A [`Property` event] is generated on the `target` [window].
+///
+/// # Errors
+/// A [`Window` error] is generated if `target` does not refer to a defined
+/// [window].
+///
+/// An [`Atom` error] is generated if either `property` or `type` do not
+/// refer to defined [windows][window].
+///
+/// If the `modify_mode` is [`Prepend`] or [`Append`], the `type` and
+/// `format` must match that of the existing property's value, else a
+/// [`Match` error] is generated.
+///
+/// [window]: Window
+/// [request]: Request
+///
+/// [`Prepend`]: ModifyPropertyMode::Prepend
+/// [`Append`]: ModifyPropertyMode::Append
+///
+/// [`Property` event]: crate::x11::event::Property
+///
+/// [`Window` error]: error::Window
+/// [`Atom` error]: error::Atom
+/// [`Match` error]: error::Match
+#[doc(alias = "ChangeProperty")]
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct ModifyProperty<Data: PropertyData = DataList> {
+	/// The way in which the property is modified.
+	///
+	/// If the mode is [`Replace`], the previous property value is
+	/// discarded.
 	///
-	/// An [`Atom` error] is generated if either `property` or `type` do not
-	/// refer to defined [windows][window].
+	/// If the mode is [`Prepend`], the data is prepended to the existing
+	/// data. If the mode is [`Append`], the data is appended to the
+	/// existing data.
 	///
-	/// If the `modify_mode` is [`Prepend`] or [`Append`], the `type` and
-	/// `format` must match that of the existing property's value, else a
+	/// # Errors
+	/// If the mode is [`Prepend`] or [`Append`], the `type` and `format`
+	/// must match that of the existing property's value, else a
 	/// [`Match` error] is generated.
 	///
 	/// [window]: Window
-	/// [request]: Request
 	///
+	/// [`Replace`]: ModifyPropertyMode::Replace
 	/// [`Prepend`]: ModifyPropertyMode::Prepend
 	/// [`Append`]: ModifyPropertyMode::Append
 	///
-	/// [`Property` event]: crate::x11::event::Property
+	/// [`Match` error]: error::Match
+	#[doc(alias = "mode")]
+	pub modify_mode: ModifyPropertyMode,
+
+	/// The [window] which the `property` is modified for.
+	///
+	/// # Errors
+	/// A [`Window` error] is generated if this does not refer to a defined
+	/// [window].
+	///
+	/// [window]: Window
 	///
 	/// [`Window` error]: error::Window
+	#[doc(alias = "window")]
+	pub target: Window,
+
+	/// The property which is modified.
+	///
+	/// # Errors
+	/// An [`Atom` error] is generated if this does not refer to a defined
+	/// [atom].
+	///
+	/// [atom]: Atom
+	///
 	/// [`Atom` error]: error::Atom
-	/// [`Match` error]: error::Match
-	#[doc(alias = "ChangeProperty")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
-	pub struct ModifyProperty: Request(18, ModifyPropertyError) {
-		#[metabyte]
-		/// The way in which the property is modified.
-		///
-		/// If the mode is [`Replace`], the previous property value is
-		/// discarded.
-		///
-		/// If the mode is [`Prepend`], the data is prepended to the existing
-		/// data. If the mode is [`Append`], the data is appended to the
-		/// existing data.
-		///
-		/// # Errors
-		/// If the mode is [`Prepend`] or [`Append`], the `type` and `format`
-		/// must match that of the existing property's value, else a
-		/// [`Match` error] is generated.
-		///
-		/// [window]: Window
-		///
-		/// [`Replace`]: ModifyPropertyMode::Replace
-		/// [`Prepend`]: ModifyPropertyMode::Prepend
-		/// [`Append`]: ModifyPropertyMode::Append
-		///
-		/// [`Match` error]: error::Match
-		#[doc(alias = "mode")]
-		pub modify_mode: ModifyPropertyMode,
+	pub property: Atom,
+	/// The type of the property's data.
+	///
+	/// For example, if the property is of type [`Window`], then this would
+	/// be [`atom::WINDOW`].
+	///
+	/// # Errors
+	/// An [`Atom` error] is generated if this does not refer to a defined
+	/// [atom].
+	///
+	/// [atom]: Atom
+	/// [`atom::WINDOW`]: crate::atom::WINDOW
+	///
+	/// [`Atom` error]: error::Atom
+	pub r#type: Atom,
 
-		/// The [window] which the `property` is modified for.
-		///
-		/// # Errors
-		/// A [`Window` error] is generated if this does not refer to a defined
-		/// [window].
-		///
-		/// [window]: Window
-		///
-		/// [`Window` error]: error::Window
-		#[doc(alias = "window")]
-		pub target: Window,
+	/// The property's value.
+	///
+	/// This is any [`PropertyData`]; it defaults to a [`DataList`], which
+	/// is what a `ModifyProperty` request is read as.
+	pub data: Data,
+}
 
-		/// The property which is modified.
-		///
-		/// # Errors
-		/// An [`Atom` error] is generated if this does not refer to a defined
-		/// [atom].
-		///
-		/// [atom]: Atom
-		///
-		/// [`Atom` error]: error::Atom
-		pub property: Atom,
-		/// The type of the property's data.
-		///
-		/// For example, if the property is of type [`Window`], then this would
-		/// be [`atom::WINDOW`].
-		///
-		/// # Errors
-		/// An [`Atom` error] is generated if this does not refer to a defined
-		/// [atom].
-		///
-		/// [atom]: Atom
-		/// [`atom::WINDOW`]: crate::atom::WINDOW
-		///
-		/// [`Atom` error]: error::Atom
-		pub r#type: Atom,
+impl<Data: PropertyData> Request for ModifyProperty<Data> {
+	type OtherErrors = ModifyPropertyError;
+	type Reply = ();
+
+	const MAJOR_OPCODE: u8 = 18;
+	const MINOR_OPCODE: Option<u16> = None;
+}
+
+impl<Data: PropertyData> Message for ModifyProperty<Data> {
+	fn name(&self) -> &'static str {
+		"ModifyProperty"
+	}
+
+	fn kind(&self) -> MessageKind {
+		MessageKind::of_request::<Self>()
+	}
+
+	fn fields(&self) -> Vec<(&'static str, &dyn Debug)> {
+		vec![
+			("modify_mode", &self.modify_mode),
+			("target", &self.target),
+			("property", &self.property),
+			("r#type", &self.r#type),
+			("data", &self.data),
+		]
+	}
+
+	fn unused(&self) -> Vec<Range<usize>> {
+		let size = self.x11_size();
+		let padding = pad(&self.data);
+
+		// 3 unused bytes follow the `format`.
+		let mut unused = vec![17..20];
+		if padding > 0 {
+			unused.push((size - padding)..size);
+		}
+
+		unused
+	}
+}
+
+impl<Data: PropertyData> X11Size for ModifyProperty<Data> {
+	fn x11_size(&self) -> usize {
+		const HEADER: usize = 4;
+
+		const CONSTANT_SIZES: usize = {
+			HEADER
+			+ Window::X11_SIZE // `target`
+			+ Atom::X11_SIZE // `property`
+			+ Atom::X11_SIZE // `type`
+			+ DataFormat::X11_SIZE // `format`
+			+ 3 // 3 unused bytes
+			+ u32::X11_SIZE // the length of `data`
+		};
+
+		CONSTANT_SIZES + self.data.x11_size() + pad(&self.data)
+	}
+}
+
+impl<Data: PropertyData> Writable for ModifyProperty<Data> {
+	#[allow(clippy::cast_possible_truncation)]
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		let buf = &mut buf.limit(self.x11_size());
+
+		buf.put_u8(Self::MAJOR_OPCODE);
+		self.modify_mode.write_to(buf)?;
+		buf.put_u16(self.length());
+
+		self.target.write_to(buf)?;
+		self.property.write_to(buf)?;
+		self.r#type.write_to(buf)?;
 
 		// Whether the `data` is formatted as `i8` values, `i16` values, or
 		// `i32` values.
-		let format: DataFormat = data => match data {
-			DataList::I8(_) => DataFormat::I8,
-			DataList::I16(_) => DataFormat::I16,
-			DataList::I32(_) => DataFormat::I32,
-		},
-		[_; 3],
+		self.data.format().write_to(buf)?;
+		// 3 unused bytes.
+		buf.put_bytes(0, 3);
 
 		// The length of `data` in number of values (i.e., an `i32` value is
 		// counted as one, rather than the number of bytes).
-		#[allow(clippy::cast_possible_truncation)]
-		let data_len: u32 = data => data.len() as u32,
+		(self.data.len() as u32).write_to(buf)?;
 
-		/// The property's value.
-		///
-		/// See [`DataList`] for information on the format of this data.
-		#[context(format, data_len => (*format, *data_len))]
-		pub data: DataList,
+		self.data.write_to(buf)?;
+		// Unused padding bytes at the end.
+		buf.put_bytes(0, pad(&self.data));
+
+		Ok(())
+	}
+}
+
+impl<Data: PropertyData + InfallibleWritable> InfallibleWritable for ModifyProperty<Data> {}
+
+// `ModifyProperty` can only be read if its `data` is owned, so `Readable` is
+// implemented for a `DataList` only.
+impl Readable for ModifyProperty {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self> {
+		let modify_mode = ModifyPropertyMode::read_from(buf)?;
+
+		// The length includes the 4-byte header, so it must be at least 1.
+		let length = usize::from(u16::read_from(buf)?)
+			.checked_sub(1)
+			.ok_or_else(|| ReadError::Other(Box::new("the request's length is zero")))?;
+		let buf = &mut buf.take(length * 4);
+
+		let target = Window::read_from(buf)?;
+		let property = Atom::read_from(buf)?;
+		let r#type = Atom::read_from(buf)?;
+
+		let format = DataFormat::read_from(buf)?;
+		check_remaining(buf, 3)?;
+		buf.advance(3);
+
+		let data_len = u32::read_from(buf)?;
+		let data = DataList::read_with(buf, &(format, data_len))?;
+		check_remaining(buf, pad(&data))?;
+		buf.advance(pad(&data));
+
+		Ok(Self {
+			modify_mode,
+			target,
+			property,
+			r#type,
+			data,
+		})
 	}
 }
 
@@ -448,7 +667,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct DeleteProperty: Request(19, DeletePropertyError) {
 		/// The [window] for which this [request] removes the `property`.
 		///
@@ -507,7 +726,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetProperty: Request(20, GetPropertyError) -> reply::GetProperty {
 		/// Whether the `property` should be deleted from the `target` [window].
 		///
@@ -585,7 +804,7 @@ derive_xrb! {
 	/// [`ListProperties` reply]: reply::ListProperties
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct ListProperties: Request(21, error::Window) -> reply::ListProperties {
 		/// The [window] for which this [request] returns its properties.
 		///
@@ -638,7 +857,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct SetSelectionOwner: Request(22, SetSelectionOwnerError) {
 		/// Sets the new owner of the `selection`.
 		///
@@ -692,7 +911,7 @@ derive_xrb! {
 	/// [`GetSelectionOwner` reply]: reply::GetSelectionOwner
 	///
 	/// [`Atom` error]: error::Atom
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetSelectionOwner: Request(23) -> reply::GetSelectionOwner {
 		/// The selection for which this [request] returns its owner.
 		///
@@ -732,7 +951,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct ConvertSelection: Request(24, ConvertSelectionError) {
 		/// Your [window] which is requesting this conversion.
 		///
@@ -767,6 +986,19 @@ derive_xrb! {
 		///
 		/// [`Atom` error]: error::Atom
 		pub target_type: Atom,
+		/// The property on the `requester` in which the converted selection
+		/// should be stored.
+		///
+		/// The [ICCCM] recommends that this is always [`Some`].
+		///
+		/// # Errors
+		/// An [`Atom` error] is generated if this is [`Some`] but does not
+		/// refer to a defined [atom].
+		///
+		/// [atom]: Atom
+		/// [ICCCM]: https://x.org/releases/X11R7.7/doc/xorg-docs/icccm/icccm.html
+		///
+		/// [`Atom` error]: error::Atom
 		pub property: Option<Atom>,
 
 		/// The [time] at which this conversion is recorded as having taken
@@ -822,8 +1054,8 @@ derive_xrb! {
 	//
 	// This feature would be nice for this:
 	// <https://github.com/rust-lang/rust/issues/92827>
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
-	pub struct SendEvent<E: Event + ConstantX11Size>: Request(25, SendEventError) {
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Writable, InfallibleWritable, ConstantX11Size)]
+	pub struct SendEvent<E: Event + ConstantX11Size + Debug>: Request(25, SendEventError) {
 		/// Whether the `event` should be propagated to the closest appropriate
 		/// ancestor, if necessary.
 		///
@@ -856,6 +1088,31 @@ derive_xrb! {
 	}
 }
 
+impl<E: Event + ConstantX11Size + Debug> Readable for SendEvent<E> {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let propagate = bool::read_from(buf)?;
+		// The length of the request, which is always 11.
+		check_remaining(buf, 2)?;
+		buf.advance(2);
+
+		let destination = DestinationWindow::read_from(buf)?;
+		let event_mask = EventMask::read_from(buf)?;
+		// Unlike `read_from`, `read_received` reads the event's code, which is
+		// written with the event.
+		let event = E::read_received(buf)?.event;
+
+		Ok(Self {
+			propagate,
+			destination,
+			event_mask,
+			event,
+		})
+	}
+}
+
 request_error! {
 	pub enum RotatePropertiesError for RotateProperties {
 		Atom,
@@ -914,7 +1171,7 @@ derive_xrb! {
 	/// [`Window` error]: error::Window
 	/// [`Atom` error]: error::Atom
 	/// [`Match` error]: error::Match
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct RotateProperties: Request(114, RotatePropertiesError) {
 		/// The [window] for which the given `properties` are rotated.
 		///
@@ -960,3 +1217,137 @@ derive_xrb! {
 		pub properties: Vec<Atom>,
 	}
 }
+
+impl RotateProperties {
+	/// Creates a `RotateProperties` request which rotates the given
+	/// `properties` of the `target` [window] by the given `shift`.
+	///
+	/// The `shift` is normalized modulo the number of `properties`, so any
+	/// `shift` may be given: rotating by `properties.len() + 1` is the same as
+	/// rotating by `1`, and rotating by `-1` is the same as rotating by
+	/// `properties.len() - 1`.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub fn new(target: Window, shift: i32, properties: Vec<Atom>) -> Self {
+		Self {
+			target,
+			shift: normalize_shift(shift, properties.len()),
+			properties,
+		}
+	}
+
+	/// Returns whether this request actually rotates the `properties`, that
+	/// is, whether `shift % properties.len() != 0`.
+	///
+	/// If it does not, no [`Property` events] are generated.
+	///
+	/// [`Property` events]: crate::x11::event::Property
+	#[must_use]
+	pub fn rotates(&self) -> bool {
+		normalize_shift(i32::from(self.shift), self.properties.len()) != 0
+	}
+}
+
+/// Normalizes the given `shift` modulo `len`, preferring the smallest
+/// non-negative shift which fits in an [`i16`].
+#[allow(
+	clippy::cast_possible_truncation,
+	clippy::cast_possible_wrap,
+	reason = "the shift is normalized to fit within an `i16`"
+)]
+fn normalize_shift(shift: i32, len: usize) -> i16 {
+	if len == 0 {
+		return 0;
+	}
+
+	let len = len.min(usize::from(u16::MAX)) as i32;
+	let shift = shift.rem_euclid(len);
+
+	if shift > i32::from(i16::MAX) {
+		(shift - len) as i16
+	} else {
+		shift as i16
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use xrbk::{Readable, Writable, X11Size};
+
+	use super::*;
+
+	#[test]
+	fn test_rotate_properties_shift() {
+		let target = Window::new(0x0040_0001);
+		let properties: Vec<_> = (1..=4).map(Atom::new).collect();
+
+		assert_eq!(
+			RotateProperties::new(target, 5, properties.clone()).shift,
+			1
+		);
+		assert_eq!(
+			RotateProperties::new(target, -1, properties.clone()).shift,
+			3
+		);
+		assert!(!RotateProperties::new(target, 8, properties).rotates());
+		assert!(!RotateProperties::new(target, 3, vec![]).rotates());
+
+		let many: Vec<_> = (0..40_000).map(Atom::new).collect();
+		assert_eq!(RotateProperties::new(target, -1, many).shift, -1);
+	}
+
+	#[test]
+	fn test_modify_property_data() {
+		let atoms = [Atom::new(300), Atom::new(301)];
+
+		let borrowed = ModifyProperty {
+			modify_mode: ModifyPropertyMode::Replace,
+			target: Window::new(0x0040_0001),
+			property: Atom::new(302),
+			r#type: crate::atom::ATOM,
+			data: &atoms[..],
+		};
+		let owned = ModifyProperty {
+			modify_mode: ModifyPropertyMode::Replace,
+			target: Window::new(0x0040_0001),
+			property: Atom::new(302),
+			r#type: crate::atom::ATOM,
+			data: DataList::I32(vec![300, 301]),
+		};
+
+		let mut bytes = Vec::new();
+		borrowed.write_to(&mut bytes).unwrap();
+
+		let mut owned_bytes = Vec::new();
+		owned.write_to(&mut owned_bytes).unwrap();
+
+		assert_eq!(bytes, owned_bytes);
+		assert_eq!(borrowed.x11_size(), bytes.len());
+		assert_eq!(bytes[16], 32);
+		assert_eq!(ModifyProperty::read_from(&mut &bytes[1..]).unwrap(), owned);
+
+		let name = String8::from_latin1("xterm").unwrap();
+		let string = ModifyProperty {
+			modify_mode: ModifyPropertyMode::Append,
+			target: Window::new(0x0040_0001),
+			property: crate::atom::WM_NAME,
+			r#type: crate::atom::STRING,
+			data: &name,
+		};
+
+		let mut bytes = Vec::new();
+		string.write_to(&mut bytes).unwrap();
+		assert_eq!(bytes.len(), 32);
+		assert_eq!(&bytes[24..29], b"xterm");
+
+		// A length of zero would not even include the header.
+		let mut bytes = Vec::new();
+		owned.write_to(&mut bytes).unwrap();
+		bytes[2..4].copy_from_slice(&[0, 0]);
+		assert!(matches!(
+			ModifyProperty::read_from(&mut &bytes[1..]),
+			Err(ReadError::Other(_))
+		));
+	}
+}
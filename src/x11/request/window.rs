@@ -13,17 +13,18 @@
 
 extern crate self as xrb;
 
-use xrbk_macro::{derive_xrb, Readable, Writable, X11Size};
+use xrbk_macro::{derive_xrb, ConstantX11Size, InfallibleWritable, Readable, Writable, X11Size};
 
 use crate::{
-	message::Request,
-	set::{Attributes, WindowConfig},
+	message::{Request, Validate, ValidationError},
+	set::{Attributes, AttributesMask, WindowConfig},
 	unit::Px,
-	visual::VisualId,
+	visual::{Screen, UnsupportedVisual, VisualId},
 	x11::{error, reply},
 	Coords,
 	CopyableFromParent,
 	Drawable,
+	GeometryOutOfBounds,
 	Rectangle,
 	Window,
 	WindowClass,
@@ -77,7 +78,7 @@ derive_xrb! {
 	///
 	/// [request]: Request
 	/// [window]: Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct CreateWindow: Request(1, CreateWindowError) {
 		#[metabyte]
 		/// The [window]'s depth.
@@ -181,6 +182,106 @@ derive_xrb! {
 	}
 }
 
+impl CreateWindow {
+	/// Checks client-side that the `depth` and `visual` of this [request] are
+	/// supported by the given `screen`, which would otherwise generate a
+	/// [`Match` error].
+	///
+	/// `parent_depth` and `parent_visual` are the depth and [visual] of the
+	/// `parent` [window], used in place of [`CopyFromParent`]. A `class` of
+	/// [`CopyFromParent`] is assumed to be [`InputOutput`].
+	///
+	/// # Errors
+	/// Returns an [`UnsupportedVisual`] error describing why the combination
+	/// is not supported.
+	///
+	/// [request]: Request
+	/// [window]: Window
+	/// [visual]: crate::visual::VisualType
+	///
+	/// [`CopyFromParent`]: CopyableFromParent::CopyFromParent
+	/// [`InputOutput`]: WindowClass::InputOutput
+	///
+	/// [`Match` error]: error::Match
+	pub fn check_visual(
+		&self, screen: &Screen, parent_depth: u8, parent_visual: VisualId,
+	) -> Result<(), UnsupportedVisual> {
+		let depth = self.depth.unwrap_or(parent_depth);
+
+		if self.class == CopyableFromParent::Other(WindowClass::InputOnly) {
+			// `CopyFromParent` is encoded as zero, so both are valid.
+			return match self.depth {
+				CopyableFromParent::CopyFromParent | CopyableFromParent::Other(0) => Ok(()),
+				CopyableFromParent::Other(depth) => {
+					Err(UnsupportedVisual::InputOnlyDepth { depth })
+				},
+			};
+		}
+
+		screen.check_visual(depth, self.visual.unwrap_or(parent_visual))
+	}
+}
+
+impl Validate for CreateWindow {
+	/// Checks that the `geometry` has a non-zero width and height, and that an
+	/// [`InputOnly`] [window] has a `depth` of zero, a `border_width` of zero,
+	/// and only the attributes permitted for [`InputOnly`] [windows][window].
+	///
+	/// A `class` of [`CopyFromParent`] is not checked, as it depends on the
+	/// `parent`.
+	///
+	/// [window]: Window
+	///
+	/// [`InputOnly`]: WindowClass::InputOnly
+	/// [`CopyFromParent`]: CopyableFromParent::CopyFromParent
+	fn validate(&self) -> Result<(), ValidationError> {
+		if self.geometry.width == Px(0) || self.geometry.height == Px(0) {
+			return Err(ValidationError {
+				field: "geometry",
+				reason: "the width and height must be non-zero",
+			});
+		}
+
+		if self.class != CopyableFromParent::Other(WindowClass::InputOnly) {
+			return Ok(());
+		}
+
+		if !matches!(
+			self.depth,
+			CopyableFromParent::CopyFromParent | CopyableFromParent::Other(0)
+		) {
+			return Err(ValidationError {
+				field: "depth",
+				reason: "`InputOnly` windows must have a depth of zero",
+			});
+		}
+
+		if self.border_width != Px(0) {
+			return Err(ValidationError {
+				field: "border_width",
+				reason: "`InputOnly` windows must have a border width of zero",
+			});
+		}
+
+		let permitted = AttributesMask::WINDOW_GRAVITY
+			| AttributesMask::EVENT_MASK
+			| AttributesMask::DO_NOT_PROPAGATE_MASK
+			| AttributesMask::OVERRIDE_REDIRECT
+			| AttributesMask::CURSOR_APPEARANCE;
+
+		if !permitted.contains(self.attributes.mask()) {
+			return Err(ValidationError {
+				field: "attributes",
+				reason: "`InputOnly` windows only permit the `window_gravity`, `event_mask`, \
+				         `do_not_propagate_mask`, `override_redirect`, and `cursor_appearance` \
+				         attributes",
+			});
+		}
+
+		Ok(())
+	}
+}
+
 request_error! {
 	pub enum ChangeWindowAttributesError for ChangeWindowAttributes {
 		Access,
@@ -210,7 +311,7 @@ derive_xrb! {
 	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
 	/// [`RESIZE_REDIRECT`]: crate::EventMask::RESIZE_REDIRECT
 	/// [`BUTTON_PRESS`]: crate::EventMask::BUTTON_PRESS
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ChangeWindowAttributes: Request(2, ChangeWindowAttributesError) {
 		/// The [window] which the `attributes` are changed on.
 		///
@@ -230,7 +331,7 @@ derive_xrb! {
 	/// [request]: Request
 	/// [attributes]: Attributes
 	/// [window]: Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetWindowAttributes: Request(3, error::Window) -> reply::GetWindowAttributes {
 		/// The [window] for which this [request] gets the [attributes].
 		///
@@ -263,7 +364,7 @@ derive_xrb! {
 	///
 	/// [`UnmapWindow` request]: UnmapWindow
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct DestroyWindow: Request(4, error::Window) {
 		/// The [window] which is the target of the `DestroyWindow` [request].
 		///
@@ -295,7 +396,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[doc(alias = "DestroySubwindows")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct DestroyChildren: Request(5, error::Window) {
 		/// The [window] which will have its children [destroyed].
 		///
@@ -360,7 +461,7 @@ derive_xrb! {
 	///
 	/// [`Match` error]: error::Match
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct ReparentWindow: Request(7, ReparentWindowError) {
 		/// The [window] which will be transferred to be a child of the
 		/// `new_parent`.
@@ -430,7 +531,7 @@ derive_xrb! {
 	/// [`SUBSTRUCTURE_REDIRECT`]: crate::EventMask::SUBSTRUCTURE_REDIRECT
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct MapWindow: Request(8, error::Window) {
 		/// The [window] which is the target of the `MapWindow` [request].
 		///
@@ -463,7 +564,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[doc(alias = "MapSubwindows")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct MapChildren: Request(9, error::Window) {
 		/// The [window] which will have its unmapped children [mapped].
 		///
@@ -497,7 +598,7 @@ derive_xrb! {
 	/// [`Unmap` event]: crate::x11::event::Unmap
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct UnmapWindow: Request(10, error::Window) {
 		/// The [window] which is the target of the `UnmapWindow` [request].
 		///
@@ -529,7 +630,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	#[doc(alias = "UnmapSubwindows")]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct UnmapChildren: Request(11, error::Window) {
 		/// The [window] which will have its mapped children [unmapped].
 		///
@@ -591,7 +692,7 @@ derive_xrb! {
 	///
 	/// [`Window` error]: error::Window
 	/// [`Match` error]: error::Match
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable)]
 	pub struct ConfigureWindow: Request(12, ConfigureWindowError) {
 		/// The [window] which is the target of the `ConfigureWindow` [request].
 		///
@@ -645,6 +746,63 @@ derive_xrb! {
 	}
 }
 
+impl ConfigureWindow {
+	/// Creates a `ConfigureWindow` request which moves and resizes the `target`
+	/// [window] to the given `rectangle`.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub fn move_resize(target: Window, rectangle: Rectangle) -> Self {
+		let mut config = WindowConfig::builder();
+		config.rectangle(rectangle);
+
+		Self {
+			target,
+			config: config.build(),
+		}
+	}
+
+	/// Creates a `ConfigureWindow` request which moves and resizes the `target`
+	/// [window], returning an error if any of the given coordinates or
+	/// dimensions cannot be represented in the X11 protocol.
+	///
+	/// # Errors
+	/// Returns [`GeometryOutOfBounds`] if `x` or `y` does not fit within an
+	/// [`i16`], or if `width` or `height` does not fit within a [`u16`].
+	///
+	/// [window]: Window
+	pub fn try_move_resize(
+		target: Window, x: i32, y: i32, width: u32, height: u32,
+	) -> Result<Self, GeometryOutOfBounds> {
+		Ok(Self::move_resize(
+			target,
+			Rectangle::try_new(x, y, width, height)?,
+		))
+	}
+}
+
+impl Validate for ConfigureWindow {
+	/// Checks that a configured `width` or `height` is non-zero, and that a
+	/// `sibling` is only configured along with a `stack_mode`.
+	fn validate(&self) -> Result<(), ValidationError> {
+		if self.config.width() == Some(&Px(0)) || self.config.height() == Some(&Px(0)) {
+			return Err(ValidationError {
+				field: "config",
+				reason: "a configured width or height must be non-zero",
+			});
+		}
+
+		if self.config.sibling().is_some() && self.config.stack_mode().is_none() {
+			return Err(ValidationError {
+				field: "config",
+				reason: "a `sibling` may only be configured along with a `stack_mode`",
+			});
+		}
+
+		Ok(())
+	}
+}
+
 request_error! {
 	pub enum CirculateWindowError for CirculateWindow {
 		Value,
@@ -660,7 +818,9 @@ request_error! {
 /// [window]: Window
 ///
 /// [`CirculateWindow` request]: CirculateWindow
-#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable)]
+#[derive(
+	Debug, Hash, PartialEq, Eq, X11Size, ConstantX11Size, Readable, Writable, InfallibleWritable,
+)]
 pub enum CirculateDirection {
 	/// Raises the lowest mapped child that is occluded by another child, if
 	/// any, to the top of the stack.
@@ -694,7 +854,7 @@ derive_xrb! {
 	/// [`Circulate` event]: crate::x11::event::Circulate
 	///
 	/// [`Window` error]: error::Window
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct CirculateWindow: Request(13, CirculateWindowError) {
 		#[metabyte]
 		/// Which of the [window]'s children might be circulated and in which
@@ -740,7 +900,7 @@ derive_xrb! {
 	/// [`GetGeometry` reply]: reply::GetGeometry
 	///
 	/// [`Drawable` error]: error::Drawable
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct GetGeometry: Request(14, error::Drawable) -> reply::GetGeometry {
 		/// The [drawable] for which this [request] gets its geometry.
 		///
@@ -777,7 +937,7 @@ derive_xrb! {
 	#[doc(alias("QueryTree", "GetTree", "GetWindowTree"))]
 	#[doc(alias("QueryParent", "QueryChildren", "QueryRoot"))]
 	#[doc(alias("GetParent", "GetChildren", "GetRoot"))]
-	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, ConstantX11Size)]
+	#[derive(Debug, Hash, PartialEq, Eq, X11Size, Readable, Writable, InfallibleWritable, ConstantX11Size)]
 	pub struct QueryWindowTree: Request(15, error::Window) -> reply::QueryWindowTree {
 		/// The [window] for which this [request] gets its root [window],
 		/// parent, and children.
@@ -794,3 +954,197 @@ derive_xrb! {
 		pub target: Window,
 	}
 }
+
+impl CirculateWindow {
+	/// Creates a `CirculateWindow` request which raises the lowest mapped
+	/// child of the `target` [window] that is occluded by another child to the
+	/// top of the stack.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn raise_lowest(target: Window) -> Self {
+		Self {
+			direction: CirculateDirection::RaiseLowest,
+			target,
+		}
+	}
+
+	/// Creates a `CirculateWindow` request which lowers the highest mapped
+	/// child of the `target` [window] that occludes another child to the
+	/// bottom of the stack.
+	///
+	/// [window]: Window
+	#[must_use]
+	pub const fn lower_highest(target: Window) -> Self {
+		Self {
+			direction: CirculateDirection::LowerHighest,
+			target,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		unit::Mm,
+		visual::{ColorId, Depth, VisualClass, VisualType},
+		Colormap,
+		EventMask,
+		MaintainContents,
+	};
+
+	#[test]
+	fn test_try_move_resize() {
+		let target = Window::new(0x0020_0001);
+
+		let request = ConfigureWindow::try_move_resize(target, -32768, 32767, 65535, 1).unwrap();
+		assert_eq!(
+			request,
+			ConfigureWindow::move_resize(
+				target,
+				Rectangle::new(Px(i16::MIN), Px(i16::MAX), Px(u16::MAX), Px(1)),
+			)
+		);
+
+		assert_eq!(
+			ConfigureWindow::try_move_resize(target, 0, 0, 65536, 1)
+				.unwrap_err()
+				.field,
+			"width"
+		);
+		assert_eq!(
+			ConfigureWindow::try_move_resize(target, 0, 32768, 1, 1)
+				.unwrap_err()
+				.field,
+			"y"
+		);
+	}
+
+	#[test]
+	fn test_validation() {
+		let mut attributes = Attributes::builder();
+		attributes.override_redirect(true);
+
+		let mut request = CreateWindow {
+			depth: CopyableFromParent::CopyFromParent,
+			window_id: Window::new(0x0020_0001),
+			parent: Window::new(0x0000_0100),
+			geometry: Rectangle::new(Px(0), Px(0), Px(1), Px(1)),
+			border_width: Px(0),
+			class: CopyableFromParent::Other(WindowClass::InputOnly),
+			visual: CopyableFromParent::CopyFromParent,
+			attributes: attributes.build(),
+		};
+		assert_eq!(request.validate(), Ok(()));
+
+		request.depth = CopyableFromParent::Other(24);
+		assert_eq!(request.validate().unwrap_err().field, "depth");
+		request.depth = CopyableFromParent::Other(0);
+
+		let mut attributes = Attributes::builder();
+		attributes.background_color(ColorId::ZERO);
+		request.attributes = attributes.build();
+		assert_eq!(request.validate().unwrap_err().field, "attributes");
+
+		request.class = CopyableFromParent::Other(WindowClass::InputOutput);
+		assert_eq!(request.validate(), Ok(()));
+
+		request.geometry.height = Px(0);
+		assert_eq!(request.validate().unwrap_err().field, "geometry");
+
+		let mut config = WindowConfig::builder();
+		config.sibling(Window::new(0x0020_0002));
+		let request = ConfigureWindow {
+			target: Window::new(0x0020_0001),
+			config: config.build(),
+		};
+		assert_eq!(request.validate().unwrap_err().field, "config");
+	}
+
+	#[test]
+	fn test_check_visual() {
+		let visual_type = |id| {
+			VisualType::new(
+				VisualId::new(id),
+				VisualClass::TrueColor,
+				8,
+				256,
+				0x00ff_0000,
+				0x0000_ff00,
+				0x0000_00ff,
+			)
+		};
+
+		let screen = Screen {
+			root: Window::new(0x0000_0100),
+			default_colormap: Colormap::new(0x20),
+			white: ColorId::new(0x00ff_ffff),
+			black: ColorId::ZERO,
+			current_input_masks: EventMask::empty(),
+			width_px: Px(1920),
+			height_px: Px(1080),
+			width_mm: Mm(508),
+			height_mm: Mm(286),
+			min_installed_colormaps: 1,
+			max_installed_colormaps: 1,
+			root_visual: VisualId::new(0x21),
+			maintain_contents_mode: MaintainContents::Never,
+			maintain_windows_under: false,
+			root_depth: 24,
+			allowed_depths: vec![
+				Depth::new(24, vec![visual_type(0x21)]),
+				Depth::new(32, vec![visual_type(0x23)]),
+			],
+		};
+		let (parent_depth, parent_visual) = (24, VisualId::new(0x21));
+
+		let mut request = CreateWindow {
+			depth: CopyableFromParent::CopyFromParent,
+			window_id: Window::new(0x0020_0001),
+			parent: Window::new(0x0000_0100),
+			geometry: Rectangle::new(Px(0), Px(0), Px(1), Px(1)),
+			border_width: Px(0),
+			class: CopyableFromParent::Other(WindowClass::InputOutput),
+			visual: CopyableFromParent::CopyFromParent,
+			attributes: Attributes::builder().build(),
+		};
+		let check =
+			|request: &CreateWindow| request.check_visual(&screen, parent_depth, parent_visual);
+
+		// The depth and visual copied from the parent are supported.
+		assert_eq!(check(&request), Ok(()));
+
+		request.depth = CopyableFromParent::Other(32);
+		request.visual = CopyableFromParent::Other(VisualId::new(0x23));
+		assert_eq!(check(&request), Ok(()));
+
+		// The parent's visual is not supported at a depth of 32.
+		request.visual = CopyableFromParent::CopyFromParent;
+		assert_eq!(
+			check(&request),
+			Err(UnsupportedVisual::UnsupportedVisual {
+				depth: 32,
+				visual: parent_visual,
+			}),
+		);
+
+		request.depth = CopyableFromParent::Other(16);
+		assert_eq!(
+			check(&request),
+			Err(UnsupportedVisual::UnsupportedDepth { depth: 16 }),
+		);
+
+		// `InputOnly` windows must have a depth of zero, but their visual is
+		// not checked against the depth.
+		request.class = CopyableFromParent::Other(WindowClass::InputOnly);
+		request.visual = CopyableFromParent::Other(VisualId::new(0x23));
+		assert_eq!(
+			check(&request),
+			Err(UnsupportedVisual::InputOnlyDepth { depth: 16 }),
+		);
+
+		request.depth = CopyableFromParent::Other(0);
+		assert_eq!(check(&request), Ok(()));
+	}
+}
@@ -0,0 +1,291 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Parsing and building font names in the X Logical Font Description (XLFD)
+//! format.
+//!
+//! An XLFD font name consists of 14 fields, each preceded by a hyphen:
+//! ```text
+//! -misc-fixed-medium-r-normal--13-120-75-75-c-70-iso8859-1
+//! ```
+//!
+//! An [`Xlfd`] with [wildcard] fields can be used as the `pattern` of a
+//! [`ListFonts` request], and the names returned in its [reply] can be parsed
+//! back into [`Xlfd`]s.
+//!
+//! # Examples
+//! ```
+//! use xrb::xlfd::Xlfd;
+//!
+//! let pattern = Xlfd {
+//!     family_name: Some("fixed".to_owned()),
+//!     pixel_size: Some("13".to_owned()),
+//!     ..Xlfd::default()
+//! };
+//! assert_eq!(pattern.to_string(), "-*-fixed-*-*-*-*-13-*-*-*-*-*-*-*");
+//!
+//! let name: Xlfd = "-misc-fixed-medium-r-normal--13-120-75-75-c-70-iso8859-1".parse()?;
+//! assert!(pattern.matches(&name));
+//! # Ok::<(), xrb::xlfd::InvalidXlfd>(())
+//! ```
+//!
+//! [wildcard]: Xlfd::default
+//! [`ListFonts` request]: crate::x11::request::ListFonts
+//! [reply]: crate::x11::reply::ListFonts
+
+use std::{fmt, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{LengthString8, String8, UnencodableCharacter};
+
+/// The number of fields in an XLFD font name.
+const FIELD_COUNT: usize = 14;
+
+/// A font name in the X Logical Font Description (XLFD) format.
+///
+/// A field of [`None`] is a wildcard which matches any value; it is written as
+/// `*`. The [`Default`] `Xlfd` matches every XLFD font name.
+///
+/// Fields which are given may also contain the wildcards `*`, matching any
+/// number of characters, and `?`, matching any one character.
+///
+/// See the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Xlfd {
+	/// The organization which registered the font, such as `adobe` or `misc`.
+	pub foundry: Option<String>,
+	/// The name of the font's typeface family, such as `helvetica`.
+	pub family_name: Option<String>,
+	/// The font's weight, such as `medium` or `bold`.
+	pub weight_name: Option<String>,
+	/// The font's slant: `r` for roman, `i` for italic, `o` for oblique,
+	/// `ri` for reverse italic, `ro` for reverse oblique, or `ot` for other.
+	pub slant: Option<String>,
+	/// The font's width, such as `normal` or `condensed`.
+	pub setwidth_name: Option<String>,
+	/// Any additional style of the font, such as `sans`. This is often empty.
+	pub add_style_name: Option<String>,
+	/// The height of the font's body in pixels.
+	pub pixel_size: Option<String>,
+	/// The height of the font's body in tenths of a point.
+	pub point_size: Option<String>,
+	/// The horizontal resolution the font was designed for, in pixels per
+	/// inch.
+	pub resolution_x: Option<String>,
+	/// The vertical resolution the font was designed for, in pixels per inch.
+	pub resolution_y: Option<String>,
+	/// The font's spacing: `p` for proportional, `m` for monospaced, or `c`
+	/// for character cell.
+	pub spacing: Option<String>,
+	/// The average width of the font's characters in tenths of a pixel.
+	pub average_width: Option<String>,
+	/// The registry of the font's character set, such as `iso8859` or
+	/// `iso10646`.
+	pub charset_registry: Option<String>,
+	/// The encoding of the font's character set within its registry, such as
+	/// `1`.
+	pub charset_encoding: Option<String>,
+}
+
+/// An error generated when parsing a font name which is not in the XLFD
+/// format.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Error)]
+pub enum InvalidXlfd {
+	/// The font name does not start with a hyphen.
+	#[error("an XLFD font name must start with a hyphen")]
+	MissingLeadingHyphen,
+	/// The font name does not have 14 fields.
+	#[error("an XLFD font name must have 14 fields, found {0}")]
+	FieldCount(usize),
+}
+
+impl Xlfd {
+	/// Returns the fields of this `Xlfd` in the order they appear in a font
+	/// name.
+	const fn fields(&self) -> [&Option<String>; FIELD_COUNT] {
+		[
+			&self.foundry,
+			&self.family_name,
+			&self.weight_name,
+			&self.slant,
+			&self.setwidth_name,
+			&self.add_style_name,
+			&self.pixel_size,
+			&self.point_size,
+			&self.resolution_x,
+			&self.resolution_y,
+			&self.spacing,
+			&self.average_width,
+			&self.charset_registry,
+			&self.charset_encoding,
+		]
+	}
+
+	/// Returns whether the given font `name` matches this `Xlfd` as a pattern.
+	///
+	/// Fields are matched case-insensitively, as they are by the X server.
+	#[must_use]
+	pub fn matches(&self, name: &Self) -> bool {
+		self.fields()
+			.into_iter()
+			.zip(name.fields())
+			.all(|(pattern, field)| match (pattern, field) {
+				(None, _) => true,
+				(Some(pattern), Some(field)) => glob_matches(pattern.as_bytes(), field.as_bytes()),
+				(Some(pattern), None) => pattern.bytes().all(|byte| byte == b'*'),
+			})
+	}
+
+	/// Encodes this `Xlfd` as a [`String8`], such as for the `pattern` of a
+	/// [`ListFonts` request].
+	///
+	/// # Errors
+	/// Returns an [`UnencodableCharacter`] error for the first character which
+	/// is not in Latin-1.
+	///
+	/// [`ListFonts` request]: crate::x11::request::ListFonts
+	pub fn to_string8(&self) -> Result<String8, UnencodableCharacter> {
+		String8::from_latin1(&self.to_string())
+	}
+}
+
+/// Returns whether `text` matches the given `pattern`, in which `*` matches any
+/// number of characters and `?` matches any one character.
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+	match pattern.split_first() {
+		None => text.is_empty(),
+
+		Some((b'*', rest)) => (0..=text.len()).any(|skip| glob_matches(rest, &text[skip..])),
+
+		Some((&expected, rest)) => match text.split_first() {
+			Some((&actual, text)) => {
+				(expected == b'?' || expected.eq_ignore_ascii_case(&actual))
+					&& glob_matches(rest, text)
+			},
+
+			None => false,
+		},
+	}
+}
+
+impl fmt::Display for Xlfd {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for field in self.fields() {
+			write!(f, "-{}", field.as_deref().unwrap_or("*"))?;
+		}
+
+		Ok(())
+	}
+}
+
+impl FromStr for Xlfd {
+	type Err = InvalidXlfd;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		let name = name
+			.strip_prefix('-')
+			.ok_or(InvalidXlfd::MissingLeadingHyphen)?;
+
+		let fields: Vec<_> = name.split('-').collect();
+		let fields = <[_; FIELD_COUNT]>::try_from(fields)
+			.map_err(|fields| InvalidXlfd::FieldCount(fields.len()))?;
+
+		// Fields of a struct expression are evaluated in the order they are
+		// written, which is the order the fields appear in the font name.
+		let mut fields = fields
+			.into_iter()
+			.map(|field| (field != "*").then(|| field.to_owned()));
+		let mut next = || fields.next().flatten();
+
+		Ok(Self {
+			foundry: next(),
+			family_name: next(),
+			weight_name: next(),
+			slant: next(),
+			setwidth_name: next(),
+			add_style_name: next(),
+			pixel_size: next(),
+			point_size: next(),
+			resolution_x: next(),
+			resolution_y: next(),
+			spacing: next(),
+			average_width: next(),
+			charset_registry: next(),
+			charset_encoding: next(),
+		})
+	}
+}
+
+impl TryFrom<&String8> for Xlfd {
+	type Error = InvalidXlfd;
+
+	fn try_from(name: &String8) -> Result<Self, Self::Error> {
+		name.to_latin1().parse()
+	}
+}
+
+impl TryFrom<&LengthString8> for Xlfd {
+	type Error = InvalidXlfd;
+
+	fn try_from(name: &LengthString8) -> Result<Self, Self::Error> {
+		name.to_latin1().parse()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_xlfd_round_trip() {
+		let name = "-misc-fixed-medium-r-normal--13-120-75-75-c-70-iso8859-1";
+		let xlfd: Xlfd = name.parse().unwrap();
+
+		assert_eq!(xlfd.family_name.as_deref(), Some("fixed"));
+		assert_eq!(xlfd.add_style_name.as_deref(), Some(""));
+		assert_eq!(xlfd.charset_encoding.as_deref(), Some("1"));
+		assert_eq!(xlfd.to_string(), name);
+
+		let reply_name = LengthString8::try_from(name).unwrap();
+		assert_eq!(Xlfd::try_from(&reply_name), Ok(xlfd));
+
+		assert_eq!(
+			"misc-fixed".parse::<Xlfd>(),
+			Err(InvalidXlfd::MissingLeadingHyphen)
+		);
+		assert_eq!(
+			"-misc-fixed".parse::<Xlfd>(),
+			Err(InvalidXlfd::FieldCount(2))
+		);
+	}
+
+	#[test]
+	fn test_xlfd_matches() {
+		let name: Xlfd = "-Misc-Fixed-Bold-R-Normal--13-120-75-75-C-70-ISO10646-1"
+			.parse()
+			.unwrap();
+
+		assert!(Xlfd::default().matches(&name));
+		assert!(Xlfd {
+			family_name: Some("fixed".to_owned()),
+			charset_registry: Some("iso*".to_owned()),
+			pixel_size: Some("1?".to_owned()),
+			..Xlfd::default()
+		}
+		.matches(&name));
+		assert!(!Xlfd {
+			weight_name: Some("medium".to_owned()),
+			..Xlfd::default()
+		}
+		.matches(&name));
+
+		assert_eq!(
+			Xlfd::default().to_string8().unwrap().to_latin1(),
+			"-*-*-*-*-*-*-*-*-*-*-*-*-*-*",
+		);
+	}
+}
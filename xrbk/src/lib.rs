@@ -34,6 +34,11 @@ use std::{
 };
 
 pub use bytes::{Buf, BufMut};
+// Re-exported for use in code generated by `xrbk_macro` when its `tracing`
+// feature is enabled.
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing;
 
 use thiserror::Error;
 
@@ -51,6 +56,25 @@ pub fn pad<T: X11Size>(value: &T) -> usize {
 	(ALIGNMENT - (x11_size % ALIGNMENT)) % ALIGNMENT
 }
 
+/// Returns a [`ReadError::NotEnoughBytes`] error if fewer than `needed` bytes
+/// remain in the given `buf`.
+///
+/// [`Buf`]'s methods panic if more bytes are read than remain. This is called
+/// before reading from or advancing a [`Buf`] directly, so that a message which
+/// is too short for its contents generates an error instead.
+///
+/// # Errors
+/// Returns [`ReadError::NotEnoughBytes`] if fewer than `needed` bytes remain.
+pub fn check_remaining(buf: &impl Buf, needed: usize) -> ReadResult<()> {
+	let remaining = buf.remaining();
+
+	if remaining < needed {
+		Err(ReadError::NotEnoughBytes { needed, remaining })
+	} else {
+		Ok(())
+	}
+}
+
 pub type ReadResult<T> = Result<T, ReadError>;
 pub type WriteResult = Result<(), WriteError>;
 
@@ -67,6 +91,83 @@ pub enum ReadError {
 	FailedConversion(Box<dyn Any>),
 	#[error("{0}")]
 	Other(Box<dyn DebugDisplay>),
+
+	/// There were fewer bytes remaining than were needed to read a value.
+	///
+	/// This is generated by [`check_remaining`].
+	#[error("expected {needed} more bytes, but only {remaining} remain")]
+	NotEnoughBytes {
+		/// The number of bytes needed to read the value.
+		needed: usize,
+		/// The number of bytes remaining.
+		remaining: usize,
+	},
+
+	/// Reading a field of a message or structure failed.
+	///
+	/// Readers generated by `derive_xrb!` wrap the errors generated when
+	/// reading their fields with this variant, so that the field which failed
+	/// can be found. Errors in nested structures are wrapped once for each
+	/// level of nesting.
+	#[error("failed to read `{field}` of `{message}` at byte {offset}: {source}")]
+	Context {
+		/// The name of the type which was being read.
+		message: &'static str,
+		/// The name of the field which could not be read.
+		field: &'static str,
+		/// The offset at which the field begins from the start of the message
+		/// or structure, in bytes.
+		offset: usize,
+
+		/// The error generated when reading the field.
+		source: Box<Self>,
+	},
+
+	/// Reading from a [`TrackingBuf`] failed.
+	///
+	/// This is generated by [`TrackingBuf::wrap_error`], so that the bytes
+	/// around the failure can be shown.
+	///
+	/// Unlike the `offset` of a [`ReadError::Context`], which is where the
+	/// field which failed begins, this `offset` is the number of bytes which
+	/// had been consumed when the error was generated. That includes any bytes
+	/// of the field which were read before it failed.
+	#[error("{source} (after {offset} bytes: {})", tracking_buf::hex(preceding.iter().copied()))]
+	AtOffset {
+		/// The number of bytes which had been consumed from the
+		/// [`TrackingBuf`] when the error was generated.
+		offset: usize,
+		/// The bytes which were read most recently before the error.
+		preceding: Vec<u8>,
+
+		/// The error generated when reading.
+		source: Box<Self>,
+	},
+}
+
+impl ReadError {
+	/// Wraps this error in a [`ReadError::Context`] for the given `field` of
+	/// the given `message` at the given `offset`.
+	#[must_use]
+	pub fn in_field(self, message: &'static str, field: &'static str, offset: usize) -> Self {
+		Self::Context {
+			message,
+			field,
+			offset,
+
+			source: Box::new(self),
+		}
+	}
+
+	/// Returns the innermost error, without any [`ReadError::Context`] or
+	/// [`ReadError::AtOffset`].
+	#[must_use]
+	pub fn root(&self) -> &Self {
+		match self {
+			Self::Context { source, .. } | Self::AtOffset { source, .. } => source.root(),
+			other => other,
+		}
+	}
 }
 
 #[non_exhaustive]
@@ -74,15 +175,31 @@ pub enum ReadError {
 pub enum WriteError {
 	#[error("a conversion failed")]
 	FailedConversion(Box<dyn Any>),
+	/// A request is longer than the maximum request length accepted by the X
+	/// server.
+	///
+	/// Both lengths are measured in units of 4 bytes.
+	#[error("request length of {needed} exceeds the maximum request length of {allowed}")]
+	RequestTooLarge {
+		/// The length of the request.
+		needed: usize,
+		/// The maximum request length accepted by the X server.
+		allowed: usize,
+	},
 	#[error("{0}")]
 	Other(Box<dyn DebugDisplay>),
 }
 
 mod readable;
+mod tracking_buf;
 mod wrap;
+mod wrap4;
 mod writable;
 mod x11_size;
 
+pub use tracking_buf::TrackingBuf;
+pub use wrap4::Wrap4;
+
 /// Gives the type size in bytes.
 /// The size can vary depending on the quantity of data it contains
 pub trait X11Size {
@@ -168,6 +285,34 @@ pub trait Writable: X11Size {
 	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult;
 }
 
+/// A [`Writable`] type which is never unable to be written.
+///
+/// Writing to a [`BufMut`] can't fail, so a [`Writable`] type can only
+/// generate a [`WriteError`] if it converts a value in a way which can fail.
+/// Types which don't can implement `InfallibleWritable` so that they can be
+/// written without handling a [`WriteResult`].
+///
+/// Implementing `InfallibleWritable` is a promise that [`write_to`] never
+/// returns an error. It can be derived for types which only contain
+/// `InfallibleWritable` types.
+///
+/// [`write_to`]: Writable::write_to
+pub trait InfallibleWritable: Writable {
+	/// Writes [`self`](Self) as bytes to a [`BufMut`].
+	///
+	/// # Panics
+	/// Panics if [`write_to`] returned an error, which means that
+	/// `InfallibleWritable` was implemented incorrectly.
+	///
+	/// [`write_to`]: Writable::write_to
+	#[inline]
+	fn write_infallible(&self, buf: &mut impl BufMut) {
+		if let Err(error) = self.write_to(buf) {
+			unreachable!("failed to write an `InfallibleWritable` type: {error}");
+		}
+	}
+}
+
 /// A trait implemented for types which 'wrap' some primitive integer type.
 ///
 /// This trait is used so that XRBK traits may be implemented for
@@ -193,7 +338,7 @@ where
 	where
 		Self: Sized,
 	{
-		Ok(match <T::Integer>::read_from(buf).unwrap() {
+		Ok(match <T::Integer>::read_from(buf)? {
 			discrim if discrim.into() == 0_u64 => None,
 			value => Some(match T::try_from(value) {
 				Ok(value) => value,
@@ -226,6 +371,16 @@ where
 	}
 }
 
+impl<T: Wrap> InfallibleWritable for Option<T>
+where
+	// The conversion of `0` to an integer never fails,
+	// and neither does the conversion of `T` to its
+	// integer, because `T` implements `Into` for it.
+	<T::Integer as TryFrom<u64>>::Error: 'static,
+	T::Integer: InfallibleWritable,
+{
+}
+
 // This function is unused, but writing it here asserts that these traits are
 // _object safe_; that is, that the Rust compiler will generate an error if any
 // of these traits are accidentally made _object unsafe_, which means that they
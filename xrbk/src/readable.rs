@@ -4,7 +4,7 @@
 
 //! [`Readable`] implementations for primitive types
 
-use crate::{ReadResult, Readable, ReadableWithContext, X11Size};
+use crate::{check_remaining, ReadResult, Readable, ReadableWithContext, X11Size};
 use bytes::Buf;
 use std::ops::{Range, RangeInclusive};
 
@@ -12,7 +12,10 @@ macro_rules! implement {
 	($($reader:ident, $ty:ty => $expr:expr),*$(,)?) => {
 		$(
 			impl $crate::Readable for $ty {
+				#[inline]
 				fn read_from($reader: &mut impl bytes::Buf) -> Result<Self, $crate::ReadError> {
+					$crate::check_remaining($reader, ::std::mem::size_of::<$ty>())?;
+
 					Ok($expr)
 				}
 			}
@@ -44,7 +47,7 @@ impl<T: Readable, const N: usize> Readable for [T; N] {
 	where
 		Self: Sized,
 	{
-		let mut vec = Vec::new();
+		let mut vec = Vec::with_capacity(N);
 
 		for _ in 0..N {
 			vec.push(T::read_from(reader)?);
@@ -68,11 +71,13 @@ impl<T: Readable> Readable for Box<T> {
 impl<T: Readable> ReadableWithContext for Vec<T> {
 	type Context = usize;
 
-	fn read_with(reader: &mut impl Buf, context: &Self::Context) -> ReadResult<Self>
+	default fn read_with(reader: &mut impl Buf, context: &Self::Context) -> ReadResult<Self>
 	where
 		Self: Sized,
 	{
-		let mut vec = Self::new();
+		// The length is read from the message, so it is limited by the number
+		// of bytes remaining to avoid allocating for a bogus length.
+		let mut vec = Self::with_capacity((*context).min(reader.remaining()));
 
 		for _ in 0..*context {
 			vec.push(T::read_from(reader)?);
@@ -82,6 +87,44 @@ impl<T: Readable> ReadableWithContext for Vec<T> {
 	}
 }
 
+// Byte lists, such as image data, can be large, so they are copied in one go.
+impl ReadableWithContext for Vec<u8> {
+	fn read_with(reader: &mut impl Buf, context: &Self::Context) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		// Like reading the bytes one at a time, this fails if there are too
+		// few, but before allocating for a bogus length.
+		check_remaining(reader, *context)?;
+
+		let mut vec = vec![0; *context];
+		reader.copy_to_slice(&mut vec);
+
+		Ok(vec)
+	}
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> ReadableWithContext for smallvec::SmallVec<A>
+where
+	A::Item: Readable,
+{
+	type Context = usize;
+
+	fn read_with(reader: &mut impl Buf, context: &Self::Context) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let mut vec = Self::with_capacity((*context).min(reader.remaining()));
+
+		for _ in 0..*context {
+			vec.push(A::Item::read_from(reader)?);
+		}
+
+		Ok(vec)
+	}
+}
+
 impl<T: X11Size + Clone> ReadableWithContext for Range<T> {
 	type Context = (T, T);
 
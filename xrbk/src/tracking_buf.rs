@@ -0,0 +1,232 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::{collections::VecDeque, fmt::Write};
+
+use crate::ReadError;
+use bytes::Buf;
+
+/// A [`Buf`] adapter which records how far it has been read and the bytes
+/// which were read most recently.
+///
+/// This is intended for diagnostics: when reading a value fails, the offset
+/// and the preceding bytes show where in the input the failure happened. The
+/// offsets at which each read began can also be recorded with
+/// [`record_boundaries`], which shows where each value in the input starts.
+///
+/// [`record_boundaries`]: TrackingBuf::record_boundaries
+///
+/// # Examples
+/// ```
+/// # use xrbk::{Buf, TrackingBuf};
+/// let mut buf = TrackingBuf::new(&[1, 2, 3, 4, 5][..]).record_boundaries();
+///
+/// assert_eq!(buf.get_u8(), 1);
+/// assert_eq!(buf.get_u16(), 0x0203);
+///
+/// assert_eq!(buf.offset(), 3);
+/// assert_eq!(buf.recent(), [1, 2, 3]);
+/// assert_eq!(buf.boundaries(), [0, 1]);
+/// assert_eq!(buf.hex_context(), "01 02 03");
+/// ```
+#[derive(Clone, Debug)]
+pub struct TrackingBuf<B> {
+	inner: B,
+	offset: usize,
+
+	recent: VecDeque<u8>,
+	capacity: usize,
+
+	boundaries: Option<Vec<usize>>,
+}
+
+impl<B: Buf> TrackingBuf<B> {
+	/// The number of recent bytes kept by [`TrackingBuf::new`].
+	pub const DEFAULT_CAPACITY: usize = 16;
+
+	/// Creates a new `TrackingBuf` which keeps the last
+	/// [`DEFAULT_CAPACITY`] bytes read from `inner`.
+	///
+	/// [`DEFAULT_CAPACITY`]: TrackingBuf::DEFAULT_CAPACITY
+	pub fn new(inner: B) -> Self {
+		Self::with_capacity(inner, Self::DEFAULT_CAPACITY)
+	}
+
+	/// Creates a new `TrackingBuf` which keeps the last `capacity` bytes read
+	/// from `inner`.
+	pub fn with_capacity(inner: B, capacity: usize) -> Self {
+		Self {
+			inner,
+			offset: 0,
+
+			recent: VecDeque::with_capacity(capacity),
+			capacity,
+
+			boundaries: None,
+		}
+	}
+
+	/// Records the offset at which each read begins from now on.
+	///
+	/// Each value is read from a [`Buf`] with at least one call to
+	/// [`advance`], so the recorded [boundaries] are the offsets at which
+	/// each value begins.
+	///
+	/// [`advance`]: Buf::advance
+	/// [boundaries]: TrackingBuf::boundaries
+	#[must_use]
+	pub fn record_boundaries(mut self) -> Self {
+		self.boundaries.get_or_insert_with(Vec::new);
+
+		self
+	}
+
+	/// The number of bytes which have been read.
+	pub const fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// The bytes which were read most recently, oldest first.
+	pub fn recent(&self) -> Vec<u8> {
+		self.recent.iter().copied().collect()
+	}
+
+	/// The offsets at which each read began, if they are being
+	/// [recorded][record].
+	///
+	/// Consecutive reads of single bytes cannot be told apart from one read of
+	/// multiple bytes if the underlying [`Buf`] is not contiguous, but every
+	/// read of a slice does begin at a recorded offset.
+	///
+	/// [record]: TrackingBuf::record_boundaries
+	pub fn boundaries(&self) -> &[usize] {
+		self.boundaries.as_deref().unwrap_or_default()
+	}
+
+	/// Formats the [recent] bytes as space-separated hexadecimal, such as
+	/// `00 2a ff`.
+	///
+	/// [recent]: TrackingBuf::recent
+	pub fn hex_context(&self) -> String {
+		hex(self.recent.iter().copied())
+	}
+
+	/// Wraps the given `error` in a [`ReadError::AtOffset`] with the current
+	/// [offset] and the [recent] bytes.
+	///
+	/// The current [offset] is the number of bytes consumed so far, which may
+	/// be past the start of the value which failed to be read.
+	///
+	/// [offset]: TrackingBuf::offset
+	/// [recent]: TrackingBuf::recent
+	pub fn wrap_error(&self, error: ReadError) -> ReadError {
+		ReadError::AtOffset {
+			offset: self.offset,
+			preceding: self.recent(),
+
+			source: Box::new(error),
+		}
+	}
+
+	/// Returns a reference to the underlying [`Buf`].
+	pub const fn get_ref(&self) -> &B {
+		&self.inner
+	}
+
+	/// Returns the underlying [`Buf`].
+	pub fn into_inner(self) -> B {
+		self.inner
+	}
+}
+
+impl<B: Buf> Buf for TrackingBuf<B> {
+	fn remaining(&self) -> usize {
+		self.inner.remaining()
+	}
+
+	fn chunk(&self) -> &[u8] {
+		self.inner.chunk()
+	}
+
+	fn advance(&mut self, mut cnt: usize) {
+		if let Some(boundaries) = &mut self.boundaries {
+			boundaries.push(self.offset);
+		}
+
+		while cnt > 0 {
+			let chunk = self.inner.chunk();
+			let len = chunk.len().min(cnt);
+
+			if len == 0 {
+				// Let the underlying `Buf` report advancing past its end.
+				self.inner.advance(cnt);
+				return;
+			}
+
+			track(&mut self.recent, self.capacity, &chunk[..len]);
+
+			self.inner.advance(len);
+			self.offset += len;
+			cnt -= len;
+		}
+	}
+}
+
+/// Records that `bytes` have been read in the `recent` bytes, keeping no more
+/// than `capacity` bytes.
+fn track(recent: &mut VecDeque<u8>, capacity: usize, bytes: &[u8]) {
+	// Only the last `capacity` bytes can be kept.
+	let bytes = &bytes[bytes.len().saturating_sub(capacity)..];
+	let overflow = (recent.len() + bytes.len()).saturating_sub(capacity);
+
+	recent.drain(..overflow);
+	recent.extend(bytes);
+}
+
+/// Formats `bytes` as space-separated hexadecimal.
+pub fn hex(bytes: impl IntoIterator<Item = u8>) -> String {
+	let mut hex = String::new();
+
+	for (i, byte) in bytes.into_iter().enumerate() {
+		if i > 0 {
+			hex.push(' ');
+		}
+
+		// Writing to a `String` cannot fail.
+		let _ = write!(hex, "{byte:02x}");
+	}
+
+	hex
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_tracking_buf() {
+		let bytes: Vec<u8> = (0..8).collect();
+		// Two chunks, so that a read may span both.
+		let mut buf = TrackingBuf::with_capacity((&bytes[..3]).chain(&bytes[3..]), 4);
+
+		assert_eq!(buf.get_u16(), 0x0001);
+		assert_eq!(buf.get_u16(), 0x0203);
+		assert_eq!(buf.offset(), 4);
+		assert_eq!(buf.recent(), [0, 1, 2, 3]);
+
+		buf.advance(3);
+		assert_eq!(buf.recent(), [3, 4, 5, 6]);
+		assert_eq!(buf.remaining(), 1);
+
+		let error = buf.wrap_error(ReadError::UnrecognizedDiscriminant(7));
+		assert_eq!(
+			error.to_string(),
+			"unrecognized variant discriminant: 7 (after 7 bytes: 03 04 05 06)"
+		);
+		assert!(matches!(
+			error.root(),
+			ReadError::UnrecognizedDiscriminant(7)
+		));
+	}
+}
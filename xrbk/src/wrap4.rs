@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::{
+	ConstantX11Size,
+	InfallibleWritable,
+	ReadError,
+	ReadResult,
+	Readable,
+	Writable,
+	WriteResult,
+	X11Size,
+};
+use bytes::{Buf, BufMut};
+
+/// Encodes a value of up to four bytes in a four-byte slot.
+///
+/// The X11 protocol encodes every value in a value list (such as the
+/// attributes of a window) as four bytes, even when the value itself is
+/// encoded as a single byte elsewhere. `Wrap4` zero-extends the wrapped value
+/// to four bytes when writing it, and requires the extra bytes to be zero when
+/// reading it.
+///
+/// This is only correct for unsigned values and enums: signed values are
+/// sign-extended by the X11 protocol, not zero-extended.
+///
+/// # Examples
+/// ```
+/// # use xrbk::{Readable, Writable, Wrap4};
+/// let mut bytes = Vec::new();
+/// Wrap4(3_u8).write_to(&mut bytes)?;
+///
+/// assert_eq!(bytes, [0, 0, 0, 3]);
+/// assert_eq!(Wrap4::<u8>::read_from(&mut &bytes[..])?, Wrap4(3));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Wrap4<T>(pub T);
+
+impl<T: ConstantX11Size> Wrap4<T> {
+	/// The number of zero bytes which precede the wrapped value.
+	const PADDING: usize = {
+		assert!(
+			T::X11_SIZE <= 4,
+			"`Wrap4` can only wrap values of up to 4 bytes"
+		);
+
+		4 - T::X11_SIZE
+	};
+}
+
+impl<T> Wrap4<T> {
+	/// Unwraps the wrapped value.
+	pub fn unwrap(self) -> T {
+		let Self(value) = self;
+
+		value
+	}
+}
+
+impl<T: ConstantX11Size> ConstantX11Size for Wrap4<T> {
+	const X11_SIZE: usize = 4;
+}
+
+impl<T: ConstantX11Size> X11Size for Wrap4<T> {
+	fn x11_size(&self) -> usize {
+		Self::X11_SIZE
+	}
+}
+
+impl<T: ConstantX11Size + Readable> Readable for Wrap4<T> {
+	fn read_from(buf: &mut impl Buf) -> ReadResult<Self>
+	where
+		Self: Sized,
+	{
+		let bytes = u32::read_from(buf)?.to_be_bytes();
+		let (padding, value) = bytes.split_at(Self::PADDING);
+
+		if padding.iter().any(|&byte| byte != 0) {
+			return Err(ReadError::UnrecognizedDiscriminant(
+				u32::from_be_bytes(bytes) as usize,
+			));
+		}
+
+		Ok(Self(T::read_from(&mut &value[..])?))
+	}
+}
+
+impl<T: ConstantX11Size + Writable> Writable for Wrap4<T> {
+	fn write_to(&self, buf: &mut impl BufMut) -> WriteResult {
+		buf.put_bytes(0, Self::PADDING);
+		self.0.write_to(buf)?;
+
+		Ok(())
+	}
+}
+
+impl<T: ConstantX11Size + InfallibleWritable> InfallibleWritable for Wrap4<T> {}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_wrap4_round_trip() {
+		let mut bytes = Vec::new();
+		Wrap4(0x0102_u16).write_to(&mut bytes).unwrap();
+		assert_eq!(bytes, [0, 0, 1, 2]);
+
+		let value = Wrap4::<u16>::read_from(&mut &bytes[..]).unwrap();
+		assert_eq!(value, Wrap4(0x0102));
+		assert_eq!(value.x11_size(), 4);
+
+		assert!(matches!(
+			Wrap4::<u8>::read_from(&mut &[0, 1, 0, 0][..]),
+			Err(ReadError::UnrecognizedDiscriminant(0x0001_0000)),
+		));
+	}
+}
@@ -4,13 +4,14 @@
 
 //! [`Writable`] implementations for primitive types
 
-use crate::{Writable, WriteResult};
+use crate::{InfallibleWritable, Writable, WriteResult};
 use bytes::BufMut;
 
 macro_rules! implement {
 	($($ident:ident: &$ty:ty => BufMut::$fun:ident($expr:expr)),*$(,)?) => {
 		$(
 			impl $crate::Writable for $ty {
+				#[inline]
 				fn write_to(
 					&self,
 					writer: &mut impl bytes::BufMut,
@@ -21,6 +22,8 @@ macro_rules! implement {
 					Ok(())
 				}
 			}
+
+			impl $crate::InfallibleWritable for $ty {}
 		)*
 	};
 }
@@ -54,6 +57,8 @@ impl<T: Writable> Writable for &[T] {
 	}
 }
 
+impl<T: InfallibleWritable> InfallibleWritable for &[T] {}
+
 impl<T: Writable, const N: usize> Writable for [T; N] {
 	fn write_to(&self, writer: &mut impl BufMut) -> WriteResult {
 		for x in self {
@@ -64,7 +69,25 @@ impl<T: Writable, const N: usize> Writable for [T; N] {
 	}
 }
 
+impl<T: InfallibleWritable, const N: usize> InfallibleWritable for [T; N] {}
+
 impl<T: Writable> Writable for Vec<T> {
+	default fn write_to(&self, writer: &mut impl BufMut) -> WriteResult {
+		for x in self {
+			x.write_to(writer)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl<T: InfallibleWritable> InfallibleWritable for Vec<T> {}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Writable for smallvec::SmallVec<A>
+where
+	A::Item: Writable,
+{
 	fn write_to(&self, writer: &mut impl BufMut) -> WriteResult {
 		for x in self {
 			x.write_to(writer)?;
@@ -74,6 +97,21 @@ impl<T: Writable> Writable for Vec<T> {
 	}
 }
 
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> InfallibleWritable for smallvec::SmallVec<A> where
+	A::Item: InfallibleWritable
+{
+}
+
+// Byte lists, such as image data, can be large, so they are copied in one go.
+impl Writable for Vec<u8> {
+	fn write_to(&self, writer: &mut impl BufMut) -> WriteResult {
+		writer.put_slice(self);
+
+		Ok(())
+	}
+}
+
 impl<T: Writable> Writable for &T {
 	fn write_to(&self, writer: &mut impl BufMut) -> WriteResult {
 		T::write_to(self, writer)?;
@@ -82,6 +120,8 @@ impl<T: Writable> Writable for &T {
 	}
 }
 
+impl<T: InfallibleWritable> InfallibleWritable for &T {}
+
 impl<T: Writable> Writable for &mut T {
 	fn write_to(&self, writer: &mut impl BufMut) -> WriteResult {
 		T::write_to(self, writer)?;
@@ -90,6 +130,8 @@ impl<T: Writable> Writable for &mut T {
 	}
 }
 
+impl<T: InfallibleWritable> InfallibleWritable for &mut T {}
+
 impl<T: Writable> Writable for Box<T> {
 	fn write_to(&self, writer: &mut impl BufMut) -> WriteResult {
 		T::write_to(self, writer)?;
@@ -97,3 +139,5 @@ impl<T: Writable> Writable for Box<T> {
 		Ok(())
 	}
 }
+
+impl<T: InfallibleWritable> InfallibleWritable for Box<T> {}
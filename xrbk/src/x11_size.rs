@@ -16,6 +16,7 @@ macro_rules! constant_x11_size {
 			}
 
 			impl X11Size for $type {
+				#[inline]
 				fn x11_size(&self) -> usize {
 					Self::X11_SIZE
 				}
@@ -49,6 +50,16 @@ impl<T: X11Size> X11Size for Vec<T> {
 	}
 }
 
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> X11Size for smallvec::SmallVec<A>
+where
+	A::Item: X11Size,
+{
+	fn x11_size(&self) -> usize {
+		self.iter().map(X11Size::x11_size).sum()
+	}
+}
+
 impl<T: X11Size, const N: usize> X11Size for [T; N] {
 	fn x11_size(&self) -> usize {
 		let mut data_size = 0;
@@ -61,6 +72,10 @@ impl<T: X11Size, const N: usize> X11Size for [T; N] {
 	}
 }
 
+impl<T: ConstantX11Size, const N: usize> ConstantX11Size for [T; N] {
+	const X11_SIZE: usize = T::X11_SIZE * N;
+}
+
 impl<T: X11Size> X11Size for &[T] {
 	fn x11_size(&self) -> usize {
 		let mut x11_size: usize = 0;
@@ -45,6 +45,7 @@ pub struct ParsedItemAttributes {
 	pub derive_x11_sizes: Punctuated<Path, Token![,]>,
 	pub derive_constant_x11_sizes: Punctuated<Path, Token![,]>,
 	pub derive_writables: Punctuated<Path, Token![,]>,
+	pub derive_infallible_writables: Punctuated<Path, Token![,]>,
 	pub derive_readables: Punctuated<Path, Token![,]>,
 	pub derive_readable_with_contexts: Punctuated<Path, Token![,]>,
 }
@@ -54,6 +55,7 @@ impl ParsedItemAttributes {
 		!self.derive_x11_sizes.is_empty()
 			|| !self.derive_constant_x11_sizes.is_empty()
 			|| !self.derive_writables.is_empty()
+			|| !self.derive_infallible_writables.is_empty()
 			|| !self.derive_readables.is_empty()
 			|| !self.derive_readable_with_contexts.is_empty()
 	}
@@ -244,6 +246,7 @@ impl Parse for ParsedItemAttributes {
 		let mut derive_x11_sizes = Punctuated::new();
 		let mut derive_constant_x11_sizes = Punctuated::new();
 		let mut derive_writables = Punctuated::new();
+		let mut derive_infallible_writables = Punctuated::new();
 		let mut derive_readables = Punctuated::new();
 		let mut derive_readable_with_contexts = Punctuated::new();
 
@@ -288,6 +291,12 @@ impl Parse for ParsedItemAttributes {
 						if let Some(comma) = comma {
 							derive_writables.push_punct(comma);
 						}
+					} else if path.is_ident("InfallibleWritable") {
+						derive_infallible_writables.push_value(path);
+
+						if let Some(comma) = comma {
+							derive_infallible_writables.push_punct(comma);
+						}
 					} else if path.is_ident("Readable") {
 						derive_readables.push_value(path);
 
@@ -343,6 +352,7 @@ impl Parse for ParsedItemAttributes {
 			derive_x11_sizes,
 			derive_constant_x11_sizes,
 			derive_writables,
+			derive_infallible_writables,
 			derive_readables,
 			derive_readable_with_contexts,
 		})
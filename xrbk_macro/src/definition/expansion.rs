@@ -2,8 +2,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+mod constant_x11_size;
+mod infallible_writable;
 mod message_trait;
 mod readable;
+mod trace;
 mod writable;
 mod x11_size;
 
@@ -34,12 +37,26 @@ impl ToTokens for Definition {
 					r#struct.impl_writable(tokens, path);
 				}
 
+				for path in &attrs.derive_infallible_writables {
+					r#struct.impl_infallible_writable(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					r#struct.impl_readable(tokens, path);
 				}
 
+				for path in &attrs.derive_constant_x11_sizes {
+					r#struct.impl_constant_x11_size(tokens, path);
+				}
+
+				// Types with a constant size use that size rather than
+				// calculating it at runtime.
 				for path in &attrs.derive_x11_sizes {
-					r#struct.impl_x11_size(tokens, path);
+					if attrs.derive_constant_x11_sizes.is_empty() {
+						r#struct.impl_x11_size(tokens, path);
+					} else {
+						r#struct.impl_x11_size_from_constant(tokens, path);
+					}
 				}
 			},
 
@@ -52,12 +69,26 @@ impl ToTokens for Definition {
 					r#enum.impl_writable(tokens, path);
 				}
 
+				for path in &attrs.derive_infallible_writables {
+					r#enum.impl_infallible_writable(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					r#enum.impl_readable(tokens, path);
 				}
 
+				for path in &attrs.derive_constant_x11_sizes {
+					r#enum.impl_constant_x11_size(tokens, path);
+				}
+
+				// Types with a constant size use that size rather than
+				// calculating it at runtime.
 				for path in &attrs.derive_x11_sizes {
-					r#enum.impl_x11_size(tokens, path);
+					if attrs.derive_constant_x11_sizes.is_empty() {
+						r#enum.impl_x11_size(tokens, path);
+					} else {
+						r#enum.impl_x11_size_from_constant(tokens, path);
+					}
 				}
 			},
 
@@ -71,6 +102,10 @@ impl ToTokens for Definition {
 					request.impl_writable(tokens, path);
 				}
 
+				for path in &attrs.derive_infallible_writables {
+					request.impl_infallible_writable(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					request.impl_readable(tokens, path);
 				}
@@ -90,6 +125,10 @@ impl ToTokens for Definition {
 					reply.impl_writable(tokens, path);
 				}
 
+				for path in &attrs.derive_infallible_writables {
+					reply.impl_infallible_writable(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					reply.impl_readable(tokens, path);
 				}
@@ -109,6 +148,10 @@ impl ToTokens for Definition {
 					event.impl_writable(tokens, path);
 				}
 
+				for path in &attrs.derive_infallible_writables {
+					event.impl_infallible_writable(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					event.impl_readable(tokens, path);
 				}
@@ -128,6 +171,10 @@ impl ToTokens for Definition {
 					error.impl_writable(tokens, path);
 				}
 
+				for path in &attrs.derive_infallible_writables {
+					error.impl_infallible_writable(tokens, path);
+				}
+
 				for path in &attrs.derive_readables {
 					error.impl_readable(tokens, path);
 				}
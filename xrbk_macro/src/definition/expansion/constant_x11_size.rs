@@ -0,0 +1,151 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote_spanned, ToTokens};
+use syn::{Generics, Ident, Path, WhereClause};
+
+use crate::TsExt;
+
+use super::*;
+
+/// Implements `X11Size` in terms of `ConstantX11Size`, so that the size of a
+/// definition with a constant size is not calculated at runtime.
+fn impl_x11_size_from_constant(
+	tokens: &mut TokenStream2, ident: &Ident, generics: &Generics,
+	where_clause: Option<&WhereClause>, trait_path: &Path,
+) {
+	// TODO: add generic bounds
+	let (impl_generics, type_generics, _) = generics.split_for_impl();
+
+	tokens.append_tokens(quote_spanned!(trait_path.span()=>
+		#[automatically_derived]
+		impl #impl_generics ::xrbk::X11Size for #ident #type_generics #where_clause {
+			#[inline]
+			fn x11_size(&self) -> usize {
+				<Self as ::xrbk::ConstantX11Size>::X11_SIZE
+			}
+		}
+	));
+}
+
+impl Struct {
+	pub fn impl_constant_x11_size(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let ident = &self.ident;
+
+		// TODO: add generic bounds
+		let (impl_generics, type_generics, _) = self.generics.split_for_impl();
+		let where_clause = match &self.content {
+			StructlikeContent::Regular { where_clause, .. } => where_clause,
+			StructlikeContent::Tuple { where_clause, .. } => where_clause,
+			StructlikeContent::Unit { where_clause, .. } => where_clause,
+		};
+
+		let sizes = TokenStream2::with_tokens(|tokens| {
+			for element in &self.content {
+				element.constant_x11_size_tokens(tokens);
+			}
+		});
+
+		tokens.append_tokens(quote_spanned!(trait_path.span()=>
+			#[automatically_derived]
+			impl #impl_generics ::xrbk::ConstantX11Size for #ident #type_generics #where_clause {
+				#[allow(clippy::identity_op, unused_mut)]
+				const X11_SIZE: usize = {
+					let mut size: usize = 0;
+
+					// Add the size of each element.
+					#sizes
+
+					// Return the cumulative size.
+					size
+				};
+			}
+		));
+	}
+
+	pub fn impl_x11_size_from_constant(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let where_clause = match &self.content {
+			StructlikeContent::Regular { where_clause, .. } => where_clause,
+			StructlikeContent::Tuple { where_clause, .. } => where_clause,
+			StructlikeContent::Unit { where_clause, .. } => where_clause,
+		};
+
+		impl_x11_size_from_constant(
+			tokens,
+			&self.ident,
+			&self.generics,
+			where_clause.as_ref(),
+			trait_path,
+		);
+	}
+}
+
+impl Enum {
+	pub fn impl_constant_x11_size(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		let ident = &self.ident;
+		let discrim_type = self.discriminant_type.as_ref().map_or_else(
+			|| quote_spanned!(trait_path.span()=> u8),
+			|(_, r#type)| r#type.to_token_stream(),
+		);
+
+		// TODO: add generic bounds
+		let (impl_generics, type_generics, _) = self.generics.split_for_impl();
+		let where_clause = &self.where_clause;
+
+		// The size of each variant, which must all be the same.
+		let sizes = TokenStream2::with_tokens(|tokens| {
+			for variant in &self.variants {
+				let sizes = TokenStream2::with_tokens(|tokens| {
+					for element in &variant.content {
+						element.constant_x11_size_tokens(tokens);
+					}
+				});
+
+				tokens.append_tokens(quote_spanned!(variant.ident.span()=>
+					{
+						let mut size: usize = 0;
+
+						#sizes
+
+						size
+					},
+				));
+			}
+		});
+
+		tokens.append_tokens(quote_spanned!(trait_path.span()=>
+			#[automatically_derived]
+			impl #impl_generics ::xrbk::ConstantX11Size for #ident #type_generics #where_clause {
+				#[allow(clippy::identity_op, unused_mut)]
+				const X11_SIZE: usize = {
+					let sizes = [#sizes];
+
+					let mut i = 1;
+					while i < sizes.len() {
+						assert!(
+							sizes[i] == sizes[0],
+							"every variant must be the same size to implement `ConstantX11Size`",
+						);
+
+						i += 1;
+					}
+
+					<#discrim_type as ::xrbk::ConstantX11Size>::X11_SIZE
+						+ if sizes.is_empty() { 0 } else { sizes[0] }
+				};
+			}
+		));
+	}
+
+	pub fn impl_x11_size_from_constant(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		impl_x11_size_from_constant(
+			tokens,
+			&self.ident,
+			&self.generics,
+			self.where_clause.as_ref(),
+			trait_path,
+		);
+	}
+}
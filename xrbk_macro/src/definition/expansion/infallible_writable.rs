@@ -0,0 +1,111 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote_spanned, ToTokens};
+use syn::{parse_quote_spanned, Generics, Ident, Path, Type, WhereClause, WherePredicate};
+
+use crate::{element::Element, TsExt};
+
+use super::*;
+
+/// Implements `InfallibleWritable` for a definition, bounded by every written
+/// element's type implementing `InfallibleWritable`.
+fn impl_infallible_writable<'a>(
+	tokens: &mut TokenStream2, ident: &Ident, generics: &Generics,
+	where_clause: Option<&WhereClause>, elements: impl Iterator<Item = &'a Element>,
+	trait_path: &Path,
+) {
+	let mut generics = generics.clone();
+	generics.where_clause = where_clause.cloned();
+
+	let mut predicates: Vec<WherePredicate> = Vec::new();
+	// Each type is only bounded once, however many elements share it.
+	let mut bounded = Vec::new();
+
+	for element in elements {
+		let r#type = match element {
+			Element::Field(field) if !field.is_ignoring_trait("Writable") => &field.r#type,
+			Element::Let(r#let) => &r#let.r#type,
+
+			_ => continue,
+		};
+
+		let type_string = r#type.to_token_stream().to_string();
+
+		if bounded.contains(&type_string) {
+			continue;
+		}
+		bounded.push(type_string);
+
+		// Generic parameters are bounded where they are declared.
+		let param = generics.type_params_mut().find(|param| match r#type {
+			Type::Path(path) => path.qself.is_none() && path.path.is_ident(&param.ident),
+			_ => false,
+		});
+
+		if let Some(param) = param {
+			param.bounds.push(parse_quote_spanned!(trait_path.span()=>
+				::xrbk::InfallibleWritable
+			));
+
+			continue;
+		}
+
+		predicates.push(parse_quote_spanned!(trait_path.span()=>
+			#r#type: ::xrbk::InfallibleWritable
+		));
+	}
+
+	generics.make_where_clause().predicates.extend(predicates);
+
+	let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+	tokens.append_tokens(quote_spanned!(trait_path.span()=>
+		#[automatically_derived]
+		impl #impl_generics ::xrbk::InfallibleWritable for #ident #type_generics #where_clause {}
+	));
+}
+
+macro_rules! structlike_impl_infallible_writable {
+	($($Struct:ty),+) => {
+		$(
+			impl $Struct {
+				pub fn impl_infallible_writable(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+					let where_clause = match &self.content {
+						StructlikeContent::Regular { where_clause, .. } => where_clause,
+						StructlikeContent::Tuple { where_clause, .. } => where_clause,
+						StructlikeContent::Unit { where_clause, .. } => where_clause,
+					};
+
+					impl_infallible_writable(
+						tokens,
+						&self.ident,
+						&self.generics,
+						where_clause.as_ref(),
+						(&self.content).into_iter(),
+						trait_path,
+					);
+				}
+			}
+		)+
+	};
+}
+
+structlike_impl_infallible_writable!(Struct, Request, Reply, Event, Error);
+
+impl Enum {
+	pub fn impl_infallible_writable(&self, tokens: &mut TokenStream2, trait_path: &Path) {
+		impl_infallible_writable(
+			tokens,
+			&self.ident,
+			&self.generics,
+			self.where_clause.as_ref(),
+			self.variants
+				.iter()
+				.flat_map(|variant| &variant.content),
+			trait_path,
+		);
+	}
+}
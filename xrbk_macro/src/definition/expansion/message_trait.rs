@@ -4,11 +4,127 @@
 
 use crate::element::Element;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, quote_spanned};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{Generics, Ident};
 
 use super::*;
 use crate::TsExt;
 
+/// Generates the body of `Message::unused` for a message with the given
+/// `content`.
+///
+/// The elements of `content` which are not in the header begin at
+/// `header_size`, and `header_unused` lists the ranges of unused bytes within
+/// the header. Messages with a minimum length are padded to it, so any bytes
+/// between the last element and the minimum length are unused too.
+fn unused_body(
+	content: &StructlikeContent, definition_type: DefinitionType, header_size: usize,
+	header_unused: &[(usize, usize)],
+) -> TokenStream2 {
+	let pat = TokenStream2::with_tokens(|tokens| {
+		content.pat_cons_to_tokens(tokens);
+	});
+
+	let header_unused = header_unused
+		.iter()
+		.map(|(start, end)| quote!(unused.push(#start..#end);));
+
+	let elements = TokenStream2::with_tokens(|tokens| {
+		for element in content {
+			if element.is_normal() {
+				element.unused_tokens(tokens, definition_type);
+			} else if let Element::Let(r#let) = element && element.is_metabyte() {
+				r#let.function_call_tokens(tokens);
+			}
+		}
+	});
+
+	let padding = definition_type.min_length().map(|min_length| {
+		quote!(
+			if size < #min_length {
+				unused.push(size..#min_length);
+			}
+		)
+	});
+
+	quote!(
+		let mut unused = ::std::vec::Vec::new();
+		let mut size: usize = #header_size;
+		// Destructure the message's fields, if any.
+		let Self #pat = self;
+
+		#(#header_unused)*
+		#elements
+		#padding
+
+		unused
+	)
+}
+
+/// Whether the metabyte position of a message with the given `content` is
+/// unused.
+fn metabyte_unused(content: &StructlikeContent) -> bool {
+	matches!(
+		content.metabyte_element(),
+		None | Some(Element::SingleUnused(_))
+	)
+}
+
+/// Generates an implementation of `Message` for the message type `name`,
+/// where `kind` evaluates to its `MessageKind`.
+///
+/// The fields listed by `Message::fields` are the fields of `content`, in the
+/// order in which they are defined, and `unused` is the body of
+/// `Message::unused`.
+fn impl_message(
+	name: &Ident, generics: &Generics, where_clause: &impl ToTokens, content: &StructlikeContent,
+	kind: &TokenStream2, unused: &TokenStream2, tokens: &mut TokenStream2,
+) {
+	let (impl_generics, type_generics, _) = generics.split_for_impl();
+
+	let fields = TokenStream2::with_tokens(|tokens| {
+		for element in content {
+			if let Element::Field(field) = element {
+				let id = &field.id;
+				let field_name = id.to_string();
+
+				tokens.append_tokens(quote!(
+					(#field_name, &self.#id as &dyn ::core::fmt::Debug),
+				));
+			}
+		}
+	});
+
+	tokens.append_tokens(quote!(
+		#[automatically_derived]
+		impl #impl_generics xrb::message::Message for #name #type_generics #where_clause {
+			fn name(&self) -> &'static str {
+				::core::stringify!(#name)
+			}
+
+			fn kind(&self) -> xrb::message::MessageKind {
+				#kind
+			}
+
+			fn fields(&self) -> ::std::vec::Vec<(&'static str, &dyn ::core::fmt::Debug)> {
+				::std::vec![#fields]
+			}
+
+			#[allow(
+				clippy::items_after_statements,
+				clippy::trivially_copy_pass_by_ref,
+				clippy::needless_borrow,
+				clippy::identity_op,
+				unused_mut,
+				unused_variables,
+			)]
+			fn unused(&self) -> ::std::vec::Vec<::core::ops::Range<usize>> {
+				#unused
+			}
+		}
+	));
+}
+
 impl Request {
 	pub fn impl_trait(&self, tokens: &mut TokenStream2) {
 		let name = &self.ident;
@@ -62,6 +178,24 @@ impl Request {
 				}
 			)
 		});
+
+		// If there is a minor opcode, it is in the metabyte position.
+		let header_unused: &[_] = if self.minor_opcode.is_none() && metabyte_unused(&self.content) {
+			&[(1, 2)]
+		} else {
+			&[]
+		};
+		let unused = unused_body(&self.content, DefinitionType::Request, 4, header_unused);
+
+		impl_message(
+			name,
+			&self.generics,
+			where_clause,
+			&self.content,
+			&quote!(xrb::message::MessageKind::of_request::<Self>()),
+			&unused,
+			tokens,
+		);
 	}
 }
 
@@ -110,6 +244,23 @@ impl Reply {
 				}
 			)
 		});
+
+		let header_unused: &[_] = if metabyte_unused(&self.content) {
+			&[(1, 2)]
+		} else {
+			&[]
+		};
+		let unused = unused_body(&self.content, DefinitionType::Reply, 8, header_unused);
+
+		impl_message(
+			name,
+			&self.generics,
+			where_clause,
+			&self.content,
+			&quote!(xrb::message::MessageKind::of_reply::<Self>()),
+			&unused,
+			tokens,
+		);
 	}
 }
 
@@ -161,6 +312,27 @@ impl Event {
 				}
 			)
 		});
+
+		// Events without a sequence field have no metabyte position either.
+		let unused = if self.content.sequence_element().is_none() {
+			unused_body(&self.content, DefinitionType::Event, 1, &[])
+		} else if metabyte_unused(&self.content) {
+			unused_body(&self.content, DefinitionType::Event, 4, &[(1, 2)])
+		} else {
+			unused_body(&self.content, DefinitionType::Event, 4, &[])
+		};
+
+		impl_message(
+			name,
+			&self.generics,
+			where_clause,
+			&self.content,
+			&quote!(xrb::message::MessageKind::Event {
+				code: <Self as xrb::message::Event>::CODE,
+			}),
+			&unused,
+			tokens,
+		);
 	}
 }
 
@@ -238,6 +410,25 @@ impl Error {
 			_ => panic!("expected a major opcode field"),
 		};
 
+		// The error data is always four bytes on the wire, so it is written
+		// into a `[u8; 4]` to be read as a `u32`.
+		let bad_value = match self.content.error_data_element() {
+			Some(Element::Field(field)) => {
+				let id = &field.id;
+
+				Some(quote_spanned!(error_path.span()=>
+					fn bad_value(&self) -> Option<u32> {
+						let mut bytes = [0; 4];
+						<_ as ::xrbk::Writable>::write_to(&self.#id, &mut &mut bytes[..]).ok()?;
+
+						Some(u32::from_be_bytes(bytes))
+					}
+				))
+			},
+
+			_ => None,
+		};
+
 		tokens.append_tokens({
 			quote_spanned!(error_path.span()=>
 				#[automatically_derived]
@@ -257,8 +448,30 @@ impl Error {
 					fn major_opcode(&self) -> u8 {
 						#major_opcode
 					}
+
+					#bad_value
 				}
 			)
 		});
+
+		// The error data is unused if the error has no error data field.
+		let header_unused: &[_] = if self.content.error_data_element().is_none() {
+			&[(4, 8)]
+		} else {
+			&[]
+		};
+		let unused = unused_body(&self.content, DefinitionType::Error, 11, header_unused);
+
+		impl_message(
+			name,
+			&self.generics,
+			where_clause,
+			&self.content,
+			&quote!(xrb::message::MessageKind::Error {
+				code: <Self as xrb::message::Error>::CODE,
+			}),
+			&unused,
+			tokens,
+		);
 	}
 }
@@ -104,7 +104,10 @@ impl Request {
 				element.read_tokens(tokens, DefinitionType::Request);
 			}))
 		} else {
-			Some(quote_spanned!(trait_path.span()=> <_ as ::xrbk::Buf>::advance(buf, 1);))
+			Some(quote_spanned!(trait_path.span()=>
+				::xrbk::check_remaining(buf, 1)?;
+				<_ as ::xrbk::Buf>::advance(buf, 1);
+			))
 		};
 
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
@@ -128,7 +131,14 @@ impl Request {
 					// read.
 					#metabyte
 					// Read the request's length.
-					let length = <_ as ::xrbk::Buf>::get_u16(buf);
+					let length = <u16 as ::xrbk::Readable>::read_from(buf)?;
+					// The length includes the 4-byte header, so it must be at
+					// least 1.
+					if length == 0 {
+						return Err(::xrbk::ReadError::Other(::std::boxed::Box::new(
+							"the request's length is zero",
+						)));
+					}
 					let buf = &mut <_ as ::xrbk::Buf>::take(
 						buf,
 						((length - 1) as usize) * 4,
@@ -178,7 +188,10 @@ impl Reply {
 				element.read_tokens(tokens, DefinitionType::Reply);
 			})
 		} else {
-			quote_spanned!(trait_path.span()=> <_ as ::xrbk::Buf>::advance(buf, 1);)
+			quote_spanned!(trait_path.span()=>
+				::xrbk::check_remaining(buf, 1)?;
+				<_ as ::xrbk::Buf>::advance(buf, 1);
+			)
 		};
 
 		let sequence = match self.content.sequence_element() {
@@ -186,6 +199,19 @@ impl Reply {
 			_ => panic!("replies must have a sequence field"),
 		};
 
+		// Construct Self and, if tracing is enabled, trace it before
+		// returning it.
+		let ret = match self.trace_tokens() {
+			Some(trace) => quote_spanned!(trait_path.span()=>
+				let message = Self #cons;
+				#trace
+
+				Ok(message)
+			),
+
+			None => quote_spanned!(trait_path.span()=> Ok(Self #cons)),
+		};
+
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
@@ -204,9 +230,9 @@ impl Reply {
 					// Metabyte position
 					#metabyte
 					// Sequence field
-					let #sequence = <_ as ::xrbk::Buf>::get_u16(buf);
+					let #sequence = <u16 as ::xrbk::Readable>::read_from(buf)?;
 					// Length
-					let length = <_ as ::xrbk::Buf>::get_u32(buf);
+					let length = <u32 as ::xrbk::Readable>::read_from(buf)?;
 					let buf = &mut <_ as ::xrbk::Buf>::take(
 						buf,
 						(((length) as usize) * 4) + (32 - 8),
@@ -215,8 +241,7 @@ impl Reply {
 					// Other elements
 					#reads
 
-					// Construct and return Self.
-					Ok(Self #cons)
+					#ret
 				}
 			}
 		));
@@ -265,6 +290,7 @@ impl Event {
 			}))
 		} else {
 			Some(quote_spanned!(trait_path.span()=>
+				::xrbk::check_remaining(buf, 1)?;
 				<_ as ::xrbk::Buf>::advance(buf, 1);
 			))
 		};
@@ -273,12 +299,25 @@ impl Event {
 			let formatted = &field.formatted;
 
 			Some(quote_spanned!(trait_path.span()=>
-				let #formatted = <_ as ::xrbk::Buf>::get_u16(buf);
+				let #formatted = <u16 as ::xrbk::Readable>::read_from(buf)?;
 			))
 		} else {
 			None
 		};
 
+		// Construct Self and, if tracing is enabled, trace it before
+		// returning it.
+		let ret = match self.trace_tokens() {
+			Some(trace) => quote_spanned!(trait_path.span()=>
+				let message = Self #cons;
+				#trace
+
+				Ok(message)
+			),
+
+			None => quote_spanned!(trait_path.span()=> Ok(Self #cons)),
+		};
+
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Readable for #ident #type_generics #where_clause {
@@ -302,8 +341,7 @@ impl Event {
 					// Other elements
 					#reads
 
-					// Construct and return Self.
-					Ok(Self #cons)
+					#ret
 				}
 			}
 		));
@@ -342,7 +380,7 @@ impl Error {
 				let formatted = &field.formatted;
 
 				quote_spanned!(trait_path.span()=>
-					let #formatted = <_ as ::xrbk::Buf>::get_u16(buf);
+					let #formatted = <u16 as ::xrbk::Readable>::read_from(buf)?;
 				)
 			},
 
@@ -354,7 +392,7 @@ impl Error {
 				let formatted = &field.formatted;
 
 				quote_spanned!(trait_path.span()=>
-					let #formatted = <_ as ::xrbk::Buf>::get_u16(buf);
+					let #formatted = <u16 as ::xrbk::Readable>::read_from(buf)?;
 				)
 			},
 
@@ -366,7 +404,7 @@ impl Error {
 				let formatted = &field.formatted;
 
 				quote_spanned!(trait_path.span()=>
-					let #formatted = <_ as ::xrbk::Buf>::get_u8(buf);
+					let #formatted = <u8 as ::xrbk::Readable>::read_from(buf)?;
 				)
 			},
 
@@ -378,7 +416,23 @@ impl Error {
 				TokenStream2::with_tokens(|tokens| field.read_tokens(tokens))
 			},
 
-			_ => quote_spanned!(trait_path.span()=> <_ as ::xrbk::Buf>::advance(buf, 4);),
+			_ => quote_spanned!(trait_path.span()=>
+				::xrbk::check_remaining(buf, 4)?;
+				<_ as ::xrbk::Buf>::advance(buf, 4);
+			),
+		};
+
+		// Construct Self and, if tracing is enabled, trace it before
+		// returning it.
+		let ret = match self.trace_tokens() {
+			Some(trace) => quote_spanned!(trait_path.span()=>
+				let message = Self #cons;
+				#trace
+
+				Ok(message)
+			),
+
+			None => quote_spanned!(trait_path.span()=> Ok(Self #cons)),
 		};
 
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
@@ -410,7 +464,7 @@ impl Error {
 
 					#reads
 
-					Ok(Self #cons)
+					#ret
 				}
 			}
 		));
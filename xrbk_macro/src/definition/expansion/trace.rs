@@ -0,0 +1,123 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Generation of the `tracing` events emitted when messages are serialized and
+//! deserialized.
+//!
+//! These are only generated with the `tracing` feature enabled; otherwise,
+//! every method here returns `None`.
+
+use proc_macro2::TokenStream as TokenStream2;
+#[cfg(feature = "tracing")]
+use quote::quote_spanned;
+#[cfg(feature = "tracing")]
+use syn::spanned::Spanned;
+
+use super::*;
+
+impl Request {
+	/// Generates a `tracing` event for a request which has just been written.
+	#[cfg(feature = "tracing")]
+	pub fn trace_tokens(&self) -> Option<TokenStream2> {
+		let ident = &self.ident;
+
+		Some(quote_spanned!(self.request_token.span()=>
+			::xrbk::tracing::trace!(
+				target: "xrb::request",
+				name = ::core::stringify!(#ident),
+				major_opcode = <Self as xrb::message::Request>::MAJOR_OPCODE,
+				minor_opcode = ?<Self as xrb::message::Request>::MINOR_OPCODE,
+				length = <Self as ::xrbk::X11Size>::x11_size(self),
+				"serialized request",
+			);
+		))
+	}
+
+	#[cfg(not(feature = "tracing"))]
+	pub fn trace_tokens(&self) -> Option<TokenStream2> {
+		None
+	}
+}
+
+impl Reply {
+	/// Generates a `tracing` event for a reply, named `message`, which has just
+	/// been read.
+	#[cfg(feature = "tracing")]
+	pub fn trace_tokens(&self) -> Option<TokenStream2> {
+		let ident = &self.ident;
+		let reply_token = &self.reply_token;
+
+		Some(quote_spanned!(self.reply_token.span()=>
+			::xrbk::tracing::trace!(
+				target: "xrb::reply",
+				name = ::core::stringify!(#ident),
+				major_opcode = <
+					<Self as #reply_token>::Request as xrb::message::Request
+				>::MAJOR_OPCODE,
+				sequence = <Self as #reply_token>::sequence(&message),
+				length = <Self as ::xrbk::X11Size>::x11_size(&message),
+				"deserialized reply",
+			);
+		))
+	}
+
+	#[cfg(not(feature = "tracing"))]
+	pub fn trace_tokens(&self) -> Option<TokenStream2> {
+		None
+	}
+}
+
+impl Event {
+	/// Generates a `tracing` event for an event, named `message`, which has
+	/// just been read.
+	#[cfg(feature = "tracing")]
+	pub fn trace_tokens(&self) -> Option<TokenStream2> {
+		let ident = &self.ident;
+		let event_token = &self.event_token;
+
+		Some(quote_spanned!(self.event_token.span()=>
+			::xrbk::tracing::trace!(
+				target: "xrb::event",
+				name = ::core::stringify!(#ident),
+				code = <Self as #event_token>::CODE,
+				sequence = ?<Self as #event_token>::sequence(&message),
+				length = <Self as ::xrbk::X11Size>::x11_size(&message),
+				"deserialized event",
+			);
+		))
+	}
+
+	#[cfg(not(feature = "tracing"))]
+	pub fn trace_tokens(&self) -> Option<TokenStream2> {
+		None
+	}
+}
+
+impl Error {
+	/// Generates a `tracing` event for an error, named `message`, which has
+	/// just been read.
+	#[cfg(feature = "tracing")]
+	pub fn trace_tokens(&self) -> Option<TokenStream2> {
+		let ident = &self.ident;
+		let error_token = &self.error_token;
+
+		Some(quote_spanned!(self.error_token.span()=>
+			::xrbk::tracing::trace!(
+				target: "xrb::error",
+				name = ::core::stringify!(#ident),
+				code = <Self as #error_token>::CODE,
+				sequence = <Self as #error_token>::sequence(&message),
+				major_opcode = <Self as #error_token>::major_opcode(&message),
+				minor_opcode = <Self as #error_token>::minor_opcode(&message),
+				length = <Self as ::xrbk::X11Size>::x11_size(&message),
+				"deserialized error",
+			);
+		))
+	}
+
+	#[cfg(not(feature = "tracing"))]
+	pub fn trace_tokens(&self) -> Option<TokenStream2> {
+		None
+	}
+}
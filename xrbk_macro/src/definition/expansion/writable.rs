@@ -111,6 +111,8 @@ impl Request {
 			)
 		};
 
+		let trace = self.trace_tokens();
+
 		tokens.append_tokens(quote_spanned!(trait_path.span()=>
 			#[automatically_derived]
 			impl #impl_generics ::xrbk::Writable for #ident #type_generics #where_clause {
@@ -145,6 +147,8 @@ impl Request {
 					// Other elements
 					#writes
 
+					#trace
+
 					Ok(())
 				}
 			}
@@ -4,7 +4,17 @@
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote, ToTokens};
-use syn::{punctuated::Pair, Attribute, Data, Fields, FieldsNamed, FieldsUnnamed, Index, Type};
+use syn::{
+	punctuated::Pair,
+	Attribute,
+	Data,
+	Fields,
+	FieldsNamed,
+	FieldsUnnamed,
+	Index,
+	Type,
+	Variant,
+};
 
 use crate::TsExt;
 
@@ -140,6 +150,51 @@ pub fn unwrap_return(fields: &Fields) -> TokenStream2 {
 	})
 }
 
+/// Whether the given `variant` is the catch-all variant for unrecognized
+/// discriminants, marked with `#[unknown]`.
+///
+/// See [`check_unknown`] for the requirements of the `#[unknown]` variant.
+pub fn is_unknown(variant: &Variant) -> bool {
+	variant.attrs.iter().any(|attr| attr.path.is_ident("unknown"))
+}
+
+/// Checks that the `#[unknown]` variant of an enum, if there is one, is its
+/// last variant and has a single unnamed field, which holds the unrecognized
+/// discriminant.
+///
+/// The `#[unknown]` variant has no discriminant of its own, so the
+/// discriminants of any variants following it would not match those which
+/// Rust gives them.
+pub fn check_unknown(data: &Data) -> syn::Result<()> {
+	let Data::Enum(r#enum) = data else {
+		return Ok(());
+	};
+
+	let last = r#enum.variants.len().saturating_sub(1);
+
+	for (i, variant) in r#enum.variants.iter().enumerate() {
+		let Some(attr) = variant.attrs.iter().find(|attr| attr.path.is_ident("unknown")) else {
+			continue;
+		};
+
+		if i != last {
+			return Err(syn::Error::new_spanned(
+				attr,
+				"the #[unknown] variant must be the last variant",
+			));
+		}
+
+		if !matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1) {
+			return Err(syn::Error::new_spanned(
+				&variant.fields,
+				"the #[unknown] variant must have a single unnamed field for the discriminant",
+			));
+		}
+	}
+
+	Ok(())
+}
+
 pub fn integer_type(data: &Data) -> &Type {
 	match data {
 		Data::Struct(data) => {
@@ -231,6 +286,18 @@ pub fn derive_writes(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 			let arms = r#enum.variants.iter().map(|variant| {
 				let ident = &variant.ident;
 
+				if is_unknown(variant) {
+					assert!(!no_discrim, "cannot use #[unknown] with #[no_discrim]");
+
+					// The unrecognized discriminant is written in place of the
+					// discriminant.
+					return quote!(
+						Self::#ident(field0) => {
+							buf.put_u8(*field0);
+						},
+					);
+				}
+
 				if !no_discrim && let Some((_, expr)) = &variant.discriminant {
 					discrim = quote!((#expr));
 				}
@@ -340,7 +407,10 @@ pub fn derive_reads(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 		Data::Enum(r#enum) => {
 			let mut discrim = quote!(0);
 
-			let arms = r#enum.variants.iter().map(|variant| {
+			// The `#[unknown]` variant has no discriminant of its own.
+			let variants = r#enum.variants.iter().filter(|variant| !is_unknown(variant));
+
+			let arms = variants.map(|variant| {
 				let ident = &variant.ident;
 
 				let cons = pat_cons(&variant.fields);
@@ -363,13 +433,27 @@ pub fn derive_reads(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 				arm
 			});
 
-			quote!(
-				match buf.get_u8() {
-					#(#arms)*
+			// Unrecognized discriminants are read as the `#[unknown]` variant,
+			// if there is one.
+			let other_arm = match r#enum.variants.iter().find(|variant| is_unknown(variant)) {
+				Some(variant) => {
+					let ident = &variant.ident;
 
+					quote!(other_discrim => Ok(Self::#ident(other_discrim)),)
+				},
+
+				None => quote!(
 					other_discrim => Err(
 						::xrbk::ReadError::UnrecognizedDiscriminant(other_discrim as usize),
 					),
+				),
+			};
+
+			quote!(
+				match <u8 as ::xrbk::Readable>::read_from(buf)? {
+					#(#arms)*
+
+					#other_arm
 				}
 			)
 		},
@@ -443,6 +527,13 @@ pub fn derive_x11_sizes(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 			let arms = r#enum.variants.iter().map(|variant| {
 				let ident = &variant.ident;
 
+				// The `#[unknown]` variant is written as just its discriminant.
+				if is_unknown(variant) {
+					return quote!(
+						Self::#ident(_) => 1,
+					);
+				}
+
 				let pat = pat_cons(&variant.fields);
 				let sizes = derive_for_fields(&variant.fields);
 
@@ -477,7 +568,7 @@ pub fn derive_x11_sizes(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 	}
 }
 
-pub fn derive_constant_x11_sizes(_attributes: &[Attribute], data: &Data) -> TokenStream2 {
+pub fn derive_constant_x11_sizes(attributes: &[Attribute], data: &Data) -> TokenStream2 {
 	fn derive_for_fields(fields: &Fields) -> TokenStream2 {
 		TokenStream2::with_tokens(|tokens| match fields {
 			Fields::Named(FieldsNamed { named: fields, .. })
@@ -512,7 +603,58 @@ pub fn derive_constant_x11_sizes(_attributes: &[Attribute], data: &Data) -> Toke
 			)
 		},
 
+		// Enums without any fields are always the size of their discriminant.
+		Data::Enum(r#enum)
+			if r#enum
+				.variants
+				.iter()
+				.all(|variant| matches!(variant.fields, Fields::Unit) || is_unknown(variant)) =>
+		{
+			let no_discrim = attributes
+				.iter()
+				.any(|attribute| attribute.path.is_ident("no_discrim"));
+
+			if no_discrim {
+				quote!(0)
+			} else {
+				quote!(1)
+			}
+		},
+
 		// TODO: derive for enums if all variants are the same constant size
 		Data::Enum(_) | Data::Union(_) => unimplemented!(),
 	}
 }
+
+/// The types of the fields which are written when writing a struct or enum,
+/// each listed once.
+///
+/// These are used to bound the derived `InfallibleWritable` implementation, so
+/// that it is only implemented if every written field is `InfallibleWritable`.
+pub fn written_field_types(data: &Data) -> Vec<&Type> {
+	fn for_fields<'a>(fields: &'a Fields, types: &mut Vec<&'a Type>) {
+		for field in fields {
+			let hidden = field.attrs.iter().any(|attr| attr.path.is_ident("hide"));
+
+			if !hidden && !types.contains(&&field.ty) {
+				types.push(&field.ty);
+			}
+		}
+	}
+
+	let mut types = Vec::new();
+
+	match data {
+		Data::Struct(r#struct) => for_fields(&r#struct.fields, &mut types),
+
+		Data::Enum(r#enum) => {
+			for variant in &r#enum.variants {
+				for_fields(&variant.fields, &mut types);
+			}
+		},
+
+		Data::Union(_) => unimplemented!(),
+	}
+
+	types
+}
@@ -38,6 +38,42 @@ impl Element {
 		}
 	}
 
+	/// Adds the size of this element to `size`, first pushing the range of
+	/// bytes it occupies to `unused` if it is an unused bytes element.
+	pub fn unused_tokens(&self, tokens: &mut TokenStream2, definition_type: DefinitionType) {
+		match self {
+			Self::Field(_) | Self::Let(_) => self.x11_size_tokens(tokens, definition_type),
+
+			Self::SingleUnused(unused) => unused.unused_tokens(tokens),
+			Self::ArrayUnused(unused) => unused.unused_tokens(tokens, definition_type),
+		}
+	}
+
+	/// Adds the constant size of this element to `size` in a const context.
+	pub fn constant_x11_size_tokens(&self, tokens: &mut TokenStream2) {
+		match self {
+			Self::Field(field) => {
+				if !field.is_ignoring_trait("X11Size") {
+					let r#type = &field.r#type;
+
+					tokens.append_tokens(quote_spanned!(field.span()=>
+						size += <#r#type as ::xrbk::ConstantX11Size>::X11_SIZE;
+					));
+				}
+			},
+			Self::Let(r#let) => {
+				let r#type = &r#let.r#type;
+
+				tokens.append_tokens(quote_spanned!(r#let.span()=>
+					size += <#r#type as ::xrbk::ConstantX11Size>::X11_SIZE;
+				));
+			},
+
+			Self::SingleUnused(unused) => unused.add_x11_size_tokens(tokens),
+			Self::ArrayUnused(unused) => unused.constant_x11_size_tokens(tokens),
+		}
+	}
+
 	pub fn read_tokens(&self, tokens: &mut TokenStream2, definition_type: DefinitionType) {
 		match self {
 			Self::Field(field) => {
@@ -93,6 +129,17 @@ impl Field {
 		let formatted = &self.formatted;
 		let r#type = &self.r#type;
 
+		let field = self.id.to_string();
+		// Metabyte fields are read before `size` is updated past the header.
+		let offset = if self.is_metabyte() {
+			quote_spanned!(self.span()=> 1)
+		} else {
+			quote_spanned!(self.span()=> size)
+		};
+		let with_context = quote_spanned!(self.span()=>
+			.map_err(|error| error.in_field(::core::any::type_name::<Self>(), #field, #offset))
+		);
+
 		match &self.context_attribute {
 			Some(ContextAttribute { context, .. }) => {
 				context.source().function_to_tokens(
@@ -117,7 +164,7 @@ impl Field {
 						let #formatted = #r#type::read_with(
 							buf,
 							&#function_call,
-						)?;
+						)#with_context?;
 					)
 				});
 			},
@@ -129,7 +176,7 @@ impl Field {
 					);
 
 					quote_spanned!(self.span()=>
-						let #formatted = #r#type::read_from(buf)?;
+						let #formatted = #r#type::read_from(buf)#with_context?;
 					)
 				});
 			},
@@ -196,6 +243,17 @@ impl Let {
 		let formatted = &self.formatted;
 		let r#type = &self.r#type;
 
+		let field = self.ident.to_string();
+		// Metabyte elements are read before `size` is updated past the header.
+		let offset = if self.is_metabyte() {
+			quote_spanned!(self.span()=> 1)
+		} else {
+			quote_spanned!(self.span()=> size)
+		};
+		let with_context = quote_spanned!(self.span()=>
+			.map_err(|error| error.in_field(::core::any::type_name::<Self>(), #field, #offset))
+		);
+
 		match &self.context_attribute {
 			Some(ContextAttribute { context, .. }) => {
 				context.source().function_to_tokens(
@@ -220,7 +278,7 @@ impl Let {
 						let #formatted = #r#type::read_with(
 							buf,
 							#function_call,
-						)?;
+						)#with_context?;
 					)
 				});
 			},
@@ -232,7 +290,7 @@ impl Let {
 					);
 
 					quote_spanned!(self.span()=>
-						let #formatted = #r#type::read_from(buf)?;
+						let #formatted = #r#type::read_from(buf)#with_context?;
 					)
 				});
 			},
@@ -269,11 +327,22 @@ impl SingleUnused {
 	pub fn read_tokens(&self, tokens: &mut TokenStream2) {
 		tokens.append_tokens({
 			quote_spanned!(self.span()=>
+				::xrbk::check_remaining(buf, 1)?;
 				<_ as ::xrbk::Buf>::advance(buf, 1);
 			)
 		});
 	}
 
+	pub fn unused_tokens(&self, tokens: &mut TokenStream2) {
+		tokens.append_tokens({
+			quote_spanned!(self.span()=>
+				unused.push(size..(size + 1));
+			)
+		});
+
+		self.add_x11_size_tokens(tokens);
+	}
+
 	pub fn add_x11_size_tokens(&self, tokens: &mut TokenStream2) {
 		tokens.append_tokens({
 			quote_spanned!(self.span()=>
@@ -349,11 +418,28 @@ impl ArrayUnused {
 
 		tokens.append_tokens({
 			quote_spanned!(self.span()=>
+				::xrbk::check_remaining(buf, #formatted)?;
 				<_ as ::xrbk::Buf>::advance(buf, #formatted);
 			)
 		})
 	}
 
+	pub fn unused_tokens(&self, tokens: &mut TokenStream2, definition_type: DefinitionType) {
+		let formatted = &self.formatted;
+
+		self.r#impl(tokens, definition_type);
+
+		tokens.append_tokens({
+			quote_spanned!(self.span()=>
+				if #formatted > 0 {
+					unused.push(size..(size + #formatted));
+				}
+			)
+		});
+
+		self.add_x11_size_tokens(tokens);
+	}
+
 	pub fn add_x11_size_tokens(&self, tokens: &mut TokenStream2) {
 		let formatted = &self.formatted;
 
@@ -363,6 +449,32 @@ impl ArrayUnused {
 			)
 		});
 	}
+
+	/// Adds the number of unused bytes to `size` in a const context.
+	///
+	/// This is only possible if the number of unused bytes does not depend on
+	/// any other elements.
+	pub fn constant_x11_size_tokens(&self, tokens: &mut TokenStream2) {
+		tokens.append_tokens(match &self.content {
+			UnusedContent::Infer { .. } => quote_spanned!(self.span()=>
+				size += (4 - (size % 4)) % 4;
+			),
+
+			UnusedContent::Source(source) if source.args.is_none() => {
+				let expr = &source.expr;
+
+				quote_spanned!(self.span()=>
+					size += #expr;
+				)
+			},
+
+			UnusedContent::Source(_) => quote_spanned!(self.span()=>
+				::core::compile_error!(
+					"unused bytes which depend on other elements do not have a constant size"
+				);
+			),
+		});
+	}
 }
 
 // }}}
@@ -121,10 +121,14 @@ pub fn derive_wrap(item: TokenStream) -> TokenStream {
 }
 
 // Potential idea: source attribute to use a source to serialize a field...?
-#[proc_macro_derive(Writable, attributes(no_discrim, hide))]
+#[proc_macro_derive(Writable, attributes(no_discrim, hide, unknown))]
 pub fn derive_writable(item: TokenStream) -> TokenStream {
 	let item = parse_macro_input!(item as DeriveInput);
 
+	if let Err(error) = check_unknown(&item.data) {
+		return error.to_compile_error().into();
+	}
+
 	let ident = &item.ident;
 	// TODO: add generic bounds
 	let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
@@ -147,11 +151,40 @@ pub fn derive_writable(item: TokenStream) -> TokenStream {
 	.into()
 }
 
+#[proc_macro_derive(InfallibleWritable, attributes(hide))]
+pub fn derive_infallible_writable(item: TokenStream) -> TokenStream {
+	let mut item = parse_macro_input!(item as DeriveInput);
+
+	// Only implement `InfallibleWritable` if every written field is
+	// `InfallibleWritable`.
+	let types: Vec<_> = written_field_types(&item.data).into_iter().cloned().collect();
+	let where_clause = item.generics.make_where_clause();
+
+	for r#type in types {
+		where_clause
+			.predicates
+			.push(syn::parse_quote!(#r#type: ::xrbk::InfallibleWritable));
+	}
+
+	let ident = &item.ident;
+	let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
+
+	quote!(
+		#[automatically_derived]
+		impl #impl_generics ::xrbk::InfallibleWritable for #ident #type_generics #where_clause {}
+	)
+	.into()
+}
+
 // TODO: context attribute support
-#[proc_macro_derive(Readable, attributes(no_discrim, hide, context))]
+#[proc_macro_derive(Readable, attributes(no_discrim, hide, context, unknown))]
 pub fn derive_readable(item: TokenStream) -> TokenStream {
 	let item = parse_macro_input!(item as DeriveInput);
 
+	if let Err(error) = check_unknown(&item.data) {
+		return error.to_compile_error().into();
+	}
+
 	let ident = &item.ident;
 	// TODO: add generic bounds
 	let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
@@ -171,10 +204,14 @@ pub fn derive_readable(item: TokenStream) -> TokenStream {
 	.into()
 }
 
-#[proc_macro_derive(X11Size, attributes(no_discrim, hide))]
+#[proc_macro_derive(X11Size, attributes(no_discrim, hide, unknown))]
 pub fn derive_x11_size(item: TokenStream) -> TokenStream {
 	let item = parse_macro_input!(item as DeriveInput);
 
+	if let Err(error) = check_unknown(&item.data) {
+		return error.to_compile_error().into();
+	}
+
 	let ident = &item.ident;
 	// TODO: add generic bounds
 	let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
@@ -192,10 +229,14 @@ pub fn derive_x11_size(item: TokenStream) -> TokenStream {
 	.into()
 }
 
-#[proc_macro_derive(ConstantX11Size, attributes(no_discrim, hide))]
+#[proc_macro_derive(ConstantX11Size, attributes(no_discrim, hide, unknown))]
 pub fn derive_constant_x11_size(item: TokenStream) -> TokenStream {
 	let item = parse_macro_input!(item as DeriveInput);
 
+	if let Err(error) = check_unknown(&item.data) {
+		return error.to_compile_error().into();
+	}
+
 	let ident = &item.ident;
 	// TODO: add generic bounds
 	let (impl_generics, type_generics, where_clause) = item.generics.split_for_impl();
@@ -60,9 +60,11 @@ impl SourceArgs {
 	pub fn formatted_tokens(&self, tokens: &mut TokenStream2) {
 		if let Some((_, definition_type)) = &self.remaining_arg {
 			match definition_type {
-				DefinitionType::Request => quote!((((length - 1) as usize) * 4) - size,),
+				// `size` includes the header, so it is subtracted from the
+				// total size of the message.
+				DefinitionType::Request => quote!(((length as usize) * 4) - size,),
 				DefinitionType::Reply => {
-					quote!(((length as usize) * 4) + (32 - 8) - size,)
+					quote!(((length as usize) * 4) + 32 - size,)
 				},
 				_ => unreachable!(),
 			}